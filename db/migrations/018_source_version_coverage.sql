@@ -0,0 +1,5 @@
+-- Units a partially published source version covers, as JSON
+-- ({ selectors, unitIds, discoveredUnits }). NULL means the version covers
+-- every unit discovery found.
+
+ALTER TABLE source_versions ADD COLUMN coverage TEXT;
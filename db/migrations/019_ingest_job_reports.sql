@@ -0,0 +1,5 @@
+-- R2 key of the report a container posts once every unit of a job has
+-- finished (review flags, broken links, crawl compliance). The report can
+-- run to megabytes for large sources, so only its key is kept in D1.
+
+ALTER TABLE ingest_jobs ADD COLUMN report_key TEXT;
@@ -0,0 +1,8 @@
+-- When a source version went live. Containers create versions staged
+-- (NULL) and publish them only once the run passes its publish gates, so
+-- readers never pick up a half-ingested or anomalous version as latest.
+-- Versions that predate staging were live as soon as they were created.
+
+ALTER TABLE source_versions ADD COLUMN published_at TEXT;
+
+UPDATE source_versions SET published_at = created_at;
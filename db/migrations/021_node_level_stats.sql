@@ -0,0 +1,6 @@
+-- Child, section, and word counts of structural level nodes, as JSON. The
+-- container sends them once a run has finished every unit, so a level shared
+-- by several units counts all of them. Kept beside the node rather than in a
+-- content blob, since level nodes have no content.
+
+ALTER TABLE nodes ADD COLUMN level_stats TEXT;
@@ -26,6 +26,8 @@ CREATE TABLE IF NOT EXISTS source_versions (
   source_id TEXT NOT NULL REFERENCES sources(id),
   version_date TEXT NOT NULL,        -- ISO date identifier for this version
   root_node_id TEXT,                 -- Tree root (set after nodes created)
+  coverage TEXT,                     -- JSON units covered by a partial version; NULL when complete
+  published_at TEXT,                 -- When the version went live; NULL while staged
   created_at TEXT DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -51,6 +53,9 @@ CREATE TABLE IF NOT EXISTS nodes (
   -- Blob reference (16-char hex hash into blobs table)
   blob_hash TEXT,
 
+  -- Child, section, and word counts of a structural level node (JSON)
+  level_stats TEXT,
+
   -- Source tracking
   source_url TEXT,                   -- Original URL this data was fetched from
   accessed_at TEXT                   -- ISO timestamp when content was fetched
@@ -73,6 +78,7 @@ CREATE TABLE IF NOT EXISTS ingest_jobs (
   processed_nodes INTEGER NOT NULL DEFAULT 0,
   error_count INTEGER NOT NULL DEFAULT 0,
   last_error TEXT,
+  report_key TEXT,                   -- R2 key of the container's end-of-run report
   started_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
   completed_at TEXT,
   created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -1,3 +1,4 @@
+use ingest::sources::synthetic::{usc_title, SyntheticShape};
 use ingest::sources::usc::parser::parse_usc_xml;
 use quick_xml::events::Event;
 use quick_xml::Reader;
@@ -17,10 +18,22 @@ fn count_xml_nodes(xml: &str) -> usize {
 }
 
 fn main() {
-    let path = std::env::args()
-        .nth(1)
-        .expect("Usage: bench_parser <xml_file>");
-    let xml = std::fs::read_to_string(&path).expect("Failed to read XML file");
+    let usage = "Usage: bench_parser <xml_file | --synthetic CHAPTERSxSECTIONSxNOTES[xDEPTH]>";
+    let mut args = std::env::args().skip(1);
+    let arg = args.next().expect(usage);
+    let xml = if arg == "--synthetic" {
+        let shape =
+            SyntheticShape::parse(&args.next().expect(usage)).unwrap_or_else(|err| panic!("{err}"));
+        let xml = usc_title("42", &shape);
+        println!(
+            "Synthetic title: {} sections, {:.1} MB\n",
+            shape.total_sections(),
+            xml.len() as f64 / 1_048_576.0
+        );
+        xml
+    } else {
+        std::fs::read_to_string(&arg).expect("Failed to read XML file")
+    };
 
     let iterations = 5;
 
@@ -4,15 +4,19 @@ use ingest::runtime::types::{
 };
 use ingest::sources::cgs::adapter::CGS_ADAPTER;
 use ingest::sources::mgl::adapter::MGL_ADAPTER;
+use ingest::sources::punctuation::SourceText;
 use ingest::sources::rigl::adapter::RIGL_ADAPTER;
+use ingest::sources::selectors::SelectorDocument;
 use ingest::sources::usc::adapter::USC_ADAPTER;
 use ingest::sources::vt::adapter::VT_ADAPTER;
 use ingest::sources::SourceAdapter;
-use ingest::types::{NodePayload, SectionContent};
+use ingest::types::{IngestProfile, NodePayload, SectionContent};
 use serde_json::{json, Value};
 use std::collections::VecDeque;
+use std::io::{BufRead, Write};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
@@ -41,8 +45,13 @@ impl SourceArg {
 #[tokio::main]
 async fn main() -> Result<(), DynError> {
     let args = std::env::args().skip(1).collect::<Vec<_>>();
-    if args.len() != 3 {
+    if args.first().map(String::as_str) == Some("select") && matches!(args.len(), 2 | 3) {
+        return run_select(&args[1], args.get(2).map(String::as_str));
+    }
+    if args.len() != 3 && !(args.len() == 4 && args[2] == "--audit") {
         eprintln!("Usage: explore <usc|cgs|mgl|rigl|vt> <file> <needle>");
+        eprintln!("       explore <usc|cgs|mgl|rigl|vt> <file> --audit <sample-every-nth-node>");
+        eprintln!("       explore select <file> [selector]");
         std::process::exit(2);
     }
 
@@ -60,12 +69,14 @@ async fn main() -> Result<(), DynError> {
             root_node_id: "root",
             accessed_at: "now",
             unit_sort_order: 0,
+            profile: IngestProfile::Full,
         },
         nodes: Box::new(node_store.clone()),
         blobs: Arc::new(NoopBlobStore),
         cache: Arc::new(NoopCache::new(&file_path, &input)),
         queue: queue.clone(),
         logger: Arc::new(ConsoleLogger),
+        cancel: CancellationToken::new(),
     };
 
     let item = build_queue_item(source, &file_path);
@@ -94,6 +105,15 @@ async fn main() -> Result<(), DynError> {
     }
 
     let nodes = node_store.nodes();
+    if needle == "--audit" {
+        let stride = args[3]
+            .parse::<usize>()
+            .map_err(|_| "sample stride must be a positive integer")?;
+        let audit = SourceText::new(&input).audit(&nodes, stride);
+        println!("{}", serde_json::to_string_pretty(&audit)?);
+        return Ok(());
+    }
+
     let matches = nodes
         .iter()
         .filter(|node| {
@@ -124,6 +144,55 @@ async fn main() -> Result<(), DynError> {
     Ok(())
 }
 
+/// Evaluates `selector` against the fixture at `file_path`, or, without one,
+/// reads selectors from stdin until EOF. Prefix a selector with `html ` to
+/// print the matches' inner markup instead of their text.
+fn run_select(file_path: &str, selector: Option<&str>) -> Result<(), DynError> {
+    let input = std::fs::read_to_string(file_path)?;
+    let document = SelectorDocument::parse(&input)?;
+    if let Some(selector) = selector {
+        print_selection(&document, selector);
+        return Ok(());
+    }
+
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        print!("select> ");
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            println!();
+            return Ok(());
+        };
+        let line = line.trim();
+        if !line.is_empty() {
+            print_selection(&document, line);
+        }
+    }
+}
+
+fn print_selection(document: &SelectorDocument<'_>, query: &str) {
+    let (html, selector) = match query.strip_prefix("html ") {
+        Some(selector) => (true, selector.trim()),
+        None => (false, query),
+    };
+    let matches = match document.select(selector) {
+        Ok(matches) => matches,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    for (index, matched) in matches.iter().enumerate() {
+        let output = if html {
+            &matched.inner_html
+        } else {
+            &matched.text
+        };
+        println!("[{index}] <{}> {output}", matched.tag);
+    }
+    println!("{} match(es)", matches.len());
+}
+
 fn build_queue_item(source: SourceArg, file_path: &str) -> QueueItem {
     let file_name = Path::new(file_path)
         .file_name()
@@ -408,6 +477,14 @@ impl Cache for NoopCache {
     ) -> Result<String, String> {
         self.fetch_cached(url, "", None).await
     }
+
+    async fn read_artifact(&self, _key: &str) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+
+    async fn write_artifact(&self, _key: &str, _content: &str) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 struct ConsoleLogger;
@@ -0,0 +1,249 @@
+use crate::runtime::provenance::sha256_hex;
+use crate::types::NodePayload;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use ruzstd::encoding::{compress, CompressionLevel};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Seek, SeekFrom, Write};
+
+/// Layout version of the dataset archive; bump when files are added, renamed,
+/// or change shape.
+pub const DATASET_FORMAT_VERSION: u32 = 1;
+
+/// What to bundle alongside a source version's node export.
+#[derive(Debug, Clone)]
+pub struct PackageOptions {
+    pub source_version_id: String,
+    /// SPDX identifier recorded in the manifest, e.g. `CC0-1.0`.
+    pub license_id: String,
+    /// Full license text, shipped as `LICENSE`.
+    pub license_text: String,
+}
+
+/// `manifest.json` at the root of a dataset archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetManifest {
+    pub format_version: u32,
+    pub source_version_id: String,
+    pub license: String,
+    pub created_at: String,
+    pub node_count: usize,
+    pub files: Vec<DatasetFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetFile {
+    pub path: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+impl DatasetFile {
+    fn new(path: &str, bytes: &[u8]) -> Self {
+        Self {
+            path: path.to_string(),
+            sha256: sha256_hex(bytes),
+            size_bytes: bytes.len() as u64,
+        }
+    }
+}
+
+/// Bundles a JSONL node export (one [`NodePayload`] per line) into a
+/// `.tar.zst` whose entries sit under `{source_version_id}/`: `manifest.json`,
+/// `LICENSE`, `nodes.jsonl`, and a `SHA256SUMS` covering the other three.
+///
+/// Nodes are spooled to a temporary file as they are added, so memory stays
+/// flat however large the export is. The manifest time and every entry's
+/// mtime are the latest `accessed_at` among the nodes, so packaging the same
+/// export twice yields identical bytes.
+pub struct DatasetPackager {
+    options: PackageOptions,
+    nodes: BufWriter<File>,
+    hasher: Sha256,
+    size_bytes: u64,
+    node_count: usize,
+    lines_read: usize,
+    accessed_at: Option<DateTime<FixedOffset>>,
+}
+
+impl DatasetPackager {
+    pub fn new(options: PackageOptions) -> Result<Self, String> {
+        let spool =
+            tempfile::tempfile().map_err(|e| format!("Failed to create export spool: {e}"))?;
+        Ok(Self {
+            options,
+            nodes: BufWriter::new(spool),
+            hasher: Sha256::new(),
+            size_bytes: 0,
+            node_count: 0,
+            lines_read: 0,
+            accessed_at: None,
+        })
+    }
+
+    /// Checks that every line of `nodes_jsonl` is a node of the packaged
+    /// source version and appends it. Blank lines are dropped; line numbers
+    /// in errors count across every call.
+    pub fn add_nodes(&mut self, mut nodes_jsonl: impl BufRead) -> Result<(), String> {
+        let source_version_id = &self.options.source_version_id;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = nodes_jsonl
+                .read_line(&mut line)
+                .map_err(|e| format!("Failed to read the export: {e}"))?;
+            if read == 0 {
+                return Ok(());
+            }
+            self.lines_read += 1;
+            let line = line.trim_end();
+            if line.trim_start().is_empty() {
+                continue;
+            }
+            let node: NodePayload = serde_json::from_str(line).map_err(|e| {
+                format!(
+                    "Invalid node on line {} of the export: {e}",
+                    self.lines_read
+                )
+            })?;
+            if node.meta.source_version_id != *source_version_id {
+                return Err(format!(
+                    "Node {} on line {} belongs to source version {}, not {source_version_id}",
+                    node.meta.id, self.lines_read, node.meta.source_version_id
+                ));
+            }
+            if let Some(accessed_at) = node.meta.accessed_at.as_deref() {
+                let accessed_at = DateTime::parse_from_rfc3339(accessed_at).map_err(|e| {
+                    format!(
+                        "Invalid accessed_at {accessed_at} on line {}: {e}",
+                        self.lines_read
+                    )
+                })?;
+                self.accessed_at = self.accessed_at.max(Some(accessed_at));
+            }
+
+            for bytes in [line.as_bytes(), b"\n"] {
+                self.nodes
+                    .write_all(bytes)
+                    .map_err(|e| format!("Failed to spool the export: {e}"))?;
+                self.hasher.update(bytes);
+                self.size_bytes += bytes.len() as u64;
+            }
+            self.node_count += 1;
+        }
+    }
+
+    /// Writes the `.tar.zst` to `out`.
+    pub fn finish(self, out: impl Write) -> Result<(), String> {
+        let source_version_id = &self.options.source_version_id;
+        if self.node_count == 0 {
+            return Err(format!("Export for {source_version_id} has no nodes"));
+        }
+        // Exports whose nodes never record an access time package at the epoch.
+        let created_at = self.accessed_at.unwrap_or_default();
+        let mtime = created_at.timestamp().max(0) as u64;
+
+        let mut nodes = self
+            .nodes
+            .into_inner()
+            .map_err(|e| format!("Failed to spool the export: {e}"))?;
+        nodes
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| format!("Failed to rewind the export spool: {e}"))?;
+        let nodes_sha256 = self
+            .hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        let license = self.options.license_text.as_bytes();
+        let manifest = DatasetManifest {
+            format_version: DATASET_FORMAT_VERSION,
+            source_version_id: source_version_id.clone(),
+            license: self.options.license_id.clone(),
+            created_at: created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            node_count: self.node_count,
+            files: vec![
+                DatasetFile::new("LICENSE", license),
+                DatasetFile {
+                    path: "nodes.jsonl".to_string(),
+                    sha256: nodes_sha256.clone(),
+                    size_bytes: self.size_bytes,
+                },
+            ],
+        };
+        let manifest = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize dataset manifest: {e}"))?;
+        let checksums = [
+            format!("{}  manifest.json\n", sha256_hex(&manifest)),
+            format!("{}  LICENSE\n", sha256_hex(license)),
+            format!("{nodes_sha256}  nodes.jsonl\n"),
+        ]
+        .concat();
+
+        let entry = |size: u64| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            header
+        };
+        // The tar stream feeds the compressor through a pipe, so neither the
+        // archive nor its compressed form is ever held whole.
+        let (reader, writer) =
+            std::io::pipe().map_err(|e| format!("Failed to open archive pipe: {e}"))?;
+        std::thread::scope(|scope| {
+            let tar = scope.spawn(move || -> Result<(), String> {
+                let mut archive = tar::Builder::new(writer);
+                let small: [(&str, &[u8]); 2] =
+                    [("manifest.json", &manifest), ("LICENSE", license)];
+                for (path, bytes) in small {
+                    archive
+                        .append_data(
+                            &mut entry(bytes.len() as u64),
+                            format!("{source_version_id}/{path}"),
+                            bytes,
+                        )
+                        .map_err(|e| format!("Failed to add {path} to dataset archive: {e}"))?;
+                }
+                archive
+                    .append_data(
+                        &mut entry(self.size_bytes),
+                        format!("{source_version_id}/nodes.jsonl"),
+                        &mut nodes,
+                    )
+                    .map_err(|e| format!("Failed to add nodes.jsonl to dataset archive: {e}"))?;
+                archive
+                    .append_data(
+                        &mut entry(checksums.len() as u64),
+                        format!("{source_version_id}/SHA256SUMS"),
+                        checksums.as_bytes(),
+                    )
+                    .map_err(|e| format!("Failed to add SHA256SUMS to dataset archive: {e}"))?;
+                archive
+                    .into_inner()
+                    .map_err(|e| format!("Failed to finish dataset archive: {e}"))?;
+                Ok(())
+            });
+            compress(reader, out, CompressionLevel::Fastest);
+            tar.join()
+                .map_err(|_| "Dataset archive writer panicked".to_string())?
+        })
+    }
+}
+
+/// Packages one JSONL node export read from `nodes_jsonl`; see
+/// [`DatasetPackager`].
+pub fn package_dataset(
+    nodes_jsonl: impl BufRead,
+    options: &PackageOptions,
+    out: impl Write,
+) -> Result<(), String> {
+    let mut packager = DatasetPackager::new(options.clone())?;
+    packager.add_nodes(nodes_jsonl)?;
+    packager.finish(out)
+}
@@ -0,0 +1,228 @@
+use crate::types::{NodePayload, SectionContent};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)\s]+)\)").unwrap());
+static BOLD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*(.+?)\*\*").unwrap());
+static HEADING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.*)$").unwrap());
+
+const PRINT_STYLE: &str =
+    "body{font-family:Georgia,serif;line-height:1.45;margin:0 auto;max-width:42em}\
+h1,h2,h3,h4,h5,h6{break-after:avoid-page}\
+.page-break{break-before:page}\
+.citation{color:#555;font-size:.85em;margin:0}\
+.source{color:#555;font-size:.8em}\
+@page{margin:2cm}";
+
+/// Knobs for [`render_print_html`].
+#[derive(Debug, Clone)]
+pub struct PrintOptions {
+    /// Document `<title>`; defaults to the subtree root's heading.
+    pub title: Option<String>,
+    /// Level names that start on a new printed page (the root never does).
+    pub page_break_levels: Vec<String>,
+    /// Content block types to print, in node order; empty prints every block.
+    pub block_types: Vec<String>,
+    /// Print each section's heading citation above its heading.
+    pub include_citations: bool,
+    /// Print each section's source URL after its body.
+    pub include_source_urls: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            page_break_levels: vec!["chapter".to_string()],
+            block_types: Vec::new(),
+            include_citations: true,
+            include_source_urls: false,
+        }
+    }
+}
+
+/// Renders the subtree rooted at `root_id` as a standalone, print-ready HTML
+/// document. `nodes` may contain unrelated nodes; only descendants of the root
+/// are printed, ordered by `sort_order`.
+pub fn render_print_html(
+    nodes: &[NodePayload],
+    root_id: &str,
+    options: &PrintOptions,
+) -> Result<String, String> {
+    let root = nodes
+        .iter()
+        .find(|node| node.meta.id == root_id)
+        .ok_or_else(|| format!("Export root not found: {root_id}"))?;
+
+    let mut children: HashMap<&str, Vec<&NodePayload>> = HashMap::new();
+    for node in nodes {
+        if let Some(parent_id) = node.meta.parent_id.as_deref() {
+            children.entry(parent_id).or_default().push(node);
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|node| node.meta.sort_order);
+    }
+
+    let title = options.title.clone().unwrap_or_else(|| heading_text(root));
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+    out.push_str(&format!("<style>{PRINT_STYLE}</style>\n</head>\n<body>\n"));
+    render_node(&mut out, root, &children, 1, options, true)?;
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+fn render_node(
+    out: &mut String,
+    node: &NodePayload,
+    children: &HashMap<&str, Vec<&NodePayload>>,
+    depth: usize,
+    options: &PrintOptions,
+    is_root: bool,
+) -> Result<(), String> {
+    let page_break = !is_root && options.page_break_levels.contains(&node.meta.level_name);
+    let class = if page_break {
+        format!("{} page-break", node.meta.level_name)
+    } else {
+        node.meta.level_name.clone()
+    };
+    out.push_str(&format!(
+        "<section class=\"{}\" id=\"{}\">\n",
+        escape_html(&class),
+        escape_html(&node.meta.id)
+    ));
+
+    let heading_level = depth.min(6);
+    if options.include_citations {
+        if let Some(citation) = node.meta.heading_citation.as_deref() {
+            out.push_str(&format!(
+                "<p class=\"citation\">{}</p>\n",
+                escape_html(citation)
+            ));
+        }
+    }
+    out.push_str(&format!(
+        "<h{heading_level}>{}</h{heading_level}>\n",
+        escape_html(&heading_text(node))
+    ));
+
+    if let Some(content) = node.content.as_ref() {
+        let content: SectionContent = serde_json::from_value(content.clone())
+            .map_err(|e| format!("Invalid content for {}: {e}", node.meta.id))?;
+        for block in &content.blocks {
+            if !options.block_types.is_empty() && !options.block_types.contains(&block.type_) {
+                continue;
+            }
+            let Some(text) = block.content.as_deref() else {
+                continue;
+            };
+            out.push_str(&format!(
+                "<div class=\"block {}\">\n",
+                escape_html(&block.type_)
+            ));
+            if let Some(label) = block.label.as_deref() {
+                let label_level = (heading_level + 1).min(6);
+                out.push_str(&format!(
+                    "<h{label_level}>{}</h{label_level}>\n",
+                    escape_html(label)
+                ));
+            }
+            out.push_str(&markdown_to_html(text, heading_level));
+            out.push_str("</div>\n");
+        }
+        if options.include_source_urls && !content.blocks.is_empty() {
+            if let Some(url) = node.meta.source_url.as_deref() {
+                out.push_str(&format!(
+                    "<p class=\"source\">Source: {}</p>\n",
+                    escape_html(url)
+                ));
+            }
+        }
+    }
+
+    for child in children.get(node.meta.id.as_str()).into_iter().flatten() {
+        render_node(out, child, children, depth + 1, options, false)?;
+    }
+    out.push_str("</section>\n");
+    Ok(())
+}
+
+fn heading_text(node: &NodePayload) -> String {
+    match (node.meta.readable_id.as_deref(), node.meta.name.as_deref()) {
+        (Some(id), Some(name)) if !name.contains(id) => format!("{id}. {name}"),
+        (_, Some(name)) => name.to_string(),
+        (Some(id), None) => id.to_string(),
+        (None, None) => node.meta.id.clone(),
+    }
+}
+
+/// Converts the markdown subset emitted by the adapters (paragraphs,
+/// blockquote outline nesting, ATX headings, bold, and links) to HTML.
+/// Headings inside the body are demoted below `heading_level`.
+fn markdown_to_html(markdown: &str, heading_level: usize) -> String {
+    let mut out = String::new();
+    for paragraph in markdown.split("\n\n") {
+        let mut depth = 0;
+        let mut lines = Vec::new();
+        for line in paragraph.lines() {
+            let mut rest = line;
+            let mut line_depth = 0;
+            while let Some(stripped) = rest.strip_prefix('>') {
+                line_depth += 1;
+                rest = stripped.strip_prefix(' ').unwrap_or(stripped);
+            }
+            depth = depth.max(line_depth);
+            if !rest.trim().is_empty() {
+                lines.push(rest.trim());
+            }
+        }
+        if lines.is_empty() {
+            continue;
+        }
+
+        if let [line] = lines.as_slice() {
+            if let Some(captures) = HEADING_RE.captures(line) {
+                let level = (heading_level + captures[1].len()).min(6);
+                out.push_str(&format!(
+                    "<h{level}>{}</h{level}>\n",
+                    render_inline(&captures[2])
+                ));
+                continue;
+            }
+        }
+
+        let body = lines
+            .iter()
+            .map(|line| render_inline(line))
+            .collect::<Vec<_>>()
+            .join("<br>\n");
+        if depth == 0 {
+            out.push_str(&format!("<p>{body}</p>\n"));
+        } else {
+            out.push_str(&format!(
+                "<p style=\"margin-left:{}em\">{body}</p>\n",
+                depth * 2
+            ));
+        }
+    }
+    out
+}
+
+fn render_inline(text: &str) -> String {
+    let escaped = escape_html(text);
+    let linked = LINK_RE.replace_all(&escaped, "<a href=\"$2\">$1</a>");
+    BOLD_RE
+        .replace_all(&linked, "<strong>$1</strong>")
+        .into_owned()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
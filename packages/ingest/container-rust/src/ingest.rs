@@ -1,5 +1,16 @@
+use crate::runtime::crash::CrashRecorder;
+use crate::runtime::eta::JobProgress;
 use crate::types::IngestConfig;
+use std::sync::Arc;
 
 pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
     crate::runtime::orchestrator::ingest_source(config).await
 }
+
+pub async fn ingest_source_with_progress(
+    config: IngestConfig,
+    progress: Arc<JobProgress>,
+    crash: Arc<CrashRecorder>,
+) -> Result<(), String> {
+    crate::runtime::orchestrator::ingest_source_with_progress(config, progress, crash).await
+}
@@ -1,4 +1,8 @@
+pub mod export;
 pub mod ingest;
+pub mod prelude;
 pub mod runtime;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod sources;
 pub mod types;
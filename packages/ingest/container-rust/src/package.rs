@@ -0,0 +1,31 @@
+use ingest::export::dataset::{package_dataset, PackageOptions};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+fn main() -> Result<(), DynError> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.len() != 5 {
+        eprintln!(
+            "Usage: package <source-version-id> <nodes.jsonl> <license-id> <license-file> <out.tar.zst>"
+        );
+        std::process::exit(2);
+    }
+
+    let nodes = BufReader::new(File::open(&args[1])?);
+    let options = PackageOptions {
+        source_version_id: args[0].clone(),
+        license_id: args[2].clone(),
+        license_text: std::fs::read_to_string(&args[3])?,
+    };
+    let mut archive = BufWriter::new(File::create(&args[4])?);
+    package_dataset(nodes, &options, &mut archive)?;
+    archive.flush()?;
+    println!(
+        "Wrote {} ({} bytes)",
+        args[4],
+        std::fs::metadata(&args[4])?.len()
+    );
+    Ok(())
+}
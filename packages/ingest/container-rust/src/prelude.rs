@@ -0,0 +1,66 @@
+//! Supported public API for external consumers.
+//!
+//! Everything re-exported here follows semver: items are only removed or
+//! changed incompatibly in a major version bump. Deep module paths such as
+//! `ingest::sources::usc::parser` are implementation detail and may move
+//! between releases, so tools built on this crate should import from here.
+//! Adapters and parsers are re-exported only when their source's cargo
+//! feature is enabled.
+//!
+//! ```no_run
+//! use ingest::prelude::*;
+//!
+//! let adapter = adapter_for(SourceKind::Usc);
+//! let uspl = try_adapter_for(SourceKind::Uspl)?;
+//! # let _ = (adapter, uspl);
+//! # Ok::<(), String>(())
+//! ```
+
+pub use crate::ingest::ingest_source;
+
+pub use crate::types::{
+    ContentBlock, DiscoveryResult, IngestConfig, IngestProfile, LevelStats, NodeLevelStats,
+    NodeMeta, NodePayload, SectionContent, SectionMetadata, SourceKind, UnitEntry, UnitRoot,
+};
+
+pub use crate::runtime::politeness::HostCompliance;
+pub use crate::runtime::report::IngestReport;
+pub use crate::runtime::review::{ReviewFlag, ReviewSource};
+pub use crate::runtime::types::{
+    BlobStore, BuildContext, Cache, IngestContext, Logger, NodeStore, QueueItem, UrlQueue,
+};
+
+pub use crate::sources::common::{ParseError, SourcePosition};
+pub use crate::sources::configs::NoteTaxonomy;
+pub use crate::sources::{adapter_for, try_adapter_for, SourceAdapter};
+
+#[cfg(feature = "cgs")]
+pub use crate::sources::cgs::adapter::{CgsAdapter, CGS_ADAPTER};
+#[cfg(feature = "mgl")]
+pub use crate::sources::mgl::adapter::{MglAdapter, MGL_ADAPTER};
+#[cfg(feature = "nh")]
+pub use crate::sources::nh::adapter::{NhAdapter, NH_ADAPTER};
+#[cfg(feature = "rigl")]
+pub use crate::sources::rigl::adapter::{RiglAdapter, RIGL_ADAPTER};
+#[cfg(feature = "usc")]
+pub use crate::sources::usc::adapter::{UscAdapter, USC_ADAPTER};
+#[cfg(feature = "uspl")]
+pub use crate::sources::uspl::adapter::{UsplAdapter, USPL_ADAPTER};
+#[cfg(feature = "vt")]
+pub use crate::sources::vt::adapter::{VtAdapter, VT_ADAPTER};
+
+#[cfg(feature = "cgs")]
+pub use crate::sources::cgs::parser::{
+    parse_cgs_chapter_html, parse_cgs_chapter_html_with_toc, CgsChapterParseResult,
+    CgsParsedSection, CgsTocMap, CgsUnitKind,
+};
+#[cfg(feature = "usc")]
+pub use crate::sources::usc::parser::{
+    parse_usc_xml, parse_usc_xml_stream, usc_stream_events, usc_stream_events_from_reader,
+    usc_stream_events_with_taxonomy, with_usc_zip_events, USCLevel, USCParentRef, USCParseResult,
+    USCSection, USCSectionBlock, USCSectionStatus, USCStreamEvent, USCStreamIter,
+};
+#[cfg(feature = "uspl")]
+pub use crate::sources::uspl::markdown::law_to_markdown;
+#[cfg(feature = "uspl")]
+pub use crate::sources::uspl::parser::{parse_uslm_volume, parse_uslm_volume_until, ParsedLaw};
@@ -0,0 +1,67 @@
+use crate::runtime::crash::{current_recorder, with_blocking_recorder};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Sending half handed to a parser running on the blocking pool.
+pub struct StreamSink<T> {
+    tx: mpsc::Sender<T>,
+    cancel: CancellationToken,
+}
+
+impl<T> StreamSink<T> {
+    /// Forwards `item` to the async consumer. Returns `false` once the run is
+    /// cancelled or the consumer has gone away, at which point the parser
+    /// should stop producing.
+    pub fn emit(&self, item: T) -> bool {
+        if self.cancel.is_cancelled() {
+            return false;
+        }
+        self.tx.blocking_send(item).is_ok()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+/// Runs a streaming parse on tokio's blocking pool so large documents don't
+/// stall the async workers. Events arrive on the returned receiver with
+/// `capacity` as backpressure; the receiver closes when the parse finishes
+/// or stops early.
+pub fn spawn_blocking_stream<T, F>(
+    cancel: &CancellationToken,
+    capacity: usize,
+    parse: F,
+) -> mpsc::Receiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&StreamSink<T>) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(capacity);
+    let sink = StreamSink {
+        tx,
+        cancel: cancel.clone(),
+    };
+    let crash = current_recorder();
+    tokio::task::spawn_blocking(move || with_blocking_recorder(crash, || parse(&sink)));
+    rx
+}
+
+/// Runs a one-shot parse on tokio's blocking pool. The parser receives the
+/// token so it can check for cancellation between units of work; the caller
+/// stops waiting as soon as the token is cancelled.
+pub async fn run_blocking<T, F>(cancel: &CancellationToken, parse: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&CancellationToken) -> T + Send + 'static,
+{
+    let token = cancel.clone();
+    let crash = current_recorder();
+    let handle =
+        tokio::task::spawn_blocking(move || with_blocking_recorder(crash, || parse(&token)));
+
+    tokio::select! {
+        _ = cancel.cancelled() => Err("Parse cancelled".to_string()),
+        joined = handle => joined.map_err(|err| format!("Blocking parse task failed: {err}")),
+    }
+}
@@ -1,4 +1,7 @@
 use crate::runtime::callbacks::callback_fetch;
+use crate::runtime::politeness::PolitenessTracker;
+use crate::runtime::provenance::{FetchRecord, UnitManifest, UpstreamResponse};
+use crate::types::SourceKind;
 use reqwest::Client;
 use std::io::{Cursor, Read};
 
@@ -25,6 +28,7 @@ fn extract_xml_from_zip(file_bytes: &[u8], url: &str) -> Result<String, String>
     Err(format!("No XML entry found in ZIP from {url}"))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn ensure_cached(
     client: &Client,
     url: &str,
@@ -33,7 +37,8 @@ pub async fn ensure_cached(
     extract_zip: bool,
     cache_key: &str,
     throttle_requests_per_second: Option<u32>,
-) -> Result<Option<String>, String> {
+    politeness: &PolitenessTracker,
+) -> Result<Option<(String, FetchRecord)>, String> {
     let cache_read_res = callback_fetch(
         client,
         callback_base,
@@ -55,6 +60,16 @@ pub async fn ensure_cached(
     .await?;
 
     let status = cache_read_res.status();
+    // Only set when the proxy went to the origin rather than its cache.
+    let upstream_status = cache_read_res
+        .headers()
+        .get("X-Upstream-Status")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u16>().ok());
+    match upstream_status {
+        Some(upstream) => politeness.record(url, upstream, throttle_requests_per_second),
+        None => politeness.record_cached(url, throttle_requests_per_second),
+    }
 
     if status.as_u16() == 422 {
         let body: serde_json::Value = cache_read_res
@@ -70,10 +85,18 @@ pub async fn ensure_cached(
     }
 
     if !status.is_success() {
+        if upstream_status == Some(404) {
+            return Err(not_found_error(url));
+        }
         let text = cache_read_res.text().await.unwrap_or_default();
         return Err(format!("Cache proxy failed: {status} {text}"));
     }
 
+    let upstream = cache_read_res
+        .headers()
+        .get("X-Upstream-Response")
+        .and_then(|value| value.to_str().ok())
+        .and_then(UpstreamResponse::from_header);
     let file_bytes = cache_read_res
         .bytes()
         .await
@@ -86,5 +109,196 @@ pub async fn ensure_cached(
             .map_err(|e| format!("File bytes are not valid UTF-8: {e}"))?
     };
 
-    Ok(Some(content))
+    // The proxy's own headers describe R2, not the origin, so they are only
+    // recorded through the metadata it stored when filling the cache.
+    let mut record = FetchRecord::new(url, Some(cache_key), status.as_u16(), &file_bytes);
+    if let Some(upstream) = upstream {
+        record = record.with_upstream(upstream);
+    }
+    Ok(Some((content, record)))
+}
+
+const NOT_FOUND_PREFIX: &str = "Upstream returned 404 for ";
+
+/// Error for a source URL the upstream server no longer serves.
+pub fn not_found_error(url: &str) -> String {
+    format!("{NOT_FOUND_PREFIX}{url}")
+}
+
+/// Whether a fetch error came from [`not_found_error`].
+pub fn is_not_found_error(error: &str) -> bool {
+    error.starts_with(NOT_FOUND_PREFIX)
+}
+
+/// Key prefix that keeps derived artifacts from one parser version or set of
+/// extraction options out of runs using another. Fetched source bytes are not
+/// namespaced; they don't depend on how they are parsed.
+pub fn artifact_namespace(source: SourceKind, parser_version: u32, options_hash: &str) -> String {
+    format!(
+        "{}/parser-v{parser_version}/{options_hash}",
+        source.as_str()
+    )
+}
+
+pub async fn read_artifact(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    namespace: &str,
+    key: &str,
+) -> Result<Option<String>, String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        &format!(
+            "/api/proxy/r2-read?key={}",
+            urlencoding::encode(&artifact_r2_key(namespace, key))
+        ),
+        reqwest::Method::GET,
+        None,
+    )
+    .await?;
+
+    let status = res.status();
+    if status.as_u16() == 404 {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Artifact read failed for {key}: {status} {text}"));
+    }
+
+    res.text()
+        .await
+        .map(Some)
+        .map_err(|e| format!("Failed to read artifact body for {key}: {e}"))
+}
+
+pub async fn write_artifact(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    namespace: &str,
+    key: &str,
+    content: &str,
+) -> Result<(), String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/proxy/r2-write",
+        reqwest::Method::POST,
+        Some(serde_json::json!({
+            "key": artifact_r2_key(namespace, key),
+            "content": content,
+        })),
+    )
+    .await?;
+
+    if !res.status().is_success() {
+        let status = res.status();
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Artifact write failed for {key}: {status} {text}"));
+    }
+
+    Ok(())
+}
+
+/// Stores `manifest` at `provenance/{source_version_id}/{unit_id}.json`.
+pub async fn write_unit_manifest(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    manifest: &UnitManifest,
+) -> Result<(), String> {
+    let content = serde_json::to_string(manifest)
+        .map_err(|e| format!("Failed to serialize manifest for {}: {e}", manifest.unit_id))?;
+    let key = format!(
+        "provenance/{}/{}.json",
+        manifest.source_version_id, manifest.unit_id
+    );
+    write_object(client, callback_base, callback_token, &key, &content).await
+}
+
+/// Reads the manifest [`write_unit_manifest`] stored for `unit_id` under
+/// `source_version_id`, or `None` when that unit has none.
+pub async fn read_unit_manifest(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source_version_id: &str,
+    unit_id: &str,
+) -> Result<Option<UnitManifest>, String> {
+    let key = format!("provenance/{source_version_id}/{unit_id}.json");
+    read_object(client, callback_base, callback_token, &key)
+        .await?
+        .map(|content| {
+            serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse manifest {key}: {e}"))
+        })
+        .transpose()
+}
+
+/// Reads the R2 object at `key` as text, or `None` when it doesn't exist.
+pub async fn read_object(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    key: &str,
+) -> Result<Option<String>, String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        &format!("/api/proxy/r2-read?key={}", urlencoding::encode(key)),
+        reqwest::Method::GET,
+        None,
+    )
+    .await?;
+
+    let status = res.status();
+    if status.as_u16() == 404 {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Object read failed for {key}: {status} {text}"));
+    }
+
+    res.text()
+        .await
+        .map(Some)
+        .map_err(|e| format!("Failed to read object body for {key}: {e}"))
+}
+
+/// Writes `content` to the R2 object at `key`.
+pub async fn write_object(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    key: &str,
+    content: &str,
+) -> Result<(), String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/proxy/r2-write",
+        reqwest::Method::POST,
+        Some(serde_json::json!({ "key": key, "content": content })),
+    )
+    .await?;
+
+    if !res.status().is_success() {
+        let status = res.status();
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Object write failed for {key}: {status} {text}"));
+    }
+
+    Ok(())
+}
+
+fn artifact_r2_key(namespace: &str, key: &str) -> String {
+    format!("artifacts/{namespace}/{key}")
 }
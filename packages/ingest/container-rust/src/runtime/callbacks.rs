@@ -1,4 +1,7 @@
-use crate::types::NodePayload;
+use crate::runtime::coverage::VersionCoverage;
+use crate::runtime::eta::ProgressSnapshot;
+use crate::runtime::report::IngestReport;
+use crate::types::{NodeLevelStats, NodePayload};
 use reqwest::Client;
 
 pub async fn callback_fetch(
@@ -82,6 +85,30 @@ pub async fn post_node_batch(
     Ok(())
 }
 
+pub async fn post_level_stats(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    levels: &[NodeLevelStats],
+) -> Result<(), String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/callback/setLevelStats",
+        reqwest::Method::POST,
+        Some(serde_json::json!({ "levels": levels })),
+    )
+    .await?;
+
+    if !res.status().is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Level stats callback failed: {text}"));
+    }
+
+    Ok(())
+}
+
 pub async fn post_unit_start(
     client: &Client,
     callback_base: &str,
@@ -114,16 +141,19 @@ pub async fn post_unit_progress(
     unit_id: &str,
     status: &str,
     error: Option<&str>,
+    progress: &ProgressSnapshot,
 ) {
     let body = match error {
         Some(error_message) => serde_json::json!({
             "unitId": unit_id,
             "status": status,
             "error": error_message,
+            "progress": progress,
         }),
         None => serde_json::json!({
             "unitId": unit_id,
             "status": status,
+            "progress": progress,
         }),
     };
 
@@ -138,23 +168,99 @@ pub async fn post_unit_progress(
     .await;
 }
 
+/// Reports a failed job. `crash_dump` is the R2 key of the job's crash dump
+/// when it panicked.
 pub async fn post_ingest_error(
     client: &Client,
     callback_base: &str,
     callback_token: &str,
     error: &str,
+    crash_dump: Option<&str>,
 ) {
+    let mut body = serde_json::json!({ "error": error });
+    if let Some(key) = crash_dump {
+        body["crashDump"] = serde_json::json!(key);
+    }
     let _ = callback_fetch(
         client,
         callback_base,
         callback_token,
         "/api/callback/ingestError",
         reqwest::Method::POST,
-        Some(serde_json::json!({ "error": error })),
+        Some(body),
+    )
+    .await;
+}
+
+/// Tells the backend a job finished, so it can stop the container once no
+/// other job is running on it.
+pub async fn post_container_stop(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    reason: &str,
+) {
+    let _ = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/callback/containerStop",
+        reqwest::Method::POST,
+        Some(serde_json::json!({ "reason": reason })),
     )
     .await;
 }
 
+pub async fn post_ingest_report(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    report: &IngestReport,
+) -> Result<(), String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/callback/ingestReport",
+        reqwest::Method::POST,
+        Some(serde_json::json!(report)),
+    )
+    .await?;
+
+    if !res.status().is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Ingest report callback failed: {text}"));
+    }
+
+    Ok(())
+}
+
+/// Promotes a staged source version once the run has passed its publish gates.
+pub async fn post_publish_source_version(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source_version_id: &str,
+) -> Result<(), String> {
+    let res = callback_fetch(
+        client,
+        callback_base,
+        callback_token,
+        "/api/callback/publishSourceVersion",
+        reqwest::Method::POST,
+        Some(serde_json::json!({ "sourceVersionId": source_version_id })),
+    )
+    .await?;
+
+    if !res.status().is_success() {
+        let text = res.text().await.unwrap_or_default();
+        return Err(format!("Publish source version callback failed: {text}"));
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn post_ensure_source_version(
     client: &Client,
     callback_base: &str,
@@ -163,6 +269,7 @@ pub async fn post_ensure_source_version(
     source_version_id: &str,
     root_node: &crate::types::NodeMeta,
     units: &[crate::types::UnitRoot],
+    coverage: Option<&VersionCoverage>,
 ) -> Result<(), String> {
     let res = callback_fetch(
         client,
@@ -175,6 +282,8 @@ pub async fn post_ensure_source_version(
             "sourceVersionId": source_version_id,
             "rootNode": root_node,
             "units": units,
+            "staged": true,
+            "coverage": coverage,
         })),
     )
     .await?;
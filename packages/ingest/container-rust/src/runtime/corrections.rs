@@ -0,0 +1,136 @@
+use crate::types::{ContentBlock, CorrectionSpan, NodePayload, SectionContent, SectionMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Block type of the annotation an applied correction adds.
+pub const CORRECTION_BLOCK_TYPE: &str = "correction";
+
+/// A known error in upstream text and what it should read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Correction {
+    /// Exact text as published; its first occurrence in the node's blocks is
+    /// the span being corrected.
+    pub text: String,
+    pub replacement: String,
+    /// Why the correction is made, shown after it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Editorial corrections keyed by canonical node id, loaded from
+/// `corrections.json` next to `sources.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorrectionOverlay {
+    pub corrections: HashMap<String, Vec<Correction>>,
+}
+
+/// `node_id` without its source version segment, so a correction keeps
+/// applying to later versions of the node: `cgs/2025/root/title-1` becomes
+/// `cgs/root/title-1`.
+pub fn canonical_node_id(node_id: &str) -> String {
+    let mut segments = node_id.splitn(3, '/');
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(source), Some(_version), Some(rest)) => format!("{source}/{rest}"),
+        _ => node_id.to_string(),
+    }
+}
+
+impl CorrectionOverlay {
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    /// Loads `corrections.json` from `CONFIGS_PATH`, or from the repo root
+    /// like `sources.json`. A missing file is an empty overlay.
+    pub fn load_default() -> Result<Self, String> {
+        let path = if let Ok(dir) = std::env::var("CONFIGS_PATH") {
+            Path::new(&dir).join("corrections.json")
+        } else {
+            std::path::PathBuf::from("../../corrections.json")
+        };
+        Self::load_from_file(path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.corrections.is_empty()
+    }
+
+    /// Appends a `correction` block for each correction of `node` whose text
+    /// is found, and records its span in the node metadata. The original
+    /// blocks are left as they are. Returns the corrections whose text no
+    /// longer appears in the node.
+    pub fn apply(&self, node: &mut NodePayload) -> Result<Vec<Correction>, String> {
+        let Some(corrections) = self.corrections.get(&canonical_node_id(&node.meta.id)) else {
+            return Ok(Vec::new());
+        };
+        let Some(value) = node.content.as_ref() else {
+            return Ok(corrections.clone());
+        };
+        let mut content: SectionContent = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Failed to read content of {}: {e}", node.meta.id))?;
+
+        let mut unmatched = Vec::new();
+        let mut spans = Vec::new();
+        let mut blocks = Vec::new();
+        for correction in corrections {
+            let found = content
+                .blocks
+                .iter()
+                .enumerate()
+                .find_map(|(index, block)| {
+                    let offset = block.content.as_deref()?.find(&correction.text)?;
+                    Some((index, offset))
+                });
+            let Some((block, offset)) = found else {
+                unmatched.push(correction.clone());
+                continue;
+            };
+            spans.push(CorrectionSpan {
+                block,
+                offset,
+                length: correction.text.len(),
+                replacement: correction.replacement.clone(),
+            });
+            blocks.push(correction_block(correction));
+        }
+        if spans.is_empty() {
+            return Ok(unmatched);
+        }
+
+        content.blocks.extend(blocks);
+        content
+            .metadata
+            .get_or_insert_with(SectionMetadata::default)
+            .corrections = spans;
+        node.content = Some(
+            serde_json::to_value(&content)
+                .map_err(|e| format!("Failed to write content of {}: {e}", node.meta.id))?,
+        );
+        Ok(unmatched)
+    }
+}
+
+fn correction_block(correction: &Correction) -> ContentBlock {
+    let mut text = format!(
+        "\"{}\" should read \"{}\".",
+        correction.text, correction.replacement
+    );
+    if let Some(note) = &correction.note {
+        text.push(' ');
+        text.push_str(note);
+    }
+    ContentBlock {
+        type_: CORRECTION_BLOCK_TYPE.to_string(),
+        content: Some(text),
+        label: Some("Editorial Correction".to_string()),
+    }
+}
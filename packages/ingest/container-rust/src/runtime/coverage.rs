@@ -0,0 +1,110 @@
+use crate::types::UnitRoot;
+use serde::{Deserialize, Serialize};
+
+/// The units a source version covers when its run was limited by selectors.
+/// Recorded with the version so a partial publish is never mistaken for a
+/// complete one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionCoverage {
+    pub selectors: Vec<String>,
+    /// Ids of the units ingested, in discovery order.
+    pub unit_ids: Vec<String>,
+    /// Units discovery found before selection.
+    pub discovered_units: usize,
+}
+
+/// One entry of an ingest's `selectors`: a unit id or title number, or an
+/// inclusive `FIRST..LAST` range over titles with a leading number (`5a`
+/// counts as 5).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitSelector {
+    Exact(String),
+    Range(u64, u64),
+}
+
+impl UnitSelector {
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let selector = selector.trim();
+        if selector.is_empty() {
+            return Err("Empty unit selector".to_string());
+        }
+        let Some((first, last)) = selector.split_once("..") else {
+            return Ok(Self::Exact(selector.to_lowercase()));
+        };
+        let bound = |value: &str| {
+            value.trim().parse::<u64>().map_err(|_| {
+                format!("Invalid unit range {selector:?}: {value:?} is not a title number")
+            })
+        };
+        let (first, last) = (bound(first)?, bound(last)?);
+        if first > last {
+            return Err(format!("Invalid unit range {selector:?}: {first} > {last}"));
+        }
+        Ok(Self::Range(first, last))
+    }
+
+    pub fn matches(&self, unit: &UnitRoot) -> bool {
+        match self {
+            Self::Exact(value) => {
+                unit.id.eq_ignore_ascii_case(value) || unit.title_num.eq_ignore_ascii_case(value)
+            }
+            Self::Range(first, last) => {
+                leading_number(&unit.title_num).is_some_and(|num| (*first..=*last).contains(&num))
+            }
+        }
+    }
+}
+
+fn leading_number(title_num: &str) -> Option<u64> {
+    let digits = title_num
+        .trim()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+/// Keeps the discovered units matching any of `selectors`. Coverage is
+/// recorded only when units were left out. A selector that matches
+/// nothing is an error, so a typo cannot publish a smaller version than
+/// intended.
+pub fn select_units(
+    units: Vec<UnitRoot>,
+    selectors: Option<&[String]>,
+) -> Result<(Vec<UnitRoot>, Option<VersionCoverage>), String> {
+    let Some(selectors) = selectors.filter(|selectors| !selectors.is_empty()) else {
+        return Ok((units, None));
+    };
+    let parsed = selectors
+        .iter()
+        .map(|selector| UnitSelector::parse(selector))
+        .collect::<Result<Vec<_>, _>>()?;
+    let unmatched = selectors
+        .iter()
+        .zip(&parsed)
+        .filter(|(_, selector)| !units.iter().any(|unit| selector.matches(unit)))
+        .map(|(raw, _)| raw.as_str())
+        .collect::<Vec<_>>();
+    if !unmatched.is_empty() {
+        return Err(format!(
+            "Unit selectors matched no discovered units: {}",
+            unmatched.join(", ")
+        ));
+    }
+
+    let discovered_units = units.len();
+    let selected = units
+        .into_iter()
+        .filter(|unit| parsed.iter().any(|selector| selector.matches(unit)))
+        .collect::<Vec<_>>();
+    if selected.len() == discovered_units {
+        return Ok((selected, None));
+    }
+    let coverage = VersionCoverage {
+        selectors: selectors.to_vec(),
+        unit_ids: selected.iter().map(|unit| unit.id.clone()).collect(),
+        discovered_units,
+    };
+    Ok((selected, Some(coverage)))
+}
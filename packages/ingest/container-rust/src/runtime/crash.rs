@@ -0,0 +1,279 @@
+use crate::runtime::cache::write_object;
+use crate::runtime::eta::{JobProgress, ProgressSnapshot};
+use crate::runtime::orchestrator::SimpleUrlQueue;
+use crate::runtime::types::{Logger, QueueItem};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::panic::PanicHookInfo;
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+
+/// Log events kept per job for its crash dump.
+pub const MAX_LOG_EVENTS: usize = 100;
+
+static INSTALL_HOOK: Once = Once::new();
+
+tokio::task_local! {
+    static JOB_CRASH: Arc<CrashRecorder>;
+}
+
+thread_local! {
+    static BLOCKING_CRASH: RefCell<Option<Arc<CrashRecorder>>> = const { RefCell::new(None) };
+}
+
+/// Where and why a thread panicked, as captured by the panic hook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+}
+
+/// Installs, once per process, a panic hook that records each panic in the
+/// [`CrashRecorder`] of the job that raised it, if any. The previous hook
+/// still runs, so panics are printed as before.
+pub fn install_panic_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if let Some(crash) = current_recorder() {
+                crash.set_panic(panic_report(info));
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Runs `future` with the panics it raises recorded in `crash`. Tasks it
+/// spawns need their own scope; blocking work started through
+/// [`crate::runtime::blocking`] inherits it.
+pub async fn with_crash_recorder<F: Future>(crash: Arc<CrashRecorder>, future: F) -> F::Output {
+    JOB_CRASH.scope(crash, future).await
+}
+
+/// Recorder for panics on the calling task or blocking thread.
+pub fn current_recorder() -> Option<Arc<CrashRecorder>> {
+    JOB_CRASH.try_with(Arc::clone).ok().or_else(|| {
+        BLOCKING_CRASH
+            .try_with(|slot| slot.borrow().clone())
+            .ok()
+            .flatten()
+    })
+}
+
+/// Runs `work` on the current thread with its panics recorded in `crash`,
+/// for carrying a job's recorder onto the blocking pool.
+pub fn with_blocking_recorder<T>(crash: Option<Arc<CrashRecorder>>, work: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<CrashRecorder>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            let _ = BLOCKING_CRASH.try_with(|slot| *slot.borrow_mut() = previous);
+        }
+    }
+    let _restore = Restore(BLOCKING_CRASH.with(|slot| slot.replace(crash)));
+    work()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
+fn panic_report(info: &PanicHookInfo<'_>) -> PanicReport {
+    PanicReport {
+        message: panic_message(info.payload()),
+        location: info.location().map(|location| location.to_string()),
+        thread: std::thread::current().name().map(str::to_string),
+        backtrace: Backtrace::force_capture().to_string(),
+    }
+}
+
+/// A panicking thread may have poisoned the lock; the state is still worth
+/// dumping.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEvent {
+    pub at: String,
+    pub level: String,
+    pub message: String,
+    pub context: Option<Value>,
+}
+
+/// A unit's state at the time of the dump.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitState {
+    pub unit_id: String,
+    /// Item `process_url` was running on.
+    pub item: Option<QueueItem>,
+    /// Items still waiting in the unit's queue.
+    pub queue: Vec<QueueItem>,
+}
+
+/// Serialized to R2 when a job panics.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashDump {
+    pub captured_at: String,
+    pub error: String,
+    pub panic: Option<PanicReport>,
+    pub progress: ProgressSnapshot,
+    pub units: Vec<UnitState>,
+    pub events: Vec<LogEvent>,
+}
+
+impl CrashDump {
+    /// R2 key the dump is stored under.
+    pub fn key(&self) -> String {
+        format!(
+            "crash-dumps/{}/{}.json",
+            self.progress.source_id, self.captured_at
+        )
+    }
+}
+
+struct RunningUnit {
+    item: Option<QueueItem>,
+    queue: Arc<SimpleUrlQueue>,
+}
+
+/// In-flight state of one job, kept up to date as units run so it can be
+/// dumped after a panic.
+#[derive(Default)]
+pub struct CrashRecorder {
+    units: Mutex<BTreeMap<String, RunningUnit>>,
+    events: Mutex<VecDeque<LogEvent>>,
+    panic: Mutex<Option<PanicReport>>,
+}
+
+impl CrashRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_unit(&self, unit_id: &str, queue: Arc<SimpleUrlQueue>) {
+        lock(&self.units).insert(unit_id.to_string(), RunningUnit { item: None, queue });
+    }
+
+    pub fn begin_item(&self, unit_id: &str, item: &QueueItem) {
+        if let Some(unit) = lock(&self.units).get_mut(unit_id) {
+            unit.item = Some(item.clone());
+        }
+    }
+
+    pub fn finish_unit(&self, unit_id: &str) {
+        lock(&self.units).remove(unit_id);
+    }
+
+    pub fn record_log(&self, level: &str, message: &str, context: Option<Value>) {
+        let mut events = lock(&self.events);
+        if events.len() == MAX_LOG_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(LogEvent {
+            at: chrono::Utc::now().to_rfc3339(),
+            level: level.to_string(),
+            message: message.to_string(),
+            context,
+        });
+    }
+
+    /// Keeps the first panic; later ones are usually fallout from it.
+    fn set_panic(&self, report: PanicReport) {
+        let mut panic = lock(&self.panic);
+        if panic.is_none() {
+            *panic = Some(report);
+        }
+    }
+
+    /// Records the panic a task's `JoinError` carried, for panics the hook
+    /// didn't attribute to this job. Only the message survives the join.
+    pub fn record_join_panic(&self, payload: Box<dyn Any + Send>) {
+        self.set_panic(PanicReport {
+            message: panic_message(payload.as_ref()),
+            location: None,
+            thread: None,
+            backtrace: String::new(),
+        });
+    }
+
+    pub fn panicked(&self) -> bool {
+        lock(&self.panic).is_some()
+    }
+
+    pub fn dump(&self, error: &str, progress: &JobProgress) -> CrashDump {
+        let units = lock(&self.units)
+            .iter()
+            .map(|(unit_id, unit)| UnitState {
+                unit_id: unit_id.clone(),
+                item: unit.item.clone(),
+                queue: unit.queue.snapshot(),
+            })
+            .collect();
+        CrashDump {
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            error: error.to_string(),
+            panic: lock(&self.panic).clone(),
+            progress: progress.snapshot(),
+            units,
+            events: lock(&self.events).iter().cloned().collect(),
+        }
+    }
+}
+
+/// Writes the dump of `crash` to R2 and returns its key. A dump that can't be
+/// stored is logged and skipped; the failure callback still goes out.
+pub async fn store_crash_dump(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    crash: &CrashRecorder,
+    progress: &JobProgress,
+    error: &str,
+) -> Option<String> {
+    let dump = crash.dump(error, progress);
+    let key = dump.key();
+    let result = match serde_json::to_string(&dump) {
+        Ok(content) => write_object(client, callback_base, callback_token, &key, &content).await,
+        Err(err) => Err(format!("Failed to serialize crash dump: {err}")),
+    };
+    match result {
+        Ok(()) => Some(key),
+        Err(err) => {
+            tracing::error!("[Crash] Failed to store crash dump: {}", err);
+            None
+        }
+    }
+}
+
+/// Records each event for the crash dump, then passes it on to `inner`.
+pub struct RecordingLogger {
+    pub inner: Arc<dyn Logger>,
+    pub crash: Arc<CrashRecorder>,
+}
+
+#[async_trait]
+impl Logger for RecordingLogger {
+    async fn log(&self, level: &str, message: &str, context: Option<Value>) {
+        self.crash.record_log(level, message, context.clone());
+        self.inner.log(level, message, context).await;
+    }
+}
@@ -0,0 +1,123 @@
+use crate::types::{NodePayload, SectionContent};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Words per shingle.
+const SHINGLE_WORDS: usize = 5;
+/// Bodies shorter than this are skipped; short boilerplate such as
+/// "Repealed." would otherwise match across the whole source.
+const MIN_WORDS: usize = 25;
+const NUM_HASHES: usize = 64;
+/// LSH banding: pairs agreeing on every row of any band become candidates.
+const BANDS: usize = 16;
+const ROWS: usize = NUM_HASHES / BANDS;
+/// Estimated Jaccard similarity at or above which a pair is reported.
+const SIMILARITY_THRESHOLD: f64 = 0.9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NearDuplicate {
+    pub node_id: String,
+    pub other_node_id: String,
+    /// Estimated Jaccard similarity of the two bodies' word shingles.
+    pub similarity: f64,
+}
+
+/// MinHash signatures of section bodies, compared at the end of a run to find
+/// renumbered, double-emitted, or editorially duplicated sections.
+#[derive(Default)]
+pub struct DuplicateDetector {
+    signatures: Mutex<Vec<(String, [u64; NUM_HASHES])>>,
+}
+
+impl DuplicateDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&self, node: &NodePayload) {
+        let Some(content) = node
+            .content
+            .as_ref()
+            .and_then(|value| serde_json::from_value::<SectionContent>(value.clone()).ok())
+        else {
+            return;
+        };
+
+        let words: Vec<String> = content
+            .blocks
+            .iter()
+            .filter(|block| block.type_ == "body")
+            .filter_map(|block| block.content.as_deref())
+            .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.len() < MIN_WORDS {
+            return;
+        }
+
+        let mut signature = [u64::MAX; NUM_HASHES];
+        for shingle in words.windows(SHINGLE_WORDS) {
+            let mut hasher = DefaultHasher::new();
+            shingle.hash(&mut hasher);
+            let base = hasher.finish();
+            for (seed, slot) in signature.iter_mut().enumerate() {
+                *slot = (*slot).min(mix(base ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)));
+            }
+        }
+
+        self.signatures
+            .lock()
+            .unwrap()
+            .push((node.meta.id.clone(), signature));
+    }
+
+    /// Pairs at or above the similarity threshold, in observation order.
+    pub fn near_duplicates(&self) -> Vec<NearDuplicate> {
+        let signatures = self.signatures.lock().unwrap();
+        let mut candidates = BTreeSet::new();
+        for band in 0..BANDS {
+            let rows = band * ROWS..(band + 1) * ROWS;
+            let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+            for (index, (_, signature)) in signatures.iter().enumerate() {
+                buckets
+                    .entry(&signature[rows.clone()])
+                    .or_default()
+                    .push(index);
+            }
+            for bucket in buckets.values() {
+                for (i, &left) in bucket.iter().enumerate() {
+                    for &right in &bucket[i + 1..] {
+                        candidates.insert((left, right));
+                    }
+                }
+            }
+        }
+
+        candidates
+            .into_iter()
+            .filter_map(|(left, right)| {
+                let (node_id, a) = &signatures[left];
+                let (other_node_id, b) = &signatures[right];
+                let matching = a.iter().zip(b).filter(|(x, y)| x == y).count();
+                let similarity = matching as f64 / NUM_HASHES as f64;
+                (similarity >= SIMILARITY_THRESHOLD).then(|| NearDuplicate {
+                    node_id: node_id.clone(),
+                    other_node_id: other_node_id.clone(),
+                    similarity,
+                })
+            })
+            .collect()
+    }
+}
+
+/// splitmix64 finalizer, used to derive independent hash functions.
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
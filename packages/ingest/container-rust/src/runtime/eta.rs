@@ -0,0 +1,216 @@
+use crate::types::SourceKind;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Seconds each unit of a source took on its last completed run, carried
+/// between runs so the next one can estimate how long it has left.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitTimings {
+    pub units: BTreeMap<String, f64>,
+}
+
+impl UnitTimings {
+    /// R2 key the timings for `source` are stored under.
+    pub fn key(source: SourceKind) -> String {
+        format!("timings/{}.json", source.as_str())
+    }
+
+    pub fn record(&mut self, unit_id: &str, secs: f64) {
+        self.units.insert(unit_id.to_string(), secs);
+    }
+
+    /// Orders `units` slowest first by their last timing, so the longest
+    /// units start while workers are free instead of trailing the run.
+    /// Units without a timing count as the median; ties keep their order.
+    pub fn order_slowest_first<T>(&self, units: &mut [T], unit_id: impl Fn(&T) -> String) {
+        let median = self.median().unwrap_or(0.0);
+        let expected = |unit: &T| self.units.get(&unit_id(unit)).copied().unwrap_or(median);
+        units.sort_by(|a, b| expected(b).total_cmp(&expected(a)));
+    }
+
+    fn median(&self) -> Option<f64> {
+        median(self.units.values().copied().collect())
+    }
+}
+
+fn median(mut secs: Vec<f64>) -> Option<f64> {
+    if secs.is_empty() {
+        return None;
+    }
+    secs.sort_by(f64::total_cmp);
+    Some(secs[secs.len() / 2])
+}
+
+/// Point-in-time view of a running job, served by `/ingest/status` and sent
+/// with each unit progress callback.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressSnapshot {
+    pub source_id: String,
+    pub total_units: usize,
+    pub completed_units: usize,
+    pub failed_units: usize,
+    pub running_units: usize,
+    pub elapsed_secs: f64,
+    /// Seconds until the last queued unit should finish; absent until some
+    /// unit of the source has a recorded timing.
+    pub eta_secs: Option<f64>,
+}
+
+#[derive(Default)]
+struct ProgressState {
+    workers: usize,
+    history: UnitTimings,
+    queued: VecDeque<String>,
+    running: HashMap<String, Instant>,
+    completed: usize,
+    failed: usize,
+    measured: UnitTimings,
+}
+
+impl ProgressState {
+    /// Median of every timing known so far, this run's over the history's.
+    fn median(&self) -> Option<f64> {
+        let history = self
+            .history
+            .units
+            .iter()
+            .filter(|(unit_id, _)| !self.measured.units.contains_key(*unit_id))
+            .map(|(_, secs)| *secs);
+        median(
+            history
+                .chain(self.measured.units.values().copied())
+                .collect(),
+        )
+    }
+
+    /// Expected seconds for `unit_id`: its own last timing, else `median`.
+    fn expected(&self, unit_id: &str, median: Option<f64>) -> Option<f64> {
+        self.measured
+            .units
+            .get(unit_id)
+            .or_else(|| self.history.units.get(unit_id))
+            .copied()
+            .or(median)
+    }
+}
+
+/// Tracks which units of a job are queued, running, and done.
+pub struct JobProgress {
+    source_id: String,
+    started: Instant,
+    state: Mutex<ProgressState>,
+}
+
+impl JobProgress {
+    pub fn new(source_id: &str) -> Self {
+        Self {
+            source_id: source_id.to_string(),
+            started: Instant::now(),
+            state: Mutex::new(ProgressState::default()),
+        }
+    }
+
+    /// Queues the job's units in the order they will be started, run
+    /// `workers` at a time, with timings from earlier runs.
+    pub fn plan(&self, history: UnitTimings, workers: usize, unit_ids: Vec<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.history = history;
+        state.workers = workers.max(1);
+        state.queued = unit_ids.into();
+    }
+
+    pub fn start(&self, unit_id: &str) {
+        self.start_at(unit_id, Instant::now());
+    }
+
+    pub fn start_at(&self, unit_id: &str, at: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.queued.retain(|queued| queued != unit_id);
+        state.running.insert(unit_id.to_string(), at);
+    }
+
+    /// Marks `unit_id` done. Only completed units update the timings; a
+    /// failed unit's duration says little about a normal run.
+    pub fn finish(&self, unit_id: &str, completed: bool) {
+        self.finish_at(unit_id, completed, Instant::now());
+    }
+
+    pub fn finish_at(&self, unit_id: &str, completed: bool, at: Instant) {
+        let mut state = self.state.lock().unwrap();
+        let started = state.running.remove(unit_id);
+        if !completed {
+            state.failed += 1;
+            return;
+        }
+        state.completed += 1;
+        if let Some(started) = started {
+            let secs = at.saturating_duration_since(started).as_secs_f64();
+            state.measured.record(unit_id, secs);
+        }
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    pub fn snapshot_at(&self, now: Instant) -> ProgressSnapshot {
+        let state = self.state.lock().unwrap();
+        let median = state.median();
+        let running = state
+            .running
+            .iter()
+            .map(|(unit_id, started)| {
+                let elapsed = now.saturating_duration_since(*started).as_secs_f64();
+                state
+                    .expected(unit_id, median)
+                    .map(|expected| (expected - elapsed).max(0.0))
+            })
+            .collect::<Option<Vec<_>>>();
+        let queued = state
+            .queued
+            .iter()
+            .map(|unit_id| state.expected(unit_id, median))
+            .collect::<Option<Vec<_>>>();
+
+        ProgressSnapshot {
+            source_id: self.source_id.clone(),
+            total_units: state.queued.len() + state.running.len() + state.completed + state.failed,
+            completed_units: state.completed,
+            failed_units: state.failed,
+            running_units: state.running.len(),
+            elapsed_secs: now.saturating_duration_since(self.started).as_secs_f64(),
+            eta_secs: running
+                .zip(queued)
+                .map(|(running, queued)| estimate_remaining(&running, &queued, state.workers)),
+        }
+    }
+
+    /// Timings to store for the next run: this run's measurements over the
+    /// history it started with.
+    pub fn timings(&self) -> UnitTimings {
+        let state = self.state.lock().unwrap();
+        let mut timings = state.history.clone();
+        timings.units.extend(state.measured.units.clone());
+        timings
+    }
+}
+
+/// Seconds until the work finishes when `workers` units run at once: each
+/// queued unit, in order, starts on whichever worker frees up first, after
+/// the units already running finish their `running` remaining seconds.
+pub fn estimate_remaining(running: &[f64], queued: &[f64], workers: usize) -> f64 {
+    let mut free_at = running.to_vec();
+    free_at.resize(free_at.len().max(workers.max(1)), 0.0);
+    for secs in queued {
+        let next = free_at
+            .iter_mut()
+            .min_by(|a, b| a.total_cmp(b))
+            .expect("at least one worker");
+        *next += secs;
+    }
+    free_at.into_iter().fold(0.0, f64::max)
+}
@@ -0,0 +1,217 @@
+use crate::runtime::cache::{not_found_error, read_object, write_object};
+use crate::runtime::types::Cache;
+use crate::sources::configs::SourcesConfig;
+use crate::sources::try_adapter_for;
+use crate::sources::version::SourceVersion;
+use crate::types::SourceKind;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+
+/// The latest run of a source that passed its publish gates, kept in R2 so
+/// freshness can be reported between runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestedVersion {
+    pub source_id: String,
+    pub source_version_id: String,
+    pub completed_at: String,
+    /// False when publishing was disabled and the version was left staged.
+    pub published: bool,
+}
+
+impl IngestedVersion {
+    /// R2 key the record for `source` is stored under.
+    pub fn key(source: SourceKind) -> String {
+        format!("ingested/{}.json", source.as_str())
+    }
+
+    /// Whether `upstream_version_id` is newer than this version; `None` when
+    /// the two cannot be ordered.
+    pub fn is_behind(&self, upstream_version_id: &str) -> Option<bool> {
+        SourceVersion::parse(upstream_version_id).is_newer_than(
+            &SourceVersion::from_source_version_id(&self.source_id, &self.source_version_id),
+        )
+    }
+}
+
+/// One row of the container's `/freshness` report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceFreshness {
+    pub source: SourceKind,
+    pub ingested: Option<IngestedVersion>,
+    /// Version the upstream site advertises now.
+    pub upstream_version_id: Option<String>,
+    /// Whether upstream is ahead of the ingested version; absent when either
+    /// is unknown or the two cannot be ordered.
+    pub stale: Option<bool>,
+    /// Why the record could not be read or the probe failed.
+    pub errors: Vec<String>,
+}
+
+/// Serves every fetch straight from upstream. Discovery normally reads the
+/// root page through the cache proxy, which would return the copy cached by
+/// the last run rather than what upstream advertises now.
+struct ProbeCache {
+    client: Client,
+}
+
+#[async_trait]
+impl Cache for ProbeCache {
+    async fn fetch_cached(
+        &self,
+        url: &str,
+        _key: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        self.fetch_uncached(url, throttle_requests_per_second).await
+    }
+
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        _throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let response = self
+            .client
+            .get(url)
+            .header("User-Agent", "fastlaw-ingest/1.0")
+            .send()
+            .await
+            .map_err(|e| format!("Probe request to {url} failed: {e}"))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(not_found_error(url));
+        }
+        if !response.status().is_success() {
+            return Err(format!(
+                "Probe request to {url} failed: {}",
+                response.status()
+            ));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read probe response from {url}: {e}"))
+    }
+
+    async fn read_artifact(&self, _key: &str) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+
+    async fn write_artifact(&self, _key: &str, _content: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub async fn store_ingested_version(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source: SourceKind,
+    version: &IngestedVersion,
+) -> Result<(), String> {
+    let content = serde_json::to_string(version)
+        .map_err(|e| format!("Failed to serialize ingested version: {e}"))?;
+    write_object(
+        client,
+        callback_base,
+        callback_token,
+        &IngestedVersion::key(source),
+        &content,
+    )
+    .await
+}
+
+pub async fn read_ingested_version(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source: SourceKind,
+) -> Result<Option<IngestedVersion>, String> {
+    let key = IngestedVersion::key(source);
+    read_object(client, callback_base, callback_token, &key)
+        .await?
+        .map(|content| {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse {key}: {e}"))
+        })
+        .transpose()
+}
+
+/// Asks `source`'s adapter for the version upstream advertises now. Each
+/// adapter reads only the pages carrying the version (the index page for
+/// most, the collections API pages for USPL), straight from upstream on
+/// every probe.
+pub async fn probe_upstream_version(
+    client: &Client,
+    source: SourceKind,
+    root_url: &str,
+) -> Result<String, String> {
+    let adapter = try_adapter_for(source)?;
+    let cache = ProbeCache {
+        client: client.clone(),
+    };
+    adapter.discover_version(&cache, root_url, None).await
+}
+
+pub async fn source_freshness(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source: SourceKind,
+    root_url: &str,
+) -> SourceFreshness {
+    let mut errors = Vec::new();
+    let ingested = read_ingested_version(client, callback_base, callback_token, source)
+        .await
+        .unwrap_or_else(|err| {
+            errors.push(err);
+            None
+        });
+    let upstream_version_id = probe_upstream_version(client, source, root_url)
+        .await
+        .map_err(|err| errors.push(err))
+        .ok();
+    let stale = match (&ingested, &upstream_version_id) {
+        (Some(ingested), Some(upstream)) => ingested.is_behind(upstream),
+        _ => None,
+    };
+    SourceFreshness {
+        source,
+        ingested,
+        upstream_version_id,
+        stale,
+        errors,
+    }
+}
+
+/// Freshness of every source in `config`, probed concurrently and ordered by
+/// source name.
+pub async fn freshness_report(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    config: &SourcesConfig,
+) -> Vec<SourceFreshness> {
+    let mut probes = JoinSet::new();
+    for (&source, source_config) in &config.sources {
+        let client = client.clone();
+        let callback_base = callback_base.to_string();
+        let callback_token = callback_token.to_string();
+        let root_url = source_config.root_url.clone();
+        probes.spawn(async move {
+            source_freshness(&client, &callback_base, &callback_token, source, &root_url).await
+        });
+    }
+
+    let mut report = Vec::with_capacity(config.sources.len());
+    while let Some(joined) = probes.join_next().await {
+        match joined {
+            Ok(freshness) => report.push(freshness),
+            Err(err) => tracing::error!("[Freshness] Probe task failed: {}", err),
+        }
+    }
+    report.sort_by_key(|freshness| freshness.source.as_str());
+    report
+}
@@ -0,0 +1,311 @@
+use crate::runtime::types::{Logger, NodeSink};
+use crate::types::{NodeLevelStats, NodePayload};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::client::Grpc;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::Request;
+use tonic_prost::ProstCodec;
+
+const STREAM_NODES_PATH: &str = "/fastlaw.ingest.v1.IngestSink/StreamNodes";
+const LOG_PATH: &str = "/fastlaw.ingest.v1.IngestSink/Log";
+/// Batches queued on the stream before senders wait; units beyond this
+/// wait for the channel rather than buffering unboundedly.
+const IN_FLIGHT_BATCHES: usize = 32;
+
+// Hand-written mirrors of proto/ingest_sink.proto; keep tags in sync.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NodeBatch {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub unit_id: String,
+    #[prost(message, repeated, tag = "3")]
+    pub nodes: Vec<Node>,
+    #[prost(message, repeated, tag = "4")]
+    pub level_stats: Vec<LevelStats>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Node {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub source_version_id: String,
+    #[prost(string, optional, tag = "3")]
+    pub parent_id: Option<String>,
+    #[prost(string, tag = "4")]
+    pub level_name: String,
+    #[prost(int32, tag = "5")]
+    pub level_index: i32,
+    #[prost(int32, tag = "6")]
+    pub sort_order: i32,
+    #[prost(string, optional, tag = "7")]
+    pub name: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub path: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub readable_id: Option<String>,
+    #[prost(string, optional, tag = "10")]
+    pub heading_citation: Option<String>,
+    #[prost(string, optional, tag = "11")]
+    pub source_url: Option<String>,
+    #[prost(string, optional, tag = "12")]
+    pub accessed_at: Option<String>,
+    #[prost(bool, tag = "13")]
+    pub review_required: bool,
+    #[prost(string, optional, tag = "14")]
+    pub content_json: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LevelStats {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(uint32, tag = "2")]
+    pub child_count: u32,
+    #[prost(uint32, tag = "3")]
+    pub section_count: u32,
+    #[prost(uint64, tag = "4")]
+    pub word_count: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchAck {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub error: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LogEntry {
+    #[prost(string, tag = "1")]
+    pub level: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(string, optional, tag = "3")]
+    pub context_json: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LogAck {}
+
+impl From<&NodePayload> for Node {
+    fn from(node: &NodePayload) -> Self {
+        let meta = node.meta.clone();
+        Self {
+            id: meta.id,
+            source_version_id: meta.source_version_id,
+            parent_id: meta.parent_id,
+            level_name: meta.level_name,
+            level_index: meta.level_index,
+            sort_order: meta.sort_order,
+            name: meta.name,
+            path: meta.path,
+            readable_id: meta.readable_id,
+            heading_citation: meta.heading_citation,
+            source_url: meta.source_url,
+            accessed_at: meta.accessed_at,
+            review_required: meta.review_required,
+            content_json: node.content.as_ref().map(Value::to_string),
+        }
+    }
+}
+
+impl From<&NodeLevelStats> for LevelStats {
+    fn from(level: &NodeLevelStats) -> Self {
+        Self {
+            id: level.id.clone(),
+            child_count: level.stats.child_count,
+            section_count: level.stats.section_count,
+            word_count: level.stats.word_count,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PendingAcks {
+    waiters: HashMap<u64, oneshot::Sender<Result<(), String>>>,
+    /// Why the ack stream ended; set once no further acks can arrive.
+    closed: Option<String>,
+}
+
+/// gRPC client for the `IngestSink` service. Node batches share one
+/// bidirectional stream for the whole run, and each `send_batch` resolves
+/// when the server acks that batch.
+pub struct GrpcSink {
+    grpc: Grpc<Channel>,
+    authorization: MetadataValue<Ascii>,
+    batches: mpsc::Sender<NodeBatch>,
+    pending: Arc<Mutex<PendingAcks>>,
+    sequence: AtomicU64,
+}
+
+impl GrpcSink {
+    pub async fn connect(endpoint: &str, callback_token: &str) -> Result<Self, String> {
+        let mut channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|e| format!("Invalid gRPC endpoint {endpoint}: {e}"))?;
+        if endpoint.starts_with("https://") {
+            channel = channel
+                .tls_config(ClientTlsConfig::new().with_webpki_roots())
+                .map_err(|e| format!("Failed to configure gRPC TLS: {e}"))?;
+        }
+        let channel = channel
+            .connect()
+            .await
+            .map_err(|e| format!("Failed to connect to gRPC endpoint {endpoint}: {e}"))?;
+        let authorization = format!("Bearer {callback_token}")
+            .parse::<MetadataValue<Ascii>>()
+            .map_err(|e| format!("Invalid callback token for gRPC metadata: {e}"))?;
+
+        let mut grpc = Grpc::new(channel);
+        let (batches, receiver) = mpsc::channel(IN_FLIGHT_BATCHES);
+        let mut request = Request::new(ReceiverStream::new(receiver));
+        request
+            .metadata_mut()
+            .insert("authorization", authorization.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| format!("gRPC endpoint not ready: {e}"))?;
+        let mut acks = grpc
+            .streaming(
+                request,
+                PathAndQuery::from_static(STREAM_NODES_PATH),
+                ProstCodec::<NodeBatch, BatchAck>::default(),
+            )
+            .await
+            .map_err(|status| format!("Failed to open gRPC node stream: {status}"))?
+            .into_inner();
+
+        let pending = Arc::new(Mutex::new(PendingAcks::default()));
+        let dispatch = pending.clone();
+        tokio::spawn(async move {
+            let reason = loop {
+                match acks.message().await {
+                    Ok(Some(ack)) => {
+                        let waiter = dispatch.lock().unwrap().waiters.remove(&ack.sequence);
+                        if let Some(waiter) = waiter {
+                            let result = if ack.error.is_empty() {
+                                Ok(())
+                            } else {
+                                Err(ack.error)
+                            };
+                            let _ = waiter.send(result);
+                        }
+                    }
+                    Ok(None) => break "gRPC node stream closed by server".to_string(),
+                    Err(status) => break format!("gRPC node stream failed: {status}"),
+                }
+            };
+            let mut pending = dispatch.lock().unwrap();
+            for (_, waiter) in pending.waiters.drain() {
+                let _ = waiter.send(Err(reason.clone()));
+            }
+            pending.closed = Some(reason);
+        });
+
+        Ok(Self {
+            grpc,
+            authorization,
+            batches,
+            pending,
+            sequence: AtomicU64::new(0),
+        })
+    }
+}
+
+impl GrpcSink {
+    async fn send(
+        &self,
+        unit_id: &str,
+        nodes: Vec<Node>,
+        level_stats: Vec<LevelStats>,
+    ) -> Result<(), String> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let (ack, acked) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().unwrap();
+            if let Some(reason) = &pending.closed {
+                return Err(reason.clone());
+            }
+            pending.waiters.insert(sequence, ack);
+        }
+
+        let batch = NodeBatch {
+            sequence,
+            unit_id: unit_id.to_string(),
+            nodes,
+            level_stats,
+        };
+        if self.batches.send(batch).await.is_err() {
+            self.pending.lock().unwrap().waiters.remove(&sequence);
+            return Err("gRPC node stream closed".to_string());
+        }
+        acked
+            .await
+            .map_err(|_| format!("gRPC node stream dropped ack for batch {sequence}"))?
+    }
+}
+
+#[async_trait]
+impl NodeSink for GrpcSink {
+    async fn send_batch(&self, unit_id: &str, nodes: &[NodePayload]) -> Result<(), String> {
+        self.send(unit_id, nodes.iter().map(Node::from).collect(), Vec::new())
+            .await
+    }
+
+    async fn send_level_stats(&self, levels: &[NodeLevelStats]) -> Result<(), String> {
+        self.send(
+            "",
+            Vec::new(),
+            levels.iter().map(LevelStats::from).collect(),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Logger for GrpcSink {
+    async fn log(&self, level: &str, message: &str, context: Option<Value>) {
+        match level {
+            "debug" => tracing::debug!("[Container] {}", message),
+            "warn" => tracing::warn!("[Container] {}", message),
+            "error" => tracing::error!("[Container] {}", message),
+            _ => tracing::info!("[Container] {}", message),
+        }
+
+        let mut request = Request::new(LogEntry {
+            level: level.to_string(),
+            message: message.to_string(),
+            context_json: context.as_ref().map(Value::to_string),
+        });
+        request
+            .metadata_mut()
+            .insert("authorization", self.authorization.clone());
+        let mut grpc = self.grpc.clone();
+        let result = match grpc.ready().await {
+            Ok(()) => grpc
+                .unary(
+                    request,
+                    PathAndQuery::from_static(LOG_PATH),
+                    ProstCodec::<LogEntry, LogAck>::default(),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|status| status.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(err) = result {
+            tracing::warn!("[Container] Failed to send log over gRPC: {}", err);
+        }
+    }
+}
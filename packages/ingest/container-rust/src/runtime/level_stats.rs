@@ -0,0 +1,101 @@
+use crate::types::{LevelStats, NodeLevelStats, NodePayload, SectionContent};
+use std::collections::HashMap;
+
+struct Level {
+    id: String,
+    parent_id: Option<String>,
+}
+
+/// Tallies the run's nodes so each structural level node can carry counts
+/// for everything emitted beneath it once every unit has finished.
+///
+/// Level nodes are the ones without content. The tree is shared by all
+/// units, so a level several units emit (a USPL congress, once per volume)
+/// is counted once and covers the nodes each of them emitted under it. It
+/// keeps each level's parent and, per parent, running totals of the nodes
+/// beneath it, never their content.
+#[derive(Default)]
+pub struct LevelTree {
+    levels: Vec<Level>,
+    index: HashMap<String, usize>,
+    children: HashMap<String, u32>,
+    /// Sections and their words directly under each parent id.
+    sections: HashMap<String, (u32, u64)>,
+}
+
+impl LevelTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `node` toward the stats of the levels above it.
+    pub fn observe(&mut self, node: &NodePayload) {
+        if node.content.is_none() && self.index.contains_key(&node.meta.id) {
+            return;
+        }
+        if let Some(parent_id) = &node.meta.parent_id {
+            *self.children.entry(parent_id.clone()).or_default() += 1;
+        }
+
+        let Some(content) = node.content.as_ref() else {
+            self.index.insert(node.meta.id.clone(), self.levels.len());
+            self.levels.push(Level {
+                id: node.meta.id.clone(),
+                parent_id: node.meta.parent_id.clone(),
+            });
+            return;
+        };
+        let Some(parent_id) = node.meta.parent_id.clone() else {
+            return;
+        };
+
+        let words = serde_json::from_value::<SectionContent>(content.clone())
+            .map(|content| {
+                content
+                    .blocks
+                    .iter()
+                    .filter_map(|block| block.content.as_deref())
+                    .map(|text| text.split_whitespace().count() as u64)
+                    .sum()
+            })
+            .unwrap_or(0);
+        let totals = self.sections.entry(parent_id).or_default();
+        totals.0 += 1;
+        totals.1 += words;
+    }
+
+    /// Returns the stats of every observed level node, in emission order.
+    pub fn finish(&mut self) -> Vec<NodeLevelStats> {
+        let mut stats = self
+            .levels
+            .iter()
+            .map(|level| LevelStats {
+                child_count: self.children.get(&level.id).copied().unwrap_or_default(),
+                ..LevelStats::default()
+            })
+            .collect::<Vec<_>>();
+
+        for (parent_id, (sections, words)) in std::mem::take(&mut self.sections) {
+            let mut parent = self.index.get(&parent_id);
+            while let Some(&index) = parent {
+                stats[index].section_count += sections;
+                stats[index].word_count += words;
+                parent = self.levels[index]
+                    .parent_id
+                    .as_deref()
+                    .and_then(|id| self.index.get(id));
+            }
+        }
+
+        self.index.clear();
+        self.children.clear();
+        std::mem::take(&mut self.levels)
+            .into_iter()
+            .zip(stats)
+            .map(|(level, stats)| NodeLevelStats {
+                id: level.id,
+                stats,
+            })
+            .collect()
+    }
+}
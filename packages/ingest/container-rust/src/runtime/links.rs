@@ -0,0 +1,78 @@
+use crate::sources::SourceAdapter;
+use crate::types::{NodePayload, SectionContent};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+static MARKDOWN_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\]\(([^)\s]+)\)").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenLink {
+    pub node_id: String,
+    pub href: String,
+}
+
+/// Collects the link targets emitted during a run and the internal links
+/// inlined into node content, so unresolved links can be reported at the end.
+#[derive(Default)]
+pub struct LinkChecker {
+    targets: Mutex<HashSet<String>>,
+    links: Mutex<Vec<BrokenLink>>,
+}
+
+impl LinkChecker {
+    /// `known_targets` are hrefs that resolve outside this run, such as nodes
+    /// from the prior source version.
+    pub fn new(known_targets: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            targets: Mutex::new(known_targets.into_iter().collect()),
+            links: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn observe(&self, adapter: &dyn SourceAdapter, node: &NodePayload) {
+        if let Some(target) = adapter.link_target(&node.meta) {
+            self.targets.lock().unwrap().insert(target);
+        }
+
+        let Some(content) = node
+            .content
+            .as_ref()
+            .and_then(|value| serde_json::from_value::<SectionContent>(value.clone()).ok())
+        else {
+            return;
+        };
+
+        let mut links = self.links.lock().unwrap();
+        for text in content
+            .blocks
+            .iter()
+            .filter_map(|block| block.content.as_deref())
+        {
+            for captures in MARKDOWN_LINK_RE.captures_iter(text) {
+                let href = captures[1].split('#').next().unwrap_or_default();
+                if adapter.is_internal_link(href) {
+                    links.push(BrokenLink {
+                        node_id: node.meta.id.clone(),
+                        href: href.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Internal links whose target was never emitted, in discovery order.
+    pub fn broken_links(&self) -> Vec<BrokenLink> {
+        let targets = self.targets.lock().unwrap();
+        self.links
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|link| !targets.contains(&link.href))
+            .cloned()
+            .collect()
+    }
+}
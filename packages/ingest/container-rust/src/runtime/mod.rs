@@ -1,5 +1,26 @@
+pub mod blocking;
 pub mod cache;
 pub mod callbacks;
+pub mod corrections;
+pub mod coverage;
+pub mod crash;
+pub mod duplicates;
+pub mod eta;
+pub mod freshness;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod level_stats;
+pub mod links;
 pub mod logging;
+#[cfg(feature = "nats")]
+pub mod nats;
+pub mod node_export;
 pub mod orchestrator;
+pub mod ordering;
+pub mod politeness;
+pub mod provenance;
+pub mod report;
+pub mod review;
+pub mod topics;
 pub mod types;
+pub mod wayback;
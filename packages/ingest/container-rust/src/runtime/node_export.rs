@@ -0,0 +1,201 @@
+use crate::runtime::cache::{read_object, write_object};
+use crate::runtime::types::NodeSink;
+use crate::types::{NodeLevelStats, NodePayload};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Index of a source version's JSONL node export: the chunks written while
+/// its nodes were sent, in the order a dataset lists them. Covers the units
+/// of the run that wrote it, so a partial run exports only its units.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeExport {
+    pub source_version_id: String,
+    pub chunks: Vec<String>,
+    /// JSONL of the run's level stats, one [`NodeLevelStats`] per line; kept
+    /// out of `chunks` since its lines are not nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level_stats: Option<String>,
+}
+
+impl NodeExport {
+    /// R2 key the index for `source_version_id` is stored under.
+    pub fn key(source_version_id: &str) -> String {
+        format!("exports/{source_version_id}/index.json")
+    }
+
+    fn chunk_key(source_version_id: &str, unit_id: &str, index: usize) -> String {
+        format!("exports/{source_version_id}/{unit_id}/{index:06}.jsonl")
+    }
+
+    pub async fn read(
+        client: &Client,
+        callback_base: &str,
+        callback_token: &str,
+        source_version_id: &str,
+    ) -> Result<Option<Self>, String> {
+        let key = Self::key(source_version_id);
+        read_object(client, callback_base, callback_token, &key)
+            .await?
+            .map(|content| {
+                serde_json::from_str(&content)
+                    .map_err(|e| format!("Failed to parse export index {key}: {e}"))
+            })
+            .transpose()
+    }
+}
+
+/// Node sink that also writes every batch it forwards to `inner` as a JSONL
+/// chunk of the source version's export. Level stats are held until
+/// [`finish`](Self::finish) and written beside the chunks.
+pub struct ExportingSink {
+    inner: Arc<dyn NodeSink>,
+    client: Client,
+    callback_base: String,
+    callback_token: String,
+    source_version_id: String,
+    root: Option<NodePayload>,
+    chunks_per_unit: Mutex<BTreeMap<String, usize>>,
+    level_stats: Mutex<Vec<NodeLevelStats>>,
+}
+
+impl ExportingSink {
+    /// `root` is the source version's root node when this run created it; it
+    /// leads the export since it never passes through the sink.
+    pub fn new(
+        inner: Arc<dyn NodeSink>,
+        client: Client,
+        callback_base: &str,
+        callback_token: &str,
+        source_version_id: &str,
+        root: Option<NodePayload>,
+    ) -> Self {
+        Self {
+            inner,
+            client,
+            callback_base: callback_base.to_string(),
+            callback_token: callback_token.to_string(),
+            source_version_id: source_version_id.to_string(),
+            root,
+            chunks_per_unit: Mutex::new(BTreeMap::new()),
+            level_stats: Mutex::new(Vec::new()),
+        }
+    }
+
+    async fn write_chunk(&self, key: &str, nodes: &[NodePayload]) -> Result<(), String> {
+        let mut content = String::new();
+        for node in nodes {
+            content.push_str(
+                &serde_json::to_string(node)
+                    .map_err(|e| format!("Failed to serialize node {}: {e}", node.meta.id))?,
+            );
+            content.push('\n');
+        }
+        self.write(key, &content).await
+    }
+
+    async fn write(&self, key: &str, content: &str) -> Result<(), String> {
+        write_object(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            key,
+            content,
+        )
+        .await
+    }
+
+    /// Writes the root chunk, the level stats and the export index once every
+    /// unit is done.
+    pub async fn finish(&self) -> Result<NodeExport, String> {
+        let mut chunks = Vec::new();
+        if let Some(root) = &self.root {
+            let key = format!("exports/{}/root.jsonl", self.source_version_id);
+            self.write_chunk(&key, std::slice::from_ref(root)).await?;
+            chunks.push(key);
+        }
+        let chunks_per_unit = self
+            .chunks_per_unit
+            .lock()
+            .map_err(|e| e.to_string())?
+            .clone();
+        for (unit_id, count) in chunks_per_unit {
+            chunks.extend(
+                (0..count)
+                    .map(|index| NodeExport::chunk_key(&self.source_version_id, &unit_id, index)),
+            );
+        }
+
+        let level_stats = std::mem::take(&mut *self.level_stats.lock().map_err(|e| e.to_string())?);
+        let level_stats_key = if level_stats.is_empty() {
+            None
+        } else {
+            let mut content = String::new();
+            for level in &level_stats {
+                content.push_str(
+                    &serde_json::to_string(level)
+                        .map_err(|e| format!("Failed to serialize stats of {}: {e}", level.id))?,
+                );
+                content.push('\n');
+            }
+            let key = format!("exports/{}/level-stats.jsonl", self.source_version_id);
+            self.write(&key, &content).await?;
+            Some(key)
+        };
+
+        let export = NodeExport {
+            source_version_id: self.source_version_id.clone(),
+            chunks,
+            level_stats: level_stats_key,
+        };
+        let content = serde_json::to_string(&export)
+            .map_err(|e| format!("Failed to serialize export index: {e}"))?;
+        write_object(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            &NodeExport::key(&self.source_version_id),
+            &content,
+        )
+        .await?;
+        Ok(export)
+    }
+}
+
+#[async_trait]
+impl NodeSink for ExportingSink {
+    async fn send_batch(&self, unit_id: &str, nodes: &[NodePayload]) -> Result<(), String> {
+        self.inner.send_batch(unit_id, nodes).await?;
+        // A unit sends its batches one at a time, so its count can't move
+        // while the chunk is written; it only advances once the chunk exists.
+        let index = self
+            .chunks_per_unit
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(unit_id)
+            .copied()
+            .unwrap_or_default();
+        self.write_chunk(
+            &NodeExport::chunk_key(&self.source_version_id, unit_id, index),
+            nodes,
+        )
+        .await?;
+        self.chunks_per_unit
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(unit_id.to_string(), index + 1);
+        Ok(())
+    }
+
+    async fn send_level_stats(&self, levels: &[NodeLevelStats]) -> Result<(), String> {
+        self.inner.send_level_stats(levels).await?;
+        self.level_stats
+            .lock()
+            .map_err(|e| e.to_string())?
+            .extend_from_slice(levels);
+        Ok(())
+    }
+}
@@ -1,38 +1,163 @@
-use crate::runtime::cache::ensure_cached;
+use crate::runtime::cache::{
+    artifact_namespace, ensure_cached, not_found_error, read_artifact, read_object,
+    read_unit_manifest, write_artifact, write_object, write_unit_manifest,
+};
 use crate::runtime::callbacks::{
-    post_ensure_source_version, post_node_batch, post_unit_progress, post_unit_start,
+    post_ensure_source_version, post_ingest_report, post_level_stats, post_node_batch,
+    post_publish_source_version, post_unit_progress, post_unit_start,
 };
+use crate::runtime::corrections::CorrectionOverlay;
+use crate::runtime::coverage::select_units;
+use crate::runtime::crash::{with_crash_recorder, CrashRecorder, RecordingLogger};
+use crate::runtime::duplicates::DuplicateDetector;
+use crate::runtime::eta::{JobProgress, UnitTimings};
+use crate::runtime::freshness::{store_ingested_version, IngestedVersion};
+#[cfg(feature = "grpc")]
+use crate::runtime::grpc::GrpcSink;
+use crate::runtime::level_stats::LevelTree;
+use crate::runtime::links::LinkChecker;
 use crate::runtime::logging::{log_event_with_callback, LogLevel};
+#[cfg(feature = "nats")]
+use crate::runtime::nats::NatsNodeSink;
+use crate::runtime::node_export::ExportingSink;
+use crate::runtime::ordering::ParentOrderedStore;
+use crate::runtime::politeness::PolitenessTracker;
+use crate::runtime::provenance::{FetchLog, FetchRecord, ProvenanceCache, UnitManifest};
+use crate::runtime::report::IngestReport;
+use crate::runtime::review::{review_node, ReviewRegistry, ReviewSource};
+use crate::runtime::topics::{tag_topics, KeywordClassifier, TopicClassifier};
 use crate::runtime::types::{
-    BlobStore, BuildContext, Cache, IngestContext, Logger, NodeStore, QueueItem, UrlQueue,
+    BlobStore, BuildContext, Cache, IngestContext, Logger, NodeSink, NodeStore, QueueItem, UrlQueue,
+};
+use crate::runtime::wayback::{ItemArchive, WaybackCache, WaybackLog};
+use crate::sources::configs::{
+    configured_extraction_options_hash, configured_heading_case, configured_slug_stop_words,
+    HeadingCase, SourcesConfig,
+};
+use crate::sources::paths::{PathIndex, SlugIndex};
+use crate::sources::try_adapter_for;
+use crate::sources::version::{version_regression, SourceVersion};
+use crate::types::{
+    IngestConfig, IngestProfile, NodeLevelStats, NodePayload, OutputTransport, SectionContent,
+    SectionMetadata, SourceKind, WaybackFallback,
 };
-use crate::sources::adapter_for;
-use crate::sources::configs::SourcesConfig;
-use crate::types::{IngestConfig, NodePayload};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::json;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 const BATCH_SIZE: usize = 200;
 const UNIT_CONCURRENCY: usize = 8;
+/// Each running unit holds its fetched source and the nodes it hasn't sent
+/// yet, so peak memory grows with the pool; requests above this are capped.
+const MAX_UNIT_CONCURRENCY: usize = 16;
+const DEFAULT_UNIT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
 
-#[derive(Clone)]
-struct HttpNodeStore {
+struct HttpNodeSink {
     client: Client,
     callback_base: String,
     callback_token: String,
+}
+
+#[async_trait]
+impl NodeSink for HttpNodeSink {
+    async fn send_batch(&self, unit_id: &str, nodes: &[NodePayload]) -> Result<(), String> {
+        post_node_batch(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            unit_id,
+            nodes,
+        )
+        .await
+    }
+
+    async fn send_level_stats(&self, levels: &[NodeLevelStats]) -> Result<(), String> {
+        post_level_stats(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            levels,
+        )
+        .await
+    }
+}
+
+#[derive(Clone)]
+struct BufferedNodeStore {
+    sink: Arc<dyn NodeSink>,
     unit_id: String,
     buffer: Arc<Mutex<Vec<NodePayload>>>,
+    levels: Arc<Mutex<LevelTree>>,
+    paths: Arc<Mutex<PathIndex>>,
+    slugs: Arc<Mutex<SlugIndex>>,
+    review: Arc<ReviewRegistry>,
+    adapter: &'static (dyn crate::sources::SourceAdapter + Send + Sync),
+    links: Arc<LinkChecker>,
+    duplicates: Arc<DuplicateDetector>,
+    topics: Option<Arc<dyn TopicClassifier>>,
+    corrections: Arc<CorrectionOverlay>,
+    heading_case: HeadingCase,
+    archive: Arc<ItemArchive>,
 }
 
 #[async_trait]
-impl NodeStore for HttpNodeStore {
-    async fn insert_node(&self, node: NodePayload) -> Result<(), String> {
+impl NodeStore for BufferedNodeStore {
+    async fn insert_node(&self, mut node: NodePayload) -> Result<(), String> {
+        if node.meta.level_name != "section" {
+            if let Some(name) = node.meta.name.as_mut() {
+                *name = self.heading_case.apply(name);
+            }
+        }
+        let path_collision = self
+            .paths
+            .lock()
+            .map_err(|e| e.to_string())?
+            .claim(&mut node.meta);
+        if node.content.is_some() {
+            let slug = self
+                .slugs
+                .lock()
+                .map_err(|e| e.to_string())?
+                .assign(&node.meta);
+            if let Some(slug) = slug {
+                set_slug(&mut node, slug)?;
+            }
+        }
+        self.archive.mark(&mut node)?;
+        if let Some(topics) = &self.topics {
+            tag_topics(topics.as_ref(), &mut node).await?;
+        }
+        for stale in self.corrections.apply(&mut node)? {
+            tracing::warn!(
+                "[Orchestrator] Correction for {} no longer matches: {:?}",
+                node.meta.id,
+                stale.text
+            );
+            self.review.flag(
+                &node.meta.id,
+                ReviewSource::Validator,
+                format!("editorial correction no longer matches {:?}", stale.text),
+            );
+        }
+        review_node(&mut node, &self.review);
+        if let Some(reason) = path_collision {
+            tracing::warn!("[Orchestrator] {}: {reason}", node.meta.id);
+            self.review
+                .flag(&node.meta.id, ReviewSource::AnomalyDetector, reason);
+            node.meta.review_required = true;
+        }
+        self.links.observe(self.adapter, &node);
+        self.duplicates.observe(&node);
+        self.levels
+            .lock()
+            .map_err(|e| e.to_string())?
+            .observe(&node);
         let batch = {
             let mut buffer = self.buffer.lock().map_err(|e| e.to_string())?;
             buffer.push(node);
@@ -44,37 +169,15 @@ impl NodeStore for HttpNodeStore {
         };
 
         if let Some(batch) = batch {
-            post_node_batch(
-                &self.client,
-                &self.callback_base,
-                &self.callback_token,
-                &self.unit_id,
-                &batch,
-            )
-            .await?;
+            self.sink.send_batch(&self.unit_id, &batch).await?;
         }
         Ok(())
     }
 
     async fn flush(&self) -> Result<(), String> {
-        let batch = {
-            let mut buffer = self.buffer.lock().map_err(|e| e.to_string())?;
-            if buffer.is_empty() {
-                None
-            } else {
-                Some(std::mem::take(&mut *buffer))
-            }
-        };
-
-        if let Some(batch) = batch {
-            post_node_batch(
-                &self.client,
-                &self.callback_base,
-                &self.callback_token,
-                &self.unit_id,
-                &batch,
-            )
-            .await?;
+        let batch = std::mem::take(&mut *self.buffer.lock().map_err(|e| e.to_string())?);
+        for chunk in batch.chunks(BATCH_SIZE) {
+            self.sink.send_batch(&self.unit_id, chunk).await?;
         }
         Ok(())
     }
@@ -93,6 +196,9 @@ struct HttpCache {
     client: Client,
     callback_base: String,
     callback_token: String,
+    politeness: Arc<PolitenessTracker>,
+    artifact_namespace: String,
+    fetch_log: FetchLog,
 }
 
 #[async_trait]
@@ -111,15 +217,18 @@ impl Cache for HttpCache {
             url.to_lowercase().ends_with(".zip"),
             key,
             throttle_requests_per_second,
+            &self.politeness,
         )
         .await?;
 
-        cache_result.ok_or_else(|| {
+        let (content, record) = cache_result.ok_or_else(|| {
             format!(
                 "Cache proxy returned 422 for URL (likely HTML response): {}",
                 url
             )
-        })
+        })?;
+        self.fetch_log.record(record);
+        Ok(content)
     }
 
     async fn fetch_uncached(
@@ -134,17 +243,54 @@ impl Cache for HttpCache {
             .send()
             .await
             .map_err(|e| format!("Direct request to {url} failed: {e}"))?;
+        self.politeness
+            .record(url, response.status().as_u16(), None);
 
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(not_found_error(url));
+        }
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
             return Err(format!("Direct request failed: {status} {text}"));
         }
 
-        response
-            .text()
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let bytes = response
+            .bytes()
             .await
-            .map_err(|e| format!("Failed to read direct response text from {url}: {e}"))
+            .map_err(|e| format!("Failed to read direct response text from {url}: {e}"))?;
+        self.fetch_log
+            .record(FetchRecord::new(url, None, status, &bytes).with_headers(&headers));
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        read_artifact(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            &self.artifact_namespace,
+            key,
+        )
+        .await
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        write_artifact(
+            &self.client,
+            &self.callback_base,
+            &self.callback_token,
+            &self.artifact_namespace,
+            key,
+            content,
+        )
+        .await
+    }
+
+    fn take_fetch_record(&self, url: &str) -> Option<FetchRecord> {
+        self.fetch_log.take(url)
     }
 }
 
@@ -192,6 +338,15 @@ impl SimpleUrlQueue {
         let mut items = self.items.lock().unwrap();
         items.pop_front()
     }
+
+    /// Items still queued, front first.
+    pub fn snapshot(&self) -> Vec<QueueItem> {
+        let items = self
+            .items
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        items.iter().cloned().collect()
+    }
 }
 
 impl UrlQueue for SimpleUrlQueue {
@@ -221,7 +376,37 @@ fn create_unit_roots(config: &IngestConfig, root_node_id: &str) -> Vec<QueueItem
     Vec::new()
 }
 
-async fn process_unit_root(
+fn set_slug(node: &mut NodePayload, slug: String) -> Result<(), String> {
+    let Some(value) = node.content.as_ref() else {
+        return Ok(());
+    };
+    let mut content: SectionContent = serde_json::from_value(value.clone())
+        .map_err(|e| format!("Failed to read content of {}: {e}", node.meta.id))?;
+    content
+        .metadata
+        .get_or_insert_with(SectionMetadata::default)
+        .slug = Some(slug);
+    node.content = Some(
+        serde_json::to_value(&content)
+            .map_err(|e| format!("Failed to write content of {}: {e}", node.meta.id))?,
+    );
+    Ok(())
+}
+
+fn unit_id_of(unit_root: &QueueItem) -> String {
+    unit_root.metadata["unit_id"]
+        .as_str()
+        .unwrap_or("root")
+        .to_string()
+}
+
+enum UnitOutcome {
+    Completed,
+    Failed(String),
+}
+
+/// Run-wide state every unit shares, built once per run.
+struct UnitRunContext {
     adapter: &'static (dyn crate::sources::SourceAdapter + Send + Sync),
     client: Client,
     callback_base: String,
@@ -229,112 +414,414 @@ async fn process_unit_root(
     source_version_id: String,
     root_node_id: String,
     accessed_at: String,
+    profile: IngestProfile,
     blob_store: Arc<dyn BlobStore>,
     cache_store: Arc<dyn Cache>,
+    artifact_namespace: String,
     logger: Arc<dyn Logger>,
+    sink: Arc<dyn NodeSink>,
+    review: Arc<ReviewRegistry>,
+    links: Arc<LinkChecker>,
+    duplicates: Arc<DuplicateDetector>,
+    levels: Arc<Mutex<LevelTree>>,
+    topics: Option<Arc<dyn TopicClassifier>>,
+    corrections: Arc<CorrectionOverlay>,
+    heading_case: HeadingCase,
+    slug_stop_words: Vec<String>,
+    wayback: Option<WaybackFallback>,
+    wayback_log: Arc<WaybackLog>,
+    progress: Arc<JobProgress>,
+    crash: Arc<CrashRecorder>,
+    unit_timeout: Duration,
+}
+
+async fn process_unit_root(
+    run: Arc<UnitRunContext>,
+    cancel: CancellationToken,
     unit_root: QueueItem,
-) -> Result<(), String> {
-    let unit_id = unit_root.metadata["unit_id"]
-        .as_str()
-        .unwrap_or("root")
-        .to_string();
+) -> Result<UnitOutcome, String> {
+    let adapter = run.adapter;
+    let (client, callback_base, callback_token) =
+        (&run.client, &run.callback_base, &run.callback_token);
+    let unit_id = unit_id_of(&unit_root);
+    run.progress.start(&unit_id);
     let unit_label = adapter.unit_label(&unit_root);
     let unit_sort_order = unit_root.metadata["sort_order"].as_i64().unwrap_or(0) as i32;
 
-    post_unit_start(&client, &callback_base, &callback_token, &unit_id, 0).await?;
+    post_unit_start(client, callback_base, callback_token, &unit_id, 0).await?;
+
+    let provenance = Arc::new(ProvenanceCache::new(run.cache_store.clone()));
+    let item_archive = Arc::new(ItemArchive::default());
+    let cache: Arc<dyn Cache> = match &run.wayback {
+        Some(fallback) => {
+            let prior_urls: HashSet<String> = match read_unit_manifest(
+                client,
+                callback_base,
+                callback_token,
+                &fallback.prior_source_version_id,
+                &unit_id,
+            )
+            .await
+            {
+                Ok(manifest) => manifest
+                    .map(|manifest| manifest.fetches.into_iter().map(|f| f.url).collect())
+                    .unwrap_or_default(),
+                Err(err) => {
+                    tracing::warn!(
+                        "[Orchestrator] No Wayback fallback for {}: {}",
+                        unit_label,
+                        err
+                    );
+                    HashSet::new()
+                }
+            };
+            Arc::new(WaybackCache::new(
+                provenance.clone(),
+                prior_urls,
+                fallback.timestamp.clone(),
+                item_archive.clone(),
+                run.wayback_log.clone(),
+            ))
+        }
+        None => provenance.clone(),
+    };
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let unit_url = unit_root.url.clone();
+    let manifest = |completed: bool| UnitManifest {
+        unit_id: unit_id.clone(),
+        source_version_id: run.source_version_id.clone(),
+        url: unit_url.clone(),
+        parser_version: adapter.parser_version(),
+        artifact_namespace: run.artifact_namespace.clone(),
+        started_at: started_at.clone(),
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        completed,
+        fetches: provenance.fetches(),
+    };
 
     let queue = Arc::new(SimpleUrlQueue::new());
     queue.enqueue(unit_root);
+    run.crash.start_unit(&unit_id, queue.clone());
 
-    let node_store = HttpNodeStore {
-        client: client.clone(),
-        callback_base: callback_base.clone(),
-        callback_token: callback_token.clone(),
+    let node_store = BufferedNodeStore {
+        sink: run.sink.clone(),
         unit_id: unit_id.clone(),
         buffer: Arc::new(Mutex::new(Vec::with_capacity(BATCH_SIZE))),
+        levels: run.levels.clone(),
+        paths: Arc::new(Mutex::new(PathIndex::new())),
+        slugs: Arc::new(Mutex::new(SlugIndex::new(run.slug_stop_words.clone()))),
+        review: run.review.clone(),
+        adapter,
+        links: run.links.clone(),
+        duplicates: run.duplicates.clone(),
+        topics: run.topics.clone(),
+        corrections: run.corrections.clone(),
+        heading_case: run.heading_case,
+        archive: item_archive.clone(),
     };
+    let node_store = ParentOrderedStore::new(node_store, &run.root_node_id);
 
-    while let Some(item) = queue.pop() {
-        let build_context = BuildContext {
-            source_version_id: &source_version_id,
-            root_node_id: &root_node_id,
-            accessed_at: &accessed_at,
-            unit_sort_order,
-        };
+    // The timeout covers the unit as a whole, not each item, so a unit that
+    // keeps queueing slow pages can't run past it.
+    let unit_timeout = run.unit_timeout;
+    let unit_cancel = cancel.child_token();
+    let current_item: Mutex<Option<QueueItem>> = Mutex::new(None);
+    let items = async {
+        while let Some(item) = queue.pop() {
+            if cancel.is_cancelled() {
+                return Err("Ingest cancelled".to_string());
+            }
 
-        let mut context = IngestContext {
-            build: build_context,
-            nodes: Box::new(node_store.clone()),
-            blobs: blob_store.clone(),
-            cache: cache_store.clone(),
-            queue: queue.clone(),
-            logger: logger.clone(),
-        };
+            item_archive.begin_item();
+            run.crash.begin_item(&unit_id, &item);
+            *current_item.lock().map_err(|e| e.to_string())? = Some(item.clone());
+            let build_context = BuildContext {
+                source_version_id: &run.source_version_id,
+                root_node_id: &run.root_node_id,
+                accessed_at: &run.accessed_at,
+                unit_sort_order,
+                profile: run.profile,
+            };
 
-        if let Err(err) = adapter.process_url(&mut context, &item).await {
-            tracing::error!("[Orchestrator] {} failed: {}", unit_label, err);
-            node_store.flush().await?;
-            post_unit_progress(
-                &client,
-                &callback_base,
-                &callback_token,
-                &unit_id,
-                "error",
-                Some(&err),
+            let mut context = IngestContext {
+                build: build_context,
+                nodes: Box::new(node_store.clone()),
+                blobs: run.blob_store.clone(),
+                cache: cache.clone(),
+                queue: queue.clone(),
+                logger: run.logger.clone(),
+                cancel: unit_cancel.child_token(),
+            };
+            adapter.process_url(&mut context, &item).await?;
+        }
+        Ok(())
+    };
+
+    let result = match tokio::time::timeout(unit_timeout, items).await {
+        Ok(result) => result,
+        Err(_) => {
+            // Stops blocking parses the dropped future left running.
+            unit_cancel.cancel();
+            let item = current_item.lock().map_err(|e| e.to_string())?.take();
+            log_event_with_callback(
+                client,
+                Some(callback_base),
+                Some(callback_token),
+                LogLevel::Error,
+                &format!("{} timed out after {}s", unit_label, unit_timeout.as_secs()),
+                Some(json!({
+                    "event": "unit_timeout",
+                    "unitId": unit_id,
+                    "timeoutSecs": unit_timeout.as_secs(),
+                    "item": item.as_ref().map(|item| json!({
+                        "url": item.url,
+                        "parentId": item.parent_id,
+                        "levelName": item.level_name,
+                        "levelIndex": item.level_index,
+                        "metadata": item.metadata,
+                    })),
+                })),
             )
             .await;
-            return Ok(());
+            Err(match item {
+                Some(item) => format!(
+                    "Timed out after {}s processing {}",
+                    unit_timeout.as_secs(),
+                    item.url
+                ),
+                None => format!("Timed out after {}s", unit_timeout.as_secs()),
+            })
         }
+    };
+
+    if let Err(err) = result {
+        tracing::error!("[Orchestrator] {} failed: {}", unit_label, err);
+        node_store.flush().await?;
+        store_unit_manifest(client, callback_base, callback_token, &manifest(false)).await;
+        run.progress.finish(&unit_id, false);
+        run.crash.finish_unit(&unit_id);
+        post_unit_progress(
+            client,
+            callback_base,
+            callback_token,
+            &unit_id,
+            "error",
+            Some(&err),
+            &run.progress.snapshot(),
+        )
+        .await;
+        return Ok(UnitOutcome::Failed(unit_id));
     }
 
     node_store.flush().await?;
+    store_unit_manifest(client, callback_base, callback_token, &manifest(true)).await;
+    run.progress.finish(&unit_id, true);
+    run.crash.finish_unit(&unit_id);
     post_unit_progress(
-        &client,
-        &callback_base,
-        &callback_token,
+        client,
+        callback_base,
+        callback_token,
         &unit_id,
         "completed",
         None,
+        &run.progress.snapshot(),
     )
     .await;
 
-    Ok(())
+    Ok(UnitOutcome::Completed)
+}
+
+/// A missing manifest shouldn't fail a unit whose nodes were already sent,
+/// so write failures are logged rather than returned.
+async fn store_unit_manifest(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    manifest: &UnitManifest,
+) {
+    if let Err(err) = write_unit_manifest(client, callback_base, callback_token, manifest).await {
+        log_event_with_callback(
+            client,
+            Some(callback_base),
+            Some(callback_token),
+            LogLevel::Warn,
+            &format!(
+                "Failed to store provenance manifest for {}: {}",
+                manifest.unit_id, err
+            ),
+            None,
+        )
+        .await;
+    }
+}
+
+/// Timings from the last run of `source`. Missing or unreadable timings only
+/// cost the ETA, so errors are logged rather than returned.
+async fn load_unit_timings(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source: SourceKind,
+) -> UnitTimings {
+    let key = UnitTimings::key(source);
+    let content = match read_object(client, callback_base, callback_token, &key).await {
+        Ok(content) => content,
+        Err(err) => {
+            tracing::warn!("[Orchestrator] Failed to read unit timings: {}", err);
+            None
+        }
+    };
+    content
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+async fn store_unit_timings(
+    client: &Client,
+    callback_base: &str,
+    callback_token: &str,
+    source: SourceKind,
+    timings: &UnitTimings,
+) {
+    let result = match serde_json::to_string(timings) {
+        Ok(content) => {
+            write_object(
+                client,
+                callback_base,
+                callback_token,
+                &UnitTimings::key(source),
+                &content,
+            )
+            .await
+        }
+        Err(err) => Err(format!("Failed to serialize unit timings: {err}")),
+    };
+    if let Err(err) = result {
+        tracing::warn!("[Orchestrator] Failed to store unit timings: {}", err);
+    }
+}
+
+async fn output_transport(
+    config: &IngestConfig,
+    client: &Client,
+) -> Result<(Arc<dyn NodeSink>, Arc<dyn Logger>), String> {
+    let http_logger: Arc<dyn Logger> = Arc::new(HttpLogger {
+        client: client.clone(),
+        callback_base: config.callback_base.clone(),
+        callback_token: config.callback_token.clone(),
+    });
+    match &config.transport {
+        OutputTransport::Http => Ok((
+            Arc::new(HttpNodeSink {
+                client: client.clone(),
+                callback_base: config.callback_base.clone(),
+                callback_token: config.callback_token.clone(),
+            }),
+            http_logger,
+        )),
+        #[cfg(feature = "grpc")]
+        OutputTransport::Grpc { endpoint } => {
+            let sink = Arc::new(GrpcSink::connect(endpoint, &config.callback_token).await?);
+            Ok((sink.clone(), sink))
+        }
+        #[cfg(not(feature = "grpc"))]
+        OutputTransport::Grpc { .. } => {
+            Err("gRPC transport requested but this build lacks the `grpc` feature".to_string())
+        }
+        #[cfg(feature = "nats")]
+        OutputTransport::Nats { url, subject } => Ok((
+            Arc::new(NatsNodeSink::connect(url, subject).await?),
+            http_logger,
+        )),
+        #[cfg(not(feature = "nats"))]
+        OutputTransport::Nats { .. } => {
+            Err("NATS transport requested but this build lacks the `nats` feature".to_string())
+        }
+    }
 }
 
 pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
+    let progress = Arc::new(JobProgress::new(&config.source_id));
+    ingest_source_with_progress(config, progress, Arc::new(CrashRecorder::new())).await
+}
+
+/// Runs the ingest, reporting unit progress and the ETA through `progress`
+/// and keeping the in-flight state a crash dump needs in `crash`, including
+/// any panic the job raises.
+pub async fn ingest_source_with_progress(
+    config: IngestConfig,
+    progress: Arc<JobProgress>,
+    crash: Arc<CrashRecorder>,
+) -> Result<(), String> {
+    with_crash_recorder(crash.clone(), run_ingest(config, progress, crash)).await
+}
+
+async fn run_ingest(
+    config: IngestConfig,
+    progress: Arc<JobProgress>,
+    crash: Arc<CrashRecorder>,
+) -> Result<(), String> {
     let client = Client::builder()
         .connect_timeout(Duration::from_secs(10))
         .timeout(Duration::from_secs(45))
         .build()
         .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
 
-    let adapter = adapter_for(config.source);
+    let adapter = try_adapter_for(config.source)?;
 
     let blob_store: Arc<dyn BlobStore> = Arc::new(DummyBlobStore);
+    let politeness = Arc::new(PolitenessTracker::new());
+    let run_artifact_namespace = artifact_namespace(
+        config.source,
+        adapter.parser_version(),
+        &configured_extraction_options_hash(config.source),
+    );
     let cache_store: Arc<dyn Cache> = Arc::new(HttpCache {
         client: client.clone(),
         callback_base: config.callback_base.clone(),
         callback_token: config.callback_token.clone(),
+        politeness: politeness.clone(),
+        artifact_namespace: run_artifact_namespace.clone(),
+        fetch_log: FetchLog::default(),
     });
 
-    let logger: Arc<dyn Logger> = Arc::new(HttpLogger {
-        client: client.clone(),
-        callback_base: config.callback_base.clone(),
-        callback_token: config.callback_token.clone(),
+    let (sink, logger) = output_transport(&config, &client).await?;
+    let logger: Arc<dyn Logger> = Arc::new(RecordingLogger {
+        inner: logger,
+        crash: crash.clone(),
     });
 
+    let review = Arc::new(ReviewRegistry::for_profile(config.profile));
+    let links = Arc::new(LinkChecker::new(
+        config.known_link_targets.clone().unwrap_or_default(),
+    ));
+    let duplicates = Arc::new(DuplicateDetector::new());
+    let levels = Arc::new(Mutex::new(LevelTree::new()));
+    let topics = config
+        .tag_topics
+        .then(|| Arc::new(KeywordClassifier::default()) as Arc<dyn TopicClassifier>);
+    let corrections = Arc::new(CorrectionOverlay::load_default()?);
+    let wayback_log = Arc::new(WaybackLog::new());
+    let cancel = CancellationToken::new();
+
     let accessed_at = chrono::Utc::now().to_rfc3339();
     let mut source_version_id: Option<String> = config.source_version_id.clone();
     let mut root_node_id: Option<String> = config.root_node_id.clone();
+    // Only known when this run discovers the source and creates its root.
+    let mut export_root: Option<NodePayload> = None;
 
     let mut unit_roots = if let Some(root_id) = &root_node_id {
         create_unit_roots(&config, root_id)
     } else {
         Vec::new()
     };
+    // Explicitly listed units, like selector-limited ones, cover part of the
+    // source.
+    let mut partial_run = !unit_roots.is_empty();
 
     if unit_roots.is_empty() {
         let config_data = SourcesConfig::load_default().expect("Failed to load sources.json");
+        config_data.ensure_accepting_runs(config.source)?;
         let root_url = config_data
             .get_root_url(config.source)
             .expect("Missing root URL in sources.json")
@@ -351,6 +838,17 @@ pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
         let full_version_id = format!("{}-{}", config.source_id, discovery.version_id);
         source_version_id = Some(full_version_id.clone());
         root_node_id = Some(discovery.root_node.id.clone());
+        let (selected_units, coverage) =
+            select_units(discovery.unit_roots, config.selectors.as_deref())?;
+        partial_run = coverage.is_some();
+        if let Some(coverage) = &coverage {
+            tracing::info!(
+                "[Orchestrator] Selectors kept {} of {} units; {} covers only those.",
+                coverage.unit_ids.len(),
+                coverage.discovered_units,
+                full_version_id
+            );
+        }
 
         post_ensure_source_version(
             &client,
@@ -359,13 +857,19 @@ pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
             &config.source_id,
             &full_version_id,
             &discovery.root_node,
-            &discovery.unit_roots,
+            &selected_units,
+            coverage.as_ref(),
         )
         .await?;
 
-        let parent_id = discovery.root_node.id;
-        unit_roots = discovery
-            .unit_roots
+        let mut root_meta = discovery.root_node;
+        root_meta.source_version_id = full_version_id.clone();
+        let parent_id = root_meta.id.clone();
+        export_root = Some(NodePayload {
+            meta: root_meta,
+            content: None,
+        });
+        unit_roots = selected_units
             .into_iter()
             .enumerate()
             .map(|(idx, root)| QueueItem {
@@ -385,8 +889,78 @@ pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
     let (Some(source_version_id), Some(root_node_id)) = (source_version_id, root_node_id) else {
         return Err("source_version_id/root_node_id not set after discovery".to_string());
     };
+    let export = Arc::new(ExportingSink::new(
+        sink,
+        client.clone(),
+        &config.callback_base,
+        &config.callback_token,
+        &source_version_id,
+        export_root,
+    ));
+    let sink: Arc<dyn NodeSink> = export.clone();
 
-    let semaphore = Arc::new(Semaphore::new(UNIT_CONCURRENCY));
+    let unit_timeout = config
+        .unit_timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_UNIT_TIMEOUT);
+    let requested_workers = config.unit_concurrency.unwrap_or(UNIT_CONCURRENCY);
+    let workers = requested_workers.clamp(1, MAX_UNIT_CONCURRENCY);
+    if workers != requested_workers {
+        tracing::warn!(
+            "[Orchestrator] unitConcurrency {} is outside 1..={}; running {} units at once",
+            requested_workers,
+            MAX_UNIT_CONCURRENCY,
+            workers
+        );
+    }
+    let history = load_unit_timings(
+        &client,
+        &config.callback_base,
+        &config.callback_token,
+        config.source,
+    )
+    .await;
+    if history.units.is_empty() {
+        tracing::info!(
+            "[Orchestrator] No unit timings stored for {}; units start in discovery order",
+            config.source.as_str()
+        );
+    }
+    history.order_slowest_first(&mut unit_roots, unit_id_of);
+    progress.plan(
+        history,
+        workers,
+        unit_roots.iter().map(unit_id_of).collect(),
+    );
+    let run = Arc::new(UnitRunContext {
+        adapter,
+        client: client.clone(),
+        callback_base: config.callback_base.clone(),
+        callback_token: config.callback_token.clone(),
+        source_version_id: source_version_id.clone(),
+        root_node_id,
+        accessed_at,
+        profile: config.profile,
+        blob_store,
+        cache_store,
+        artifact_namespace: run_artifact_namespace,
+        logger,
+        sink,
+        review: review.clone(),
+        links: links.clone(),
+        duplicates: duplicates.clone(),
+        levels: levels.clone(),
+        topics,
+        corrections,
+        heading_case: configured_heading_case(config.source),
+        slug_stop_words: configured_slug_stop_words(config.source),
+        wayback: config.wayback.clone(),
+        wayback_log: wayback_log.clone(),
+        progress: progress.clone(),
+        crash: crash.clone(),
+        unit_timeout,
+    });
+    let semaphore = Arc::new(Semaphore::new(workers));
     let mut tasks = JoinSet::new();
 
     for unit_root in unit_roots {
@@ -396,40 +970,161 @@ pub async fn ingest_source(config: IngestConfig) -> Result<(), String> {
             .await
             .map_err(|err| format!("Failed to acquire unit permit: {err}"))?;
 
-        let callback_base = config.callback_base.clone();
-        let callback_token = config.callback_token.clone();
-        let source_version_id = source_version_id.clone();
-        let root_node_id = root_node_id.clone();
-        let accessed_at = accessed_at.clone();
-        let client = client.clone();
-        let blob_store = blob_store.clone();
-        let cache_store = cache_store.clone();
-        let logger = logger.clone();
-
-        tasks.spawn(async move {
+        let run = run.clone();
+        let cancel = cancel.child_token();
+        tasks.spawn(with_crash_recorder(crash.clone(), async move {
             let _permit = permit;
-            process_unit_root(
-                adapter,
-                client,
-                callback_base,
-                callback_token,
-                source_version_id,
-                root_node_id,
-                accessed_at,
-                blob_store,
-                cache_store,
-                logger,
-                unit_root,
-            )
-            .await
-        });
+            process_unit_root(run, cancel, unit_root).await
+        }));
     }
 
+    let mut failed_units: Vec<String> = Vec::new();
     while let Some(join_result) = tasks.join_next().await {
         match join_result {
-            Ok(Ok(())) => {}
-            Ok(Err(err)) => return Err(err),
-            Err(err) => return Err(format!("Unit task failed to join: {err}")),
+            Ok(Ok(UnitOutcome::Completed)) => {}
+            Ok(Ok(UnitOutcome::Failed(unit_id))) => failed_units.push(unit_id),
+            Ok(Err(err)) => {
+                cancel.cancel();
+                return Err(err);
+            }
+            Err(err) => {
+                cancel.cancel();
+                let message = format!("Unit task failed to join: {err}");
+                if err.is_panic() {
+                    crash.record_join_panic(err.into_panic());
+                }
+                return Err(message);
+            }
+        }
+    }
+
+    // Level nodes went out as they were emitted; their stats follow once every
+    // unit has, so a level several units share counts all of their nodes.
+    let level_stats = levels.lock().map_err(|e| e.to_string())?.finish();
+    for chunk in level_stats.chunks(BATCH_SIZE) {
+        if let Err(err) = export.send_level_stats(chunk).await {
+            tracing::warn!("[Orchestrator] Failed to send level stats: {}", err);
+            break;
+        }
+    }
+    if let Err(err) = export.finish().await {
+        tracing::warn!(
+            "[Orchestrator] Failed to store the node export index: {}",
+            err
+        );
+    }
+    store_unit_timings(
+        &client,
+        &config.callback_base,
+        &config.callback_token,
+        config.source,
+        &progress.timings(),
+    )
+    .await;
+
+    failed_units.sort();
+    let mut report = IngestReport::from_review(&review)
+        .with_compliance(&politeness)
+        .with_duplicates(&duplicates)
+        .with_archive(&wayback_log);
+    if partial_run && config.known_link_targets.is_none() {
+        tracing::info!(
+            "[Orchestrator] Skipping link check: partial run without known link targets."
+        );
+    } else {
+        report = report.with_links(&links);
+    }
+    report.failed_units = failed_units;
+    for host in &report.compliance {
+        tracing::info!(
+            "[Orchestrator] {}: {} requests, {} rate-limited responses.",
+            host.host,
+            host.requests,
+            host.rate_limited_responses
+        );
+    }
+    if !report.broken_links.is_empty() {
+        tracing::warn!(
+            "[Orchestrator] {} inlined links do not resolve to an emitted node.",
+            report.broken_links.len()
+        );
+    }
+    if !report.near_duplicates.is_empty() {
+        tracing::warn!(
+            "[Orchestrator] {} section pairs have near-identical bodies.",
+            report.near_duplicates.len()
+        );
+    }
+    if !report.archived_sources.is_empty() {
+        tracing::warn!(
+            "[Orchestrator] {} pages were served from Wayback Machine snapshots.",
+            report.archived_sources.len()
+        );
+    }
+    if !report.review_node_ids.is_empty() {
+        tracing::info!(
+            "[Orchestrator] {} nodes flagged for review.",
+            report.review_node_ids.len()
+        );
+    }
+    // The report is informational; publishing only depends on its blockers.
+    if let Err(err) = post_ingest_report(
+        &client,
+        &config.callback_base,
+        &config.callback_token,
+        &report,
+    )
+    .await
+    {
+        tracing::warn!("[Orchestrator] Failed to post ingest report: {}", err);
+    }
+
+    let mut blockers = report.publish_blockers();
+    if let Some(published) = &config.published_source_version_id {
+        let version = |id: &str| SourceVersion::from_source_version_id(&config.source_id, id);
+        blockers.extend(version_regression(
+            &version(published),
+            &version(&source_version_id),
+        ));
+    }
+    if !blockers.is_empty() {
+        tracing::warn!(
+            "[Orchestrator] Source version {} left staged: {}",
+            source_version_id,
+            blockers.join("; ")
+        );
+    } else if config.publish {
+        post_publish_source_version(
+            &client,
+            &config.callback_base,
+            &config.callback_token,
+            &source_version_id,
+        )
+        .await?;
+    } else {
+        tracing::info!(
+            "[Orchestrator] Source version {} staged; publish disabled by config.",
+            source_version_id
+        );
+    }
+
+    if blockers.is_empty() {
+        let ingested = IngestedVersion {
+            source_id: config.source_id.clone(),
+            source_version_id: source_version_id.clone(),
+            completed_at: chrono::Utc::now().to_rfc3339(),
+            published: config.publish,
+        };
+        if let Err(err) = store_ingested_version(
+            &client,
+            &config.callback_base,
+            &config.callback_token,
+            config.source,
+            &ingested,
+        )
+        .await
+        {
+            tracing::warn!("[Orchestrator] Failed to store ingested version: {}", err);
         }
     }
 
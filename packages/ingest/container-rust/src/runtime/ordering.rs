@@ -0,0 +1,121 @@
+use crate::runtime::types::NodeStore;
+use crate::types::NodePayload;
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Orders a unit's nodes so none is released before its parent, letting the
+/// consumer enforce the parent foreign key without deferred checks.
+///
+/// Adapters do not all emit parents first: the USC parser only emits a level
+/// once its closing tag is read, after every section inside it. A node whose
+/// parent this unit has not released yet waits for it. Parents that never
+/// arrive (the source root, or a level another unit wrote) are assumed to
+/// exist downstream, and their children are released when the unit finishes.
+pub struct ParentOrder {
+    released: HashSet<String>,
+    waiting: HashMap<String, Vec<(usize, NodePayload)>>,
+    next_seq: usize,
+}
+
+impl ParentOrder {
+    /// `root_node_id` exists before any unit runs, so its children never wait.
+    pub fn new(root_node_id: &str) -> Self {
+        Self {
+            released: HashSet::from([root_node_id.to_string()]),
+            waiting: HashMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Returns the nodes that can be released now, parents first: `node`, if
+    /// its parent was released, followed by everything that was waiting on it.
+    pub fn release(&mut self, node: NodePayload) -> Vec<NodePayload> {
+        if let Some(parent_id) = node.meta.parent_id.as_deref() {
+            if !self.released.contains(parent_id) {
+                self.next_seq += 1;
+                self.waiting
+                    .entry(parent_id.to_string())
+                    .or_default()
+                    .push((self.next_seq, node));
+                return Vec::new();
+            }
+        }
+
+        let mut ready = vec![node];
+        let mut next = 0;
+        while next < ready.len() {
+            let id = ready[next].meta.id.clone();
+            if let Some(mut children) = self.waiting.remove(&id) {
+                children.sort_by_key(|(seq, _)| *seq);
+                ready.extend(children.into_iter().map(|(_, child)| child));
+            }
+            self.released.insert(id);
+            next += 1;
+        }
+        ready
+    }
+
+    /// Releases every node still waiting, in emission order except that a
+    /// parent still goes out ahead of its children.
+    pub fn finish(&mut self) -> Vec<NodePayload> {
+        let mut held = std::mem::take(&mut self.waiting)
+            .into_values()
+            .flatten()
+            .collect::<Vec<_>>();
+        held.sort_by_key(|(seq, _)| *seq);
+
+        let held_ids = held
+            .iter()
+            .map(|(_, node)| node.meta.id.clone())
+            .collect::<HashSet<_>>();
+        for (_, node) in &held {
+            if let Some(parent_id) = &node.meta.parent_id {
+                if !held_ids.contains(parent_id) {
+                    self.released.insert(parent_id.clone());
+                }
+            }
+        }
+
+        let mut ready = Vec::with_capacity(held.len());
+        for (_, node) in held {
+            ready.extend(self.release(node));
+        }
+        ready
+    }
+}
+
+/// Node store that hands nodes to `inner` in [`ParentOrder`].
+#[derive(Clone)]
+pub struct ParentOrderedStore<S> {
+    inner: S,
+    order: Arc<Mutex<ParentOrder>>,
+}
+
+impl<S> ParentOrderedStore<S> {
+    pub fn new(inner: S, root_node_id: &str) -> Self {
+        Self {
+            inner,
+            order: Arc::new(Mutex::new(ParentOrder::new(root_node_id))),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: NodeStore> NodeStore for ParentOrderedStore<S> {
+    async fn insert_node(&self, node: NodePayload) -> Result<(), String> {
+        let ready = self.order.lock().map_err(|e| e.to_string())?.release(node);
+        for node in ready {
+            self.inner.insert_node(node).await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<(), String> {
+        let ready = self.order.lock().map_err(|e| e.to_string())?.finish();
+        for node in ready {
+            self.inner.insert_node(node).await?;
+        }
+        self.inner.flush().await
+    }
+}
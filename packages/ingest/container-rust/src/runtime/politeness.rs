@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Per-host crawling summary included in the ingest report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostCompliance {
+    pub host: String,
+    /// Requests issued for this host, including ones the cache proxy served
+    /// without contacting the origin.
+    pub requests: u64,
+    /// Requests that reached the origin.
+    #[serde(default)]
+    pub origin_requests: u64,
+    /// Requests per second between the first and last request; absent when
+    /// fewer than two requests were made.
+    pub average_requests_per_second: Option<f64>,
+    /// Strictest throttle (requests per second) the proxy was asked to honor
+    /// for this host, if any.
+    pub throttle_requests_per_second: Option<u32>,
+    /// Requests that carried the throttle above.
+    pub throttled_requests: u64,
+    /// HTTP 429 responses received from the origin.
+    pub rate_limited_responses: u64,
+}
+
+struct HostActivity {
+    requests: u64,
+    origin_requests: u64,
+    throttled_requests: u64,
+    throttle_requests_per_second: Option<u32>,
+    rate_limited_responses: u64,
+    first_request: Instant,
+    last_request: Instant,
+}
+
+/// Records outbound fetches per host so a run can show it crawled politely.
+#[derive(Default)]
+pub struct PolitenessTracker {
+    hosts: Mutex<BTreeMap<String, HostActivity>>,
+}
+
+impl PolitenessTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a request the origin answered with `status`.
+    pub fn record(&self, url: &str, status: u16, throttle_requests_per_second: Option<u32>) {
+        self.record_at(url, status, throttle_requests_per_second, Instant::now());
+    }
+
+    pub fn record_at(
+        &self,
+        url: &str,
+        status: u16,
+        throttle_requests_per_second: Option<u32>,
+        at: Instant,
+    ) {
+        self.record_request(url, Some(status), throttle_requests_per_second, at);
+    }
+
+    /// Records a request the cache proxy served without contacting the origin.
+    pub fn record_cached(&self, url: &str, throttle_requests_per_second: Option<u32>) {
+        self.record_request(url, None, throttle_requests_per_second, Instant::now());
+    }
+
+    fn record_request(
+        &self,
+        url: &str,
+        origin_status: Option<u16>,
+        throttle_requests_per_second: Option<u32>,
+        at: Instant,
+    ) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_ascii_lowercase))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut hosts = self.hosts.lock().unwrap();
+        let activity = hosts.entry(host).or_insert_with(|| HostActivity {
+            requests: 0,
+            origin_requests: 0,
+            throttled_requests: 0,
+            throttle_requests_per_second: None,
+            rate_limited_responses: 0,
+            first_request: at,
+            last_request: at,
+        });
+
+        activity.requests += 1;
+        activity.first_request = activity.first_request.min(at);
+        activity.last_request = activity.last_request.max(at);
+        if let Some(rps) = throttle_requests_per_second {
+            activity.throttled_requests += 1;
+            activity.throttle_requests_per_second = Some(
+                activity
+                    .throttle_requests_per_second
+                    .map_or(rps, |current| current.min(rps)),
+            );
+        }
+        if let Some(status) = origin_status {
+            activity.origin_requests += 1;
+            if status == 429 {
+                activity.rate_limited_responses += 1;
+            }
+        }
+    }
+
+    /// Per-host summaries sorted by host name.
+    pub fn summary(&self) -> Vec<HostCompliance> {
+        let hosts = self.hosts.lock().unwrap();
+        hosts
+            .iter()
+            .map(|(host, activity)| {
+                let elapsed = activity
+                    .last_request
+                    .duration_since(activity.first_request)
+                    .as_secs_f64();
+                let average_requests_per_second = if activity.requests > 1 && elapsed > 0.0 {
+                    Some((activity.requests - 1) as f64 / elapsed)
+                } else {
+                    None
+                };
+                HostCompliance {
+                    host: host.clone(),
+                    requests: activity.requests,
+                    origin_requests: activity.origin_requests,
+                    average_requests_per_second,
+                    throttle_requests_per_second: activity.throttle_requests_per_second,
+                    throttled_requests: activity.throttled_requests,
+                    rate_limited_responses: activity.rate_limited_responses,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,196 @@
+use crate::runtime::types::Cache;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// One upstream input read while processing a unit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchRecord {
+    pub url: String,
+    /// Cache key the bytes are stored under; absent for uncached fetches.
+    pub cache_key: Option<String>,
+    /// When the origin was fetched. For objects the cache proxy stored
+    /// without upstream metadata, when they were read instead.
+    pub fetched_at: String,
+    /// Status the origin answered with.
+    pub status: u16,
+    /// Origin response headers, lowercased. Empty when the cache layer doesn't
+    /// expose them.
+    pub headers: BTreeMap<String, String>,
+    /// SHA-256 of the bytes as downloaded (the archive itself for ZIPs).
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+impl FetchRecord {
+    pub fn new(url: &str, cache_key: Option<&str>, status: u16, bytes: &[u8]) -> Self {
+        Self {
+            url: url.to_string(),
+            cache_key: cache_key.map(str::to_string),
+            fetched_at: chrono::Utc::now().to_rfc3339(),
+            status,
+            headers: BTreeMap::new(),
+            sha256: sha256_hex(bytes),
+            size_bytes: bytes.len() as u64,
+        }
+    }
+
+    /// Takes the status, fetch time and headers from the origin response the
+    /// bytes were cached from.
+    pub fn with_upstream(mut self, upstream: UpstreamResponse) -> Self {
+        self.status = upstream.status;
+        self.fetched_at = upstream.fetched_at;
+        self.headers = upstream
+            .headers
+            .into_iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value))
+            .collect();
+        self
+    }
+
+    pub fn with_headers(mut self, headers: &reqwest::header::HeaderMap) -> Self {
+        self.headers = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.as_str().to_string(), value.to_string()))
+            })
+            .collect();
+        self
+    }
+}
+
+/// The origin response a cached object was filled from, as the cache proxy
+/// reports it in `X-Upstream-Response` on hits and misses alike.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamResponse {
+    pub status: u16,
+    pub fetched_at: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl UpstreamResponse {
+    /// Parses the header's URI-encoded JSON; `None` when it is malformed.
+    pub fn from_header(value: &str) -> Option<Self> {
+        let json = urlencoding::decode(value).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+}
+
+/// Provenance for one unit, stored alongside its source version so any
+/// emitted node can be traced to the exact inputs it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitManifest {
+    pub unit_id: String,
+    pub source_version_id: String,
+    /// Unit root URL.
+    pub url: String,
+    pub parser_version: u32,
+    /// Prefix for derived artifacts, covering parser version and extraction options.
+    pub artifact_namespace: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub completed: bool,
+    pub fetches: Vec<FetchRecord>,
+}
+
+/// Most recent fetch record per URL, filled in by a cache implementation and
+/// drained by [`ProvenanceCache`].
+#[derive(Default)]
+pub struct FetchLog {
+    records: Mutex<HashMap<String, FetchRecord>>,
+}
+
+impl FetchLog {
+    pub fn record(&self, record: FetchRecord) {
+        self.records
+            .lock()
+            .unwrap()
+            .insert(record.url.clone(), record);
+    }
+
+    pub fn take(&self, url: &str) -> Option<FetchRecord> {
+        self.records.lock().unwrap().remove(url)
+    }
+}
+
+/// Per-unit cache wrapper that records every fetch the unit makes.
+pub struct ProvenanceCache {
+    inner: Arc<dyn Cache>,
+    fetches: Mutex<Vec<FetchRecord>>,
+}
+
+impl ProvenanceCache {
+    pub fn new(inner: Arc<dyn Cache>) -> Self {
+        Self {
+            inner,
+            fetches: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fetches recorded so far, in request order.
+    pub fn fetches(&self) -> Vec<FetchRecord> {
+        self.fetches.lock().unwrap().clone()
+    }
+
+    fn push(&self, url: &str, cache_key: Option<&str>, content: &str) {
+        let record = self
+            .inner
+            .take_fetch_record(url)
+            .unwrap_or_else(|| FetchRecord::new(url, cache_key, 200, content.as_bytes()));
+        self.fetches.lock().unwrap().push(record);
+    }
+}
+
+#[async_trait]
+impl Cache for ProvenanceCache {
+    async fn fetch_cached(
+        &self,
+        url: &str,
+        key: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let content = self
+            .inner
+            .fetch_cached(url, key, throttle_requests_per_second)
+            .await?;
+        self.push(url, Some(key), &content);
+        Ok(content)
+    }
+
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let content = self
+            .inner
+            .fetch_uncached(url, throttle_requests_per_second)
+            .await?;
+        self.push(url, None, &content);
+        Ok(content)
+    }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        self.inner.read_artifact(key).await
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        self.inner.write_artifact(key, content).await
+    }
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
@@ -0,0 +1,89 @@
+use crate::runtime::duplicates::{DuplicateDetector, NearDuplicate};
+use crate::runtime::links::{BrokenLink, LinkChecker};
+use crate::runtime::politeness::{HostCompliance, PolitenessTracker};
+use crate::runtime::review::{ReviewFlag, ReviewRegistry, ReviewSource};
+use crate::runtime::wayback::WaybackLog;
+use crate::types::ArchivedSource;
+use serde::{Deserialize, Serialize};
+
+/// Summary posted to the callback API once every unit of a run has finished.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestReport {
+    pub review_node_ids: Vec<String>,
+    pub review_flags: Vec<ReviewFlag>,
+    /// Per-host crawling behaviour, for demonstrating responsible crawling.
+    #[serde(default)]
+    pub compliance: Vec<HostCompliance>,
+    /// Units that errored, timed out, or were cancelled.
+    #[serde(default)]
+    pub failed_units: Vec<String>,
+    /// Inlined internal links that don't resolve to an emitted node.
+    #[serde(default)]
+    pub broken_links: Vec<BrokenLink>,
+    /// Section pairs with near-identical bodies.
+    #[serde(default)]
+    pub near_duplicates: Vec<NearDuplicate>,
+    /// Pages that 404ed upstream and were taken from an archive snapshot.
+    #[serde(default)]
+    pub archived_sources: Vec<ArchivedSource>,
+}
+
+impl IngestReport {
+    pub fn from_review(registry: &ReviewRegistry) -> Self {
+        Self {
+            review_node_ids: registry.node_ids(),
+            review_flags: registry.flags(),
+            compliance: Vec::new(),
+            failed_units: Vec::new(),
+            broken_links: Vec::new(),
+            near_duplicates: Vec::new(),
+            archived_sources: Vec::new(),
+        }
+    }
+
+    pub fn with_compliance(mut self, tracker: &PolitenessTracker) -> Self {
+        self.compliance = tracker.summary();
+        self
+    }
+
+    pub fn with_links(mut self, checker: &LinkChecker) -> Self {
+        self.broken_links = checker.broken_links();
+        self
+    }
+
+    pub fn with_duplicates(mut self, detector: &DuplicateDetector) -> Self {
+        self.near_duplicates = detector.near_duplicates();
+        self
+    }
+
+    pub fn with_archive(mut self, log: &WaybackLog) -> Self {
+        self.archived_sources = log.fallbacks();
+        self
+    }
+
+    /// Reasons the run's source version must stay staged; empty when it may be
+    /// published. Failed units and anomaly-detector flags block publishing;
+    /// lint and validator flags only mark nodes for review. A level several
+    /// units emit alike (a USPL congress) is not an anomaly, so it never
+    /// blocks on its own.
+    pub fn publish_blockers(&self) -> Vec<String> {
+        let mut blockers = Vec::new();
+        if !self.failed_units.is_empty() {
+            blockers.push(format!(
+                "{} unit(s) failed: {}",
+                self.failed_units.len(),
+                self.failed_units.join(", ")
+            ));
+        }
+        let anomalies = self
+            .review_flags
+            .iter()
+            .filter(|flag| flag.source == ReviewSource::AnomalyDetector)
+            .count();
+        if anomalies > 0 {
+            blockers.push(format!("{anomalies} anomaly flag(s) raised"));
+        }
+        blockers
+    }
+}
@@ -0,0 +1,155 @@
+use crate::types::{IngestProfile, NodeMeta, NodePayload, SectionContent};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSource {
+    Validator,
+    Linter,
+    AnomalyDetector,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewFlag {
+    pub node_id: String,
+    pub source: ReviewSource,
+    pub reason: String,
+}
+
+/// Collects review flags raised while a source is ingested so they can be
+/// reported once the run completes.
+#[derive(Default)]
+pub struct ReviewRegistry {
+    flags: Mutex<Vec<ReviewFlag>>,
+    /// Every node id seen this run, with the meta of content-less level nodes
+    /// so a level re-emitted by another unit can be told from a clash.
+    seen: Mutex<HashMap<String, Option<NodeMeta>>>,
+    profile: IngestProfile,
+}
+
+impl ReviewRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry for a run ingesting with `profile`, so nodes are only linted
+    /// for content that profile captures.
+    pub fn for_profile(profile: IngestProfile) -> Self {
+        Self {
+            profile,
+            ..Self::default()
+        }
+    }
+
+    pub fn flag(&self, node_id: &str, source: ReviewSource, reason: impl Into<String>) {
+        let mut flags = self.flags.lock().unwrap();
+        flags.push(ReviewFlag {
+            node_id: node_id.to_string(),
+            source,
+            reason: reason.into(),
+        });
+    }
+
+    pub fn flags(&self) -> Vec<ReviewFlag> {
+        self.flags.lock().unwrap().clone()
+    }
+
+    /// Flagged node ids in first-flagged order, without duplicates.
+    pub fn node_ids(&self) -> Vec<String> {
+        let flags = self.flags.lock().unwrap();
+        let mut seen = HashSet::new();
+        flags
+            .iter()
+            .filter(|flag| seen.insert(flag.node_id.as_str()))
+            .map(|flag| flag.node_id.clone())
+            .collect()
+    }
+}
+
+/// Structural checks applied to every node before it is stored.
+/// Returns one reason per problem found.
+pub fn lint_node(node: &NodePayload) -> Vec<String> {
+    lint_node_for_profile(node, IngestProfile::Full)
+}
+
+/// [`lint_node`] for a run using `profile`. Sections with no blocks are only
+/// a problem when the profile captures body text.
+pub fn lint_node_for_profile(node: &NodePayload, profile: IngestProfile) -> Vec<String> {
+    let mut reasons = Vec::new();
+    let meta = &node.meta;
+
+    if meta
+        .name
+        .as_deref()
+        .is_none_or(|name| name.trim().is_empty())
+        && meta.level_name != "root"
+    {
+        reasons.push("missing name".to_string());
+    }
+
+    if meta.level_name == "section" && profile.captures_body() {
+        let content = node
+            .content
+            .as_ref()
+            .and_then(|value| serde_json::from_value::<SectionContent>(value.clone()).ok());
+        let has_body = content.is_some_and(|content| {
+            content.blocks.iter().any(|block| {
+                block
+                    .content
+                    .as_deref()
+                    .is_some_and(|text| !text.trim().is_empty())
+            })
+        });
+        if !has_body {
+            reasons.push("section has no body text".to_string());
+        }
+    }
+
+    reasons
+}
+
+/// Runs the linter and anomaly checks on `node` and records every flag that
+/// applies to it. Nodes an adapter already marked for review are recorded as
+/// validator flags.
+pub fn review_node(node: &mut NodePayload, registry: &ReviewRegistry) {
+    if node.meta.review_required {
+        registry.flag(
+            &node.meta.id,
+            ReviewSource::Validator,
+            "flagged during parsing",
+        );
+    }
+
+    if is_duplicate(node, registry) {
+        registry.flag(
+            &node.meta.id,
+            ReviewSource::AnomalyDetector,
+            "duplicate node id",
+        );
+        node.meta.review_required = true;
+    }
+
+    for reason in lint_node_for_profile(node, registry.profile) {
+        registry.flag(&node.meta.id, ReviewSource::Linter, reason);
+        node.meta.review_required = true;
+    }
+}
+
+/// Whether `node` reuses an id seen earlier in the run. Units may each emit
+/// a level they share (USPL emits the congress from every volume in it), so
+/// a content-less node with the same meta as the first one is not a clash.
+fn is_duplicate(node: &NodePayload, registry: &ReviewRegistry) -> bool {
+    let mut seen = registry.seen.lock().unwrap();
+    match seen.get(&node.meta.id) {
+        None => {
+            let level = node.content.is_none().then(|| node.meta.clone());
+            seen.insert(node.meta.id.clone(), level);
+            false
+        }
+        Some(Some(level)) => node.content.is_some() || *level != node.meta,
+        Some(None) => true,
+    }
+}
@@ -0,0 +1,271 @@
+use crate::types::{NodePayload, SectionContent, SectionMetadata};
+use async_trait::async_trait;
+use regex::Regex;
+
+/// Body keyword hits a topic needs when its keywords are absent from the
+/// heading; one passing mention of "tax" in a long section says little.
+const MIN_BODY_HITS: usize = 2;
+
+/// Assigns legal-topic tags (`criminal`, `tax`, ...) to a section for faceted
+/// browsing. Called once per section during ingest, so a remote classifier
+/// should batch or cache on its side.
+#[async_trait]
+pub trait TopicClassifier: Send + Sync {
+    async fn classify(&self, node: &NodePayload) -> Vec<String>;
+}
+
+/// Keywords and phrases that mark a section as belonging to `topic`.
+pub struct TopicKeywords {
+    pub topic: &'static str,
+    pub keywords: &'static [&'static str],
+}
+
+pub static DEFAULT_TOPICS: &[TopicKeywords] = &[
+    TopicKeywords {
+        topic: "criminal",
+        keywords: &[
+            "crime",
+            "crimes",
+            "criminal",
+            "felony",
+            "felonies",
+            "misdemeanor",
+            "imprisonment",
+            "imprisoned",
+            "offender",
+            "offenders",
+            "prosecution",
+            "sentencing",
+            "probation",
+            "parole",
+        ],
+    },
+    TopicKeywords {
+        topic: "tax",
+        keywords: &[
+            "tax",
+            "taxes",
+            "taxation",
+            "taxable",
+            "taxpayer",
+            "taxpayers",
+            "income tax",
+            "excise",
+            "assessor",
+            "assessment",
+            "levy",
+            "tax return",
+        ],
+    },
+    TopicKeywords {
+        topic: "family",
+        keywords: &[
+            "marriage",
+            "divorce",
+            "custody",
+            "child support",
+            "alimony",
+            "adoption",
+            "spouse",
+            "paternity",
+            "guardianship",
+            "domestic relations",
+        ],
+    },
+    TopicKeywords {
+        topic: "environmental",
+        keywords: &[
+            "environmental",
+            "pollution",
+            "pollutant",
+            "pollutants",
+            "emissions",
+            "wetlands",
+            "hazardous waste",
+            "solid waste",
+            "air quality",
+            "water quality",
+            "wildlife",
+            "conservation",
+        ],
+    },
+    TopicKeywords {
+        topic: "labor",
+        keywords: &[
+            "employer",
+            "employers",
+            "employee",
+            "employees",
+            "wages",
+            "minimum wage",
+            "collective bargaining",
+            "workers' compensation",
+            "unemployment compensation",
+        ],
+    },
+    TopicKeywords {
+        topic: "health",
+        keywords: &[
+            "health care",
+            "public health",
+            "hospital",
+            "hospitals",
+            "physician",
+            "physicians",
+            "medicaid",
+            "medicare",
+            "patient",
+            "patients",
+            "nursing",
+        ],
+    },
+    TopicKeywords {
+        topic: "education",
+        keywords: &[
+            "school",
+            "schools",
+            "pupil",
+            "pupils",
+            "student",
+            "students",
+            "teacher",
+            "teachers",
+            "board of education",
+            "tuition",
+        ],
+    },
+    TopicKeywords {
+        topic: "property",
+        keywords: &[
+            "real property",
+            "real estate",
+            "landlord",
+            "tenant",
+            "tenants",
+            "mortgage",
+            "mortgages",
+            "easement",
+            "conveyance",
+            "eviction",
+            "lease",
+        ],
+    },
+    TopicKeywords {
+        topic: "elections",
+        keywords: &[
+            "election",
+            "elections",
+            "ballot",
+            "ballots",
+            "voter",
+            "voters",
+            "candidate",
+            "candidates",
+            "polling place",
+        ],
+    },
+    TopicKeywords {
+        topic: "motor_vehicles",
+        keywords: &[
+            "motor vehicle",
+            "motor vehicles",
+            "driver's license",
+            "operator's license",
+            "highway",
+            "highways",
+            "traffic",
+        ],
+    },
+];
+
+/// Built-in classifier matching whole-word keywords in the heading and body
+/// paragraphs. Notes are ignored; amendment histories mention too much.
+pub struct KeywordClassifier {
+    rules: Vec<(String, Regex)>,
+}
+
+impl KeywordClassifier {
+    pub fn new(topics: &[TopicKeywords]) -> Self {
+        let rules = topics
+            .iter()
+            .map(|topic| {
+                let alternatives = topic
+                    .keywords
+                    .iter()
+                    .map(|keyword| regex::escape(keyword))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                let pattern = format!(r"(?i)\b(?:{alternatives})\b");
+                (topic.topic.to_string(), Regex::new(&pattern).unwrap())
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Topics for a section with `heading` and `body`, in rule order: a
+    /// topic matches on one heading keyword or on enough body keywords.
+    pub fn classify_text(&self, heading: &str, body: &str) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| {
+                pattern.is_match(heading) || pattern.find_iter(body).count() >= MIN_BODY_HITS
+            })
+            .map(|(topic, _)| topic.clone())
+            .collect()
+    }
+}
+
+impl Default for KeywordClassifier {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOPICS)
+    }
+}
+
+#[async_trait]
+impl TopicClassifier for KeywordClassifier {
+    async fn classify(&self, node: &NodePayload) -> Vec<String> {
+        let Some(content) = node
+            .content
+            .clone()
+            .and_then(|content| serde_json::from_value::<SectionContent>(content).ok())
+        else {
+            return Vec::new();
+        };
+        let body = content
+            .blocks
+            .iter()
+            .filter(|block| block.type_ == "body")
+            .filter_map(|block| block.content.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.classify_text(node.meta.name.as_deref().unwrap_or_default(), &body)
+    }
+}
+
+/// Classifies `node` and stores its topics, sorted, in the section metadata.
+/// Nodes without content (structural levels) are left untouched.
+pub async fn tag_topics(
+    classifier: &dyn TopicClassifier,
+    node: &mut NodePayload,
+) -> Result<(), String> {
+    let Some(value) = node.content.as_ref() else {
+        return Ok(());
+    };
+    let mut content: SectionContent = serde_json::from_value(value.clone())
+        .map_err(|e| format!("Failed to read content of {}: {e}", node.meta.id))?;
+    let mut topics = classifier.classify(node).await;
+    if topics.is_empty() {
+        return Ok(());
+    }
+    topics.sort();
+    topics.dedup();
+    content
+        .metadata
+        .get_or_insert_with(SectionMetadata::default)
+        .topics = topics;
+    node.content = Some(
+        serde_json::to_value(&content)
+            .map_err(|e| format!("Failed to write content of {}: {e}", node.meta.id))?,
+    );
+    Ok(())
+}
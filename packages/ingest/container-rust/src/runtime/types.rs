@@ -1,13 +1,17 @@
-use crate::types::NodePayload;
+use crate::runtime::provenance::FetchRecord;
+use crate::types::{IngestProfile, NodeLevelStats, NodePayload};
 use async_trait::async_trait;
+use serde::Serialize;
 use serde_json::Value;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 pub struct BuildContext<'a> {
     pub source_version_id: &'a str,
     pub root_node_id: &'a str,
     pub accessed_at: &'a str,
     pub unit_sort_order: i32,
+    pub profile: IngestProfile,
 }
 
 #[async_trait]
@@ -16,6 +20,18 @@ pub trait NodeStore: Send + Sync {
     async fn flush(&self) -> Result<(), String>;
 }
 
+/// Delivers a unit's finished node batches to the consumer. Nodes reach it in
+/// [`ParentOrder`](crate::runtime::ordering::ParentOrder), so a parent always
+/// arrives before its children.
+#[async_trait]
+pub trait NodeSink: Send + Sync {
+    async fn send_batch(&self, unit_id: &str, nodes: &[NodePayload]) -> Result<(), String>;
+
+    /// Sets the stats of level nodes already sent, once every unit has
+    /// finished. The stats are stored beside the node, not in its content.
+    async fn send_level_stats(&self, levels: &[NodeLevelStats]) -> Result<(), String>;
+}
+
 #[async_trait]
 pub trait BlobStore: Send + Sync {
     async fn store_blob(&self, id: &str, content: &[u8]) -> Result<String, String>;
@@ -35,9 +51,24 @@ pub trait Cache: Send + Sync {
         url: &str,
         throttle_requests_per_second: Option<u32>,
     ) -> Result<String, String>;
+
+    /// Read a derived artifact previously persisted with `write_artifact`.
+    /// Returns `None` when no artifact exists under `key`.
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String>;
+
+    /// Persist a derived artifact (e.g. a normalized TOC map) so later runs can reuse it.
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String>;
+
+    /// Removes and returns what this cache recorded about its latest fetch of
+    /// `url`, for provenance manifests. Caches that don't track fetches return
+    /// `None` and the caller records what it can from the content.
+    fn take_fetch_record(&self, _url: &str) -> Option<FetchRecord> {
+        None
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct QueueItem {
     pub url: String,
     pub parent_id: String,
@@ -62,6 +93,8 @@ pub struct IngestContext<'a> {
     pub cache: Arc<dyn Cache>,
     pub queue: Arc<dyn UrlQueue>,
     pub logger: Arc<dyn Logger>,
+    /// Cancelled when the run is aborted; long parses should stop early.
+    pub cancel: CancellationToken,
 }
 
 pub enum UnitStatus {
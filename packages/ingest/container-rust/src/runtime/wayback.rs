@@ -0,0 +1,257 @@
+use crate::runtime::cache::is_not_found_error;
+use crate::runtime::types::Cache;
+use crate::types::{ArchivedSource, NodePayload, SectionContent, SectionMetadata};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+const AVAILABILITY_API: &str = "https://archive.org/wayback/available";
+
+/// Wayback Machine availability API URL for the snapshot of `url` closest to
+/// `timestamp`, or the most recent one when `timestamp` is `None`.
+pub fn availability_url(url: &str, timestamp: Option<&str>) -> String {
+    let mut lookup = format!("{AVAILABILITY_API}?url={}", urlencoding::encode(url));
+    if let Some(timestamp) = timestamp {
+        lookup.push_str("&timestamp=");
+        lookup.push_str(&urlencoding::encode(timestamp));
+    }
+    lookup
+}
+
+#[derive(Deserialize)]
+struct Availability {
+    #[serde(default)]
+    archived_snapshots: Snapshots,
+}
+
+#[derive(Default, Deserialize)]
+struct Snapshots {
+    closest: Option<Snapshot>,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    #[serde(default)]
+    available: bool,
+    #[serde(default)]
+    status: String,
+    url: String,
+    timestamp: String,
+}
+
+/// Reads an availability API response. Only snapshots that captured a 200
+/// response count; archived error pages are no better than the 404.
+pub fn parse_availability(
+    original_url: &str,
+    json: &str,
+) -> Result<Option<ArchivedSource>, String> {
+    let availability: Availability = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse Wayback availability for {original_url}: {e}"))?;
+    Ok(availability
+        .archived_snapshots
+        .closest
+        .filter(|snapshot| snapshot.available && snapshot.status == "200")
+        .map(|snapshot| ArchivedSource {
+            original_url: original_url.to_string(),
+            snapshot_url: snapshot.url.replacen("http://", "https://", 1),
+            snapshot_timestamp: snapshot.timestamp,
+        }))
+}
+
+impl ArchivedSource {
+    /// Snapshot URL that serves the captured bytes without the Wayback toolbar
+    /// or rewritten links.
+    pub fn raw_url(&self) -> String {
+        format!(
+            "https://web.archive.org/web/{}id_/{}",
+            self.snapshot_timestamp, self.original_url
+        )
+    }
+}
+
+/// Every archive fallback taken during a run, for the end-of-run report.
+#[derive(Default)]
+pub struct WaybackLog {
+    fallbacks: Mutex<Vec<ArchivedSource>>,
+}
+
+impl WaybackLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, source: ArchivedSource) {
+        self.fallbacks.lock().unwrap().push(source);
+    }
+
+    pub fn fallbacks(&self) -> Vec<ArchivedSource> {
+        self.fallbacks.lock().unwrap().clone()
+    }
+}
+
+/// Archive snapshots that served the queue item a unit is currently
+/// processing. Shared between the unit's [`WaybackCache`] and its node store.
+#[derive(Default)]
+pub struct ItemArchive {
+    current: Mutex<Vec<ArchivedSource>>,
+}
+
+impl ItemArchive {
+    /// Clears the snapshots before the next queue item is processed.
+    pub fn begin_item(&self) {
+        self.current.lock().unwrap().clear();
+    }
+
+    /// Snapshots taken for the current item, in fetch order.
+    pub fn archived(&self) -> Vec<ArchivedSource> {
+        self.current.lock().unwrap().clone()
+    }
+
+    fn add(&self, source: ArchivedSource) {
+        self.current.lock().unwrap().push(source);
+    }
+
+    /// Records the snapshot a content node was built from in its metadata.
+    /// Only nodes whose source URL is the archived page are marked; other
+    /// pages the item fetched came from the origin. Level nodes are left
+    /// alone; they carry no text from the page.
+    pub fn mark(&self, node: &mut NodePayload) -> Result<(), String> {
+        let Some(page_url) = node
+            .meta
+            .source_url
+            .as_deref()
+            .map(|url| url.split('#').next().unwrap_or(url))
+        else {
+            return Ok(());
+        };
+        let Some(source) = self
+            .current
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|source| source.original_url == page_url)
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let Some(value) = node.content.as_ref() else {
+            return Ok(());
+        };
+        let mut content: SectionContent = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Failed to read content of {}: {e}", node.meta.id))?;
+        content
+            .metadata
+            .get_or_insert_with(SectionMetadata::default)
+            .archived_from = Some(source);
+        node.content = Some(
+            serde_json::to_value(&content)
+                .map_err(|e| format!("Failed to write content of {}: {e}", node.meta.id))?,
+        );
+        Ok(())
+    }
+}
+
+/// Per-unit cache wrapper that answers a 404 for a URL the prior version
+/// fetched with the Wayback Machine's snapshot of it.
+pub struct WaybackCache {
+    inner: Arc<dyn Cache>,
+    prior_urls: HashSet<String>,
+    timestamp: Option<String>,
+    item: Arc<ItemArchive>,
+    log: Arc<WaybackLog>,
+}
+
+impl WaybackCache {
+    pub fn new(
+        inner: Arc<dyn Cache>,
+        prior_urls: HashSet<String>,
+        timestamp: Option<String>,
+        item: Arc<ItemArchive>,
+        log: Arc<WaybackLog>,
+    ) -> Self {
+        Self {
+            inner,
+            prior_urls,
+            timestamp,
+            item,
+            log,
+        }
+    }
+
+    /// Snapshot to fetch in place of `url` after `error`, or `error` itself
+    /// when the fallback doesn't apply or no usable snapshot exists.
+    async fn snapshot_for(&self, url: &str, error: String) -> Result<ArchivedSource, String> {
+        if !is_not_found_error(&error) || !self.prior_urls.contains(url) {
+            return Err(error);
+        }
+        let availability = self
+            .inner
+            .fetch_uncached(&availability_url(url, self.timestamp.as_deref()), Some(1))
+            .await
+            .map_err(|e| format!("{error}; Wayback lookup failed: {e}"))?;
+        parse_availability(url, &availability)?
+            .ok_or_else(|| format!("{error}; no Wayback snapshot available"))
+    }
+
+    fn archived(&self, source: ArchivedSource) {
+        self.item.add(source.clone());
+        self.log.record(source);
+    }
+}
+
+#[async_trait]
+impl Cache for WaybackCache {
+    async fn fetch_cached(
+        &self,
+        url: &str,
+        key: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let error = match self
+            .inner
+            .fetch_cached(url, key, throttle_requests_per_second)
+            .await
+        {
+            Ok(content) => return Ok(content),
+            Err(error) => error,
+        };
+        let source = self.snapshot_for(url, error).await?;
+        let content = self
+            .inner
+            .fetch_cached(&source.raw_url(), &format!("wayback/{key}"), Some(1))
+            .await?;
+        self.archived(source);
+        Ok(content)
+    }
+
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let error = match self
+            .inner
+            .fetch_uncached(url, throttle_requests_per_second)
+            .await
+        {
+            Ok(content) => return Ok(content),
+            Err(error) => error,
+        };
+        let source = self.snapshot_for(url, error).await?;
+        let content = self
+            .inner
+            .fetch_uncached(&source.raw_url(), Some(1))
+            .await?;
+        self.archived(source);
+        Ok(content)
+    }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        self.inner.read_artifact(key).await
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        self.inner.write_artifact(key, content).await
+    }
+}
@@ -0,0 +1,377 @@
+use crate::export::dataset::{DatasetPackager, PackageOptions};
+use crate::ingest::ingest_source_with_progress;
+use crate::runtime::cache::read_object;
+use crate::runtime::callbacks::{post_container_stop, post_ingest_error};
+use crate::runtime::crash::{install_panic_hook, store_crash_dump, CrashRecorder};
+use crate::runtime::eta::JobProgress;
+use crate::runtime::freshness::freshness_report;
+use crate::runtime::logging::{log_event_with_callback, LogLevel};
+use crate::runtime::node_export::NodeExport;
+use crate::sources::configs::SourcesConfig;
+use crate::types::{FreshnessQuery, IngestConfig, PackageRequest};
+use axum::{
+    body::Body,
+    extract::{Json, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use serde_json::json;
+use std::io::{Seek, SeekFrom};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio_util::io::ReaderStream;
+
+/// How long the container waits without jobs before shutting down.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct AppState {
+    active_jobs: AtomicUsize,
+    total_jobs_started: AtomicUsize,
+    shutdown_notify: Arc<Notify>,
+    idle_timeout: Duration,
+    /// Progress of the ingest jobs currently running.
+    jobs: Mutex<Vec<Arc<JobProgress>>>,
+}
+
+impl AppState {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            active_jobs: AtomicUsize::new(0),
+            total_jobs_started: AtomicUsize::new(0),
+            shutdown_notify: Arc::new(Notify::new()),
+            idle_timeout,
+            jobs: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+async fn handle_ingest(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<IngestConfig>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if let Ok(sources) = SourcesConfig::load_default() {
+        if let Err(err) = sources.ensure_accepting_runs(config.source) {
+            tracing::warn!("[Container] Refusing ingest: {}", err);
+            return (StatusCode::CONFLICT, Json(json!({ "error": err })));
+        }
+    }
+
+    let callback_base = config.callback_base.clone();
+    let callback_token = config.callback_token.clone();
+    let callback_base_for_join = callback_base.clone();
+    let callback_token_for_join = callback_token.clone();
+
+    // Increment active jobs and total count strictly before spawning
+    state.active_jobs.fetch_add(1, Ordering::SeqCst);
+    state.total_jobs_started.fetch_add(1, Ordering::SeqCst);
+
+    let state_for_task = state.clone();
+    let progress = Arc::new(JobProgress::new(&config.source_id));
+    state.jobs.lock().unwrap().push(progress.clone());
+    let progress_for_task = progress.clone();
+    let progress_for_join = progress.clone();
+    let crash = Arc::new(CrashRecorder::new());
+    let crash_for_task = crash.clone();
+    let crash_for_join = crash.clone();
+
+    // Spawn the ingest task
+    let handle = tokio::spawn(async move {
+        let ingest_result = ingest_source_with_progress(config, progress, crash_for_task).await;
+        let client = reqwest::Client::new();
+
+        match &ingest_result {
+            Ok(()) => {
+                post_container_stop(&client, &callback_base, &callback_token, "completed").await;
+            }
+            Err(err) => {
+                tracing::error!("[Container] Ingest failed: {}", err);
+                let crash_dump = if crash.panicked() {
+                    store_crash_dump(
+                        &client,
+                        &callback_base,
+                        &callback_token,
+                        &crash,
+                        &progress_for_task,
+                        err,
+                    )
+                    .await
+                } else {
+                    None
+                };
+                post_ingest_error(
+                    &client,
+                    &callback_base,
+                    &callback_token,
+                    err,
+                    crash_dump.as_deref(),
+                )
+                .await;
+            }
+        }
+    });
+
+    // Spawn a monitor task to handle completion/failure and cleanup
+    tokio::spawn(async move {
+        if let Err(err) = handle.await {
+            tracing::error!("[Container] Ingest task panicked or was cancelled: {}", err);
+            let client = reqwest::Client::new();
+            let message = err.to_string();
+            let crash_dump = if err.is_panic() {
+                crash_for_join.record_join_panic(err.into_panic());
+                store_crash_dump(
+                    &client,
+                    &callback_base_for_join,
+                    &callback_token_for_join,
+                    &crash_for_join,
+                    &progress_for_join,
+                    &message,
+                )
+                .await
+            } else {
+                None
+            };
+            post_ingest_error(
+                &client,
+                &callback_base_for_join,
+                &callback_token_for_join,
+                &message,
+                crash_dump.as_deref(),
+            )
+            .await;
+            log_event_with_callback(
+                &client,
+                Some(&callback_base_for_join),
+                Some(&callback_token_for_join),
+                LogLevel::Error,
+                "ingest_task_panicked_or_cancelled",
+                Some(json!({ "error": message, "crashDump": crash_dump })),
+            )
+            .await;
+        }
+
+        state_for_task
+            .jobs
+            .lock()
+            .unwrap()
+            .retain(|job| !Arc::ptr_eq(job, &progress_for_join));
+        finish_job(&state_for_task);
+    });
+
+    (StatusCode::OK, Json(json!({ "status": "accepted" })))
+}
+
+async fn handle_status(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let jobs = state
+        .jobs
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|job| job.snapshot())
+        .collect::<Vec<_>>();
+    Json(json!({
+        "activeJobs": state.active_jobs.load(Ordering::SeqCst),
+        "jobs": jobs,
+    }))
+}
+
+/// Marks a job done and, once none are active, starts the idle shutdown timer.
+fn finish_job(state: &Arc<AppState>) {
+    let previous = state.active_jobs.fetch_sub(1, Ordering::SeqCst);
+
+    // If previous was 1 (so now 0), start the idle timer
+    if previous == 1 {
+        let current_generation = state.total_jobs_started.load(Ordering::SeqCst);
+        let state_for_timeout = state.clone();
+
+        tokio::spawn(async move {
+            let idle_timeout = state_for_timeout.idle_timeout;
+            tracing::info!(
+                "[Container] No active jobs, waiting {:?} for new jobs before shutdown...",
+                idle_timeout
+            );
+            tokio::time::sleep(idle_timeout).await;
+
+            if state_for_timeout.active_jobs.load(Ordering::SeqCst) == 0
+                && state_for_timeout.total_jobs_started.load(Ordering::SeqCst) == current_generation
+            {
+                tracing::info!(
+                    "[Container] Still no active jobs after {:?}, shutting down.",
+                    idle_timeout
+                );
+                state_for_timeout.shutdown_notify.notify_one();
+            } else {
+                tracing::info!("[Container] New jobs detected, cancelling idle shutdown.");
+            }
+        });
+    }
+}
+
+async fn handle_package(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<PackageRequest>,
+) -> Response {
+    state.active_jobs.fetch_add(1, Ordering::SeqCst);
+    state.total_jobs_started.fetch_add(1, Ordering::SeqCst);
+    let result = package_source_version(&request).await;
+    finish_job(&state);
+
+    match result {
+        Ok(archive) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "application/zstd".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"{}.tar.zst\"",
+                        request.source_version_id
+                    ),
+                ),
+            ],
+            Body::from_stream(ReaderStream::new(archive)),
+        )
+            .into_response(),
+        Err(err) => {
+            tracing::error!("[Container] Package failed: {}", err);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": err })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Packages the export chunk by chunk into a spooled archive, so neither the
+/// export nor the archive is held in memory; the archive is then streamed.
+async fn package_source_version(request: &PackageRequest) -> Result<tokio::fs::File, String> {
+    let client = reqwest::Client::new();
+    let export = NodeExport::read(
+        &client,
+        &request.callback_base,
+        &request.callback_token,
+        &request.source_version_id,
+    )
+    .await?
+    .ok_or_else(|| format!("No export found for {}", request.source_version_id))?;
+    let mut packager = DatasetPackager::new(PackageOptions {
+        source_version_id: request.source_version_id.clone(),
+        license_id: request.license_id.clone(),
+        license_text: request.license_text.clone(),
+    })?;
+    for key in &export.chunks {
+        let chunk = read_object(
+            &client,
+            &request.callback_base,
+            &request.callback_token,
+            key,
+        )
+        .await?
+        .ok_or_else(|| format!("Export chunk {key} is missing"))?;
+        packager = tokio::task::spawn_blocking(move || {
+            packager.add_nodes(chunk.as_bytes())?;
+            Ok::<_, String>(packager)
+        })
+        .await
+        .map_err(|e| format!("Package task failed: {e}"))??;
+    }
+
+    let archive = tokio::task::spawn_blocking(move || {
+        let mut archive =
+            tempfile::tempfile().map_err(|e| format!("Failed to create archive spool: {e}"))?;
+        packager.finish(&mut archive)?;
+        archive
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| format!("Failed to rewind the archive spool: {e}"))?;
+        Ok::<_, String>(archive)
+    })
+    .await
+    .map_err(|e| format!("Package task failed: {e}"))??;
+    Ok(tokio::fs::File::from_std(archive))
+}
+
+/// Last ingested version against what upstream advertises, per source. The
+/// callback token comes in the `Authorization` header so it stays out of
+/// URLs and access logs.
+async fn handle_freshness(headers: HeaderMap, Query(query): Query<FreshnessQuery>) -> Response {
+    let Some(callback_token) = bearer_token(&headers) else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "Missing bearer token" })),
+        )
+            .into_response();
+    };
+    let config = match SourcesConfig::load_default() {
+        Ok(config) => config,
+        Err(err) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": err })),
+            )
+                .into_response();
+        }
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+    let sources = freshness_report(&client, &query.callback_base, callback_token, &config).await;
+    Json(json!({ "sources": sources })).into_response()
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .filter(|token| !token.is_empty())
+}
+
+async fn handle_health() -> &'static str {
+    "ok"
+}
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/ingest", post(handle_ingest))
+        .route("/ingest/status", get(handle_status))
+        .route("/package", post(handle_package))
+        .route("/freshness", get(handle_freshness))
+        .fallback(handle_health)
+        .with_state(state)
+}
+
+/// Serves the container API on `listener` until the container has been idle
+/// for the state's idle timeout, either after startup or after its last job.
+pub async fn serve(listener: TcpListener, state: Arc<AppState>) -> Result<(), String> {
+    install_panic_hook();
+
+    // Initial idle timeout: if no jobs target us soon after startup, shut down.
+    let state_for_startup = state.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(state_for_startup.idle_timeout).await;
+        if state_for_startup.active_jobs.load(Ordering::SeqCst) == 0
+            && state_for_startup.total_jobs_started.load(Ordering::SeqCst) == 0
+        {
+            tracing::info!(
+                "[Container] No jobs received within {:?} of startup, shutting down.",
+                state_for_startup.idle_timeout
+            );
+            state_for_startup.shutdown_notify.notify_one();
+        }
+    });
+
+    let shutdown_notify = state.shutdown_notify.clone();
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async move { shutdown_notify.notified().await })
+        .await
+        .map_err(|e| format!("Server failed: {e}"))
+}
@@ -1,14 +1,20 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::cgs::cross_references::inline_section_cross_references;
+use crate::sources::cgs::cross_references::{inline_section_cross_references, split_see_also};
 use crate::sources::cgs::discover::{
     extract_chapter_urls, extract_title_name_from_html, parse_chapter_id_from_url,
 };
 use crate::sources::cgs::parser::{
-    designator_sort_order, normalize_designator, parse_cgs_chapter_html, CgsUnitKind,
+    designator_sort_order, normalize_designator, parse_cgs_chapter_html_with_toc, CgsTocMap,
+    CgsUnitKind,
 };
-use crate::sources::common::{body_block, capitalize_first, push_block};
+use crate::sources::common::{body_blocks, capitalize_first, push_block};
+use crate::sources::paths::CGS_PATHS;
 use crate::sources::SourceAdapter;
-use crate::types::{DiscoveryResult, NodeMeta, NodePayload, SectionContent};
+use crate::types::{
+    ContentBlock, DiscoveryResult, NodeMeta, NodePayload, SectionContent, SectionMetadata,
+};
+use crate::warn;
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -45,8 +51,16 @@ impl SourceAdapter for CgsAdapter {
                 let cache_key = format!("cgs/{}/title_{}.html", version_id, normalized_title_id);
                 let html = context.cache.fetch_cached(url, &cache_key, None).await?;
 
-                let title_name = extract_title_name_from_html(&html)
-                    .unwrap_or_else(|| format!("Title {normalized_title_id}"));
+                let page_url = url.clone();
+                let (title_name, chapter_urls) = run_blocking(&context.cancel, move |_| {
+                    (
+                        extract_title_name_from_html(&html),
+                        extract_chapter_urls(&html, &page_url),
+                    )
+                })
+                .await?;
+                let title_name =
+                    title_name.unwrap_or_else(|| format!("Title {normalized_title_id}"));
 
                 // Emit title node
                 let title_id =
@@ -62,19 +76,21 @@ impl SourceAdapter for CgsAdapter {
                             level_index: 0,
                             sort_order: designator_sort_order(&normalized_title_id),
                             name: Some(title_name),
-                            path: Some(format!("/title/{normalized_title_id}")),
+                            path: Some(
+                                CGS_PATHS.render("title", &[("num", &normalized_title_id)])?,
+                            ),
                             readable_id: Some(normalized_title_id.clone()),
                             heading_citation: Some(format!("Title {normalized_title_id}")),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
                     .await?;
 
-                // Extract chapter URLs and enqueue them
-                let chapter_urls = extract_chapter_urls(&html, url)?;
-                for (i, chapter) in chapter_urls.into_iter().enumerate() {
+                // Enqueue the chapters found on the title page
+                for (i, chapter) in chapter_urls?.into_iter().enumerate() {
                     context.queue.enqueue(QueueItem {
                         url: chapter.url,
                         parent_id: title_id.clone(),
@@ -106,7 +122,54 @@ impl SourceAdapter for CgsAdapter {
                 let cache_key = format!("cgs/{}/{}.html", version_id, chapter_id);
                 let html = context.cache.fetch_cached(url, &cache_key, None).await?;
 
-                let parsed = parse_cgs_chapter_html(&html, &chapter_id, url, unit_kind);
+                // Artifacts are already namespaced by source and parser
+                // version, so the TOC carries over between source versions.
+                let toc_key = format!("toc/{}.json", chapter_id);
+                // A TOC that can't be read is treated as a cache miss.
+                let prior_toc = match context.cache.read_artifact(&toc_key).await {
+                    Ok(Some(json)) => match serde_json::from_str::<CgsTocMap>(&json) {
+                        Ok(toc) => toc,
+                        Err(err) => {
+                            warn!(context, "Ignoring invalid CGS TOC {}: {}", toc_key, err);
+                            CgsTocMap::default()
+                        }
+                    },
+                    Ok(None) => CgsTocMap::default(),
+                    Err(err) => {
+                        warn!(context, "Failed to read CGS TOC {}: {}", toc_key, err);
+                        CgsTocMap::default()
+                    }
+                };
+
+                let (page_chapter_id, page_url) = (chapter_id.clone(), url.clone());
+                let parsed = run_blocking(&context.cancel, move |_| {
+                    parse_cgs_chapter_html_with_toc(
+                        &html,
+                        &page_chapter_id,
+                        &page_url,
+                        unit_kind,
+                        &prior_toc,
+                    )
+                })
+                .await?;
+
+                let missing = parsed.toc.missing_sections(&parsed.sections);
+                if !missing.is_empty() {
+                    warn!(
+                        context,
+                        "CGS {} {}: {} TOC entries have no section body: {}",
+                        unit_kind.as_str(),
+                        chapter_id,
+                        missing.len(),
+                        missing.join(", ")
+                    );
+                }
+
+                let toc_json = serde_json::to_string(&parsed.toc)
+                    .map_err(|e| format!("Failed to serialize CGS TOC {toc_key}: {e}"))?;
+                if let Err(err) = context.cache.write_artifact(&toc_key, &toc_json).await {
+                    warn!(context, "Failed to store CGS TOC {}: {}", toc_key, err);
+                }
 
                 // Emit chapter node
                 let chapter_string_id = format!(
@@ -126,12 +189,10 @@ impl SourceAdapter for CgsAdapter {
                             level_index: 1,
                             sort_order: designator_sort_order(&chapter_id),
                             name: parsed.chapter_title.clone(),
-                            path: Some(format!(
-                                "/{}/{}/{}",
+                            path: Some(CGS_PATHS.render(
                                 unit_kind.as_str(),
-                                normalized_title_id,
-                                chapter_id
-                            )),
+                                &[("title", &normalized_title_id), ("num", &chapter_id)],
+                            )?),
                             readable_id: Some(chapter_id.clone()),
                             heading_citation: Some(format!(
                                 "{} {}",
@@ -140,16 +201,20 @@ impl SourceAdapter for CgsAdapter {
                             )),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: !missing.is_empty(),
                         },
                         content: None,
                     })
                     .await?;
 
                 for section in parsed.sections {
-                    let body = inline_section_cross_references(&section.body);
-                    let mut blocks = vec![body_block(&body)];
+                    let mut blocks = body_blocks(context.build.profile, || {
+                        inline_section_cross_references(&section.body)
+                    });
 
                     let inline_refs = |s: &str| inline_section_cross_references(s);
+                    let (see_also, unlinked_see_also) =
+                        split_see_also(section.see_also.as_deref().unwrap_or_default());
                     push_block(
                         &mut blocks,
                         "history_short",
@@ -171,17 +236,32 @@ impl SourceAdapter for CgsAdapter {
                         section.citations,
                         None,
                     );
+                    blocks.extend(see_also.iter().map(|reference| ContentBlock {
+                        type_: "cross_reference".to_string(),
+                        label: Some("See Also".to_string()),
+                        content: Some(if reference.description.is_empty() {
+                            format!("[Sec. {}]({})", reference.section, reference.link)
+                        } else {
+                            format!(
+                                "[Sec. {}]({}) re {}",
+                                reference.section, reference.link, reference.description
+                            )
+                        }),
+                    }));
                     push_block(
                         &mut blocks,
                         "see_also",
                         "See Also",
-                        section.see_also,
+                        unlinked_see_also,
                         Some(&inline_refs),
                     );
 
                     let content = SectionContent {
                         blocks,
-                        metadata: None,
+                        metadata: (!see_also.is_empty()).then(|| SectionMetadata {
+                            see_also,
+                            ..Default::default()
+                        }),
                     };
                     let section_slug = section
                         .string_id
@@ -206,6 +286,7 @@ impl SourceAdapter for CgsAdapter {
                                 heading_citation: Some(format!("CGS § {}", section.readable_id)),
                                 source_url: Some(section.source_url),
                                 accessed_at: Some(context.build.accessed_at.to_string()),
+                                review_required: false,
                             },
                             content: Some(serde_json::to_value(&content).unwrap()),
                         })
@@ -232,4 +313,22 @@ impl SourceAdapter for CgsAdapter {
             other => other.to_string(),
         }
     }
+
+    // v2: see-also metadata, hidden markup and invisible characters dropped.
+    // v3: see-also references as cross_reference blocks.
+    fn parser_version(&self) -> u32 {
+        3
+    }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // Section paths are `/section/{num}`; links add the site prefix.
+        if node.level_name != "section" {
+            return None;
+        }
+        node.path.as_ref().map(|path| format!("/statutes{path}"))
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/statutes/section/")
+    }
 }
@@ -11,6 +11,8 @@ pub struct SectionCrossReference {
     pub link: String,
 }
 
+pub use crate::types::SeeAlsoReference;
+
 #[derive(Debug, Clone)]
 enum Token {
     SectionNumber {
@@ -61,6 +63,12 @@ static TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| {
     .unwrap()
 });
 
+/// The "re" cue only counts straight after a citation (`Sec. 20-86bb re`,
+/// `(a) re:`), so words such as "re-enacted" never start a subject.
+static SEE_ALSO_SUBJECT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\d[0-9a-z\-]*|\))\.?,?\s+(?P<cue>re)(?::\s*|\s+)(?P<subject>.+)$").unwrap()
+});
+
 static QUALIFIER_KEYWORDS: LazyLock<HashSet<&str>> = LazyLock::new(|| {
     [
         "subsection",
@@ -137,6 +145,62 @@ pub fn inline_section_cross_references(text: &str) -> String {
     output
 }
 
+/// Parses `cross-ref` annotation text, one annotation per line, into the
+/// sections it points at. Only sections named before the "re" count as
+/// targets; any mentioned in the subject are part of the description.
+pub fn parse_see_also(text: &str) -> Vec<SeeAlsoReference> {
+    let mut references: Vec<SeeAlsoReference> = Vec::new();
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let subject = SEE_ALSO_SUBJECT_RE.captures(line);
+        let targets_end = subject
+            .as_ref()
+            .and_then(|caps| caps.name("cue"))
+            .map_or(line.len(), |m| m.start());
+        let description = subject
+            .as_ref()
+            .and_then(|caps| caps.name("subject"))
+            .map(|m| m.as_str().trim_end_matches('.').trim().to_string())
+            .unwrap_or_default();
+
+        for reference in extract_section_cross_references(&line[..targets_end]) {
+            if references.iter().any(|r| r.section == reference.section) {
+                continue;
+            }
+            references.push(SeeAlsoReference {
+                section: reference.section,
+                link: reference.link,
+                description: description.clone(),
+            });
+        }
+    }
+
+    references
+}
+
+/// Splits `cross-ref` annotation text into the references its lines name and
+/// the lines naming no section (e.g. "See chapter 368a."), which are kept as
+/// text.
+pub fn split_see_also(text: &str) -> (Vec<SeeAlsoReference>, Option<String>) {
+    let mut references: Vec<SeeAlsoReference> = Vec::new();
+    let mut unlinked: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let line_references = parse_see_also(line);
+        if line_references.is_empty() {
+            unlinked.push(line);
+        }
+        for reference in line_references {
+            if !references.iter().any(|r| r.section == reference.section) {
+                references.push(reference);
+            }
+        }
+    }
+    (
+        references,
+        (!unlinked.is_empty()).then(|| unlinked.join("\n")),
+    )
+}
+
 fn tokenize(text: &str) -> Vec<Token> {
     let mut tokens = Vec::new();
 
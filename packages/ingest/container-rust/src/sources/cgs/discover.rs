@@ -76,6 +76,7 @@ pub async fn discover_cgs_root(
         heading_citation: Some("CGS".to_string()),
         source_url: Some(start_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
@@ -1,5 +1,8 @@
+use crate::sources::common::{strip_hidden_html, strip_invisible_chars};
+use crate::sources::paths::CGS_PATHS;
 use regex::Regex;
-use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::LazyLock;
 use tl::NodeHandle;
 
@@ -41,6 +44,7 @@ impl CgsUnitKind {
 
 #[derive(Debug, Clone)]
 pub struct CgsParsedSection {
+    pub anchor_id: String,
     pub string_id: String,
     pub level_name: String,
     pub level_index: i32,
@@ -62,6 +66,72 @@ pub struct CgsChapterParseResult {
     pub chapter_title: Option<String>,
     pub chapter_number: Option<String>,
     pub sections: Vec<CgsParsedSection>,
+    pub toc: CgsTocMap,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CgsTocEntry {
+    pub section_id: String,
+    pub label: String,
+}
+
+/// Chapter TOC entries in document order, keyed by section anchor id.
+/// Entries with empty labels are kept so coverage checks still see them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CgsTocMap {
+    pub entries: Vec<CgsTocEntry>,
+}
+
+impl CgsTocMap {
+    pub fn label(&self, section_id: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.section_id == section_id)
+            .map(|entry| entry.label.as_str())
+            .filter(|label| !label.is_empty())
+    }
+
+    pub fn section_ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.section_id.as_str())
+    }
+
+    fn insert(&mut self, section_id: &str, label: String) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.section_id == section_id)
+        {
+            Some(entry) if entry.label.is_empty() => entry.label = label,
+            Some(_) => {}
+            None => self.entries.push(CgsTocEntry {
+                section_id: section_id.to_string(),
+                label,
+            }),
+        }
+    }
+
+    /// Fill empty labels from a previously persisted map for the same chapter.
+    pub fn backfill_from(&mut self, prior: &CgsTocMap) {
+        for entry in &mut self.entries {
+            if entry.label.is_empty() {
+                if let Some(label) = prior.label(&entry.section_id) {
+                    entry.label = label.to_string();
+                }
+            }
+        }
+    }
+
+    /// TOC section ids that have no matching section in the parsed chapter body.
+    pub fn missing_sections(&self, sections: &[CgsParsedSection]) -> Vec<String> {
+        let parsed: HashSet<&str> = sections
+            .iter()
+            .map(|section| section.anchor_id.as_str())
+            .collect();
+        self.section_ids()
+            .filter(|section_id| !parsed.contains(section_id))
+            .map(ToString::to_string)
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -102,26 +172,22 @@ enum ContentTarget {
 }
 
 #[derive(Debug, Clone)]
-struct ParseState {
+struct ParseState<'a> {
     sections: Vec<SectionData>,
     current_section_index: Option<usize>,
-    in_script: bool,
-    in_style: bool,
-    in_script_or_style: bool,
     current_target: ContentTarget,
-    toc_map: BTreeMap<String, String>,
+    toc_map: CgsTocMap,
+    prior_toc: &'a CgsTocMap,
 }
 
-impl ParseState {
-    fn new(toc_map: BTreeMap<String, String>) -> Self {
+impl<'a> ParseState<'a> {
+    fn new(toc_map: CgsTocMap, prior_toc: &'a CgsTocMap) -> Self {
         Self {
             sections: Vec::new(),
             current_section_index: None,
-            in_script: false,
-            in_style: false,
-            in_script_or_style: false,
             current_target: ContentTarget::Body,
             toc_map,
+            prior_toc,
         }
     }
 
@@ -154,7 +220,12 @@ impl ParseState {
         self.current_section_index = Some(self.sections.len());
         self.current_target = ContentTarget::Body; // Reset to body for new section
 
-        let name = self.toc_map.get(section_id).cloned().unwrap_or_default();
+        let name = self
+            .toc_map
+            .label(section_id)
+            .or_else(|| self.prior_toc.label(section_id))
+            .unwrap_or_default()
+            .to_string();
         self.sections.push(SectionData {
             section_id: section_id.to_string(),
             name,
@@ -183,15 +254,36 @@ pub fn parse_cgs_chapter_html(
     source_url: &str,
     unit_kind: CgsUnitKind,
 ) -> CgsChapterParseResult {
-    let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
-    let toc_map = extract_toc_map(&dom);
+    parse_cgs_chapter_html_with_toc(
+        html,
+        chapter_id,
+        source_url,
+        unit_kind,
+        &CgsTocMap::default(),
+    )
+}
+
+/// Parse a chapter page, backfilling section names the page's own TOC omits
+/// from a TOC map persisted by an earlier run.
+pub fn parse_cgs_chapter_html_with_toc(
+    html: &str,
+    chapter_id: &str,
+    source_url: &str,
+    unit_kind: CgsUnitKind,
+    prior_toc: &CgsTocMap,
+) -> CgsChapterParseResult {
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = tl::parse(&html, tl::ParserOptions::default()).unwrap();
+    let mut toc_map = extract_toc_map(&dom);
+    toc_map.backfill_from(prior_toc);
     let chapter_title = extract_chapter_title(&dom);
     let chapter_number = extract_chapter_number(&dom);
 
     // Build skip map for catchln and nav_tbl descendants
     let skip_map = build_skip_map(&dom);
 
-    let mut state = ParseState::new(toc_map);
+    let mut state = ParseState::new(toc_map, prior_toc);
     let parser = dom.parser();
 
     // Process all nodes in flat order, skipping descendants of catchln/nav_tbl
@@ -207,9 +299,6 @@ pub fn parse_cgs_chapter_html(
 
         // Handle text nodes
         if let Some(text) = node.as_raw() {
-            if state.in_script || state.in_style {
-                continue;
-            }
             state.push_text(text.as_utf8_str().as_ref());
             continue;
         }
@@ -227,16 +316,6 @@ pub fn parse_cgs_chapter_html(
                 continue;
             }
 
-            // Track script/style
-            if tag == "script" || tag == "style" {
-                state.in_script_or_style = !state.in_script_or_style;
-                continue;
-            }
-
-            if state.in_script || state.in_style {
-                continue;
-            }
-
             // Switch content target
             if let Some(target) = classify_target(&classes) {
                 state.current_target = target;
@@ -277,6 +356,7 @@ pub fn parse_cgs_chapter_html(
             source_url,
             unit_kind,
         ),
+        toc: state.toc_map,
     }
 }
 
@@ -367,8 +447,8 @@ fn is_block_tag(tag: &str) -> bool {
     )
 }
 
-fn extract_toc_map(dom: &tl::VDom) -> BTreeMap<String, String> {
-    let mut map = BTreeMap::new();
+fn extract_toc_map(dom: &tl::VDom) -> CgsTocMap {
+    let mut map = CgsTocMap::default();
     let parser = dom.parser();
 
     for node in dom.nodes() {
@@ -390,10 +470,7 @@ fn extract_toc_map(dom: &tl::VDom) -> BTreeMap<String, String> {
                                     let href_val = href_bytes.as_utf8_str();
                                     if let Some(section_id) = href_val.as_ref().strip_prefix('#') {
                                         let text = extract_text_content(dom, *child_handle);
-                                        let collapsed = collapse_text(text);
-                                        if !collapsed.is_empty() {
-                                            map.insert(section_id.to_string(), collapsed);
-                                        }
+                                        map.insert(section_id, collapse_text(text));
                                     }
                                 }
                             }
@@ -545,11 +622,14 @@ fn build_sections_from_parsed_data(
 
         let readable_id = normalized_number.replace('_', " ");
         results.push(CgsParsedSection {
+            anchor_id: section.section_id.clone(),
             string_id: format!("cgs/section/{normalized_number}"),
             level_name: "section".to_string(),
             level_index: 2,
             name: section_name,
-            path: format!("/section/{normalized_number}"),
+            path: CGS_PATHS
+                .render("section", &[("num", &normalized_number)])
+                .expect("CGS section path template takes only num"),
             readable_id,
             body,
             history_short,
@@ -739,11 +819,18 @@ pub fn designator_sort_order(value: &str) -> i32 {
 }
 
 pub fn extract_chapter_title_from_html(html: &str) -> Option<String> {
-    let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = tl::parse(&html, tl::ParserOptions::default()).unwrap();
     extract_chapter_title(&dom)
 }
 
 pub fn extract_section_ids_from_toc(html: &str) -> Vec<String> {
-    let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
-    extract_toc_map(&dom).keys().cloned().collect()
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = tl::parse(&html, tl::ParserOptions::default()).unwrap();
+    extract_toc_map(&dom)
+        .section_ids()
+        .map(ToString::to_string)
+        .collect()
 }
@@ -1,4 +1,9 @@
-use crate::types::ContentBlock;
+use crate::runtime::types::IngestContext;
+use crate::sources::paths::PathTemplates;
+use crate::types::{ContentBlock, IngestProfile, NodeMeta, NodePayload};
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
 
 pub fn capitalize_first(value: &str) -> String {
     let mut chars = value.chars();
@@ -8,6 +13,38 @@ pub fn capitalize_first(value: &str) -> String {
     }
 }
 
+/// Deepest blockquote nesting used for outline levels. USLM's subsection
+/// through subitem hierarchy fits; deeper nesting from malformed sources is
+/// flattened to this depth with its markers left in the text.
+pub const MAX_OUTLINE_DEPTH: usize = 6;
+
+/// Blockquote prefix for an outline level, capped at [`MAX_OUTLINE_DEPTH`].
+pub fn outline_prefix(depth: usize) -> String {
+    "> ".repeat(depth.min(MAX_OUTLINE_DEPTH))
+}
+
+/// Rewrites every line nested more than `max_depth` blockquotes deep so it
+/// sits at `max_depth`, leaving the line's own text untouched.
+pub fn flatten_outline_depth(markdown: &str, max_depth: usize) -> String {
+    markdown
+        .split('\n')
+        .map(|line| {
+            let mut depth = 0;
+            let mut rest = line;
+            while let Some(stripped) = rest.strip_prefix('>') {
+                depth += 1;
+                rest = stripped.strip_prefix(' ').unwrap_or(stripped);
+            }
+            if depth <= max_depth {
+                line.to_string()
+            } else {
+                format!("{}{rest}", "> ".repeat(max_depth))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Create a body ContentBlock, setting content to None if the text is empty/whitespace.
 pub fn body_block(text: &str) -> ContentBlock {
     ContentBlock {
@@ -21,6 +58,16 @@ pub fn body_block(text: &str) -> ContentBlock {
     }
 }
 
+/// Opening blocks for a section: its body block, or none when `profile`
+/// skips body text. `body` is only rendered when it is kept.
+pub fn body_blocks(profile: IngestProfile, body: impl FnOnce() -> String) -> Vec<ContentBlock> {
+    if profile.captures_body() {
+        vec![body_block(&body())]
+    } else {
+        Vec::new()
+    }
+}
+
 /// Push a content block if the value is non-empty. Optionally transforms the content
 /// (e.g. for inlining cross-references).
 pub fn push_block(
@@ -44,3 +91,380 @@ pub fn push_block(
         }
     }
 }
+
+/// Elements whose contents are never rendered as page text.
+const NON_RENDERED_TAGS: [&str; 4] = ["script", "style", "noscript", "template"];
+
+/// Whether a browser would keep `tag` and everything in it off the page:
+/// non-rendered elements, the `hidden` attribute, `aria-hidden="true"`, and
+/// inline `display: none` or `visibility: hidden` styles.
+pub fn is_hidden_element(tag: &tl::HTMLTag<'_>) -> bool {
+    let name = tag.name().as_utf8_str();
+    if NON_RENDERED_TAGS
+        .iter()
+        .any(|hidden| name.eq_ignore_ascii_case(hidden))
+    {
+        return true;
+    }
+    let attributes = tag.attributes();
+    if attributes.contains("hidden") {
+        return true;
+    }
+    let attribute = |key: &str| {
+        attributes
+            .get(key)
+            .flatten()
+            .map(|value| value.as_utf8_str().to_ascii_lowercase())
+    };
+    if attribute("aria-hidden").is_some_and(|value| value.trim() == "true") {
+        return true;
+    }
+    attribute("style").is_some_and(|style| {
+        let style: String = style.chars().filter(|c| !c.is_whitespace()).collect();
+        style.contains("display:none") || style.contains("visibility:hidden")
+    })
+}
+
+/// Removes comments and hidden elements (see [`is_hidden_element`]) from
+/// `html` so tl-based parsers only ever see rendered text. Unclosed hidden
+/// elements lose only their start tag, since tl gives them no children.
+pub fn strip_hidden_html(html: &str) -> Cow<'_, str> {
+    let Ok(dom) = tl::parse(html, tl::ParserOptions::default()) else {
+        return Cow::Borrowed(html);
+    };
+    let parser = dom.parser();
+    let offset_of = |bytes: &[u8]| (bytes.as_ptr() as usize).checked_sub(html.as_ptr() as usize);
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for node in dom.nodes() {
+        let range = match node {
+            tl::Node::Comment(comment) => {
+                let bytes = comment.as_bytes();
+                offset_of(bytes).map(|start| (start, start + bytes.len()))
+            }
+            tl::Node::Tag(tag) if is_hidden_element(tag) => {
+                let (start, end) = tag.boundaries(parser);
+                Some((start, end + 1))
+            }
+            _ => None,
+        };
+        if let Some((start, end)) = range {
+            // Nodes come in document order, so a nested hidden node always
+            // starts inside the range of its hidden ancestor.
+            if ranges.last().is_none_or(|(_, last_end)| start >= *last_end) && end <= html.len() {
+                ranges.push((start, end));
+            }
+        }
+    }
+    if ranges.is_empty() {
+        return Cow::Borrowed(html);
+    }
+
+    let mut visible = String::with_capacity(html.len());
+    let mut cursor = 0;
+    for (start, end) in ranges {
+        visible.push_str(&html[cursor..start]);
+        cursor = end;
+    }
+    visible.push_str(&html[cursor..]);
+    Cow::Owned(visible)
+}
+
+/// Soft hyphens, zero-width spaces and joiners, word joiners, and byte-order
+/// marks, as characters or as HTML character references.
+static INVISIBLE_CHAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)[\x{00AD}\x{200B}-\x{200D}\x{2060}\x{FEFF}]|&(?:shy|zwnj|zwj|#0*(?:173|820[345]|8288|65279)|#x0*(?:ad|200[bcd]|2060|feff));",
+    )
+    .unwrap()
+});
+
+/// Removes characters that render as nothing but would otherwise split words
+/// in emitted text and break search and citation matching.
+pub fn strip_invisible_chars(text: &str) -> Cow<'_, str> {
+    INVISIBLE_CHAR_RE.replace_all(text, "")
+}
+
+static MARKDOWN_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\([^)\s]*\)").unwrap());
+
+/// Replaces each markdown link with its text, for parsers that read
+/// citations out of rendered content.
+pub fn strip_markdown_links(text: &str) -> Cow<'_, str> {
+    MARKDOWN_LINK_RE.replace_all(text, "$1")
+}
+
+static LONG_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(Jan(?:uary)?|Feb(?:ruary)?|Mar(?:ch)?|Apr(?:il)?|May|June?|July?|Aug(?:ust)?|Sept?(?:ember)?|Oct(?:ober)?|Nov(?:ember)?|Dec(?:ember)?)\.?\s+(\d{1,2}),\s+(\d{4})\b",
+    )
+    .unwrap()
+});
+
+/// Dates written out with a month name (`Oct. 1, 1978`, `September 30,
+/// 2022`) in `text`, as the byte offset of each match and the date in ISO
+/// 8601. Impossible dates are skipped.
+pub fn find_long_dates(text: &str) -> Vec<(usize, String)> {
+    LONG_DATE_RE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let month = month_number(&caps[1])?;
+            let date = chrono::NaiveDate::from_ymd_opt(
+                caps[3].parse().ok()?,
+                month,
+                caps[2].parse().ok()?,
+            )?;
+            Some((caps.get(0)?.start(), date.format("%Y-%m-%d").to_string()))
+        })
+        .collect()
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let month = match name.get(..3)?.to_lowercase().as_str() {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+/// A location in source text. `line` and `column` are 1-based; `column`
+/// counts characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub byte_offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourcePosition {
+    pub fn locate(text: &str, byte_offset: usize) -> Self {
+        let mut offset = byte_offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        Self {
+            byte_offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// Parser failure with enough context to find the offending markup in a
+/// large source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: Option<SourcePosition>,
+    /// Slash-separated local names of the open elements, e.g. `/uscDoc/main/title`.
+    pub element_path: String,
+}
+
+impl ParseError {
+    pub fn at(text: &str, byte_offset: usize, element_path: &str, message: String) -> Self {
+        Self {
+            message,
+            position: Some(SourcePosition::locate(text, byte_offset)),
+            element_path: element_path.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(position) = self.position {
+            write!(
+                f,
+                " at line {}, column {} (byte {})",
+                position.line, position.column, position.byte_offset
+            )?;
+        }
+        if !self.element_path.is_empty() {
+            write!(f, " in {}", self.element_path)?;
+        }
+        Ok(())
+    }
+}
+
+static STRUCTURAL_HEADING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(subchapter|article|part)\s+([0-9]+[A-Za-z]*(?:[.\-][0-9A-Za-z]+)*|[IVXLC]+)\s*[.:\-\u{2013}\u{2014}]?\s*(.*)$",
+    )
+    .unwrap()
+});
+
+/// A structural division between a chapter and its sections (subchapter,
+/// article, part) in sources that otherwise emit flat chapter/section trees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuralLevel {
+    pub level_name: String,
+    pub num: String,
+    pub name: String,
+}
+
+impl StructuralLevel {
+    /// Nesting rank below a chapter; lower ranks enclose higher ones.
+    pub fn rank(&self) -> usize {
+        match self.level_name.as_str() {
+            "subchapter" | "article" => 0,
+            _ => 1,
+        }
+    }
+
+    pub fn slug(&self) -> String {
+        self.num
+            .trim_start_matches('0')
+            .to_ascii_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+            .trim_matches('-')
+            .to_string()
+    }
+
+    pub fn display_num(&self) -> &str {
+        let trimmed = self.num.trim_start_matches('0');
+        if trimmed.is_empty() {
+            &self.num
+        } else {
+            trimmed
+        }
+    }
+}
+
+/// Parses a heading such as `Subchapter 001: General Provisions` or
+/// `ARTICLE 1 GENERAL PROVISIONS` into a structural level.
+pub fn parse_structural_heading(text: &str) -> Option<StructuralLevel> {
+    let captures = STRUCTURAL_HEADING_RE.captures(text.trim())?;
+    Some(StructuralLevel {
+        level_name: captures[1].to_ascii_lowercase(),
+        num: captures[2].to_string(),
+        name: captures[3].trim().trim_end_matches('.').trim().to_string(),
+    })
+}
+
+/// Tracks the open structural levels while walking a chapter in document order.
+#[derive(Debug, Default, Clone)]
+pub struct StructuralLevelStack {
+    open: Vec<StructuralLevel>,
+}
+
+impl StructuralLevelStack {
+    /// Opens `level`, closing any open level of the same or deeper rank.
+    pub fn open(&mut self, level: StructuralLevel) {
+        self.open.retain(|open| open.rank() < level.rank());
+        self.open.push(level);
+    }
+
+    pub fn current(&self) -> Vec<StructuralLevel> {
+        self.open.clone()
+    }
+}
+
+/// Parent of a section after its structural levels have been emitted.
+/// Section ids stay keyed on `chapter_id` so adding or dropping an article
+/// or part only moves the section, never renames it.
+pub struct StructuralParent {
+    pub id: String,
+    pub chapter_id: String,
+    pub path: String,
+    pub level_index: i32,
+}
+
+impl StructuralParent {
+    /// Parent for sections directly beneath the chapter `id`.
+    pub fn chapter(id: &str, path: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            chapter_id: id.to_string(),
+            path: path.to_string(),
+            level_index: 1,
+        }
+    }
+
+    pub fn section_id(&self, section_slug: &str) -> String {
+        format!("{}/section-{section_slug}", self.chapter_id)
+    }
+}
+
+/// Emits level nodes for `levels` beneath `chapter`, skipping ones already
+/// in `seen`, and returns the node sections under them should attach to.
+pub async fn insert_structural_levels(
+    context: &IngestContext<'_>,
+    chapter: StructuralParent,
+    levels: &[StructuralLevel],
+    paths: &PathTemplates,
+    source_url: &str,
+    seen: &mut Vec<String>,
+) -> Result<StructuralParent, String> {
+    let mut parent = chapter;
+
+    for level in levels {
+        let id = format!("{}/{}-{}", parent.id, level.level_name, level.slug());
+        let path = paths.render(
+            &level.level_name,
+            &[
+                ("parent", &parent.path),
+                ("level", &level.level_name),
+                ("num", &level.slug()),
+            ],
+        )?;
+        let level_index = parent.level_index + 1;
+
+        if !seen.contains(&id) {
+            context
+                .nodes
+                .insert_node(NodePayload {
+                    meta: NodeMeta {
+                        id: id.clone(),
+                        source_version_id: context.build.source_version_id.to_string(),
+                        parent_id: Some(parent.id.clone()),
+                        level_name: level.level_name.clone(),
+                        level_index,
+                        sort_order: seen.len() as i32,
+                        name: Some(if level.name.is_empty() {
+                            level.display_num().to_string()
+                        } else {
+                            level.name.clone()
+                        }),
+                        path: Some(path.clone()),
+                        readable_id: Some(level.display_num().to_string()),
+                        heading_citation: Some(format!(
+                            "{} {}",
+                            capitalize_first(&level.level_name),
+                            level.display_num()
+                        )),
+                        source_url: Some(source_url.to_string()),
+                        accessed_at: Some(context.build.accessed_at.to_string()),
+                        review_required: false,
+                    },
+                    content: None,
+                })
+                .await?;
+            seen.push(id.clone());
+        }
+
+        parent = StructuralParent {
+            id,
+            chapter_id: parent.chapter_id,
+            path,
+            level_index,
+        };
+    }
+
+    Ok(parent)
+}
@@ -1,7 +1,30 @@
+use crate::sources::common::capitalize_first;
+use crate::sources::paths::DEFAULT_SLUG_STOP_WORDS;
 use crate::types::SourceKind;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::sync::LazyLock;
+
+static DEFAULT_SOURCES_CONFIG: LazyLock<Option<SourcesConfig>> =
+    LazyLock::new(|| SourcesConfig::load_default().ok());
+static ROMAN_NUMERAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^M{0,4}(CM|CD|D?C{0,3})(XC|XL|L?X{0,3})(IX|IV|V?I{0,3})$").unwrap()
+});
+
+/// Words kept lowercase in title case unless they open or close the heading.
+const TITLE_CASE_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "per", "than", "the", "to", "under", "upon", "via", "with", "within", "without",
+];
+
+/// Acronyms kept upper case when an all-caps heading is recased. Only words
+/// that can't also be ordinary English words belong here.
+const HEADING_ACRONYMS: &[&str] = &[
+    "AIDS", "DNA", "EPA", "ERISA", "FBI", "FDIC", "FEMA", "HIV", "HUD", "IRS", "NASA", "NATO",
+    "NOAA", "OSHA", "SBA", "TANF", "TRICARE", "TVA", "USA", "WIC",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceConfig {
@@ -11,6 +34,323 @@ pub struct SourceConfig {
     pub doc_type: String,
     pub description: String,
     pub root_url: String,
+    /// Overrides for how this source's note topics map to content block types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_taxonomy: Option<NoteTaxonomy>,
+    /// URL templates for references outside the source (CFR, Statutes at
+    /// Large, public laws).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_links: Option<ExternalLinks>,
+    /// Casing applied to structural level names (titles, chapters, parts).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading_case: Option<HeadingCase>,
+    /// Words left out of heading slugs; replaces the default list when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug_stop_words: Option<Vec<String>>,
+    /// Puts the source in maintenance: new ingest runs are refused with this
+    /// reason until it is removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadingCase {
+    #[default]
+    Preserve,
+    /// Capitalizes each word except legal stopwords (`of`, `and`, `the`, ...)
+    /// in the middle of the heading.
+    TitleCase,
+    /// Capitalizes only the first word.
+    SentenceCase,
+}
+
+impl HeadingCase {
+    /// Recases `heading`. Upper-case Roman numerals, designators containing
+    /// digits or closing a clause, dotted abbreviations such as `U.S.`, and
+    /// acronyms keep their source casing. An acronym is a known one such as
+    /// `HIV`, or any all-caps word in a heading that is otherwise mixed case.
+    pub fn apply(self, heading: &str) -> String {
+        if self == HeadingCase::Preserve {
+            return heading.to_string();
+        }
+
+        let word_count = heading_words(heading).count();
+        let mixed_case = heading.chars().any(char::is_lowercase);
+        let mut out = String::with_capacity(heading.len());
+        let mut index = 0;
+        let mut starts_clause = true;
+        let mut word = String::new();
+        let mut chars = heading.chars().peekable();
+        while let Some(c) = chars.next() {
+            if is_word_char(c) {
+                word.push(c);
+                if chars.peek().is_some_and(|next| is_word_char(*next)) {
+                    continue;
+                }
+                // A lone letter closing a clause is a designator (`PART A—`).
+                let designator = word.chars().count() == 1
+                    && chars.peek().is_none_or(|next| is_clause_break(*next));
+                if designator {
+                    out.push_str(&word);
+                } else {
+                    out.push_str(&self.recase_word(
+                        &word,
+                        starts_clause,
+                        index + 1 == word_count,
+                        mixed_case,
+                    ));
+                }
+                word.clear();
+                index += 1;
+                starts_clause = false;
+            } else {
+                out.push(c);
+                if is_clause_break(c) {
+                    starts_clause = self == HeadingCase::TitleCase;
+                }
+            }
+        }
+        out
+    }
+
+    fn recase_word(self, word: &str, first: bool, last: bool, mixed_case: bool) -> String {
+        let trimmed = word.trim_end_matches('.');
+        if trimmed.chars().any(|c| c.is_ascii_digit())
+            || trimmed.contains('.')
+            || !trimmed.is_empty() && ROMAN_NUMERAL_RE.is_match(trimmed)
+            || is_acronym(trimmed, mixed_case)
+        {
+            return word.to_string();
+        }
+
+        let lower = word.to_lowercase();
+        let capitalize = match self {
+            HeadingCase::Preserve => return word.to_string(),
+            HeadingCase::TitleCase => {
+                first || last || !TITLE_CASE_STOPWORDS.contains(&trimmed.to_lowercase().as_str())
+            }
+            HeadingCase::SentenceCase => first,
+        };
+        if capitalize {
+            capitalize_first(&lower)
+        } else {
+            lower
+        }
+    }
+}
+
+fn is_acronym(word: &str, mixed_case: bool) -> bool {
+    let stem = word
+        .strip_suffix("'S")
+        .or_else(|| word.strip_suffix("'s"))
+        .unwrap_or(word);
+    let all_caps = stem.chars().count() > 1 && stem.chars().all(|c| c.is_uppercase());
+    all_caps && (mixed_case || HEADING_ACRONYMS.contains(&stem))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '.'
+}
+
+fn is_clause_break(c: char) -> bool {
+    matches!(c, ':' | '\u{2014}' | '\u{2013}')
+}
+
+fn heading_words(heading: &str) -> impl Iterator<Item = &str> {
+    heading
+        .split(|c: char| !is_word_char(c))
+        .filter(|word| !word.is_empty())
+}
+
+/// Maps source note metadata (topic, role, heading text) to content block types.
+///
+/// Lookups are case-insensitive and checked in order: role, topic, heading
+/// keyword, then `default_block_type`. The block type `heading` emits a
+/// heading block from the note heading and drops the note body.
+///
+/// Fields omitted from a config entry deserialize empty, so an override only
+/// needs to list the mappings it adds or changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteTaxonomy {
+    #[serde(default)]
+    pub roles: HashMap<String, String>,
+    #[serde(default)]
+    pub topics: HashMap<String, String>,
+    #[serde(default)]
+    pub heading_keywords: Vec<HeadingKeyword>,
+    #[serde(default)]
+    pub default_block_type: String,
+    /// Label used for a block type when the note has no heading of its own.
+    #[serde(default)]
+    pub default_labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadingKeyword {
+    pub keyword: String,
+    pub block_type: String,
+}
+
+/// USLM note topics (lowercased) with their block type and the label a
+/// note of that type gets when it has no heading.
+const NOTE_TOPICS: &[(&str, &str, &str)] = &[
+    ("amendments", "amendments", "Amendments"),
+    ("changeofname", "change_of_name", "Change of Name"),
+    ("construction", "construction", "Construction"),
+    ("effectivedate", "effective_date", "Effective Date"),
+    (
+        "effectivedateofamendment",
+        "effective_date_of_amendment",
+        "Effective Date of Amendment",
+    ),
+    ("priorprovisions", "prior_provisions", "Prior Provisions"),
+    (
+        "referencesintext",
+        "references_in_text",
+        "References in Text",
+    ),
+    ("regulations", "regulations", "Regulations"),
+    ("repeals", "repeals", "Repeals"),
+    ("savingsprovision", "savings_provision", "Savings Provision"),
+    ("separability", "separability", "Separability"),
+    ("shorttitle", "short_title", "Short Title"),
+    ("shorttitleofamendment", "short_title", "Short Title"),
+    ("terminationdate", "termination", "Termination"),
+    (
+        "transferoffunctions",
+        "transfer_of_functions",
+        "Transfer of Functions",
+    ),
+];
+
+/// Heading phrases that type a note whose topic is missing or unmapped.
+/// Specific phrases come first, since "Short Title of 1990 Amendments"
+/// also contains "amendments".
+const NOTE_HEADING_KEYWORDS: &[(&str, &str)] = &[
+    ("short title", "short_title"),
+    ("transfer of functions", "transfer_of_functions"),
+    ("savings provision", "savings_provision"),
+    ("references in text", "references_in_text"),
+    ("change of name", "change_of_name"),
+    ("separability", "separability"),
+    ("construction", "construction"),
+    ("termination", "termination"),
+    ("amendments", "amendments"),
+];
+
+impl Default for NoteTaxonomy {
+    fn default() -> Self {
+        Self {
+            roles: HashMap::from([("crossheading".to_string(), "heading".to_string())]),
+            topics: NOTE_TOPICS
+                .iter()
+                .map(|(topic, block_type, _)| (topic.to_string(), block_type.to_string()))
+                .collect(),
+            heading_keywords: NOTE_HEADING_KEYWORDS
+                .iter()
+                .map(|(keyword, block_type)| HeadingKeyword {
+                    keyword: keyword.to_string(),
+                    block_type: block_type.to_string(),
+                })
+                .collect(),
+            default_block_type: "note".to_string(),
+            default_labels: NOTE_TOPICS
+                .iter()
+                .map(|(_, block_type, label)| (block_type.to_string(), label.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl NoteTaxonomy {
+    /// Applies `overrides` on top of `self`; override entries win and
+    /// override heading keywords are checked first.
+    pub fn merged_with(&self, overrides: &NoteTaxonomy) -> NoteTaxonomy {
+        let mut merged = self.clone();
+        merged.roles.extend(lowercase_keys(&overrides.roles));
+        merged.topics.extend(lowercase_keys(&overrides.topics));
+        let mut keywords = overrides.heading_keywords.clone();
+        keywords.extend(merged.heading_keywords);
+        merged.heading_keywords = keywords;
+        if !overrides.default_block_type.is_empty() {
+            merged.default_block_type = overrides.default_block_type.clone();
+        }
+        merged
+            .default_labels
+            .extend(overrides.default_labels.clone());
+        merged
+    }
+
+    pub fn classify(&self, topic: Option<&str>, role: Option<&str>, heading: &str) -> &str {
+        if let Some(block_type) = role.and_then(|role| self.roles.get(&role.to_lowercase())) {
+            return block_type;
+        }
+        if let Some(block_type) = topic.and_then(|topic| self.topics.get(&topic.to_lowercase())) {
+            return block_type;
+        }
+        let heading = heading.to_lowercase();
+        self.heading_keywords
+            .iter()
+            .find(|rule| heading.contains(&rule.keyword.to_lowercase()))
+            .map(|rule| rule.block_type.as_str())
+            .unwrap_or(&self.default_block_type)
+    }
+
+    pub fn default_label(&self, block_type: &str) -> Option<&str> {
+        self.default_labels.get(block_type).map(String::as_str)
+    }
+}
+
+/// URL templates for references to other publications. `{name}`
+/// placeholders are filled from the reference; an empty template leaves
+/// that kind of reference as plain text.
+///
+/// Fields omitted from a config entry keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalLinks {
+    /// `{title}`, `{section}`.
+    pub cfr_section: String,
+    /// `{title}`, `{part}`.
+    pub cfr_part: String,
+    /// `{volume}`, `{page}`.
+    pub statutes_at_large: String,
+    /// `{congress}`, `{number}`.
+    pub public_law: String,
+}
+
+impl Default for ExternalLinks {
+    fn default() -> Self {
+        Self {
+            cfr_section: "https://www.ecfr.gov/current/title-{title}/section-{section}".to_string(),
+            cfr_part: "https://www.ecfr.gov/current/title-{title}/part-{part}".to_string(),
+            statutes_at_large: "https://www.govinfo.gov/link/statute/{volume}/{page}".to_string(),
+            public_law: "https://www.govinfo.gov/link/plaw/{congress}/public/{number}".to_string(),
+        }
+    }
+}
+
+impl ExternalLinks {
+    /// Fills `template` from `values`, or `None` when it is empty.
+    pub fn render(template: &str, values: &[(&str, &str)]) -> Option<String> {
+        if template.is_empty() {
+            return None;
+        }
+        Some(
+            values
+                .iter()
+                .fold(template.to_string(), |url, (name, value)| {
+                    url.replace(&format!("{{{name}}}"), value)
+                }),
+        )
+    }
+}
+
+fn lowercase_keys(map: &HashMap<String, String>) -> HashMap<String, String> {
+    map.iter()
+        .map(|(key, value)| (key.to_lowercase(), value.clone()))
+        .collect()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,4 +379,142 @@ impl SourcesConfig {
     pub fn get_root_url(&self, source: SourceKind) -> Option<&str> {
         self.sources.get(&source).map(|s| s.root_url.as_str())
     }
+
+    /// Errors with the maintenance reason when `source` is in maintenance.
+    pub fn ensure_accepting_runs(&self, source: SourceKind) -> Result<(), String> {
+        match self
+            .sources
+            .get(&source)
+            .and_then(|s| s.maintenance.as_deref())
+        {
+            Some(reason) => Err(format!(
+                "Source {} is in maintenance and not accepting ingest runs: {reason}",
+                source.as_str()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn heading_case(&self, source: SourceKind) -> HeadingCase {
+        self.sources
+            .get(&source)
+            .and_then(|s| s.heading_case)
+            .unwrap_or_default()
+    }
+
+    pub fn slug_stop_words(&self, source: SourceKind) -> Vec<String> {
+        self.sources
+            .get(&source)
+            .and_then(|s| s.slug_stop_words.as_ref())
+            .map(|words| words.iter().map(|word| word.to_lowercase()).collect())
+            .unwrap_or_else(default_slug_stop_words)
+    }
+
+    /// Fingerprint of the options that shape `source`'s extracted output.
+    pub fn extraction_options_hash(&self, source: SourceKind) -> String {
+        extraction_options_hash(
+            self.heading_case(source),
+            &self.note_taxonomy(source),
+            &self.external_links(source),
+        )
+    }
+
+    pub fn external_links(&self, source: SourceKind) -> ExternalLinks {
+        self.sources
+            .get(&source)
+            .and_then(|s| s.external_links.clone())
+            .unwrap_or_default()
+    }
+
+    /// Default note taxonomy with any per-source overrides applied.
+    pub fn note_taxonomy(&self, source: SourceKind) -> NoteTaxonomy {
+        let defaults = NoteTaxonomy::default();
+        match self
+            .sources
+            .get(&source)
+            .and_then(|s| s.note_taxonomy.as_ref())
+        {
+            Some(overrides) => defaults.merged_with(overrides),
+            None => defaults,
+        }
+    }
+}
+
+/// Note taxonomy for `source` from the deployed sources.json, falling back to
+/// the built-in defaults when the config can't be loaded.
+pub fn configured_note_taxonomy(source: SourceKind) -> NoteTaxonomy {
+    DEFAULT_SOURCES_CONFIG
+        .as_ref()
+        .map(|config| config.note_taxonomy(source))
+        .unwrap_or_default()
+}
+
+/// Extraction options fingerprint for `source` from the deployed sources.json,
+/// hashing the built-in defaults when the config can't be loaded.
+pub fn configured_extraction_options_hash(source: SourceKind) -> String {
+    DEFAULT_SOURCES_CONFIG
+        .as_ref()
+        .map(|config| config.extraction_options_hash(source))
+        .unwrap_or_else(|| {
+            extraction_options_hash(
+                HeadingCase::default(),
+                &NoteTaxonomy::default(),
+                &ExternalLinks::default(),
+            )
+        })
+}
+
+/// External reference URL templates for `source` from the deployed
+/// sources.json; the built-in templates when the config can't be loaded.
+pub fn configured_external_links(source: SourceKind) -> ExternalLinks {
+    DEFAULT_SOURCES_CONFIG
+        .as_ref()
+        .map(|config| config.external_links(source))
+        .unwrap_or_default()
+}
+
+/// Stable 64-bit FNV-1a hash, in hex, of the options as canonical JSON
+/// (object keys sorted), so it is identical across runs and toolchains.
+fn extraction_options_hash(
+    heading_case: HeadingCase,
+    note_taxonomy: &NoteTaxonomy,
+    external_links: &ExternalLinks,
+) -> String {
+    let options = serde_json::json!({
+        "external_links": external_links,
+        "heading_case": heading_case,
+        "note_taxonomy": note_taxonomy,
+    });
+    let hash = options
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Level-name casing for `source` from the deployed sources.json; preserves
+/// source casing when the config can't be loaded.
+pub fn configured_heading_case(source: SourceKind) -> HeadingCase {
+    DEFAULT_SOURCES_CONFIG
+        .as_ref()
+        .map(|config| config.heading_case(source))
+        .unwrap_or_default()
+}
+
+/// Heading slug stop words for `source` from the deployed sources.json; the
+/// built-in list when the config can't be loaded.
+pub fn configured_slug_stop_words(source: SourceKind) -> Vec<String> {
+    DEFAULT_SOURCES_CONFIG
+        .as_ref()
+        .map(|config| config.slug_stop_words(source))
+        .unwrap_or_else(default_slug_stop_words)
+}
+
+fn default_slug_stop_words() -> Vec<String> {
+    DEFAULT_SLUG_STOP_WORDS
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
 }
@@ -1,10 +1,12 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::common::body_block;
+use crate::sources::common::body_blocks;
 use crate::sources::mgl::cross_references::inline_section_cross_references;
 use crate::sources::mgl::parser::{
     designator_sort_order, normalize_body_text, normalize_designator, parse_chapter_detail,
     parse_part_detail, MglApiChapter, MglApiPart, MglApiSection,
 };
+use crate::sources::paths::MGL_PATHS;
 use crate::sources::SourceAdapter;
 use crate::types::{DiscoveryResult, NodeMeta, NodePayload, SectionContent};
 use async_trait::async_trait;
@@ -39,10 +41,16 @@ impl SourceAdapter for MglAdapter {
                 let version_id = &context.build.source_version_id;
                 let cache_key = format!("mgl/{}/part-{}.json", version_id, title_num);
                 let json_str = context.cache.fetch_cached(url, &cache_key, None).await?;
-                let part: MglApiPart = serde_json::from_str(&json_str)
-                    .map_err(|err| format!("Failed to parse MGL part JSON: {url}: {err}"))?;
-
-                let parsed_part = parse_part_detail(&part, url);
+                let page_url = url.clone();
+                let (part, parsed_part) = run_blocking(&context.cancel, move |_| {
+                    serde_json::from_str::<MglApiPart>(&json_str)
+                        .map(|part| {
+                            let parsed = parse_part_detail(&part, &page_url);
+                            (part, parsed)
+                        })
+                        .map_err(|err| format!("Failed to parse MGL part JSON: {page_url}: {err}"))
+                })
+                .await??;
 
                 // Emit part node
                 let part_id = format!(
@@ -61,11 +69,14 @@ impl SourceAdapter for MglAdapter {
                             level_index: 0,
                             sort_order: parsed_part.sort_order,
                             name: Some(parsed_part.part_name.clone()),
-                            path: Some(format!("/part/{}", title_num.to_lowercase())),
+                            path: Some(
+                                MGL_PATHS.render("part", &[("num", &title_num.to_lowercase())])?,
+                            ),
                             readable_id: Some(title_num.to_string()),
                             heading_citation: Some(format!("Part {}", title_num)),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -97,10 +108,18 @@ impl SourceAdapter for MglAdapter {
                     chapter_code.to_lowercase()
                 );
                 let json_str = context.cache.fetch_cached(url, &cache_key, None).await?;
-                let chapter: MglApiChapter = serde_json::from_str(&json_str)
-                    .map_err(|err| format!("Failed to parse MGL chapter JSON: {url}: {err}"))?;
-
-                let parsed_chapter = parse_chapter_detail(&chapter, url);
+                let page_url = url.clone();
+                let (chapter, parsed_chapter) = run_blocking(&context.cancel, move |_| {
+                    serde_json::from_str::<MglApiChapter>(&json_str)
+                        .map(|chapter| {
+                            let parsed = parse_chapter_detail(&chapter, &page_url);
+                            (chapter, parsed)
+                        })
+                        .map_err(|err| {
+                            format!("Failed to parse MGL chapter JSON: {page_url}: {err}")
+                        })
+                })
+                .await??;
 
                 // Emit chapter node
                 let chapter_id = format!(
@@ -119,11 +138,13 @@ impl SourceAdapter for MglAdapter {
                             level_index: 1,
                             sort_order: parsed_chapter.sort_order,
                             name: Some(parsed_chapter.chapter_name.clone()),
-                            path: Some(format!(
-                                "/part/{}/chapter/{}",
-                                title_num.to_lowercase(),
-                                parsed_chapter.chapter_code.to_lowercase()
-                            )),
+                            path: Some(MGL_PATHS.render(
+                                "chapter",
+                                &[
+                                    ("part", &title_num.to_lowercase()),
+                                    ("num", &parsed_chapter.chapter_code.to_lowercase()),
+                                ],
+                            )?),
                             readable_id: Some(parsed_chapter.chapter_code.clone()),
                             heading_citation: Some(format!(
                                 "Chapter {}",
@@ -131,6 +152,7 @@ impl SourceAdapter for MglAdapter {
                             )),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -204,10 +226,13 @@ impl SourceAdapter for MglAdapter {
                     }
                 }
 
-                let normalized = normalize_body_text(&raw_body);
-                let body = inline_section_cross_references(&normalized);
-
-                let blocks = vec![body_block(&body)];
+                let profile = context.build.profile;
+                let blocks = run_blocking(&context.cancel, move |_| {
+                    body_blocks(profile, || {
+                        inline_section_cross_references(&normalize_body_text(&raw_body))
+                    })
+                })
+                .await?;
 
                 let content = SectionContent {
                     blocks,
@@ -229,16 +254,19 @@ impl SourceAdapter for MglAdapter {
                             level_index: 2,
                             sort_order,
                             name: Some(section_name),
-                            path: Some(format!(
-                                "/part/{}/chapter/{}/section/{}",
-                                title_num.to_lowercase(),
-                                chapter_code.to_lowercase(),
-                                section_code.to_lowercase()
-                            )),
+                            path: Some(MGL_PATHS.render(
+                                "section",
+                                &[
+                                    ("part", &title_num.to_lowercase()),
+                                    ("chapter", &chapter_code.to_lowercase()),
+                                    ("num", &section_code.to_lowercase()),
+                                ],
+                            )?),
                             readable_id: Some(section_code.to_string()),
                             heading_citation: Some(heading_citation),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: Some(serde_json::to_value(&content).unwrap()),
                     })
@@ -267,7 +295,27 @@ impl SourceAdapter for MglAdapter {
             other => other.to_string(),
         }
     }
+    // v2: invisible characters dropped, per-level path templates.
+    fn parser_version(&self) -> u32 {
+        2
+    }
+
     fn needs_zip_extraction(&self) -> bool {
         false
     }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // `/part/{p}/chapter/{c}/section/{s}` is linked as
+        // `/statutes/chapter/{c}/section/{s}`.
+        if node.level_name != "section" {
+            return None;
+        }
+        let path = node.path.as_deref()?;
+        let chapter_start = path.find("/chapter/")?;
+        Some(format!("/statutes{}", &path[chapter_start..]))
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/statutes/chapter/")
+    }
 }
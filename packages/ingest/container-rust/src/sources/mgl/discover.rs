@@ -77,6 +77,7 @@ pub async fn discover_mgl_root(
         heading_citation: Some("MGL".to_string()),
         source_url: Some(parts_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
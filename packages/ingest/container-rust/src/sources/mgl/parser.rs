@@ -1,3 +1,4 @@
+use crate::sources::common::strip_invisible_chars;
 use regex::Regex;
 use serde::Deserialize;
 use std::sync::LazyLock;
@@ -183,11 +184,13 @@ pub fn normalize_designator(value: &str) -> String {
 }
 
 fn normalize_text(value: &str) -> String {
-    WHITESPACE_RE.replace_all(value.trim(), " ").into_owned()
+    WHITESPACE_RE
+        .replace_all(strip_invisible_chars(value).trim(), " ")
+        .into_owned()
 }
 
 pub fn normalize_body_text(value: &str) -> String {
-    let text = value
+    let text = strip_invisible_chars(value)
         .replace("\r\n", "\n")
         .replace('\r', "\n")
         .replace(['\u{a0}', '\u{202f}'], " ")
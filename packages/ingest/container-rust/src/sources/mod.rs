@@ -1,15 +1,27 @@
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::types::{DiscoveryResult, SourceKind};
+use crate::types::{DiscoveryResult, NodeMeta, SourceKind};
 use async_trait::async_trait;
 
+#[cfg(feature = "cgs")]
 pub mod cgs;
 pub mod common;
 pub mod configs;
+#[cfg(feature = "mgl")]
 pub mod mgl;
+#[cfg(feature = "nh")]
 pub mod nh;
+pub mod paths;
+pub mod punctuation;
+#[cfg(feature = "rigl")]
 pub mod rigl;
+pub mod selectors;
+pub mod synthetic;
+#[cfg(feature = "usc")]
 pub mod usc;
+#[cfg(feature = "uspl")]
 pub mod uspl;
+pub mod version;
+#[cfg(feature = "vt")]
 pub mod vt;
 
 #[async_trait]
@@ -21,6 +33,21 @@ pub trait SourceAdapter: Send + Sync {
         manual_start_url: Option<&str>,
     ) -> Result<DiscoveryResult, String>;
 
+    /// The version upstream advertises now, as `discover` would report it.
+    /// Freshness checks call this on every probe, so sources whose discovery
+    /// reads more than the pages carrying the version should override it.
+    async fn discover_version(
+        &self,
+        cache: &dyn Cache,
+        url: &str,
+        manual_start_url: Option<&str>,
+    ) -> Result<String, String> {
+        Ok(self
+            .discover(cache, url, manual_start_url)
+            .await?
+            .version_id)
+    }
+
     async fn process_url(
         &self,
         context: &mut IngestContext<'_>,
@@ -35,16 +62,61 @@ pub trait SourceAdapter: Send + Sync {
     fn needs_zip_extraction(&self) -> bool {
         false
     }
+
+    /// Version of this source's parsed output. Bump it whenever parsing or
+    /// normalization changes so derived artifacts cached by earlier runs are
+    /// not reused; each adapter's tests pin a fixture's output to it.
+    fn parser_version(&self) -> u32 {
+        1
+    }
+
+    /// The href this source's citation inliner writes when linking to `node`,
+    /// or `None` if nothing links to it.
+    fn link_target(&self, _node: &NodeMeta) -> Option<String> {
+        None
+    }
+
+    /// Whether an inlined href points into this source and should resolve to
+    /// a node emitted by the run.
+    fn is_internal_link(&self, _href: &str) -> bool {
+        false
+    }
 }
 
+/// The adapter for `source`.
+///
+/// # Panics
+///
+/// When this build was compiled without `source`'s cargo feature; use
+/// [`try_adapter_for`] where the source comes from untrusted input.
 pub fn adapter_for(source: SourceKind) -> &'static (dyn SourceAdapter + Send + Sync) {
+    try_adapter_for(source).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// The adapter for `source`, or an error when this build was compiled
+/// without that source's cargo feature.
+pub fn try_adapter_for(
+    source: SourceKind,
+) -> Result<&'static (dyn SourceAdapter + Send + Sync), String> {
     match source {
-        SourceKind::Usc => &usc::adapter::USC_ADAPTER,
-        SourceKind::Cgs => &cgs::adapter::CGS_ADAPTER,
-        SourceKind::Mgl => &mgl::adapter::MGL_ADAPTER,
-        SourceKind::Nh => &nh::adapter::NH_ADAPTER,
-        SourceKind::Rigl => &rigl::adapter::RIGL_ADAPTER,
-        SourceKind::Vt => &vt::adapter::VT_ADAPTER,
-        SourceKind::Uspl => &uspl::adapter::USPL_ADAPTER,
+        #[cfg(feature = "usc")]
+        SourceKind::Usc => Ok(&usc::adapter::USC_ADAPTER),
+        #[cfg(feature = "cgs")]
+        SourceKind::Cgs => Ok(&cgs::adapter::CGS_ADAPTER),
+        #[cfg(feature = "mgl")]
+        SourceKind::Mgl => Ok(&mgl::adapter::MGL_ADAPTER),
+        #[cfg(feature = "nh")]
+        SourceKind::Nh => Ok(&nh::adapter::NH_ADAPTER),
+        #[cfg(feature = "rigl")]
+        SourceKind::Rigl => Ok(&rigl::adapter::RIGL_ADAPTER),
+        #[cfg(feature = "vt")]
+        SourceKind::Vt => Ok(&vt::adapter::VT_ADAPTER),
+        #[cfg(feature = "uspl")]
+        SourceKind::Uspl => Ok(&uspl::adapter::USPL_ADAPTER),
+        #[allow(unreachable_patterns)]
+        source => Err(format!(
+            "Source {0} is not enabled in this build; rebuild with the `{0}` feature",
+            source.as_str()
+        )),
     }
 }
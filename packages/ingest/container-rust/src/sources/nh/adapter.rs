@@ -1,9 +1,11 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::common::{body_block, push_block};
+use crate::sources::common::{body_blocks, insert_structural_levels, push_block, StructuralParent};
 use crate::sources::nh::parser::{
     inline_nh_cross_references, normalize_designator, parse_chapter_index,
     parse_merged_chapter_sections, parse_section_detail, parse_title_index,
 };
+use crate::sources::paths::NH_PATHS;
 use crate::sources::SourceAdapter;
 use crate::types::{DiscoveryResult, NodeMeta, NodePayload, SectionContent};
 use async_trait::async_trait;
@@ -41,7 +43,11 @@ impl SourceAdapter for NhAdapter {
                     .cache
                     .fetch_cached(&item.url, &cache_key, Some(10))
                     .await?;
-                let title = parse_title_index(&html, &item.url)?;
+                let page_url = item.url.clone();
+                let title = run_blocking(&context.cancel, move |_| {
+                    parse_title_index(&html, &page_url)
+                })
+                .await??;
                 let title_id = format!("{}/title-{title_slug}", context.build.root_node_id);
 
                 context
@@ -55,11 +61,12 @@ impl SourceAdapter for NhAdapter {
                             level_index: 0,
                             sort_order: context.build.unit_sort_order,
                             name: Some(title.title_name.clone()),
-                            path: Some(format!("/title/{title_slug}")),
+                            path: Some(NH_PATHS.render("title", &[("num", &title_slug)])?),
                             readable_id: Some(title.title_num.clone()),
                             heading_citation: Some(format!("Title {}", title.title_num)),
                             source_url: Some(item.url.clone()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -99,13 +106,19 @@ impl SourceAdapter for NhAdapter {
                     .cache
                     .fetch_cached(&item.url, &cache_key, Some(10))
                     .await?;
-                let chapter = parse_chapter_index(&chapter_html, &item.url)?;
+                let page_url = item.url.clone();
+                let chapter = run_blocking(&context.cancel, move |_| {
+                    parse_chapter_index(&chapter_html, &page_url)
+                })
+                .await??;
                 let chapter_name = if chapter.chapter_name.is_empty() {
                     chapter_name_hint
                 } else {
                     chapter.chapter_name.clone()
                 };
                 let chapter_id = format!("{}/chapter-{chapter_slug}", item.parent_id);
+                let chapter_path = NH_PATHS
+                    .render("chapter", &[("title", &title_slug), ("num", &chapter_slug)])?;
 
                 context
                     .nodes
@@ -118,11 +131,12 @@ impl SourceAdapter for NhAdapter {
                             level_index: 1,
                             sort_order,
                             name: Some(chapter_name),
-                            path: Some(format!("/title/{title_slug}/chapter/{chapter_slug}")),
+                            path: Some(chapter_path.clone()),
                             readable_id: Some(chapter.chapter_num.clone()),
                             heading_citation: Some(format!("Chapter {}", chapter.chapter_num)),
                             source_url: Some(item.url.clone()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -138,13 +152,26 @@ impl SourceAdapter for NhAdapter {
                         .cache
                         .fetch_cached(&merged_url, &merged_cache_key, Some(10))
                         .await?;
-                    let sections = parse_merged_chapter_sections(&merged_html)?;
+                    let sections = run_blocking(&context.cancel, move |_| {
+                        parse_merged_chapter_sections(&merged_html)
+                    })
+                    .await??;
+                    let mut seen_levels: Vec<String> = Vec::new();
                     for (index, section) in sections.into_iter().enumerate() {
+                        let parent = insert_structural_levels(
+                            context,
+                            StructuralParent::chapter(&chapter_id, &chapter_path),
+                            &section.levels,
+                            &NH_PATHS,
+                            &merged_url,
+                            &mut seen_levels,
+                        )
+                        .await?;
                         insert_section_node(
                             context,
-                            &chapter_id,
+                            &parent,
+                            &chapter_path,
                             title_num,
-                            &chapter.chapter_num,
                             &merged_url,
                             index as i32,
                             section,
@@ -186,7 +213,8 @@ impl SourceAdapter for NhAdapter {
                     .cache
                     .fetch_cached(&item.url, &cache_key, Some(10))
                     .await?;
-                let parsed = parse_section_detail(&html)?;
+                let parsed =
+                    run_blocking(&context.cancel, move |_| parse_section_detail(&html)).await??;
                 let mut section = parsed.clone();
                 if section.section_name == section.section_num {
                     section.section_name = item.metadata["section_name_hint"]
@@ -194,11 +222,14 @@ impl SourceAdapter for NhAdapter {
                         .unwrap_or(section_num)
                         .to_string();
                 }
+                let chapter_path = NH_PATHS
+                    .render("chapter", &[("title", &title_slug), ("num", &chapter_slug)])?;
+                let parent = StructuralParent::chapter(&item.parent_id, &chapter_path);
                 insert_section_node(
                     context,
-                    &item.parent_id,
+                    &parent,
+                    &chapter_path,
                     title_num,
-                    chapter_num,
                     &item.url,
                     sort_order,
                     section,
@@ -229,27 +260,46 @@ impl SourceAdapter for NhAdapter {
         }
     }
 
+    // v2: article and part levels, hidden markup and invisible characters dropped.
+    fn parser_version(&self) -> u32 {
+        2
+    }
+
     fn needs_zip_extraction(&self) -> bool {
         false
     }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // RSA references are inlined as section paths.
+        if node.level_name != "section" {
+            return None;
+        }
+        node.path.clone()
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/title/")
+    }
 }
 
 async fn insert_section_node(
     context: &mut IngestContext<'_>,
-    chapter_id: &str,
+    parent: &StructuralParent,
+    chapter_path: &str,
     title_num: &str,
-    chapter_num: &str,
     source_url: &str,
     sort_order: i32,
     section: crate::sources::nh::parser::NhSectionDetail,
 ) -> Result<(), String> {
-    let title_slug = normalize_designator(title_num);
-    let chapter_slug = normalize_designator(chapter_num);
     let section_slug = normalize_designator(&section.section_num);
-    let section_path = format!("/title/{title_slug}/chapter/{chapter_slug}/section/{section_slug}");
+    let section_path = NH_PATHS.render(
+        "section",
+        &[("chapter_path", chapter_path), ("num", &section_slug)],
+    )?;
 
-    let body = inline_nh_cross_references(&section.body, title_num);
-    let mut blocks = vec![body_block(&body)];
+    let mut blocks = body_blocks(context.build.profile, || {
+        inline_nh_cross_references(&section.body, title_num)
+    });
     push_block(
         &mut blocks,
         "note",
@@ -269,11 +319,11 @@ async fn insert_section_node(
         .nodes
         .insert_node(NodePayload {
             meta: NodeMeta {
-                id: format!("{chapter_id}/section-{section_slug}"),
+                id: parent.section_id(&section_slug),
                 source_version_id: context.build.source_version_id.to_string(),
-                parent_id: Some(chapter_id.to_string()),
+                parent_id: Some(parent.id.clone()),
                 level_name: "section".to_string(),
-                level_index: 2,
+                level_index: parent.level_index + 1,
                 sort_order,
                 name: Some(section.section_name.clone()),
                 path: Some(section_path),
@@ -281,6 +331,7 @@ async fn insert_section_node(
                 heading_citation: Some(format!("N.H. Rev. Stat. § {}", section.section_num)),
                 source_url: Some(source_url.to_string()),
                 accessed_at: Some(context.build.accessed_at.to_string()),
+                review_required: false,
             },
             content: Some(serde_json::to_value(&content).unwrap()),
         })
@@ -42,6 +42,7 @@ pub async fn discover_nh_root(
         heading_citation: Some("N.H. RSA".to_string()),
         source_url: Some(start_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
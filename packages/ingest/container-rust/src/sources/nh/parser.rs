@@ -1,3 +1,7 @@
+use crate::sources::common::{
+    parse_structural_heading, strip_hidden_html, strip_invisible_chars, StructuralLevel,
+    StructuralLevelStack,
+};
 use regex::Regex;
 use std::cmp::Ordering;
 use std::sync::LazyLock;
@@ -68,6 +72,8 @@ pub struct NhSectionDetail {
     pub section_name: String,
     pub body: String,
     pub source_note: Option<String>,
+    /// Enclosing articles/parts on merged chapter pages, outermost first.
+    pub levels: Vec<StructuralLevel>,
 }
 
 pub fn normalize_text(input: &str) -> String {
@@ -101,7 +107,8 @@ pub fn extract_version_id_from_landing_html(html: &str) -> Option<String> {
 }
 
 pub fn parse_title_links(html: &str, base_url: &str) -> Result<Vec<NhTitleLink>, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let mut titles = Vec::new();
 
@@ -133,7 +140,8 @@ pub fn parse_title_links(html: &str, base_url: &str) -> Result<Vec<NhTitleLink>,
 }
 
 pub fn parse_title_index(html: &str, base_url: &str) -> Result<NhTitleIndex, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let mut title_num = String::new();
     let mut title_name = String::new();
@@ -195,7 +203,8 @@ pub fn parse_title_index(html: &str, base_url: &str) -> Result<NhTitleIndex, Str
 }
 
 pub fn parse_chapter_index(html: &str, base_url: &str) -> Result<NhChapterIndex, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let mut sections = Vec::new();
     for node in dom.nodes().iter() {
@@ -286,7 +295,9 @@ pub fn parse_merged_chapter_sections(html: &str) -> Result<Vec<NhSectionDetail>,
 }
 
 fn parse_sections_from_html(html: &str) -> Result<Vec<NhSectionDetail>, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let (title_num, title_name) = parse_title_header(&dom, parser)?;
     let (chapter_num, chapter_name) = parse_chapter_header(&dom, parser)?;
@@ -296,6 +307,8 @@ fn parse_sections_from_html(html: &str) -> Result<Vec<NhSectionDetail>, String>
     let mut current_name = String::new();
     let mut current_body = String::new();
     let mut current_source_note: Option<String> = None;
+    let mut current_levels: Vec<StructuralLevel> = Vec::new();
+    let mut level_stack = StructuralLevelStack::default();
 
     for node in dom.nodes().iter() {
         let Some(tag) = node.as_tag() else {
@@ -303,6 +316,11 @@ fn parse_sections_from_html(html: &str) -> Result<Vec<NhSectionDetail>, String>
         };
         let name = tag.name().as_utf8_str().to_string();
         match name.as_str() {
+            "h2" => {
+                if let Some(level) = parse_structural_heading(&heading_text(tag, parser)) {
+                    level_stack.open(level);
+                }
+            }
             "h3" => {
                 let text = normalize_text(&tag.inner_text(parser));
                 let Some(captures) = SECTION_HEADER_RE.captures(&text) else {
@@ -321,9 +339,11 @@ fn parse_sections_from_html(html: &str) -> Result<Vec<NhSectionDetail>, String>
                         section_name: current_name.clone(),
                         body,
                         source_note: current_source_note.clone(),
+                        levels: current_levels.clone(),
                     });
                 }
 
+                current_levels = level_stack.current();
                 current_num = captures[1].to_string();
                 current_name = current_num.clone();
                 current_body.clear();
@@ -379,12 +399,29 @@ fn parse_sections_from_html(html: &str) -> Result<Vec<NhSectionDetail>, String>
             section_name: current_name,
             body,
             source_note: current_source_note,
+            levels: current_levels,
         });
     }
 
     Ok(sections)
 }
 
+/// Heading text with `<br>` line breaks kept as spaces, so `ARTICLE 1<br>GENERAL`
+/// doesn't run together.
+fn heading_text(tag: &tl::HTMLTag<'_>, parser: &Parser<'_>) -> String {
+    let text = tag
+        .children()
+        .top()
+        .iter()
+        .filter_map(|handle| handle.get(parser))
+        .map(|child| match child.as_tag() {
+            Some(inner) if inner.name().as_utf8_str().eq_ignore_ascii_case("br") => " ".to_string(),
+            _ => child.inner_text(parser).to_string(),
+        })
+        .collect::<String>();
+    normalize_text(&text)
+}
+
 fn finalize_body(body: &str, section_name: &str, source_note: Option<&str>) -> String {
     let mut normalized = normalize_text(body)
         .replace('\r', "")
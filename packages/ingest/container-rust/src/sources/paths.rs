@@ -0,0 +1,253 @@
+use crate::types::{NodeMeta, SourceKind};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Path templates for one source's levels. Placeholders are written `{name}`;
+/// by convention `{num}` is the node's own designator, `{parent}` its parent's
+/// path, and other names are ancestors' designators. The `*` level is the
+/// fallback for structural levels (parts, articles, subchapters) that have no
+/// template of their own.
+pub struct PathTemplates {
+    levels: &'static [(&'static str, &'static str)],
+}
+
+impl PathTemplates {
+    pub const fn new(levels: &'static [(&'static str, &'static str)]) -> Self {
+        Self { levels }
+    }
+
+    pub fn template(&self, level: &str) -> Option<&'static str> {
+        let find = |name: &str| {
+            self.levels
+                .iter()
+                .find(|(level, _)| *level == name)
+                .map(|(_, template)| *template)
+        };
+        find(level).or_else(|| find("*"))
+    }
+
+    /// Renders the template for `level`. Every placeholder needs a value and
+    /// every value must be used, so a template that drops or renames a
+    /// placeholder fails instead of quietly merging paths.
+    pub fn render(&self, level: &str, values: &[(&str, &str)]) -> Result<String, String> {
+        let template = self
+            .template(level)
+            .ok_or_else(|| format!("No path template for level {level}"))?;
+        let mut used = vec![false; values.len()];
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let close = rest[open..]
+                .find('}')
+                .ok_or_else(|| format!("Unclosed placeholder in path template {template}"))?;
+            let name = &rest[open + 1..open + close];
+            let index = values
+                .iter()
+                .position(|(key, _)| *key == name)
+                .ok_or_else(|| format!("No value for {{{name}}} in {level} path {template}"))?;
+            used[index] = true;
+            out.push_str(values[index].1);
+            rest = &rest[open + close + 1..];
+        }
+        out.push_str(rest);
+
+        if let Some(index) = used.iter().position(|used| !used) {
+            return Err(format!(
+                "Path template {template} for {level} does not use {}",
+                values[index].0
+            ));
+        }
+        Ok(out)
+    }
+}
+
+pub static USC_PATHS: PathTemplates = PathTemplates::new(&[
+    ("title", "/title/{num}"),
+    ("section", "/section/{title}/{num}"),
+    // `levels` is the friendly `subtitle-A/part-I` chain below the title.
+    ("*", "/{title}/{levels}"),
+]);
+
+pub static CGS_PATHS: PathTemplates = PathTemplates::new(&[
+    ("title", "/title/{num}"),
+    ("chapter", "/chapter/{title}/{num}"),
+    ("article", "/article/{title}/{num}"),
+    ("section", "/section/{num}"),
+]);
+
+pub static MGL_PATHS: PathTemplates = PathTemplates::new(&[
+    ("part", "/part/{num}"),
+    ("chapter", "/part/{part}/chapter/{num}"),
+    ("section", "/part/{part}/chapter/{chapter}/section/{num}"),
+]);
+
+// NH, RIGL and VT section paths stay chapter-relative even under structural
+// levels so inlined citations keep resolving.
+pub static NH_PATHS: PathTemplates = PathTemplates::new(&[
+    ("title", "/title/{num}"),
+    ("chapter", "/title/{title}/chapter/{num}"),
+    ("section", "{chapter_path}/section/{num}"),
+    ("*", "{parent}/{level}/{num}"),
+]);
+
+pub static RIGL_PATHS: PathTemplates = PathTemplates::new(&[
+    ("title", "/title/{num}"),
+    ("chapter", "/title/{title}/chapter/{num}"),
+    ("section", "{chapter_path}/section/{num}"),
+    ("*", "{parent}/{level}/{num}"),
+]);
+
+pub static VT_PATHS: PathTemplates = PathTemplates::new(&[
+    ("title", "/title/{num}"),
+    ("chapter", "/title/{title}/chapter/{num}"),
+    ("section", "{chapter_path}/section/{num}"),
+    ("*", "{parent}/{level}/{num}"),
+]);
+
+pub static USPL_PATHS: PathTemplates =
+    PathTemplates::new(&[("congress", "/{num}"), ("law", "/{congress}/{num}")]);
+
+pub fn path_templates(source: SourceKind) -> &'static PathTemplates {
+    match source {
+        SourceKind::Usc => &USC_PATHS,
+        SourceKind::Cgs => &CGS_PATHS,
+        SourceKind::Mgl => &MGL_PATHS,
+        SourceKind::Nh => &NH_PATHS,
+        SourceKind::Rigl => &RIGL_PATHS,
+        SourceKind::Vt => &VT_PATHS,
+        SourceKind::Uspl => &USPL_PATHS,
+    }
+}
+
+/// Paths claimed by the nodes of one unit, so a node rendering to a path
+/// another node already holds is moved aside instead of shadowing it.
+#[derive(Debug, Default)]
+pub struct PathIndex {
+    owners: HashMap<String, String>,
+}
+
+impl PathIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `node`'s path. When another node already holds it, `node`
+    /// moves to the first free `{path}-2`, `{path}-3`, ... and the collision
+    /// is returned so it can be flagged for review. Re-emitting the same node
+    /// keeps the path it was given first.
+    pub fn claim(&mut self, node: &mut NodeMeta) -> Option<String> {
+        let path = node.path.clone()?;
+        let mut candidate = path.clone();
+        let mut suffix = 1;
+        loop {
+            match self.owners.entry(candidate.clone()) {
+                Entry::Occupied(entry) if *entry.get() == node.id => {
+                    node.path = Some(candidate);
+                    return None;
+                }
+                Entry::Occupied(_) => {
+                    suffix += 1;
+                    candidate = format!("{path}-{suffix}");
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(node.id.clone());
+                    break;
+                }
+            }
+        }
+        if candidate == path {
+            return None;
+        }
+        let reason = format!(
+            "path collision: {path} is already held by {}; moved to {candidate}",
+            self.owners[&path]
+        );
+        node.path = Some(candidate);
+        Some(reason)
+    }
+}
+
+/// Words dropped from heading slugs unless nothing else is left.
+pub const DEFAULT_SLUG_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "of", "on", "or", "the", "to", "with",
+];
+
+/// Longest slug, in bytes, before trailing words are cut.
+const MAX_SLUG_LEN: usize = 60;
+
+/// URL slug for a heading: lowercase ASCII words joined by `-`, stop words
+/// removed, apostrophes dropped inside words (`driver's` → `drivers`), and cut
+/// at a word boundary past [`MAX_SLUG_LEN`]. `None` when the heading has no
+/// letters or digits.
+pub fn slugify(heading: &str, stop_words: &[String]) -> Option<String> {
+    let words = heading
+        .to_lowercase()
+        .replace(['\'', '\u{2019}'], "")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let kept = words
+        .iter()
+        .filter(|word| !stop_words.contains(word))
+        .collect::<Vec<_>>();
+    let words = if kept.is_empty() {
+        words.iter().collect()
+    } else {
+        kept
+    };
+
+    let mut slug = String::new();
+    for word in words {
+        if !slug.is_empty() && slug.len() + 1 + word.len() > MAX_SLUG_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    (!slug.is_empty()).then_some(slug)
+}
+
+/// Heading slugs handed out within one unit. Slugs only need to be unique
+/// among siblings, so a repeated heading under the same parent gets `-2`,
+/// `-3`, ... while the same heading under another parent keeps its slug.
+#[derive(Debug, Default)]
+pub struct SlugIndex {
+    stop_words: Vec<String>,
+    /// Parent id to slug to the node that owns it.
+    siblings: HashMap<String, HashMap<String, String>>,
+}
+
+impl SlugIndex {
+    pub fn new(stop_words: Vec<String>) -> Self {
+        Self {
+            stop_words,
+            siblings: HashMap::new(),
+        }
+    }
+
+    /// Slug for `node`'s heading, unique under its parent. Re-emitting the
+    /// same node returns the slug it already holds.
+    pub fn assign(&mut self, node: &NodeMeta) -> Option<String> {
+        let base = slugify(node.name.as_deref()?, &self.stop_words)?;
+        let taken = self
+            .siblings
+            .entry(node.parent_id.clone().unwrap_or_default())
+            .or_default();
+        if let Some((slug, _)) = taken.iter().find(|(_, owner)| **owner == node.id) {
+            return Some(slug.clone());
+        }
+        let slug = (1..)
+            .map(|n| match n {
+                1 => base.clone(),
+                n => format!("{base}-{n}"),
+            })
+            .find(|slug| !taken.contains_key(slug))
+            .expect("unbounded suffixes");
+        taken.insert(slug.clone(), node.id.clone());
+        Some(slug)
+    }
+}
@@ -0,0 +1,351 @@
+use crate::types::{NodePayload, SectionContent};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)\s]+)\)").unwrap());
+static BOLD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*(.+?)\*\*").unwrap());
+
+/// Source spans tried per fragment. A fragment starting like more spans than
+/// this, such as one opening with "the", is only checked against the first.
+const MAX_CANDIDATE_STARTS: usize = 256;
+/// Leading units of a fragment compared against the source; the rest of a
+/// long body paragraph is not checked.
+const MAX_COMPARED_UNITS: usize = 2_000;
+
+/// A character-level normalization the parsers may apply between the source
+/// text and the emitted headings and bodies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationRule {
+    /// Line breaks, tabs, and runs of spaces collapsed to one space.
+    Whitespace,
+    /// No-break and other Unicode spaces replaced with a plain space.
+    NonBreakingSpace,
+    /// Hyphen, en/em dash, and minus variants folded together.
+    Dash,
+    /// Curly and prime quotes folded to straight quotes.
+    Quote,
+    /// Soft hyphens, zero-width characters, and byte-order marks dropped.
+    Invisible,
+}
+
+impl NormalizationRule {
+    fn of(c: char) -> Option<Self> {
+        match c {
+            '\u{00a0}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' => {
+                Some(Self::NonBreakingSpace)
+            }
+            c if c.is_whitespace() => Some(Self::Whitespace),
+            '-' | '\u{2010}'..='\u{2015}' | '\u{2212}' => Some(Self::Dash),
+            '\'' | '"' | '\u{2018}'..='\u{201f}' | '\u{2032}' | '\u{2033}' => Some(Self::Quote),
+            '\u{00ad}' | '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => Some(Self::Invisible),
+            _ => None,
+        }
+    }
+}
+
+fn fold(c: char) -> char {
+    match c {
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+        '\u{2018}'..='\u{201b}' | '\u{2032}' => '\'',
+        '\u{201c}'..='\u{201f}' | '\u{2033}' => '"',
+        c => c,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Unit {
+    Text(char),
+    Space(String),
+    Invisible(char),
+    /// A markup boundary, which parsers may render as a space or as nothing.
+    Break,
+}
+
+#[derive(Default)]
+struct Units(Vec<Unit>);
+
+impl Units {
+    fn push_char(&mut self, c: char) {
+        match NormalizationRule::of(c) {
+            Some(NormalizationRule::Whitespace | NormalizationRule::NonBreakingSpace) => {
+                match self.0.last_mut() {
+                    Some(Unit::Space(run)) => run.push(c),
+                    Some(last @ Unit::Break) => *last = Unit::Space(c.to_string()),
+                    _ => self.0.push(Unit::Space(c.to_string())),
+                }
+            }
+            Some(NormalizationRule::Invisible) => self.0.push(Unit::Invisible(c)),
+            _ => self.0.push(Unit::Text(c)),
+        }
+    }
+
+    fn push_break(&mut self) {
+        if !matches!(self.0.last(), None | Some(Unit::Space(_) | Unit::Break)) {
+            self.0.push(Unit::Break);
+        }
+    }
+}
+
+/// Emitted text checked against the source, identified by node and field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditedFragment {
+    pub node_id: String,
+    pub field: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PunctuationAudit {
+    pub fragments_checked: usize,
+    pub fragments_matched: usize,
+    /// Source characters each rule changed or dropped across matched fragments.
+    pub changed: BTreeMap<NormalizationRule, usize>,
+    /// Fragments that differ from every source span by more than the known
+    /// rules: a dropped or altered character the normalizers should not touch.
+    pub unmatched: Vec<AuditedFragment>,
+}
+
+/// Source text of one fetched unit (HTML, XML, or the MGL JSON API), reduced
+/// to characters with markup boundaries kept, for locating emitted text.
+pub struct SourceText {
+    units: Vec<Unit>,
+    /// Positions of each unit in `units`, keyed by [`start_key`].
+    starts: HashMap<Unit, Vec<usize>>,
+}
+
+impl SourceText {
+    pub fn new(raw: &str) -> Self {
+        let mut units = Units::default();
+        match serde_json::from_str::<Value>(raw.trim()) {
+            Ok(value @ (Value::Object(_) | Value::Array(_))) => {
+                push_json_strings(&value, &mut units);
+            }
+            _ => push_markup(raw, &mut units),
+        }
+        let mut starts = HashMap::<Unit, Vec<usize>>::new();
+        for (index, unit) in units.0.iter().enumerate() {
+            starts.entry(start_key(unit)).or_default().push(index);
+        }
+        Self {
+            units: units.0,
+            starts,
+        }
+    }
+
+    /// Locates `emitted` in the source modulo the normalization rules and
+    /// counts the characters each rule changed, or `None` when no source span
+    /// matches. Work per fragment is bounded by [`MAX_CANDIDATE_STARTS`] and
+    /// [`MAX_COMPARED_UNITS`].
+    pub fn compare(&self, emitted: &str) -> Option<BTreeMap<NormalizationRule, usize>> {
+        let mut target = Units::default();
+        emitted.trim().chars().for_each(|c| target.push_char(c));
+        let mut target = target.0;
+        target.truncate(MAX_COMPARED_UNITS);
+        let first = target.first()?;
+        self.starts
+            .get(&start_key(first))?
+            .iter()
+            .take(MAX_CANDIDATE_STARTS)
+            .find_map(|&start| align(&self.units[start..], &target))
+    }
+
+    /// Audits the headings and body paragraphs of every `stride`-th node.
+    pub fn audit(&self, nodes: &[NodePayload], stride: usize) -> PunctuationAudit {
+        let mut audit = PunctuationAudit::default();
+        for fragment in nodes
+            .iter()
+            .step_by(stride.max(1))
+            .flat_map(emitted_fragments)
+        {
+            audit.fragments_checked += 1;
+            match self.compare(&fragment.text) {
+                Some(changed) => {
+                    audit.fragments_matched += 1;
+                    for (rule, count) in changed {
+                        *audit.changed.entry(rule).or_default() += count;
+                    }
+                }
+                None => audit.unmatched.push(fragment),
+            }
+        }
+        audit
+    }
+}
+
+/// The unit a fragment must start with to match at `unit`: text compares
+/// folded, everything else exactly.
+fn start_key(unit: &Unit) -> Unit {
+    match unit {
+        Unit::Text(c) => Unit::Text(fold(*c)),
+        unit => unit.clone(),
+    }
+}
+
+fn align(source: &[Unit], target: &[Unit]) -> Option<BTreeMap<NormalizationRule, usize>> {
+    let mut changed = BTreeMap::new();
+    let mut count = |rule: NormalizationRule| *changed.entry(rule).or_insert(0) += 1;
+    let (mut i, mut j) = (0, 0);
+    while j < target.len() {
+        match (source.get(i)?, &target[j]) {
+            (Unit::Break, Unit::Space(_)) => j += 1,
+            (Unit::Break, _) => {}
+            (Unit::Invisible(a), Unit::Invisible(b)) if a == b => j += 1,
+            (Unit::Invisible(_), _) => count(NormalizationRule::Invisible),
+            (Unit::Space(raw), Unit::Space(out)) => {
+                let mut kept = out.chars().collect::<Vec<_>>();
+                for c in raw.chars() {
+                    match kept.iter().position(|k| *k == c) {
+                        Some(index) => {
+                            kept.remove(index);
+                        }
+                        None => count(NormalizationRule::of(c)?),
+                    }
+                }
+                j += 1;
+            }
+            (Unit::Text(raw), Unit::Text(out)) if fold(*raw) == fold(*out) => {
+                if raw != out {
+                    count(NormalizationRule::of(*raw).or(NormalizationRule::of(*out))?);
+                }
+                j += 1;
+            }
+            _ => return None,
+        }
+        i += 1;
+    }
+    Some(changed)
+}
+
+fn push_json_strings(value: &Value, units: &mut Units) {
+    match value {
+        Value::String(text) => {
+            push_markup(text, units);
+            units.push_break();
+        }
+        Value::Array(items) => items.iter().for_each(|item| push_json_strings(item, units)),
+        Value::Object(map) => map.values().for_each(|item| push_json_strings(item, units)),
+        _ => {}
+    }
+}
+
+fn push_markup(raw: &str, units: &mut Units) {
+    let mut rest = raw;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            let end = if rest.starts_with("<!--") {
+                rest.find("-->").map(|index| index + 3)
+            } else {
+                rest.find('>').map(|index| index + 1)
+            };
+            units.push_break();
+            rest = &rest[end.unwrap_or(rest.len())..];
+        } else if let Some((decoded, len)) = c.eq(&'&').then(|| decode_entity(rest)).flatten() {
+            units.push_char(decoded);
+            rest = &rest[len..];
+        } else {
+            units.push_char(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+}
+
+/// Decodes the entity at the start of `text`, returning it with its length.
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    let end = text
+        .char_indices()
+        .take(12)
+        .find_map(|(index, c)| (c == ';').then_some(index))?;
+    let name = &text[1..end];
+    let decoded = if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        match code {
+            // Windows-1252 punctuation, common in legislative HTML.
+            145 => '\u{2018}',
+            146 => '\u{2019}',
+            147 => '\u{201c}',
+            148 => '\u{201d}',
+            150 => '\u{2013}',
+            151 => '\u{2014}',
+            code => char::from_u32(code)?,
+        }
+    } else {
+        match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{00a0}',
+            "shy" => '\u{00ad}',
+            "ndash" => '\u{2013}',
+            "mdash" => '\u{2014}',
+            "lsquo" => '\u{2018}',
+            "rsquo" => '\u{2019}',
+            "ldquo" => '\u{201c}',
+            "rdquo" => '\u{201d}',
+            "sect" => '§',
+            "para" => '¶',
+            _ => return None,
+        }
+    };
+    Some((decoded, end + 1))
+}
+
+/// A node's heading and body paragraphs with markdown links and bold removed.
+fn emitted_fragments(node: &NodePayload) -> Vec<AuditedFragment> {
+    let fragment = |field: &str, text: &str| AuditedFragment {
+        node_id: node.meta.id.clone(),
+        field: field.to_string(),
+        text: text.to_string(),
+    };
+    let mut fragments = node
+        .meta
+        .name
+        .iter()
+        .map(|name| fragment("heading", name))
+        .collect::<Vec<_>>();
+
+    let Some(content) = node
+        .content
+        .clone()
+        .and_then(|content| serde_json::from_value::<SectionContent>(content).ok())
+    else {
+        return fragments;
+    };
+    for block in &content.blocks {
+        let text = block.content.as_deref().unwrap_or_default();
+        let text = LINK_RE.replace_all(text, "$1");
+        let text = BOLD_RE.replace_all(&text, "$1");
+        for paragraph in text.split("\n\n") {
+            // Table cells are audited one by one; the pipes are not source text.
+            let mut lines = Vec::new();
+            for line in paragraph.lines() {
+                let line = line.trim_start_matches(['>', ' ']);
+                if !line.contains('|') {
+                    lines.push(line);
+                    continue;
+                }
+                for cell in line.split('|').map(str::trim) {
+                    if !cell.is_empty() && !cell.trim_matches(['-', ':']).is_empty() {
+                        fragments.push(fragment(&block.type_, cell));
+                    }
+                }
+            }
+            let paragraph = lines.join("\n");
+            if !paragraph.trim().is_empty() {
+                fragments.push(fragment(&block.type_, paragraph.trim()));
+            }
+        }
+    }
+    fragments
+}
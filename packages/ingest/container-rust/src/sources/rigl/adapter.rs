@@ -1,6 +1,8 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
 use crate::sources::cgs::cross_references::extract_section_cross_references;
-use crate::sources::common::{body_block, push_block};
+use crate::sources::common::{body_blocks, insert_structural_levels, push_block, StructuralParent};
+use crate::sources::paths::RIGL_PATHS;
 use crate::sources::rigl::parser::{
     normalize_designator, parse_chapter_index, parse_section_detail, parse_title_index,
 };
@@ -42,7 +44,11 @@ impl SourceAdapter for RiglAdapter {
                     normalize_designator(title_num)
                 );
                 let html = context.cache.fetch_cached(url, &cache_key, None).await?;
-                let title = parse_title_index(&html, url)?;
+                let page_url = url.clone();
+                let title = run_blocking(&context.cancel, move |_| {
+                    parse_title_index(&html, &page_url)
+                })
+                .await??;
                 let title_num = if title_num.is_empty() {
                     title.title_num
                 } else {
@@ -62,11 +68,12 @@ impl SourceAdapter for RiglAdapter {
                             level_index: 0,
                             sort_order: context.build.unit_sort_order,
                             name: Some(title.title_name),
-                            path: Some(format!("/title/{title_slug}")),
+                            path: Some(RIGL_PATHS.render("title", &[("num", &title_slug)])?),
                             readable_id: Some(title_num.clone()),
                             heading_citation: Some(format!("Title {title_num}")),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -100,7 +107,11 @@ impl SourceAdapter for RiglAdapter {
                     version_id, title_slug, chapter_slug_hint
                 );
                 let html = context.cache.fetch_cached(url, &cache_key, None).await?;
-                let chapter = parse_chapter_index(&html, url)?;
+                let page_url = url.clone();
+                let chapter = run_blocking(&context.cancel, move |_| {
+                    parse_chapter_index(&html, &page_url)
+                })
+                .await??;
                 let chapter_num = if chapter_num_hint.is_empty() {
                     chapter.chapter_num
                 } else {
@@ -114,6 +125,8 @@ impl SourceAdapter for RiglAdapter {
                 let chapter_slug = normalize_designator(&chapter_num);
                 let sort_order = metadata["sort_order"].as_i64().unwrap_or(0) as i32;
                 let chapter_id = format!("{}/chapter-{chapter_slug}", item.parent_id);
+                let chapter_path = RIGL_PATHS
+                    .render("chapter", &[("title", &title_slug), ("num", &chapter_slug)])?;
 
                 context
                     .nodes
@@ -126,26 +139,38 @@ impl SourceAdapter for RiglAdapter {
                             level_index: 1,
                             sort_order,
                             name: Some(chapter_name),
-                            path: Some(format!("/title/{title_slug}/chapter/{chapter_slug}")),
+                            path: Some(chapter_path.clone()),
                             readable_id: Some(chapter_num.clone()),
                             heading_citation: Some(format!("Chapter {chapter_num}")),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
                     .await?;
 
+                let mut seen_levels: Vec<String> = Vec::new();
                 for (index, section) in chapter.sections.into_iter().enumerate() {
+                    let parent = insert_structural_levels(
+                        context,
+                        StructuralParent::chapter(&chapter_id, &chapter_path),
+                        &section.levels,
+                        &RIGL_PATHS,
+                        url,
+                        &mut seen_levels,
+                    )
+                    .await?;
                     context.queue.enqueue(QueueItem {
                         url: section.url,
-                        parent_id: chapter_id.clone(),
+                        parent_id: parent.id,
                         level_name: "section".to_string(),
-                        level_index: 2,
+                        level_index: parent.level_index + 1,
                         metadata: json!({
                             "unit_id": metadata["unit_id"],
                             "title_num": title_num,
                             "chapter_num": chapter_num,
+                            "chapter_id": chapter_id,
                             "section_num": section.section_num,
                             "section_name_hint": section.section_name,
                             "sort_order": index as i32
@@ -159,6 +184,7 @@ impl SourceAdapter for RiglAdapter {
                 let chapter_num = metadata["chapter_num"].as_str().unwrap_or_default();
                 let section_num_hint = metadata["section_num"].as_str().unwrap_or_default();
                 let sort_order = metadata["sort_order"].as_i64().unwrap_or(0) as i32;
+                let chapter_id = metadata["chapter_id"].as_str().unwrap_or(&item.parent_id);
                 let title_slug = normalize_designator(title_num);
                 let chapter_slug = normalize_designator(chapter_num);
                 let section_slug_hint = normalize_designator(section_num_hint);
@@ -167,13 +193,20 @@ impl SourceAdapter for RiglAdapter {
                     version_id, title_slug, chapter_slug, section_slug_hint
                 );
                 let html = context.cache.fetch_cached(url, &cache_key, None).await?;
-                let parsed = parse_section_detail(&html)?;
+                let parsed =
+                    run_blocking(&context.cancel, move |_| parse_section_detail(&html)).await??;
                 let section_num = if section_num_hint.is_empty() {
                     parsed.section_num
                 } else {
                     section_num_hint.to_string()
                 };
                 let section_slug = normalize_designator(&section_num);
+                let chapter_path = RIGL_PATHS
+                    .render("chapter", &[("title", &title_slug), ("num", &chapter_slug)])?;
+                let section_path = RIGL_PATHS.render(
+                    "section",
+                    &[("chapter_path", &chapter_path), ("num", &section_slug)],
+                )?;
                 let section_name_hint = metadata["section_name_hint"].as_str().unwrap_or_default();
                 let section_name = if parsed.section_name.is_empty() {
                     section_name_hint.to_string()
@@ -181,7 +214,9 @@ impl SourceAdapter for RiglAdapter {
                     parsed.section_name
                 };
 
-                let mut blocks = vec![body_block(&inline_rigl_cross_references(&parsed.body))];
+                let mut blocks = body_blocks(context.build.profile, || {
+                    inline_rigl_cross_references(&parsed.body)
+                });
                 push_block(
                     &mut blocks,
                     "note",
@@ -200,20 +235,19 @@ impl SourceAdapter for RiglAdapter {
                     .nodes
                     .insert_node(NodePayload {
                         meta: NodeMeta {
-                            id: format!("{}/section-{section_slug}", item.parent_id),
+                            id: format!("{chapter_id}/section-{section_slug}"),
                             source_version_id: context.build.source_version_id.to_string(),
                             parent_id: Some(item.parent_id.clone()),
                             level_name: "section".to_string(),
-                            level_index: 2,
+                            level_index: item.level_index,
                             sort_order,
                             name: Some(section_name),
-                            path: Some(format!(
-                                "/title/{title_slug}/chapter/{chapter_slug}/section/{section_slug}"
-                            )),
+                            path: Some(section_path),
                             readable_id: Some(section_num.clone()),
                             heading_citation: Some(format!("R.I. Gen. Laws § {section_num}")),
                             source_url: Some(url.to_string()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: Some(serde_json::to_value(&content).unwrap()),
                     })
@@ -243,9 +277,26 @@ impl SourceAdapter for RiglAdapter {
         }
     }
 
+    // v2: part levels, hidden markup and invisible characters dropped.
+    fn parser_version(&self) -> u32 {
+        2
+    }
+
     fn needs_zip_extraction(&self) -> bool {
         false
     }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // Section references are inlined as section paths.
+        if node.level_name != "section" {
+            return None;
+        }
+        node.path.clone()
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/title/")
+    }
 }
 
 fn inline_rigl_cross_references(text: &str) -> String {
@@ -45,6 +45,7 @@ pub async fn discover_rigl_root(
         heading_citation: Some("RIGL".to_string()),
         source_url: Some(start_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
@@ -1,3 +1,7 @@
+use crate::sources::common::{
+    parse_structural_heading, strip_hidden_html, strip_invisible_chars, StructuralLevel,
+    StructuralLevelStack,
+};
 use regex::Regex;
 use std::cmp::Ordering;
 use std::sync::LazyLock;
@@ -62,6 +66,8 @@ pub struct RiglSectionLink {
     pub section_num: String,
     pub section_name: String,
     pub url: String,
+    /// Enclosing articles/parts listed on the chapter index, outermost first.
+    pub levels: Vec<StructuralLevel>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -170,7 +176,8 @@ pub fn parse_title_links(html: &str, base_url: &str) -> Result<Vec<RiglTitleLink
 }
 
 pub fn parse_title_index(html: &str, base_url: &str) -> Result<RiglTitleIndex, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let base = reqwest::Url::parse(base_url).map_err(|e| format!("Invalid base URL: {e}"))?;
 
@@ -221,7 +228,8 @@ pub fn parse_title_index(html: &str, base_url: &str) -> Result<RiglTitleIndex, S
 }
 
 pub fn parse_chapter_index(html: &str, base_url: &str) -> Result<RiglChapterIndex, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let base = reqwest::Url::parse(base_url).map_err(|e| format!("Invalid base URL: {e}"))?;
     let chapter_header = first_tag_text(&dom, parser, "h2").unwrap_or_default();
@@ -233,11 +241,22 @@ pub fn parse_chapter_index(html: &str, base_url: &str) -> Result<RiglChapterInde
     let chapter_name = parse_chapter_name(&chapter_header, &chapter_num);
 
     let mut sections: Vec<RiglSectionLink> = Vec::new();
+    let mut level_stack = StructuralLevelStack::default();
     for node in dom.nodes().iter() {
         let Some(tag) = node.as_tag() else {
             continue;
         };
-        if tag.name().as_utf8_str().as_ref() != "a" {
+        let tag_name = tag.name().as_utf8_str();
+        if matches!(
+            tag_name.as_ref(),
+            "h3" | "h4" | "h5" | "b" | "strong" | "center"
+        ) {
+            if let Some(level) = parse_index_heading(&normalize_text(&tag.inner_text(parser))) {
+                level_stack.open(level);
+            }
+            continue;
+        }
+        if tag_name.as_ref() != "a" {
             continue;
         }
         let Some(href) = tag.attributes().get("href").flatten() else {
@@ -260,6 +279,7 @@ pub fn parse_chapter_index(html: &str, base_url: &str) -> Result<RiglChapterInde
                 .to_string(),
             section_name: caps[2].trim().to_string(),
             url: normalized.to_string(),
+            levels: level_stack.current(),
         });
     }
 
@@ -273,7 +293,9 @@ pub fn parse_chapter_index(html: &str, base_url: &str) -> Result<RiglChapterInde
 }
 
 pub fn parse_section_detail(html: &str) -> Result<RiglSectionDetail, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let title_header = first_tag_text(&dom, parser, "h1").unwrap_or_default();
     let chapter_header = first_tag_text(&dom, parser, "h2").unwrap_or_default();
@@ -388,6 +410,18 @@ fn parse_chapter_header(raw_header: &str) -> Result<(String, String), String> {
     Ok((chapter_num, chapter_name))
 }
 
+/// Index headings read `Part 1 General Provisions` or `ARTICLE II. SALES`;
+/// bold text such as `Part 2 of this chapter applies to § 6A-9-109` is a
+/// cross-reference, not a heading.
+fn parse_index_heading(text: &str) -> Option<StructuralLevel> {
+    let level = parse_structural_heading(text)?;
+    let starts_lowercase = level.name.chars().next().is_some_and(|c| c.is_lowercase());
+    if starts_lowercase || level.name.contains('§') {
+        return None;
+    }
+    Some(level)
+}
+
 fn parse_chapter_name(raw_header: &str, chapter_num: &str) -> String {
     let normalized = normalize_text(raw_header);
     let lowered = normalized.to_ascii_lowercase();
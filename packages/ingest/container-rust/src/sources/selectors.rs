@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+use tl::{Node, NodeHandle, ParserOptions, VDom};
+
+/// One element matched by a selector, as the `explore select` REPL prints it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorMatch {
+    pub tag: String,
+    /// Inner text with whitespace runs collapsed; entities are left encoded.
+    pub text: String,
+    pub inner_html: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// An HTML or XML fixture parsed once so selectors can be tried against it
+/// repeatedly. Selectors are CSS: compound selectors (tag, `#id`, `.class`,
+/// attribute tests) joined by descendant (` `) and child (`>`) combinators,
+/// with `,` between alternatives. Tag names match case-sensitively, as in the
+/// XML sources.
+pub struct SelectorDocument<'a> {
+    dom: VDom<'a>,
+}
+
+impl<'a> SelectorDocument<'a> {
+    pub fn parse(markup: &'a str) -> Result<Self, String> {
+        let dom = tl::parse(markup, ParserOptions::default())
+            .map_err(|e| format!("Failed to parse markup: {e}"))?;
+        Ok(Self { dom })
+    }
+
+    /// Elements matching `selector`, in document order.
+    pub fn select(&self, selector: &str) -> Result<Vec<SelectorMatch>, String> {
+        let parser = self.dom.parser();
+        let mut matched = BTreeSet::new();
+        for steps in split_selector(selector)? {
+            matched.extend(self.select_chain(&steps)?);
+        }
+        Ok(matched
+            .into_iter()
+            .filter_map(|id| NodeHandle::new(id).get(parser)?.as_tag())
+            .map(|tag| SelectorMatch {
+                tag: tag.name().as_utf8_str().to_string(),
+                text: tag
+                    .inner_text(parser)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                inner_html: tag.inner_html(parser).to_string(),
+            })
+            .collect())
+    }
+
+    /// Walks down the document one compound selector at a time; `tl` parses
+    /// combinators but only matches compound selectors on their own.
+    fn select_chain(&self, steps: &[(Combinator, String)]) -> Result<BTreeSet<u32>, String> {
+        let parser = self.dom.parser();
+        let invalid = |compound: &str| format!("Invalid selector: {compound}");
+        let mut current = BTreeSet::new();
+        for (index, (combinator, compound)) in steps.iter().enumerate() {
+            let compiled = tl::parse_query_selector(compound).ok_or_else(|| invalid(compound))?;
+            if index == 0 {
+                current = self
+                    .dom
+                    .query_selector(compound)
+                    .ok_or_else(|| invalid(compound))?
+                    .map(|handle| handle.get_inner())
+                    .collect();
+                continue;
+            }
+
+            let mut next = BTreeSet::new();
+            for id in &current {
+                let Some(tag) = NodeHandle::new(*id).get(parser).and_then(Node::as_tag) else {
+                    continue;
+                };
+                match combinator {
+                    Combinator::Descendant => next.extend(
+                        tag.query_selector(parser, compound)
+                            .ok_or_else(|| invalid(compound))?
+                            .map(|handle| handle.get_inner()),
+                    ),
+                    Combinator::Child => next.extend(
+                        tag.children()
+                            .top()
+                            .iter()
+                            .filter(|child| child.get(parser).is_some_and(|n| compiled.matches(n)))
+                            .map(|child| child.get_inner()),
+                    ),
+                }
+            }
+            current = next;
+        }
+        Ok(current)
+    }
+}
+
+/// Splits `selector` into `,`-separated alternatives, each a chain of compound
+/// selectors with the combinator that precedes them.
+fn split_selector(selector: &str) -> Result<Vec<Vec<(Combinator, String)>>, String> {
+    let empty = || format!("Invalid selector: {selector}");
+    let mut alternatives = Vec::new();
+    let mut steps = Vec::new();
+    let mut current = String::new();
+    let mut combinator = Combinator::Descendant;
+    let mut pending = None;
+    let mut quote = None;
+    let mut in_brackets = false;
+
+    for c in selector.chars() {
+        if quote.is_some() || in_brackets {
+            current.push(c);
+            match quote {
+                Some(open) if c == open => quote = None,
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == ']' => in_brackets = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            ',' => {
+                if current.is_empty() || pending == Some(Combinator::Child) {
+                    return Err(empty());
+                }
+                steps.push((combinator, std::mem::take(&mut current)));
+                alternatives.push(std::mem::take(&mut steps));
+                combinator = Combinator::Descendant;
+                pending = None;
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    pending.get_or_insert(Combinator::Descendant);
+                }
+            }
+            '>' => pending = Some(Combinator::Child),
+            c => {
+                if let Some(next) = pending.take() {
+                    if current.is_empty() {
+                        return Err(empty());
+                    }
+                    steps.push((combinator, std::mem::take(&mut current)));
+                    combinator = next;
+                }
+                in_brackets = c == '[';
+                current.push(c);
+            }
+        }
+    }
+
+    if current.is_empty() || pending == Some(Combinator::Child) {
+        return Err(empty());
+    }
+    steps.push((combinator, current));
+    alternatives.push(steps);
+    Ok(alternatives)
+}
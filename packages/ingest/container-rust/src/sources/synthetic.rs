@@ -0,0 +1,399 @@
+//! Synthetic source documents for load and memory tests.
+//!
+//! Output depends only on the arguments, so a given shape always produces
+//! byte-identical documents.
+
+use std::fmt::Write;
+
+/// Outline levels below a section, outermost first, as USLM element names.
+const OUTLINE_LEVELS: [&str; 6] = [
+    "subsection",
+    "paragraph",
+    "subparagraph",
+    "clause",
+    "subclause",
+    "item",
+];
+
+/// Children generated under each non-leaf outline level.
+const OUTLINE_FANOUT: usize = 2;
+
+const USC_NOTE_TOPICS: [(&str, &str); 4] = [
+    ("amendments", "Amendments"),
+    ("effectiveDate", "Effective Date"),
+    ("referencesInText", "References in Text"),
+    ("miscellaneous", "Miscellaneous"),
+];
+
+const CGS_NOTE_CLASSES: [&str; 3] = ["history", "annotation", "cross-ref"];
+
+const WORDS: &str = "the secretary shall any person state agency may under this section provide \
+     plan for such of by in assistance program commissioner regulations notice hearing each \
+     fiscal year amount not less than application";
+
+/// Size of a generated document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticShape {
+    /// Chapters in a USC title, or chapter pages for CGS.
+    pub chapters: usize,
+    /// Sections in each chapter.
+    pub sections: usize,
+    /// Notes attached to each section.
+    pub notes: usize,
+    /// Outline levels nested under each section, at most six. Each non-leaf
+    /// level has two children, so a section holds `2^depth` leaf provisions.
+    pub depth: usize,
+}
+
+impl Default for SyntheticShape {
+    fn default() -> Self {
+        Self {
+            chapters: 3,
+            sections: 10,
+            notes: 2,
+            depth: 2,
+        }
+    }
+}
+
+impl SyntheticShape {
+    /// Parses `CHAPTERSxSECTIONSxNOTES`, optionally followed by `xDEPTH`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let parts = spec
+            .split('x')
+            .map(|part| {
+                part.parse::<usize>().map_err(|_| {
+                    format!("Invalid synthetic shape {spec:?}: {part:?} is not a count")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (chapters, sections, notes, depth) = match parts[..] {
+            [chapters, sections, notes] => (chapters, sections, notes, Self::default().depth),
+            [chapters, sections, notes, depth] => (chapters, sections, notes, depth),
+            _ => {
+                return Err(format!(
+                    "Invalid synthetic shape {spec:?}: expected CHAPTERSxSECTIONSxNOTES[xDEPTH]"
+                ))
+            }
+        };
+        if depth > OUTLINE_LEVELS.len() {
+            return Err(format!(
+                "Invalid synthetic shape {spec:?}: depth is at most {}",
+                OUTLINE_LEVELS.len()
+            ));
+        }
+        Ok(Self {
+            chapters,
+            sections,
+            notes,
+            depth,
+        })
+    }
+
+    pub fn total_sections(&self) -> usize {
+        self.chapters * self.sections
+    }
+
+    /// Outline provisions generated under each section, at every depth.
+    pub fn provisions_per_section(&self) -> usize {
+        (1..=self.outline_depth())
+            .map(|level| OUTLINE_FANOUT.pow(level as u32))
+            .sum()
+    }
+
+    fn outline_depth(&self) -> usize {
+        self.depth.min(OUTLINE_LEVELS.len())
+    }
+}
+
+/// Deterministic filler text (splitmix64 over a fixed word list).
+struct Filler {
+    state: u64,
+    words: Vec<&'static str>,
+}
+
+impl Filler {
+    fn new(seed: &str) -> Self {
+        Self {
+            state: seed.bytes().fold(0x9e37_79b9_7f4a_7c15, |state, byte| {
+                (state ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            }),
+            words: WORDS.split_whitespace().collect(),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn range(&mut self, min: usize, max: usize) -> usize {
+        min + (self.next() % (max - min + 1) as u64) as usize
+    }
+
+    fn words(&mut self, min: usize, max: usize) -> String {
+        let count = self.range(min, max);
+        let mut text = String::new();
+        for i in 0..count {
+            if i > 0 {
+                text.push(' ');
+            }
+            let word = self.next() as usize % self.words.len();
+            text.push_str(self.words[word]);
+        }
+        text
+    }
+
+    fn heading(&mut self) -> String {
+        let words = self.words(2, 5);
+        let mut chars = words.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => words,
+        }
+    }
+
+    fn sentence(&mut self) -> String {
+        format!("{}.", self.words(8, 24))
+    }
+}
+
+/// Display value of the `index`th (zero-based) designator at outline `level`.
+fn outline_designator(level: usize, index: usize) -> String {
+    match level {
+        0 => alpha(index, false),
+        1 => (index + 1).to_string(),
+        2 => alpha(index, true),
+        3 => roman(index + 1).to_lowercase(),
+        4 => roman(index + 1),
+        _ => alpha(index, false).repeat(2),
+    }
+}
+
+fn alpha(index: usize, upper: bool) -> String {
+    let base = if upper { b'A' } else { b'a' };
+    let letter = (base + (index % 26) as u8) as char;
+    letter.to_string().repeat(index / 26 + 1)
+}
+
+fn roman(mut value: usize) -> String {
+    const NUMERALS: [(usize, &str); 7] = [
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut out = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            out.push_str(numeral);
+            value -= amount;
+        }
+    }
+    out
+}
+
+/// A USLM title of `shape.chapters` chapters with sections numbered from 1
+/// across the whole title.
+pub fn usc_title(title_num: &str, shape: &SyntheticShape) -> String {
+    let mut filler = Filler::new(&format!("usc{title_num}"));
+    let mut xml = String::with_capacity(shape.total_sections() * 1024);
+    let title_id = format!("/us/usc/t{title_num}");
+    let _ = write!(
+        xml,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <uscDoc xmlns=\"http://xml.house.gov/schemas/uslm/1.0\" identifier=\"{title_id}\">\n\
+         <meta><docNumber>{title_num}</docNumber></meta>\n\
+         <main>\n\
+         <title identifier=\"{title_id}\"><num value=\"{title_num}\">Title {title_num}—</num>\
+         <heading>SYNTHETIC TITLE {title_num}</heading>\n"
+    );
+
+    let mut section_num = 0;
+    for chapter in 1..=shape.chapters {
+        let _ = writeln!(
+            xml,
+            "<chapter identifier=\"{title_id}/ch{chapter}\"><num value=\"{chapter}\">CHAPTER {chapter}—</num>\
+             <heading>{}</heading>",
+            filler.heading().to_uppercase()
+        );
+        for _ in 0..shape.sections {
+            section_num += 1;
+            write_usc_section(&mut xml, &mut filler, &title_id, section_num, shape);
+        }
+        xml.push_str("</chapter>\n");
+    }
+
+    xml.push_str("</title>\n</main>\n</uscDoc>\n");
+    xml
+}
+
+fn write_usc_section(
+    xml: &mut String,
+    filler: &mut Filler,
+    title_id: &str,
+    section_num: usize,
+    shape: &SyntheticShape,
+) {
+    let section_id = format!("{title_id}/s{section_num}");
+    let _ = write!(
+        xml,
+        "<section identifier=\"{section_id}\"><num value=\"{section_num}\">§ {section_num}.</num>\
+         <heading> {}</heading>",
+        filler.heading()
+    );
+    let depth = shape.outline_depth();
+    if depth == 0 {
+        let _ = write!(xml, "<content><p>{}</p></content>", filler.sentence());
+    } else {
+        for index in 0..OUTLINE_FANOUT {
+            write_usc_outline(xml, filler, &section_id, 0, index, depth);
+        }
+    }
+    let _ = write!(
+        xml,
+        "\n<sourceCredit>(Pub. L. {}–{}, § {section_num}, {} Stat. {}.)</sourceCredit>",
+        90 + section_num % 30,
+        filler.range(1, 400),
+        filler.range(60, 136),
+        filler.range(1, 3000)
+    );
+    if shape.notes > 0 {
+        xml.push_str("\n<notes type=\"uscNote\">");
+        for note in 0..shape.notes {
+            let (topic, label) = USC_NOTE_TOPICS[note % USC_NOTE_TOPICS.len()];
+            let _ = write!(
+                xml,
+                "<note topic=\"{topic}\"><heading>{label}</heading><p>{}</p></note>",
+                filler.sentence()
+            );
+        }
+        xml.push_str("</notes>");
+    }
+    xml.push_str("\n</section>\n");
+}
+
+fn write_usc_outline(
+    xml: &mut String,
+    filler: &mut Filler,
+    parent_id: &str,
+    level: usize,
+    index: usize,
+    depth: usize,
+) {
+    let element = OUTLINE_LEVELS[level];
+    let designator = outline_designator(level, index);
+    let identifier = format!("{parent_id}/{designator}");
+    let _ = write!(
+        xml,
+        "\n<{element} identifier=\"{identifier}\"><num value=\"{designator}\">({designator})</num>"
+    );
+    if level + 1 < depth {
+        let _ = write!(xml, "<chapeau>{}—</chapeau>", filler.words(6, 14));
+        for child in 0..OUTLINE_FANOUT {
+            write_usc_outline(xml, filler, &identifier, level + 1, child, depth);
+        }
+    } else {
+        let _ = write!(xml, "<content> {}</content>", filler.sentence());
+    }
+    let _ = write!(xml, "</{element}>");
+}
+
+/// The page for CGS chapter `chapter` (1-based) of `title_num`. Sections are
+/// numbered across chapters, so pages for the same title and shape never
+/// share a section.
+pub fn cgs_chapter(title_num: &str, chapter: usize, shape: &SyntheticShape) -> String {
+    let mut filler = Filler::new(&format!("cgs{title_num}-{chapter}"));
+    let first_section = (chapter - 1) * shape.sections + 1;
+    let section_nums = (first_section..first_section + shape.sections)
+        .map(|n| format!("{title_num}-{n}"))
+        .collect::<Vec<_>>();
+    let headings = section_nums
+        .iter()
+        .map(|_| filler.heading())
+        .collect::<Vec<_>>();
+    let chapter_name = filler.heading();
+
+    let mut html = String::with_capacity(shape.sections * 1024);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en-US\">\n<head>\n<meta charset=\"utf-8\"/>\n\
+         <meta name=\"Number\" content=\"{chapter}\"/>\n\
+         <title>Chapter {chapter} - {chapter_name}</title>\n</head>\n<body>\n\
+         <div id=\"chap_{chapter}.htm\" lang=\"en-US\">\n\
+         <h2 class=\"chap-no\">CHAPTER {chapter}</h2>\n\
+         <h2 class=\"chap-name\">{}</h2>\n\
+         <h4 class=\"chap_toc_hd\" id=\"TOC\">Table of Contents</h4>\n",
+        chapter_name.to_uppercase()
+    );
+    for (section_num, heading) in section_nums.iter().zip(&headings) {
+        let _ = writeln!(
+            html,
+            "<p class=\"toc_catchln\"><a href=\"#sec_{section_num}\">Sec. {section_num}. {heading}.</a></p>"
+        );
+    }
+    html.push_str("<hr class=\"chaps_pg_bar\"/>\n");
+
+    for (section_num, heading) in section_nums.iter().zip(&headings) {
+        let _ = write!(
+            html,
+            "<p><span class=\"catchln\" id=\"sec_{section_num}\">Sec. {section_num}. {heading}.</span> "
+        );
+        let depth = shape.outline_depth();
+        if depth == 0 {
+            let _ = writeln!(html, "{}</p>", filler.sentence());
+        } else {
+            let _ = writeln!(html, "{}</p>", filler.words(6, 14));
+            for index in 0..OUTLINE_FANOUT {
+                write_cgs_outline(&mut html, &mut filler, 0, index, depth);
+            }
+        }
+        let _ = writeln!(
+            html,
+            "<p class=\"source-first\">(P.A. {}-{}, S. {}.)</p>",
+            filler.range(70, 99),
+            filler.range(1, 400),
+            filler.range(1, 60)
+        );
+        for note in 0..shape.notes {
+            let _ = writeln!(
+                html,
+                "<p class=\"{}-first\">{}</p>",
+                CGS_NOTE_CLASSES[note % CGS_NOTE_CLASSES.len()],
+                filler.sentence()
+            );
+        }
+        html.push_str(
+            "<table class=\"nav_tbl\">\n<tr>\n\
+             <td><a class=\"nav_link\" href=\"#TOC\">(Return to Chapter<br/>Table of Contents)</a></td>\n\
+             </tr>\n</table>\n",
+        );
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+fn write_cgs_outline(
+    html: &mut String,
+    filler: &mut Filler,
+    level: usize,
+    index: usize,
+    depth: usize,
+) {
+    let designator = outline_designator(level, index);
+    if level + 1 < depth {
+        let _ = writeln!(html, "<p>({designator}) {}:</p>", filler.words(6, 14));
+        for child in 0..OUTLINE_FANOUT {
+            write_cgs_outline(html, filler, level + 1, child, depth);
+        }
+    } else {
+        let _ = writeln!(html, "<p>({designator}) {}</p>", filler.sentence());
+    }
+}
@@ -1,15 +1,27 @@
 use crate::info;
+use crate::runtime::blocking::{run_blocking, spawn_blocking_stream};
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::common::{body_block, capitalize_first};
+use crate::sources::common::{body_blocks, capitalize_first};
+use crate::sources::configs::{configured_external_links, configured_note_taxonomy};
+use crate::sources::paths::USC_PATHS;
 use crate::sources::SourceAdapter;
-use crate::types::{ContentBlock, DiscoveryResult, NodeMeta, NodePayload, SectionContent};
+use crate::types::{
+    ContentBlock, DiscoveryResult, NodeMeta, NodePayload, SectionContent, SectionMetadata,
+    SourceKind,
+};
 use async_trait::async_trait;
 use std::collections::HashSet;
-use tokio::sync::mpsc;
 
+use crate::sources::usc::amendments::{split_amendments_block, AMENDMENTS_BLOCK_TYPE};
+use crate::sources::usc::definitions::{definitions_block, extract_definitions};
+use crate::sources::usc::effective_dates::{parse_effective_date_note, EFFECTIVE_DATE_BLOCK_TYPE};
+use crate::sources::usc::formulas::FormulaEntry;
 use crate::sources::usc::parser::{
-    parse_usc_xml_stream, section_level_index, USCParentRef, USCStreamEvent,
+    appendix_owner, section_level_index, usc_level_index, usc_stream_events_with_taxonomy,
+    USCParentRef, USCStreamEvent,
 };
+use crate::sources::usc::source_credits::parse_source_credit;
+use crate::sources::usc::toc::{declared_toc_entries, validate_toc};
 
 pub struct UscAdapter;
 
@@ -35,7 +47,7 @@ impl SourceAdapter for UscAdapter {
         let metadata = &item.metadata;
 
         match item.level_name.as_str() {
-            "title" => {
+            "title" | "appendix" => {
                 let title_num = metadata["title_num"].as_str().unwrap_or_default();
                 let version_id = &context.build.source_version_id;
                 let cache_key = format!("usc/{}/title-{}.zip", version_id, title_num);
@@ -44,8 +56,8 @@ impl SourceAdapter for UscAdapter {
                 let mut seen_level_ids: HashSet<String> = HashSet::new();
                 let mut seen_section_keys: HashSet<String> = HashSet::new();
                 let mut level_sort_order: i32 = 0;
+                let mut extracted_ids: Vec<String> = Vec::new();
 
-                let (tx, mut rx) = mpsc::channel(100);
                 let xml_str = xml.to_string();
                 let title_num_payload = title_num.to_string();
 
@@ -57,12 +69,19 @@ impl SourceAdapter for UscAdapter {
                     xml_str.chars().take(100).collect::<String>()
                 );
 
-                std::thread::spawn(move || {
-                    parse_usc_xml_stream(&xml_str, &title_num_payload, |event| {
-                        if let Err(e) = tx.blocking_send(event) {
-                            tracing::error!("Failed to send USC event: {e}");
+                let note_taxonomy = configured_note_taxonomy(SourceKind::Usc);
+                let external_links = configured_external_links(SourceKind::Usc);
+                let profile = context.build.profile;
+                let mut rx = spawn_blocking_stream(&context.cancel, 100, move |sink| {
+                    for event in
+                        usc_stream_events_with_taxonomy(&xml_str, &title_num_payload, note_taxonomy)
+                            .with_profile(profile)
+                            .with_external_links(external_links.clone())
+                    {
+                        if !sink.emit(event) {
+                            break;
                         }
-                    });
+                    }
                 });
 
                 let section_level_idx = section_level_index() as i32;
@@ -112,6 +131,7 @@ impl SourceAdapter for UscAdapter {
                             if seen_level_ids.contains(&level.identifier) {
                                 continue;
                             }
+                            extracted_ids.push(level.identifier.clone());
 
                             let parent_string_id = resolve_level_parent_string_id(
                                 context.build.root_node_id,
@@ -142,6 +162,7 @@ impl SourceAdapter for UscAdapter {
                                         heading_citation: Some(heading_citation),
                                         source_url: None,
                                         accessed_at: Some(context.build.accessed_at.to_string()),
+                                        review_required: false,
                                     },
                                     content: None,
                                 })
@@ -166,10 +187,30 @@ impl SourceAdapter for UscAdapter {
                             if !seen_section_keys.insert(section.section_key.clone()) {
                                 continue;
                             }
+                            let cited_num = match (&section.range_start, &section.range_end) {
+                                (Some(start), Some(end)) => format!("{start}...{end}"),
+                                _ => section.section_num.clone(),
+                            };
+                            extracted_ids.push(format!("t{}/s{cited_num}", section.title_num));
 
-                            let mut blocks = vec![body_block(&section.body)];
-                            for block in &section.blocks {
-                                blocks.push(ContentBlock {
+                            let definitions = extract_definitions(&section.body);
+                            let mut blocks =
+                                body_blocks(context.build.profile, || section.body.clone());
+                            if !definitions.is_empty() {
+                                blocks.push(definitions_block(&definitions));
+                            }
+                            let mut amendments = Vec::new();
+                            let mut formulas = Vec::new();
+                            for (index, block) in section.blocks.iter().enumerate() {
+                                if let Some(formula) =
+                                    section.formulas.iter().find(|f| f.block == index)
+                                {
+                                    formulas.push(FormulaEntry {
+                                        block: blocks.len(),
+                                        mathml: formula.mathml.clone(),
+                                    });
+                                }
+                                let block = ContentBlock {
                                     type_: block.type_.clone(),
                                     content: block.content.clone().and_then(|c| {
                                         if c.trim().is_empty() {
@@ -179,15 +220,55 @@ impl SourceAdapter for UscAdapter {
                                         }
                                     }),
                                     label: block.label.clone(),
-                                });
+                                };
+                                if block.type_ == AMENDMENTS_BLOCK_TYPE {
+                                    if let Some((split, entries)) =
+                                        split_amendments_block(&block, blocks.len())
+                                    {
+                                        blocks.extend(split);
+                                        amendments.extend(entries);
+                                        continue;
+                                    }
+                                }
+                                blocks.push(block);
                             }
 
+                            let effective_dates = blocks
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, block)| block.type_ == EFFECTIVE_DATE_BLOCK_TYPE)
+                                .filter_map(|(index, block)| {
+                                    parse_effective_date_note(index, block.content.as_deref()?)
+                                })
+                                .collect::<Vec<_>>();
+                            let source_credits = blocks
+                                .iter()
+                                .find(|block| block.type_ == "source_credit")
+                                .and_then(|block| block.content.as_deref())
+                                .map(parse_source_credit)
+                                .unwrap_or_default();
+                            let metadata = SectionMetadata {
+                                effective_dates,
+                                definitions,
+                                source_credits,
+                                amendments,
+                                formulas,
+                                ..Default::default()
+                            };
                             let content = SectionContent {
                                 blocks,
-                                metadata: None,
+                                metadata: (!metadata.effective_dates.is_empty()
+                                    || !metadata.definitions.is_empty()
+                                    || !metadata.source_credits.is_empty()
+                                    || !metadata.amendments.is_empty()
+                                    || !metadata.formulas.is_empty())
+                                .then_some(metadata),
+                            };
+                            let display_num = section.section_num.replace('_', " ");
+                            let readable_id = match appendix_owner(&section.title_num) {
+                                Some(owner) => format!("{owner} USC App. {display_num}"),
+                                None => format!("{} USC {display_num}", section.title_num),
                             };
-                            let readable_id =
-                                format!("{} USC {}", section.title_num, section.section_num);
                             let parent_id = resolve_section_parent_string_id(
                                 context.build.root_node_id,
                                 &section.parent_ref,
@@ -215,6 +296,7 @@ impl SourceAdapter for UscAdapter {
                                         heading_citation: Some(readable_id),
                                         source_url: None,
                                         accessed_at: Some(context.build.accessed_at.to_string()),
+                                        review_required: false,
                                     },
                                     content: Some(serde_json::to_value(&content).unwrap()),
                                 })
@@ -226,6 +308,36 @@ impl SourceAdapter for UscAdapter {
                     }
                 }
 
+                if context.cancel.is_cancelled() {
+                    return Err(format!("USC Title {title_num} cancelled"));
+                }
+
+                let toc_title = title_num.to_string();
+                let declared_ids = run_blocking(&context.cancel, move |_| {
+                    declared_toc_entries(&xml, &toc_title)
+                })
+                .await?;
+                let toc = validate_toc(title_num, &declared_ids, &extracted_ids);
+                let (level, summary) = if toc.is_consistent() {
+                    ("info", "matches its table of contents")
+                } else {
+                    ("warn", "differs from its table of contents")
+                };
+                context
+                    .logger
+                    .log(
+                        level,
+                        &format!(
+                            "USC Title {title_num} {summary}: {} declared, {} extracted, {} missing, {} undeclared",
+                            toc.declared,
+                            toc.extracted,
+                            toc.missing.len(),
+                            toc.undeclared.len()
+                        ),
+                        serde_json::to_value(&toc).ok(),
+                    )
+                    .await;
+
                 info!(
                     context,
                     "Finished processing USC Title {}. Total events: {}", title_num, event_count
@@ -238,10 +350,28 @@ impl SourceAdapter for UscAdapter {
     }
 
     fn unit_label(&self, item: &QueueItem) -> String {
-        format!(
-            "Title {}",
-            item.metadata["title_num"].as_str().unwrap_or("?")
-        )
+        let title_num = item.metadata["title_num"].as_str().unwrap_or("?");
+        match appendix_owner(title_num) {
+            Some(owner) => format!("Title {owner} Appendix"),
+            None => format!("Title {title_num}"),
+        }
+    }
+
+    // v2: tables, footnotes, structured notes, section ranges and appendices.
+    fn parser_version(&self) -> u32 {
+        2
+    }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // Section paths are `/section/{title}/{num}`; links add the site prefix.
+        if node.level_name != "section" {
+            return None;
+        }
+        node.path.as_ref().map(|path| format!("/statutes{path}"))
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/statutes/section/")
     }
 }
 
@@ -258,7 +388,19 @@ async fn emit_title_node(
     }
     seen_level_ids.insert(native_id.clone());
 
-    let title_string_id = format!("{}/{native_id}", context.build.root_node_id);
+    let root_node_id = context.build.root_node_id;
+    let title_string_id = format!("{root_node_id}/{native_id}");
+    // An appendix is its own unit, so its container sits beside the owning
+    // title rather than under a node another unit emits; discovery orders it
+    // right after that title.
+    let (level_name, readable_id, heading_citation) = match appendix_owner(title_num) {
+        Some(owner) => (
+            "appendix",
+            format!("{owner} App."),
+            format!("Title {owner} Appendix"),
+        ),
+        None => ("title", title_num.to_string(), format!("Title {title_num}")),
+    };
 
     context
         .nodes
@@ -266,16 +408,17 @@ async fn emit_title_node(
             meta: NodeMeta {
                 id: title_string_id,
                 source_version_id: context.build.source_version_id.to_string(),
-                parent_id: Some(context.build.root_node_id.to_string()),
-                level_name: "title".to_string(),
-                level_index: 0,
+                parent_id: Some(root_node_id.to_string()),
+                level_name: level_name.to_string(),
+                level_index: usc_level_index(level_name).unwrap_or(0) as i32,
                 sort_order: context.build.unit_sort_order,
                 name: Some(title_name.to_string()),
-                path: Some(format!("/title/{title_num}")),
-                readable_id: Some(title_num.to_string()),
-                heading_citation: Some(format!("Title {title_num}")),
+                path: Some(USC_PATHS.render("title", &[("num", title_num)])?),
+                readable_id: Some(readable_id),
+                heading_citation: Some(heading_citation),
                 source_url: Some(url.to_string()),
                 accessed_at: Some(context.build.accessed_at.to_string()),
+                review_required: false,
             },
             content: None,
         })
@@ -0,0 +1,67 @@
+use crate::types::ContentBlock;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub use crate::types::AmendmentEntry;
+
+/// Block type of a whole amendments note, as the note taxonomy emits it.
+pub const AMENDMENTS_BLOCK_TYPE: &str = "amendments";
+/// Block type of one entry of a split amendments note.
+pub const AMENDMENT_BLOCK_TYPE: &str = "amendment";
+
+static YEAR_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d{4})\s*[—–-]\s*").unwrap());
+
+/// `Subsec. (a)`, `Subsecs. (b) to (d)`, `Par. (2)(A)`, and the like, at the
+/// start of an entry.
+static SUBDIVISION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^((?:Subsecs?|Pars?|Subpars?|Cls?|Subcls?|Items?|Subitems?)\.\s*\([^)]+\)(?:\([^)]+\))*(?:(?:,\s*|\s+(?:to|and)\s+)\([^)]+\)(?:\([^)]+\))*)*)\.",
+    )
+    .unwrap()
+});
+
+/// Splits an amendments note into a heading block followed by one
+/// `amendment` block per paragraph, labeled with its year. A paragraph
+/// without a leading `1984—` continues the year before it. Returns the
+/// blocks and an entry for each amendment block, numbered from
+/// `first_index`. `None` when the note does not open with a year.
+pub fn split_amendments_block(
+    block: &ContentBlock,
+    first_index: usize,
+) -> Option<(Vec<ContentBlock>, Vec<AmendmentEntry>)> {
+    let text = block.content.as_deref()?;
+    let mut paragraphs = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .peekable();
+    YEAR_RE.captures(paragraphs.peek()?)?;
+
+    let mut blocks = vec![ContentBlock {
+        type_: "heading".to_string(),
+        content: None,
+        label: block.label.clone(),
+    }];
+    let mut entries = Vec::new();
+    let mut year = 0;
+    for paragraph in paragraphs {
+        let mut entry_text = paragraph;
+        if let Some(caps) = YEAR_RE.captures(paragraph) {
+            year = caps[1].parse().ok()?;
+            entry_text = &paragraph[caps.get(0)?.end()..];
+        }
+        entries.push(AmendmentEntry {
+            block: first_index + blocks.len(),
+            year,
+            subdivision: SUBDIVISION_RE
+                .captures(entry_text)
+                .map(|caps| caps[1].to_string()),
+        });
+        blocks.push(ContentBlock {
+            type_: AMENDMENT_BLOCK_TYPE.to_string(),
+            content: Some(entry_text.to_string()),
+            label: Some(year.to_string()),
+        });
+    }
+    Some((blocks, entries))
+}
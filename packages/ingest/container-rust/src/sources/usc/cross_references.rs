@@ -1,17 +1,8 @@
 use regex::Regex;
-use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct SectionCrossReference {
-    pub section: String,
-    #[serde(rename = "titleNum")]
-    pub title_num: Option<String>,
-    pub offset: usize,
-    pub length: usize,
-    pub link: Option<String>,
-}
+pub use crate::types::SectionCrossReference;
 
 #[derive(Debug, Clone)]
 enum Token {
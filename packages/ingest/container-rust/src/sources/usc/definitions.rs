@@ -0,0 +1,96 @@
+use crate::types::ContentBlock;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub use crate::types::DefinedTerm;
+
+/// Block type of the list of terms a section defines.
+pub const DEFINITIONS_BLOCK_TYPE: &str = "definitions";
+
+/// Quoted terms, led by "the term(s)"/"the word(s)" or opening the
+/// paragraph, followed by a defining verb. An aside between term and verb
+/// (`“born alive”, with respect to ..., means`) is allowed.
+static DEFINITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:\b[Tt]he\s+(?:terms?|words?)\s+|^)((?:“[^”]+”(?:,?\s+(?:and|or)\s+|,\s*)?)+?)(?:,[^“”.;]*,)?\s*\b(means?|includes?|has the meaning|have the meaning|shall (?:each )?(?:mean|include|have the meaning))\b",
+    )
+    .unwrap()
+});
+
+static QUOTED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"“([^”]+)”").unwrap());
+
+static OUTLINE_MARKER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\([0-9A-Za-z]+\)\s*)+").unwrap());
+
+/// Finds the terms `body` defines, in order, keeping the first definition of
+/// a term. A definition runs from its verb to the next definition in the
+/// paragraph or the paragraph's end; the last one also takes any
+/// deeper-quoted paragraphs that follow (the lettered items of "means—").
+/// Bold markers are dropped from the text.
+pub fn extract_definitions(body: &str) -> Vec<DefinedTerm> {
+    let paragraphs = body
+        .split("\n\n")
+        .map(|paragraph| {
+            let (depth, text) = quote_depth(paragraph.trim());
+            (depth, text.replace("**", ""))
+        })
+        .collect::<Vec<_>>();
+
+    let mut definitions: Vec<DefinedTerm> = Vec::new();
+    for (index, (depth, text)) in paragraphs.iter().enumerate() {
+        let text = OUTLINE_MARKER_RE.replace(text, "");
+        let matches = DEFINITION_RE.captures_iter(&text).collect::<Vec<_>>();
+        for (position, caps) in matches.iter().enumerate() {
+            let verb_start = caps.get(2).map_or(0, |m| m.start());
+            let end = matches
+                .get(position + 1)
+                .and_then(|next| next.get(0))
+                .map_or(text.len(), |m| m.start());
+            let mut definition = text[verb_start..end].trim().to_string();
+            if position + 1 == matches.len() {
+                for (child_depth, child) in &paragraphs[index + 1..] {
+                    if child_depth <= depth {
+                        break;
+                    }
+                    definition.push(' ');
+                    definition.push_str(child.trim());
+                }
+            }
+
+            for term in QUOTED_RE.captures_iter(&caps[1]) {
+                let term = term[1].trim().to_string();
+                if definitions.iter().any(|known| known.term == term) {
+                    continue;
+                }
+                definitions.push(DefinedTerm {
+                    term,
+                    definition: definition.clone(),
+                });
+            }
+        }
+    }
+    definitions
+}
+
+/// Renders `definitions` as a markdown list, one term per item.
+pub fn definitions_block(definitions: &[DefinedTerm]) -> ContentBlock {
+    let items = definitions
+        .iter()
+        .map(|definition| format!("- **{}** {}", definition.term, definition.definition))
+        .collect::<Vec<_>>();
+    ContentBlock {
+        type_: DEFINITIONS_BLOCK_TYPE.to_string(),
+        content: Some(items.join("\n")),
+        label: Some("Definitions".to_string()),
+    }
+}
+
+fn quote_depth(paragraph: &str) -> (usize, &str) {
+    let mut depth = 0;
+    let mut rest = paragraph;
+    while let Some(inner) = rest.strip_prefix('>') {
+        depth += 1;
+        rest = inner.trim_start();
+    }
+    (depth, rest)
+}
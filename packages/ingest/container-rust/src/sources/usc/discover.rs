@@ -1,4 +1,4 @@
-use crate::sources::usc::parser::title_sort_key;
+use crate::sources::usc::parser::{appendix_owner, title_sort_key, usc_level_index};
 use crate::types::{DiscoveryResult, NodeMeta, UnitRoot};
 use regex::Regex;
 use reqwest::Url;
@@ -35,7 +35,7 @@ pub async fn discover_usc_root(
             .to_string();
 
         if let Some(caps) = xml_link_re.captures(&url) {
-            let title_num = caps[1].trim_start_matches('0').to_string();
+            let title_num = caps[1].trim_start_matches('0').to_lowercase();
             let title_num = if title_num.is_empty() {
                 "0".to_string()
             } else {
@@ -81,12 +81,20 @@ pub async fn discover_usc_root(
 
     let unit_roots: Vec<UnitRoot> = titles
         .into_iter()
-        .map(|(title_num, url)| UnitRoot {
-            id: format!("title-{}", title_num),
-            title_num,
-            url,
-            level_name: "title".to_string(),
-            level_index: 0,
+        .map(|(title_num, url)| {
+            // Appendices (`5a`) are ingested below their owning title.
+            let level_name = if appendix_owner(&title_num).is_some() {
+                "appendix"
+            } else {
+                "title"
+            };
+            UnitRoot {
+                id: format!("{level_name}-{title_num}"),
+                title_num,
+                url,
+                level_name: level_name.to_string(),
+                level_index: usc_level_index(level_name).unwrap_or(0) as i32,
+            }
         })
         .collect();
 
@@ -103,6 +111,7 @@ pub async fn discover_usc_root(
         heading_citation: Some("USC".to_string()),
         source_url: Some(start_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
@@ -0,0 +1,49 @@
+use crate::sources::common::find_long_dates;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub use crate::types::EffectiveDateNote;
+
+/// Block type the note taxonomy gives `topic="effectiveDate"` notes.
+pub const EFFECTIVE_DATE_BLOCK_TYPE: &str = "effective_date";
+
+pub(crate) static PUBLIC_LAW_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bPub\.\s*L\.\s*(\d+)\s*[-–—]\s*(\d+)").unwrap());
+
+/// Pulls the dates and Public Laws out of the text of the effective-date
+/// note at `block`. Dates come back as ISO 8601 in note order; the date of
+/// an act cited by date ("act Jan. 19, 1949") is not an effective date and
+/// is skipped. `None` when the note names neither.
+pub fn parse_effective_date_note(block: usize, text: &str) -> Option<EffectiveDateNote> {
+    let mut dates = Vec::new();
+    for (start, date) in find_long_dates(text) {
+        if cites_act(&text[..start]) || dates.contains(&date) {
+            continue;
+        }
+        dates.push(date);
+    }
+
+    let mut public_laws = Vec::new();
+    for caps in PUBLIC_LAW_RE.captures_iter(text) {
+        let law = format!("{}-{}", &caps[1], &caps[2]);
+        if !public_laws.contains(&law) {
+            public_laws.push(law);
+        }
+    }
+
+    if dates.is_empty() && public_laws.is_empty() {
+        return None;
+    }
+    Some(EffectiveDateNote {
+        block,
+        dates,
+        public_laws,
+    })
+}
+
+fn cites_act(before: &str) -> bool {
+    let before = before.trim_end().to_lowercase();
+    ["act", "act of", "approved"]
+        .iter()
+        .any(|word| before.ends_with(word))
+}
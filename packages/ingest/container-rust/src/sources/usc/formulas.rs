@@ -0,0 +1,175 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub use crate::types::FormulaEntry;
+
+/// Block type of a formula read from MathML.
+pub const FORMULA_BLOCK_TYPE: &str = "formula";
+
+/// Operators written with a space on each side.
+const SPACED_OPERATORS: &[&str] = &[
+    "=", "+", "-", "\u{2212}", "\u{00d7}", "\u{00f7}", "<", ">", "\u{2264}", "\u{2265}",
+    "\u{2260}", "\u{00b1}",
+];
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Child>,
+}
+
+#[derive(Debug)]
+enum Child {
+    Element(Element),
+    Text(String),
+}
+
+/// Best-effort plain text for a MathML `<math>` element: fractions as
+/// `a/b`, scripts as `x^2` and `x_i`, roots with `√`, grouping compound
+/// operands in parentheses. Annotations and phantoms are dropped.
+pub fn mathml_to_text(mathml: &str) -> String {
+    let root = parse(mathml);
+    let text = root.children.iter().map(render_child).collect::<String>();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse(mathml: &str) -> Element {
+    let mut reader = Reader::from_str(mathml);
+    let mut stack = vec![Element::default()];
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => stack.push(element(&e)),
+            Ok(Event::Empty(e)) => {
+                let child = element(&e);
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Child::Element(child));
+                }
+            }
+            Ok(Event::Text(t)) => {
+                if let (Ok(text), Some(parent)) = (t.unescape(), stack.last_mut()) {
+                    parent.children.push(Child::Text(text.into_owned()));
+                }
+            }
+            Ok(Event::End(_)) if stack.len() > 1 => {
+                let done = stack.pop().unwrap();
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Child::Element(done));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    while stack.len() > 1 {
+        let done = stack.pop().unwrap();
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(Child::Element(done));
+        }
+    }
+    stack.pop().unwrap_or_default()
+}
+
+fn element(e: &quick_xml::events::BytesStart<'_>) -> Element {
+    Element {
+        name: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+        attrs: e
+            .attributes()
+            .flatten()
+            .map(|attr| {
+                (
+                    String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned(),
+                    attr.unescape_value()
+                        .map(|value| value.into_owned())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect(),
+        children: Vec::new(),
+    }
+}
+
+fn render_child(child: &Child) -> String {
+    match child {
+        Child::Text(text) => text.clone(),
+        Child::Element(element) => render(element),
+    }
+}
+
+fn render(element: &Element) -> String {
+    let args = element
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Child::Element(element) => Some(render(element)),
+            Child::Text(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let arg = |index: usize| group(args.get(index).map_or("", String::as_str));
+
+    match element.name.as_str() {
+        "mi" | "mn" | "mtext" | "ms" => text_of(element).trim().to_string(),
+        "mo" => {
+            let op = text_of(element).trim().to_string();
+            if SPACED_OPERATORS.contains(&op.as_str()) {
+                format!(" {op} ")
+            } else if op == "," || op == ";" {
+                format!("{op} ")
+            } else {
+                op
+            }
+        }
+        "mspace" => " ".to_string(),
+        "mfrac" => format!("{}/{}", arg(0), arg(1)),
+        "msup" | "mover" => format!("{}^{}", arg(0), arg(1)),
+        "msub" | "munder" => format!("{}_{}", arg(0), arg(1)),
+        "msubsup" | "munderover" => format!("{}_{}^{}", arg(0), arg(1), arg(2)),
+        "msqrt" => format!("√{}", group(&args.concat())),
+        "mroot" => format!("{}√{}", arg(1), arg(0)),
+        "mfenced" => {
+            let attr = |name: &str, default: &str| {
+                element
+                    .attrs
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map_or(default.to_string(), |(_, value)| value.clone())
+            };
+            format!(
+                "{}{}{}",
+                attr("open", "("),
+                args.join(&format!("{} ", attr("separators", ",").trim())),
+                attr("close", ")")
+            )
+        }
+        "mtable" => args.join("; "),
+        "mtr" | "mlabeledtr" => args.join(", "),
+        "annotation" | "annotation-xml" | "mphantom" | "none" | "mprescripts" => String::new(),
+        _ => element.children.iter().map(render_child).collect(),
+    }
+}
+
+fn text_of(element: &Element) -> String {
+    element
+        .children
+        .iter()
+        .map(|child| match child {
+            Child::Text(text) => text.clone(),
+            Child::Element(element) => text_of(element),
+        })
+        .collect()
+}
+
+/// Wraps a compound operand in parentheses so `a+b` over `2` reads
+/// `(a + b)/2`.
+fn group(text: &str) -> String {
+    let text = text.trim();
+    let simple = text
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '.' || c == '\u{2032}');
+    let fenced = text.starts_with('(') && text.ends_with(')');
+    if simple || fenced || text.is_empty() {
+        text.to_string()
+    } else {
+        format!("({text})")
+    }
+}
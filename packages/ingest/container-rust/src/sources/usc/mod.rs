@@ -1,4 +1,10 @@
 pub mod adapter;
+pub mod amendments;
 pub mod cross_references;
+pub mod definitions;
 pub mod discover;
+pub mod effective_dates;
+pub mod formulas;
 pub mod parser;
+pub mod source_credits;
+pub mod toc;
@@ -1,10 +1,20 @@
+use crate::sources::common::{
+    outline_prefix, strip_invisible_chars, strip_markdown_links, ParseError,
+};
+use crate::sources::configs::{ExternalLinks, NoteTaxonomy};
+use crate::sources::paths::USC_PATHS;
+use crate::sources::usc::formulas::{mathml_to_text, FormulaEntry, FORMULA_BLOCK_TYPE};
+use crate::types::IngestProfile;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use regex::Regex;
 use std::borrow::Cow;
 use std::cell::OnceCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Seek};
 use std::sync::LazyLock;
+use zip::read::ZipFile;
+use zip::ZipArchive;
 
 static WHITESPACE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
 static UNICODE_DASH_RE: LazyLock<Regex> =
@@ -23,6 +33,15 @@ static CONJUNCTION_PREFIX_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)\b(and|or)\s+$").unwrap());
 static STANDALONE_BOLD_MARKER_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\*\*\([^)]+\)\*\*$").unwrap());
+static STATUS_HEADING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\[?\s*(repealed|omitted|transferred|renumbered|reserved|vacant)\b").unwrap()
+});
+static TRANSFER_TARGET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:transferred|reclassified|renumbered|redesignated)\s+(?:to\s+|as\s+)?(?:section\s+|§\s*)([0-9][0-9A-Za-z\-–]*)(?:\s+of\s+(?:title\s+([0-9]+[A-Za-z]?)|this title))?",
+    )
+    .unwrap()
+});
 static LEVEL_SEGMENT_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^(?P<prefix>st|sch|spt|sd|ch|pt|t|d)(?P<num>.+)$").unwrap());
 
@@ -67,6 +86,41 @@ pub struct USCSection {
     pub blocks: Vec<USCSectionBlock>,
     pub path: String,
     pub parent_ref: USCParentRef,
+    /// First and last section a `§§ 202 to 204.` entry covers; `None` for a
+    /// single section. A range's `section_num` is `202_to_204`.
+    pub range_start: Option<String>,
+    pub range_end: Option<String>,
+    pub status: USCSectionStatus,
+    /// Where a transferred section now lives, as `title:section` (the form
+    /// of an unsuffixed `section_key`).
+    pub transferred_to: Option<String>,
+    /// MathML of each `formula` block, indexed into `blocks`.
+    pub formulas: Vec<FormulaEntry>,
+}
+
+/// Whether a section still carries law. Read from the USLM `status`
+/// attribute, else from a heading such as "Repealed." or "Transferred";
+/// a bracketed number with neither marks the section omitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum USCSectionStatus {
+    #[default]
+    Active,
+    Repealed,
+    Omitted,
+    Transferred,
+    Reserved,
+}
+
+impl USCSectionStatus {
+    fn from_word(word: &str) -> Option<Self> {
+        match word.to_ascii_lowercase().as_str() {
+            "repealed" => Some(Self::Repealed),
+            "omitted" => Some(Self::Omitted),
+            "transferred" | "renumbered" | "redesignated" => Some(Self::Transferred),
+            "reserved" | "vacant" => Some(Self::Reserved),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,7 +135,7 @@ pub enum USCStreamEvent {
     Title(String),
     Level(USCLevel),
     Section(USCSection),
-    Error(String),
+    Error(ParseError),
 }
 
 #[repr(u8)]
@@ -116,6 +170,19 @@ enum Tag {
     QuotedContent = 26,
     P = 27,
     Ref = 28,
+    Table = 29,
+    Thead = 30,
+    Tr = 31,
+    Th = 32,
+    Td = 33,
+    Layout = 34,
+    LayoutHeader = 35,
+    Row = 36,
+    Column = 37,
+    TocItem = 38,
+    Footnote = 39,
+    Appendix = 40,
+    Figure = 41,
 }
 
 #[inline(always)]
@@ -167,6 +234,19 @@ fn classify(name: &[u8]) -> Option<Tag> {
         b"quotedContent" => Some(Tag::QuotedContent),
         b"p" => Some(Tag::P),
         b"ref" => Some(Tag::Ref),
+        b"table" => Some(Tag::Table),
+        b"thead" => Some(Tag::Thead),
+        b"tr" => Some(Tag::Tr),
+        b"th" => Some(Tag::Th),
+        b"td" => Some(Tag::Td),
+        b"layout" => Some(Tag::Layout),
+        b"header" => Some(Tag::LayoutHeader),
+        b"row" => Some(Tag::Row),
+        b"column" => Some(Tag::Column),
+        b"tocItem" => Some(Tag::TocItem),
+        b"footnote" => Some(Tag::Footnote),
+        b"appendix" => Some(Tag::Appendix),
+        b"figure" => Some(Tag::Figure),
         _ => None,
     }
 }
@@ -211,6 +291,140 @@ struct ActiveNote {
     role: Option<String>,
     heading: String,
     text: String,
+    quote: Option<NoteQuote>,
+    /// Rendered quotes that hold sections or outline levels, emitted as
+    /// `quoted` blocks after the note.
+    quoted: Vec<String>,
+}
+
+/// A `<quotedContent>` inside a note, read twice: flat into the note text,
+/// as before, and here with its structure kept. When it closes, a quote
+/// that held structure replaces its flat copy with a `quoted` block.
+#[derive(Debug, Clone)]
+struct NoteQuote {
+    depth: usize,
+    /// Length of the note text when the quote opened.
+    note_start: usize,
+    structural: bool,
+    /// Tag depths of the open section and outline levels.
+    levels: Vec<usize>,
+    /// Paragraphs with their nesting level.
+    paragraphs: Vec<(usize, String)>,
+    /// Tag depth of the open num or heading and where its bold opens.
+    bold: Option<(usize, usize)>,
+    /// The last paragraph holds only a num so far; its content joins it.
+    num_only: bool,
+}
+
+impl NoteQuote {
+    fn new(depth: usize, note_start: usize) -> Self {
+        Self {
+            depth,
+            note_start,
+            structural: false,
+            levels: Vec::new(),
+            paragraphs: Vec::new(),
+            bold: None,
+            num_only: false,
+        }
+    }
+
+    fn open(&mut self, tag: Tag, depth: usize) {
+        if tag == Tag::Section || structural_tag_depth(tag).is_some() {
+            self.structural = true;
+            self.levels.push(depth);
+            self.start_paragraph();
+        } else if matches!(tag, Tag::Num | Tag::Heading) && self.bold.is_none() {
+            self.structural = true;
+            if !self.num_only {
+                self.start_paragraph();
+            }
+            self.num_only = tag == Tag::Num && self.current().is_empty();
+            let text = self.current();
+            push_bold_open(text);
+            self.bold = Some((depth, text.len() - 2));
+        } else if matches!(
+            tag,
+            Tag::Chapeau | Tag::Content | Tag::P | Tag::Continuation
+        ) {
+            if self.num_only {
+                self.current().push(' ');
+            } else {
+                self.start_paragraph();
+            }
+        }
+    }
+
+    fn close(&mut self, depth: usize) {
+        if let Some((_, start)) = self.bold.filter(|(bold_depth, _)| *bold_depth == depth) {
+            self.bold = None;
+            let text = self.current();
+            if text[start + 2..].trim().is_empty() {
+                text.truncate(start);
+            } else {
+                let trimmed = text.trim_end().len();
+                text.truncate(trimmed);
+                text.push_str("**");
+            }
+        }
+        if self.levels.last() == Some(&depth) {
+            self.levels.pop();
+        }
+    }
+
+    fn push_text(&mut self, text: &str, needs_space: bool) {
+        if self.bold.is_none() {
+            self.num_only = false;
+        }
+        append_text(self.current(), text, needs_space);
+    }
+
+    fn start_paragraph(&mut self) {
+        if self
+            .paragraphs
+            .last()
+            .is_none_or(|(_, text)| !text.is_empty())
+        {
+            self.paragraphs.push((self.levels.len(), String::new()));
+        } else if let Some(last) = self.paragraphs.last_mut() {
+            last.0 = self.levels.len();
+        }
+    }
+
+    fn current(&mut self) -> &mut String {
+        if self.paragraphs.is_empty() {
+            self.paragraphs.push((self.levels.len(), String::new()));
+        }
+        &mut self.paragraphs.last_mut().unwrap().1
+    }
+
+    /// The quote as markdown, one blockquote level per level of nesting.
+    fn render(&self) -> String {
+        self.paragraphs
+            .iter()
+            .map(|(level, text)| (level, clean_body_fragment(text)))
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(level, text)| format!("{}{text}", outline_prefix((*level).max(1))))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// A `<footnote>` or `<note type="footnote">` whose text is being read.
+#[derive(Debug, Clone)]
+struct ActiveFootnote {
+    depth: usize,
+    id: Option<String>,
+    text: String,
+}
+
+/// A `<figure>`: the file its `<img>` or `<graphic>` names and the text
+/// read around it, which becomes the caption.
+#[derive(Debug, Clone)]
+struct ActiveFigure {
+    depth: usize,
+    file: Option<String>,
+    caption: String,
 }
 
 #[derive(Debug, Clone)]
@@ -228,18 +442,64 @@ struct OpenRef {
     start: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellAlign {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct TableCell {
+    text: String,
+    align: Option<CellAlign>,
+    span: usize,
+    header: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TableRow {
+    depth: usize,
+    header: bool,
+    cells: Vec<TableCell>,
+}
+
+/// An XHTML `<table>` or USLM `<layout>` being read inside a section. Its
+/// text is kept out of the body and notes until the table closes and is
+/// rendered as one markdown table.
+#[derive(Debug, Clone)]
+struct ActiveTable {
+    depth: usize,
+    target: RefTarget,
+    caption: String,
+    rows: Vec<TableRow>,
+    row: Option<TableRow>,
+    cell: Option<(usize, TableCell)>,
+}
+
 #[derive(Debug, Clone)]
 struct ActiveSection {
     depth: usize,
     capture: NumHeadingCapture,
     identifier: Option<String>,
     parent_ref: USCParentRef,
+    status_attr: Option<String>,
+    /// The `<num>` opens with `[`, as it does for sections no longer in force.
+    bracketed_num: bool,
     body_frames: Vec<BodyFrame>,
     body_parts: Vec<String>,
     free_text: String,
     source_credit: String,
     blocks: Vec<USCSectionBlock>,
     active_notes: Vec<ActiveNote>,
+    table: Option<ActiveTable>,
+    /// Footnote ids in numbering order: first reference, then unreferenced
+    /// footnotes as they are read.
+    footnote_ids: Vec<Option<String>>,
+    footnotes: Vec<(Option<String>, String)>,
+    footnote: Option<ActiveFootnote>,
+    figure: Option<ActiveFigure>,
+    formulas: Vec<FormulaEntry>,
 }
 
 impl ActiveSection {
@@ -265,9 +525,16 @@ enum AttrName {
     Href = 4,
     Type = 5,
     Class = 6,
+    Style = 7,
+    Colspan = 8,
+    Idref = 9,
+    Id = 10,
+    Status = 11,
+    Src = 12,
+    Alt = 13,
 }
 
-const ATTR_COUNT: usize = 7;
+const ATTR_COUNT: usize = 14;
 
 fn classify_attr(name: &[u8]) -> Option<AttrName> {
     match name {
@@ -278,6 +545,13 @@ fn classify_attr(name: &[u8]) -> Option<AttrName> {
         b"href" => Some(AttrName::Href),
         b"type" => Some(AttrName::Type),
         b"class" => Some(AttrName::Class),
+        b"style" => Some(AttrName::Style),
+        b"colspan" => Some(AttrName::Colspan),
+        b"idref" => Some(AttrName::Idref),
+        b"id" => Some(AttrName::Id),
+        b"status" => Some(AttrName::Status),
+        b"src" => Some(AttrName::Src),
+        b"alt" => Some(AttrName::Alt),
         _ => None,
     }
 }
@@ -297,8 +571,9 @@ impl<'a> Attributes<'a> {
 
     fn load(&self) -> &[Option<Cow<'a, [u8]>>; ATTR_COUNT] {
         self.values.get_or_init(|| {
-            let mut values: [Option<Cow<'a, [u8]>>; ATTR_COUNT] =
-                [None, None, None, None, None, None, None];
+            let mut values: [Option<Cow<'a, [u8]>>; ATTR_COUNT] = [
+                None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+            ];
             for attr in self.event.attributes().flatten() {
                 if let Some(name) = classify_attr(attr.key.as_ref()) {
                     values[name as usize] = Some(attr.value);
@@ -340,10 +615,14 @@ struct ParserState {
     // no leading whitespace itself.
     text_had_trailing_ws: bool,
     suppressed_text_depths: Vec<usize>,
+
+    note_taxonomy: NoteTaxonomy,
+    external_links: ExternalLinks,
+    capture_body: bool,
 }
 
 impl ParserState {
-    fn new(title_num: &str) -> Self {
+    fn new(title_num: &str, note_taxonomy: NoteTaxonomy) -> Self {
         Self {
             title_num: title_num.to_string(),
             title_name_main: None,
@@ -358,6 +637,9 @@ impl ParserState {
             section_key_counts: HashMap::new(),
             text_had_trailing_ws: false,
             suppressed_text_depths: Vec::new(),
+            note_taxonomy,
+            external_links: ExternalLinks::default(),
+            capture_body: true,
         }
     }
 
@@ -396,41 +678,216 @@ pub fn parse_usc_xml_stream<F>(xml: &str, title_num: &str, mut emit: F)
 where
     F: FnMut(USCStreamEvent),
 {
-    let mut reader = Reader::from_str(xml);
-    reader.config_mut().trim_text(false);
+    for event in usc_stream_events(xml, title_num) {
+        emit(event);
+    }
+}
+
+/// Pull-based counterpart to [`parse_usc_xml_stream`]. Parsing advances only
+/// as events are requested, so dropping the iterator stops the parse early.
+pub fn usc_stream_events<'a>(xml: &'a str, title_num: &str) -> USCStreamIter<'a> {
+    usc_stream_events_with_taxonomy(xml, title_num, NoteTaxonomy::default())
+}
+
+/// Like [`usc_stream_events`], classifying notes with `note_taxonomy`
+/// instead of the built-in defaults.
+pub fn usc_stream_events_with_taxonomy<'a>(
+    xml: &'a str,
+    title_num: &str,
+    note_taxonomy: NoteTaxonomy,
+) -> USCStreamIter<'a> {
+    USCStreamIter::new(xml.as_bytes(), Some(xml), title_num).with_note_taxonomy(note_taxonomy)
+}
+
+/// Like [`usc_stream_events`], reading the XML from `reader` as parsing
+/// advances instead of from a string held in memory. Parse errors carry a
+/// byte offset but no line and column.
+pub fn usc_stream_events_from_reader<R: BufRead>(
+    reader: R,
+    title_num: &str,
+) -> USCStreamIter<'static, R> {
+    USCStreamIter::new(reader, None, title_num)
+}
+
+/// Streams the first `.xml` entry of a USC title zip through `consume`,
+/// decompressing as the parse advances. `consume` gets the event iterator
+/// and may configure it before reading.
+pub fn with_usc_zip_events<R, T>(
+    archive: R,
+    title_num: &str,
+    consume: impl FnOnce(USCStreamIter<'static, BufReader<ZipFile<'_>>>) -> T,
+) -> Result<T, String>
+where
+    R: Read + Seek,
+{
+    let mut archive =
+        ZipArchive::new(archive).map_err(|e| format!("Failed to open USC ZIP: {e}"))?;
+    let index = archive
+        .file_names()
+        .position(|name| name.to_ascii_lowercase().ends_with(".xml"))
+        .ok_or("No XML entry found in USC ZIP")?;
+    let file = archive
+        .by_index(index)
+        .map_err(|e| format!("Failed to read USC ZIP entry {index}: {e}"))?;
+    Ok(consume(usc_stream_events_from_reader(
+        BufReader::new(file),
+        title_num,
+    )))
+}
+
+pub struct USCStreamIter<'a, R = &'a [u8]> {
+    /// The whole document, when parsing from a string; used to give parse
+    /// errors a line and column.
+    source: Option<&'a str>,
+    reader: Reader<R>,
+    element_path: String,
+    path_lengths: Vec<usize>,
+    state: ParserState,
+    buf: Vec<u8>,
+    pending: VecDeque<USCStreamEvent>,
+    finished: bool,
+    /// The `<math>` element being read, re-serialized, and its nesting depth.
+    math: Option<(String, usize)>,
+}
+
+impl<'a, R: BufRead> USCStreamIter<'a, R> {
+    fn new(reader: R, source: Option<&'a str>, title_num: &str) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(false);
+
+        USCStreamIter {
+            source,
+            reader,
+            element_path: String::new(),
+            path_lengths: Vec::new(),
+            state: ParserState::new(title_num, NoteTaxonomy::default()),
+            buf: Vec::new(),
+            pending: VecDeque::new(),
+            finished: false,
+            math: None,
+        }
+    }
+
+    /// Classifies notes with `note_taxonomy` instead of the built-in
+    /// defaults.
+    pub fn with_note_taxonomy(mut self, note_taxonomy: NoteTaxonomy) -> Self {
+        self.state.note_taxonomy = note_taxonomy;
+        self
+    }
+
+    /// Restricts capture to what `profile` keeps. Under
+    /// [`IngestProfile::NotesOnly`] section body text is skipped as it is
+    /// read, so every emitted section has an empty `body`.
+    pub fn with_profile(mut self, profile: IngestProfile) -> Self {
+        self.state.capture_body = profile.captures_body();
+        self
+    }
 
-    let mut state = ParserState::new(title_num);
-    let mut buf = Vec::new();
+    /// Links CFR, Statutes at Large, and public law refs with `links`
+    /// instead of the built-in templates.
+    pub fn with_external_links(mut self, links: ExternalLinks) -> Self {
+        self.state.external_links = links;
+        self
+    }
+
+    /// Reads one XML event, queueing any stream events it completes.
+    fn advance(&mut self) {
+        let state = &mut self.state;
+        let pending = &mut self.pending;
+        let mut emit = |event| pending.push_back(event);
+
+        let event = self.reader.read_event_into(&mut self.buf);
+        if let Some((mathml, depth)) = &mut self.math {
+            match &event {
+                Ok(Event::Eof) | Err(_) => self.math = None,
+                Ok(Event::End(_)) if *depth == 0 => {
+                    mathml.push_str("</math>");
+                    handle_formula(state, mathml);
+                    self.math = None;
+                    self.buf.clear();
+                    return;
+                }
+                Ok(inner) => {
+                    match inner {
+                        Event::Start(_) => *depth += 1,
+                        Event::End(_) => *depth -= 1,
+                        _ => {}
+                    }
+                    push_raw_event(mathml, inner);
+                    self.buf.clear();
+                    return;
+                }
+            }
+        }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => handle_start(&mut state, &e),
+        match event {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"math" => {
+                let mut mathml = String::new();
+                push_raw_event(&mut mathml, &Event::Start(e));
+                self.math = Some((mathml, 0));
+            }
+            Ok(Event::Start(e)) => {
+                self.path_lengths.push(self.element_path.len());
+                self.element_path.push('/');
+                self.element_path
+                    .push_str(&String::from_utf8_lossy(e.local_name().as_ref()));
+                handle_start(state, &e);
+            }
             Ok(Event::Empty(e)) => {
-                handle_start(&mut state, &e);
-                handle_end(&mut state, e.local_name().as_ref(), &mut emit);
+                handle_start(state, &e);
+                handle_end(state, e.local_name().as_ref(), &mut emit);
             }
             Ok(Event::Text(t)) => {
                 if let Ok(text) = t.unescape() {
-                    handle_text(&mut state, &text, &mut emit);
+                    handle_text(state, &text, &mut emit);
                 }
             }
             Ok(Event::CData(t)) => {
                 let text = String::from_utf8_lossy(t.as_ref());
-                handle_text(&mut state, &text, &mut emit);
+                handle_text(state, &text, &mut emit);
             }
-            Ok(Event::End(e)) => handle_end(&mut state, e.local_name().as_ref(), &mut emit),
-            Ok(Event::Eof) => break,
+            Ok(Event::End(e)) => {
+                handle_end(state, e.local_name().as_ref(), &mut emit);
+                if let Some(length) = self.path_lengths.pop() {
+                    self.element_path.truncate(length);
+                }
+            }
+            Ok(Event::Eof) => self.finish(),
             Err(e) => {
-                emit(USCStreamEvent::Error(format!("XML parsing error: {}", e)));
-                break;
+                let offset = self.reader.error_position() as usize;
+                let message = format!("XML parsing error: {}", e);
+                emit(USCStreamEvent::Error(match self.source {
+                    Some(xml) => ParseError::at(xml, offset, &self.element_path, message),
+                    None => ParseError {
+                        message: format!("{message} at byte {offset}"),
+                        position: None,
+                        element_path: self.element_path.clone(),
+                    },
+                }));
+                self.finish();
             }
             _ => {}
         }
-        buf.clear();
+        self.buf.clear();
     }
 
-    if !state.title_emitted {
-        emit(USCStreamEvent::Title(state.title_name()));
+    fn finish(&mut self) {
+        self.finished = true;
+        if !self.state.title_emitted {
+            self.pending
+                .push_back(USCStreamEvent::Title(self.state.title_name()));
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for USCStreamIter<'_, R> {
+    type Item = USCStreamEvent;
+
+    fn next(&mut self) -> Option<USCStreamEvent> {
+        while self.pending.is_empty() && !self.finished {
+            self.advance();
+        }
+        self.pending.pop_front()
     }
 }
 
@@ -510,17 +967,99 @@ fn handle_start(state: &mut ParserState, e: &BytesStart<'_>) {
             capture: NumHeadingCapture::with_num(normalize_section_num(&section_num)),
             identifier,
             parent_ref,
+            status_attr: attrs.get(AttrName::Status),
+            bracketed_num: false,
             body_frames: Vec::new(),
             body_parts: Vec::new(),
             free_text: String::new(),
             source_credit: String::new(),
             blocks: Vec::new(),
             active_notes: Vec::new(),
+            table: None,
+            footnote_ids: Vec::new(),
+            footnotes: Vec::new(),
+            footnote: None,
+            figure: None,
+            formulas: Vec::new(),
         });
     }
 
+    if current_tag == Some(Tag::Ref)
+        && attrs
+            .get(AttrName::Class)
+            .is_some_and(|value| value.eq_ignore_ascii_case("footnoteRef"))
+    {
+        if let Some(section) = &mut state.active_section {
+            let number = footnote_number(&mut section.footnote_ids, attrs.get(AttrName::Idref));
+            let in_heading = is_section_heading(&state.tag_stack, section.depth)
+                || section
+                    .active_notes
+                    .last()
+                    .is_some_and(|note| is_note_heading(&state.tag_stack, note.depth));
+            if !in_heading {
+                let needs_space = std::mem::take(&mut state.text_had_trailing_ws);
+                push_section_text(state, &format!("[^{number}]"), needs_space);
+            }
+            state.suppressed_text_depths.push(state.tag_stack.len());
+        }
+    }
+
     if let Some(section) = &mut state.active_section {
-        if section.depth < state.tag_stack.len()
+        let is_footnote = current_tag == Some(Tag::Footnote)
+            || (current_tag == Some(Tag::Note)
+                && attrs
+                    .get(AttrName::Type)
+                    .is_some_and(|value| value.eq_ignore_ascii_case("footnote")));
+        if is_footnote && section.footnote.is_none() {
+            section.footnote = Some(ActiveFootnote {
+                depth: state.tag_stack.len(),
+                id: attrs.get(AttrName::Id),
+                text: String::new(),
+            });
+        }
+
+        if current_tag == Some(Tag::Figure) && section.figure.is_none() {
+            section.figure = Some(ActiveFigure {
+                depth: state.tag_stack.len(),
+                file: None,
+                caption: String::new(),
+            });
+        }
+        if matches!(name_ref, b"img" | b"graphic") {
+            let file = attrs
+                .get(AttrName::Src)
+                .or_else(|| attrs.get(AttrName::Href))
+                .map(|src| graphic_file_name(&src));
+            match &mut section.figure {
+                Some(figure) => figure.file = figure.file.take().or(file),
+                None => section.blocks.push(image_block(
+                    file,
+                    attrs.get(AttrName::Alt).unwrap_or_default(),
+                )),
+            }
+        }
+
+        if let Some(table) = &mut section.table {
+            table.open(current_tag, &attrs, state.tag_stack.len(), mask);
+            return;
+        }
+
+        if matches!(current_tag, Some(Tag::Table | Tag::Layout)) {
+            let target = if let Some(note) = section.active_notes.last() {
+                Some(RefTarget::Note { depth: note.depth })
+            } else if state.capture_body && !in_body_excluded_context(mask) {
+                Some(RefTarget::Body)
+            } else {
+                None
+            };
+            if let Some(target) = target {
+                section.table = Some(ActiveTable::new(state.tag_stack.len(), target));
+                return;
+            }
+        }
+
+        if state.capture_body
+            && section.depth < state.tag_stack.len()
             && current_tag.is_some_and(is_body_block_tag)
             && !in_body_excluded_context(mask)
             && !(current_tag == Some(Tag::Heading) && section.depth + 1 == state.tag_stack.len())
@@ -529,12 +1068,12 @@ fn handle_start(state: &mut ParserState, e: &BytesStart<'_>) {
             section.body_frames.push(BodyFrame {
                 depth: state.tag_stack.len(),
                 structural_depth: structural_tag_depth(current_tag.unwrap()),
-                quote_prefix: blockquote_prefix(quote_depth),
+                quote_prefix: outline_prefix(quote_depth),
                 text: String::new(),
             });
         }
 
-        if current_tag.is_some_and(is_inline_separator_tag) {
+        if state.capture_body && current_tag.is_some_and(is_inline_separator_tag) {
             let target = section.target_text_mut();
             if !target.is_empty() && !target.ends_with(' ') && !target.ends_with('\n') {
                 target.push(' ');
@@ -552,22 +1091,27 @@ fn handle_start(state: &mut ParserState, e: &BytesStart<'_>) {
                     role: attrs.get(AttrName::Role),
                     heading: String::new(),
                     text: String::new(),
+                    quote: None,
+                    quoted: Vec::new(),
                 });
             }
         }
 
-        if current_tag == Some(Tag::Ref)
-            && attrs
-                .get(AttrName::Class)
-                .is_some_and(|value| value.eq_ignore_ascii_case("footnoteRef"))
-        {
-            state.suppressed_text_depths.push(state.tag_stack.len());
+        if let Some(note) = section.active_notes.last_mut() {
+            let depth = state.tag_stack.len();
+            match (&mut note.quote, current_tag) {
+                (None, Some(Tag::QuotedContent)) => {
+                    note.quote = Some(NoteQuote::new(depth, note.text.len()));
+                }
+                (Some(quote), Some(tag)) => quote.open(tag, depth),
+                _ => {}
+            }
         }
 
         if current_tag == Some(Tag::Ref) {
             if let Some(link) = attrs
                 .get(AttrName::Href)
-                .and_then(|href| usc_section_link_from_href(&href))
+                .and_then(|href| ref_link_from_href(&href, &state.external_links))
             {
                 if is_source_credit(&state.tag_stack, section.depth) {
                     state.open_refs.push(OpenRef {
@@ -585,7 +1129,7 @@ fn handle_start(state: &mut ParserState, e: &BytesStart<'_>) {
                             start: note.text.len(),
                         });
                     }
-                } else if !in_body_excluded_context(mask) {
+                } else if state.capture_body && !in_body_excluded_context(mask) {
                     let start = section.target_text_mut().len();
                     state.open_refs.push(OpenRef {
                         depth: state.tag_stack.len(),
@@ -597,7 +1141,8 @@ fn handle_start(state: &mut ParserState, e: &BytesStart<'_>) {
             }
         }
 
-        if current_tag.is_some_and(is_body_decorated_tag)
+        if state.capture_body
+            && current_tag.is_some_and(is_body_decorated_tag)
             && !in_body_excluded_context(mask)
             && !(current_tag == Some(Tag::Num) && is_section_num(&state.tag_stack, section.depth))
             && !(current_tag == Some(Tag::Heading) && section.depth + 1 == state.tag_stack.len())
@@ -637,7 +1182,8 @@ where
         return;
     }
 
-    let text = normalize_text(raw_text);
+    let raw_text = strip_invisible_chars(raw_text);
+    let text = normalize_text(&raw_text);
     if text.is_empty() {
         return;
     }
@@ -651,7 +1197,6 @@ where
         .chars()
         .last()
         .is_some_and(|c| c.is_ascii_whitespace());
-    let mask = state.current_mask();
 
     if !state.title_emitted && is_main_title_heading(&state.tag_stack) {
         state.title_name_main = Some(text.to_string());
@@ -673,33 +1218,117 @@ where
         }
     }
 
+    push_section_text(state, &text, needs_space);
+}
+
+/// Appends `event` to `out` as markup, the way it was written.
+fn push_raw_event(out: &mut String, event: &Event<'_>) {
+    match event {
+        Event::Start(e) => {
+            out.push('<');
+            out.push_str(&String::from_utf8_lossy(e));
+            out.push('>');
+        }
+        Event::Empty(e) => {
+            out.push('<');
+            out.push_str(&String::from_utf8_lossy(e));
+            out.push_str("/>");
+        }
+        Event::End(e) => {
+            out.push_str("</");
+            out.push_str(&String::from_utf8_lossy(e));
+            out.push('>');
+        }
+        Event::Text(e) => out.push_str(&String::from_utf8_lossy(e)),
+        Event::CData(e) => {
+            out.push_str("<![CDATA[");
+            out.push_str(&String::from_utf8_lossy(e));
+            out.push_str("]]>");
+        }
+        _ => {}
+    }
+}
+
+/// Adds a `<math>` element read inside a section body as a `formula` block
+/// and writes its plain-text rendering into the surrounding text.
+fn handle_formula(state: &mut ParserState, mathml: &str) {
+    let text = mathml_to_text(mathml);
+    let mask = state.current_mask();
+    if let Some(section) = &mut state.active_section {
+        let in_body = section.footnote.is_none()
+            && section.table.is_none()
+            && section.active_notes.is_empty()
+            && state.capture_body
+            && !in_body_excluded_context(mask);
+        if in_body && !text.is_empty() {
+            section.formulas.push(FormulaEntry {
+                block: section.blocks.len(),
+                mathml: mathml.to_string(),
+            });
+            section.blocks.push(USCSectionBlock {
+                type_: FORMULA_BLOCK_TYPE.to_string(),
+                label: None,
+                content: Some(text.clone()),
+            });
+        }
+    }
+    push_section_text(state, &text, true);
+    state.text_had_trailing_ws = true;
+}
+
+/// Appends text read inside the active section to whatever it belongs to:
+/// a footnote, table cell, heading, source credit, note, or the body.
+fn push_section_text(state: &mut ParserState, text: &str, needs_space: bool) {
+    let mask = state.current_mask();
     if let Some(section) = &mut state.active_section {
+        if let Some(footnote) = &mut section.footnote {
+            let in_num = state.tag_stack.get(footnote.depth) == Some(&Tag::Num);
+            if !in_num {
+                append_text(&mut footnote.text, text, needs_space);
+            }
+            return;
+        }
+
+        if let Some(figure) = &mut section.figure {
+            append_text(&mut figure.caption, text, needs_space);
+            return;
+        }
+
+        if let Some(table) = &mut section.table {
+            table.push_text(text, needs_space);
+            return;
+        }
+
         if is_section_num(&state.tag_stack, section.depth) {
+            section.bracketed_num |= text.trim_start().starts_with('[');
             return;
         }
 
         if is_section_heading(&state.tag_stack, section.depth) {
-            append_text(&mut section.capture.heading, &text, needs_space);
+            append_text(&mut section.capture.heading, text, needs_space);
             return;
         }
 
         if is_source_credit(&state.tag_stack, section.depth) {
-            append_text(&mut section.source_credit, &text, needs_space);
+            append_text(&mut section.source_credit, text, needs_space);
             return;
         }
 
         if let Some(note) = section.active_notes.last_mut() {
             if is_note_heading(&state.tag_stack, note.depth) {
-                append_text(&mut note.heading, &text, needs_space);
+                append_text(&mut note.heading, text, needs_space);
             } else {
-                append_text(&mut note.text, &text, needs_space);
+                append_text(&mut note.text, text, needs_space);
+                if let Some(quote) = &mut note.quote {
+                    quote.push_text(text, needs_space);
+                }
             }
             return;
         }
 
-        if !in_body_excluded_context(mask) {
+        if state.capture_body && !in_body_excluded_context(mask) {
             let target = section.target_text_mut();
-            append_text(target, &text, needs_space);
+            append_text(target, text, needs_space);
         }
     }
 }
@@ -711,6 +1340,37 @@ where
     let current_tag = classify(local_name);
     let mask = state.current_mask();
 
+    if let Some(section) = &mut state.active_section {
+        let closes_footnote = matches!(current_tag, Some(Tag::Note | Tag::Footnote))
+            && section
+                .footnote
+                .as_ref()
+                .is_some_and(|footnote| footnote.depth == state.tag_stack.len());
+        if closes_footnote {
+            let footnote = section.footnote.take().unwrap();
+            section
+                .footnotes
+                .push((footnote.id, clean_body_fragment(&footnote.text)));
+        }
+
+        let closes_figure = current_tag == Some(Tag::Figure)
+            && section
+                .figure
+                .as_ref()
+                .is_some_and(|figure| figure.depth == state.tag_stack.len());
+        if closes_figure {
+            let figure = section.figure.take().unwrap();
+            section
+                .blocks
+                .push(image_block(figure.file, figure.caption));
+        }
+    }
+
+    if close_table_tag(state, current_tag) {
+        pop_tag(state, current_tag);
+        return;
+    }
+
     if let Some(section) = &mut state.active_section {
         if current_tag == Some(Tag::Ref) {
             if state
@@ -728,7 +1388,8 @@ where
             }
         }
 
-        if current_tag.is_some_and(is_body_decorated_tag)
+        if state.capture_body
+            && current_tag.is_some_and(is_body_decorated_tag)
             && !in_body_excluded_context(mask)
             && !(current_tag == Some(Tag::Num) && is_section_num(&state.tag_stack, section.depth))
             && !(current_tag == Some(Tag::Heading) && section.depth + 1 == state.tag_stack.len())
@@ -736,17 +1397,35 @@ where
             section.target_text_mut().push_str("**");
         }
 
+        if let Some(note) = section.active_notes.last_mut() {
+            let depth = state.tag_stack.len();
+            let closes_quote = current_tag == Some(Tag::QuotedContent)
+                && note
+                    .quote
+                    .as_ref()
+                    .is_some_and(|quote| quote.depth == depth);
+            if closes_quote {
+                let quote = note.quote.take().unwrap();
+                if quote.structural {
+                    note.text.truncate(quote.note_start);
+                    note.quoted.push(quote.render());
+                }
+            } else if let (Some(quote), Some(_)) = (&mut note.quote, current_tag) {
+                quote.close(depth);
+            }
+        }
+
         if current_tag == Some(Tag::Note) {
             if let Some(note) = section.active_notes.last() {
                 if note.depth == state.tag_stack.len() {
                     let note = section.active_notes.pop().unwrap();
-                    let is_cross_heading = note
-                        .role
-                        .as_deref()
-                        .is_some_and(|role| role.eq_ignore_ascii_case("crossHeading"));
-
                     let heading = normalize_heading(&note.heading);
-                    if is_cross_heading {
+                    let block_type = state
+                        .note_taxonomy
+                        .classify(note.topic.as_deref(), note.role.as_deref(), &heading)
+                        .to_string();
+
+                    if block_type == "heading" {
                         if !heading.is_empty() {
                             section.blocks.push(USCSectionBlock {
                                 type_: "heading".to_string(),
@@ -756,52 +1435,40 @@ where
                         }
                     } else {
                         let note_text = clean_body_fragment(&note.text);
-                        let is_amendments = note
-                            .topic
-                            .as_deref()
-                            .map(|topic| topic.eq_ignore_ascii_case("amendments"))
-                            .unwrap_or(false)
-                            || heading.to_ascii_lowercase().contains("amendments");
-
-                        if !note_text.is_empty() || !heading.is_empty() {
-                            if is_amendments {
-                                let label = if heading.is_empty() {
-                                    "Amendments".to_string()
-                                } else {
-                                    heading.clone()
-                                };
-                                section.blocks.push(USCSectionBlock {
-                                    type_: "amendments".to_string(),
-                                    label: Some(label),
-                                    content: if note_text.trim().is_empty() {
-                                        None
-                                    } else {
-                                        Some(note_text)
-                                    },
-                                });
-                            } else {
-                                let label = if heading.is_empty() {
+                        let has_heading = !heading.is_empty();
+                        let label = if has_heading {
+                            Some(heading)
+                        } else {
+                            state
+                                .note_taxonomy
+                                .default_label(&block_type)
+                                .map(ToString::to_string)
+                        };
+
+                        if !note_text.is_empty() || has_heading {
+                            section.blocks.push(USCSectionBlock {
+                                type_: block_type,
+                                label: label.clone(),
+                                content: if note_text.trim().is_empty() {
                                     None
                                 } else {
-                                    Some(heading)
-                                };
-                                section.blocks.push(USCSectionBlock {
-                                    type_: "note".to_string(),
-                                    label,
-                                    content: if note_text.trim().is_empty() {
-                                        None
-                                    } else {
-                                        Some(note_text)
-                                    },
-                                });
-                            }
+                                    Some(note_text)
+                                },
+                            });
+                        }
+                        for quoted in note.quoted {
+                            section.blocks.push(USCSectionBlock {
+                                type_: "quoted".to_string(),
+                                label: label.clone(),
+                                content: Some(quoted),
+                            });
                         }
                     }
                 }
             }
         }
 
-        if current_tag == Some(Tag::P) {
+        if current_tag == Some(Tag::P) && section.footnote.is_none() {
             if let Some(note) = section.active_notes.last_mut() {
                 if !note.text.trim().is_empty() && !note.text.ends_with("\n\n") {
                     note.text.push_str("\n\n");
@@ -867,8 +1534,17 @@ where
                 } else {
                     section.capture.num.clone()
                 };
+                let (base_num, range_start, range_end) = match split_section_range(&base_num) {
+                    Some((start, end)) => (format!("{start}_to_{end}"), Some(start), Some(end)),
+                    None => (base_num, None, None),
+                };
 
-                let base_path = format!("/section/{}/{}", state.title_num, base_num);
+                let base_path = USC_PATHS
+                    .render(
+                        "section",
+                        &[("title", &state.title_num), ("num", &base_num)],
+                    )
+                    .expect("USC section path template takes title and num");
                 let path = uniquify(&mut state.section_path_counts, &base_path);
 
                 let base_key = format!("{}:{}", state.title_num, base_num);
@@ -880,16 +1556,32 @@ where
                     body_parts.push(trailing);
                 }
                 let body = body_parts.join("\n\n");
+                let mut blocks = section.blocks;
+                blocks.extend(footnote_blocks(section.footnote_ids, section.footnotes));
+                let heading = normalize_heading(&section.capture.heading);
+                let status = section_status(
+                    section.status_attr.as_deref(),
+                    section.bracketed_num,
+                    &heading,
+                );
+                let transferred_to = (status == USCSectionStatus::Transferred)
+                    .then(|| transfer_target(&state.title_num, &heading, &blocks))
+                    .flatten();
 
                 emit(USCStreamEvent::Section(USCSection {
                     title_num: state.title_num.clone(),
                     section_num: base_num,
                     section_key,
-                    heading: normalize_heading(&section.capture.heading),
+                    heading,
                     body,
-                    blocks: section.blocks,
+                    blocks,
                     path,
                     parent_ref: section.parent_ref,
+                    range_start,
+                    range_end,
+                    status,
+                    transferred_to,
+                    formulas: section.formulas,
                 }));
             }
         }
@@ -928,7 +1620,12 @@ where
                     .as_deref()
                     .and_then(|f| f.strip_prefix(&format!("title-{}/", state.title_num)))
                     .unwrap_or(&identifier);
-                let path = format!("/{}/{}", state.title_num, path_suffix);
+                let path = USC_PATHS
+                    .render(
+                        level.level_type,
+                        &[("title", &state.title_num), ("levels", path_suffix)],
+                    )
+                    .expect("USC level path template takes title and levels");
 
                 let usc_level = USCLevel {
                     title_num: state.title_num.clone(),
@@ -945,7 +1642,62 @@ where
         }
     }
 
-    if let Some(tag) = current_tag {
+    pop_tag(state, current_tag);
+}
+
+/// Number of the footnote `id` in a section, assigning the next number to
+/// ids not seen before. Footnotes without an id always get a new number.
+fn footnote_number(ids: &mut Vec<Option<String>>, id: Option<String>) -> usize {
+    let known = id
+        .as_ref()
+        .and_then(|id| ids.iter().position(|known| known.as_ref() == Some(id)));
+    if let Some(index) = known {
+        return index + 1;
+    }
+    ids.push(id);
+    ids.len()
+}
+
+/// The last path segment of a graphic's `src` or `href`.
+fn graphic_file_name(src: &str) -> String {
+    src.rsplit('/').next().unwrap_or(src).to_string()
+}
+
+/// An `image` block labeled with the graphic's file name, its caption as
+/// content.
+fn image_block(file: Option<String>, caption: String) -> USCSectionBlock {
+    let caption = normalize_text(&caption).trim().to_string();
+    USCSectionBlock {
+        type_: "image".to_string(),
+        label: file,
+        content: (!caption.is_empty()).then_some(caption),
+    }
+}
+
+/// `footnote` blocks in footnote number order, labeled with the number the
+/// body's `[^n]` markers use.
+fn footnote_blocks(
+    mut ids: Vec<Option<String>>,
+    footnotes: Vec<(Option<String>, String)>,
+) -> Vec<USCSectionBlock> {
+    let mut numbered = footnotes
+        .into_iter()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(id, text)| (footnote_number(&mut ids, id), text))
+        .collect::<Vec<_>>();
+    numbered.sort_by_key(|(number, _)| *number);
+    numbered
+        .into_iter()
+        .map(|(number, text)| USCSectionBlock {
+            type_: "footnote".to_string(),
+            label: Some(format!("Footnote {number}")),
+            content: Some(text),
+        })
+        .collect()
+}
+
+fn pop_tag(state: &mut ParserState, tag: Option<Tag>) {
+    if let Some(tag) = tag {
         if state.tag_stack.last().copied() == Some(tag) {
             state.tag_stack.pop();
             state.mask_stack.pop();
@@ -953,6 +1705,284 @@ where
     }
 }
 
+/// Handles the end of an element inside an active table, rendering the
+/// table once its own element closes. Returns false outside tables.
+fn close_table_tag(state: &mut ParserState, tag: Option<Tag>) -> bool {
+    let depth = state.tag_stack.len();
+    let Some(section) = &mut state.active_section else {
+        return false;
+    };
+    let Some(table) = &mut section.table else {
+        return false;
+    };
+
+    if tag == Some(Tag::Ref)
+        && state
+            .suppressed_text_depths
+            .last()
+            .is_some_and(|suppressed| *suppressed == depth)
+    {
+        state.suppressed_text_depths.pop();
+    }
+    if !(table.depth == depth && matches!(tag, Some(Tag::Table | Tag::Layout))) {
+        table.close(tag, depth);
+        return true;
+    }
+
+    let table = section.table.take().unwrap();
+    let target = table.target.clone();
+    let markdown = table.render();
+    if markdown.is_empty() {
+        return true;
+    }
+    match target {
+        RefTarget::Note { depth } => {
+            if let Some(note) = section
+                .active_notes
+                .iter_mut()
+                .rev()
+                .find(|note| note.depth == depth)
+            {
+                push_paragraph(&mut note.text, &markdown);
+            }
+        }
+        RefTarget::Body => match section.body_frames.last_mut() {
+            Some(frame) => {
+                let quoted = markdown
+                    .lines()
+                    .map(|line| format!("{}{line}", frame.quote_prefix))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                push_paragraph(&mut frame.text, &quoted);
+            }
+            None => {
+                let pending = normalize_body_fragment(&std::mem::take(&mut section.free_text));
+                if !pending.is_empty() {
+                    section.body_parts.push(pending);
+                }
+                section.body_parts.push(markdown);
+            }
+        },
+        RefTarget::SourceCredit => {}
+    }
+    true
+}
+
+fn push_paragraph(target: &mut String, paragraph: &str) {
+    if !target.trim().is_empty() && !target.ends_with("\n\n") {
+        target.push_str("\n\n");
+    }
+    target.push_str(paragraph);
+    target.push_str("\n\n");
+}
+
+impl ActiveTable {
+    fn new(depth: usize, target: RefTarget) -> Self {
+        Self {
+            depth,
+            target,
+            caption: String::new(),
+            rows: Vec::new(),
+            row: None,
+            cell: None,
+        }
+    }
+
+    fn open(&mut self, tag: Option<Tag>, attrs: &Attributes<'_>, depth: usize, mask: u64) {
+        match tag {
+            Some(Tag::Tr | Tag::Row | Tag::TocItem | Tag::LayoutHeader) if self.row.is_none() => {
+                self.row = Some(TableRow {
+                    depth,
+                    header: tag == Some(Tag::LayoutHeader) || mask & bit(Tag::Thead) != 0,
+                    cells: Vec::new(),
+                });
+            }
+            Some(Tag::Th | Tag::Td | Tag::Column) if self.cell.is_none() => {
+                let span = attrs
+                    .get(AttrName::Colspan)
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .max(1);
+                self.cell = Some((
+                    depth,
+                    TableCell {
+                        text: String::new(),
+                        align: attrs.get(AttrName::Style).as_deref().and_then(text_align),
+                        span,
+                        header: tag == Some(Tag::Th),
+                    },
+                ));
+            }
+            Some(Tag::P) => {
+                if let Some((_, cell)) = &mut self.cell {
+                    if !cell.text.is_empty() && !cell.text.ends_with(' ') {
+                        cell.text.push(' ');
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push_text(&mut self, text: &str, needs_space: bool) {
+        match &mut self.cell {
+            Some((_, cell)) => append_text(&mut cell.text, text, needs_space),
+            None => append_text(&mut self.caption, text, needs_space),
+        }
+    }
+
+    fn close(&mut self, tag: Option<Tag>, depth: usize) {
+        let closes_cell = matches!(tag, Some(Tag::Th | Tag::Td | Tag::Column));
+        let closes_row = matches!(
+            tag,
+            Some(Tag::Tr | Tag::Row | Tag::TocItem | Tag::LayoutHeader)
+        );
+        if closes_cell
+            && self
+                .cell
+                .as_ref()
+                .is_some_and(|(cell_depth, _)| *cell_depth == depth)
+        {
+            let (_, cell) = self.cell.take().unwrap();
+            self.row
+                .get_or_insert_with(|| TableRow {
+                    depth: self.depth,
+                    header: false,
+                    cells: Vec::new(),
+                })
+                .cells
+                .push(cell);
+        } else if closes_row && self.row.as_ref().is_some_and(|row| row.depth == depth) {
+            self.finish_row();
+        }
+    }
+
+    fn finish_row(&mut self) {
+        if let Some(mut row) = self.row.take() {
+            row.header |= !row.cells.is_empty() && row.cells.iter().all(|cell| cell.header);
+            self.rows.push(row);
+        }
+    }
+
+    /// Renders the table as a GitHub-style markdown table. Leading header
+    /// rows become the header; a spanning title row above the column
+    /// headings becomes a bold caption. Tables without header rows get an
+    /// empty one, since markdown tables require it.
+    fn render(mut self) -> String {
+        self.finish_row();
+        let mut rows = self
+            .rows
+            .into_iter()
+            .filter(|row| row.cells.iter().any(|cell| !cell.text.trim().is_empty()))
+            .collect::<Vec<_>>();
+        let mut captions = vec![self.caption.trim().to_string()];
+        let header_count = rows.iter().take_while(|row| row.header).count();
+        let mut body = rows.split_off(header_count);
+        let header = rows.pop();
+        let mut leading = Vec::new();
+        for row in rows {
+            match row.cells.as_slice() {
+                [cell] => captions.push(cell.text.trim().to_string()),
+                _ => leading.push(row),
+            }
+        }
+        leading.append(&mut body);
+        let body = leading;
+
+        let caption = captions
+            .into_iter()
+            .filter(|caption| !caption.is_empty())
+            .map(|caption| format!("**{caption}**"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let width = header
+            .iter()
+            .chain(&body)
+            .map(|row| row.cells.iter().map(|cell| cell.span).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return caption;
+        }
+
+        let aligns = (0..width)
+            .map(|column| {
+                body.iter()
+                    .chain(&header)
+                    .find_map(|row| cell_at(row, column).and_then(|cell| cell.align))
+            })
+            .collect::<Vec<_>>();
+        let mut lines = vec![
+            header
+                .as_ref()
+                .map(|row| markdown_row(row, width))
+                .unwrap_or_else(|| markdown_row_cells(vec![String::new(); width])),
+            markdown_row_cells(
+                aligns
+                    .iter()
+                    .map(|align| {
+                        match align {
+                            Some(CellAlign::Left) => ":---",
+                            Some(CellAlign::Center) => ":---:",
+                            Some(CellAlign::Right) => "---:",
+                            None => "---",
+                        }
+                        .to_string()
+                    })
+                    .collect(),
+            ),
+        ];
+        lines.extend(body.iter().map(|row| markdown_row(row, width)));
+
+        let table = lines.join("\n");
+        if caption.is_empty() {
+            table
+        } else {
+            format!("{caption}\n\n{table}")
+        }
+    }
+}
+
+/// The cell covering `column`, counting column spans.
+fn cell_at(row: &TableRow, column: usize) -> Option<&TableCell> {
+    let mut start = 0;
+    for cell in &row.cells {
+        if column < start + cell.span {
+            return (column == start).then_some(cell);
+        }
+        start += cell.span;
+    }
+    None
+}
+
+fn markdown_row(row: &TableRow, width: usize) -> String {
+    let mut cells = Vec::with_capacity(width);
+    for cell in &row.cells {
+        cells.push(cell.text.trim().replace('|', "\\|"));
+        cells.extend(std::iter::repeat_n(String::new(), cell.span - 1));
+    }
+    cells.resize(width, String::new());
+    markdown_row_cells(cells)
+}
+
+fn markdown_row_cells(cells: Vec<String>) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+fn text_align(style: &str) -> Option<CellAlign> {
+    let (_, rest) = style.split_once("text-align")?;
+    let value = rest.trim_start().strip_prefix(':')?.trim_start();
+    let value = value
+        .split(|c: char| c == ';' || c.is_whitespace())
+        .next()?;
+    match value.to_ascii_lowercase().as_str() {
+        "left" | "justify" => Some(CellAlign::Left),
+        "center" => Some(CellAlign::Center),
+        "right" => Some(CellAlign::Right),
+        _ => None,
+    }
+}
+
 const LEVEL_TAG_MASK: u64 = bit(Tag::Subtitle)
     | bit(Tag::Part)
     | bit(Tag::Subpart)
@@ -971,7 +2001,7 @@ const BODY_BLOCK_TAG_MASK: u64 = bit(Tag::Subsection)
     | bit(Tag::Continuation)
     | bit(Tag::P);
 const BODY_DECORATED_TAG_MASK: u64 = bit(Tag::Num) | bit(Tag::Heading);
-const LEVEL_ANCESTOR_TAG_MASK: u64 = bit(Tag::Title) | LEVEL_TAG_MASK;
+const LEVEL_ANCESTOR_TAG_MASK: u64 = bit(Tag::Title) | bit(Tag::Appendix) | LEVEL_TAG_MASK;
 const INLINE_SEPARATOR_TAG_MASK: u64 =
     bit(Tag::Content) | bit(Tag::Chapeau) | bit(Tag::Continuation);
 
@@ -997,6 +2027,7 @@ fn is_inline_separator_tag(tag: Tag) -> bool {
 
 fn is_main_title_heading(stack: &[Tag]) -> bool {
     stack.ends_with(&[Tag::Main, Tag::Title, Tag::Heading])
+        || stack.ends_with(&[Tag::Main, Tag::Appendix, Tag::Heading])
 }
 
 fn is_meta_title(stack: &[Tag]) -> bool {
@@ -1053,7 +2084,8 @@ fn in_note_or_quoted(mask: u64) -> bool {
 
 #[inline(always)]
 fn in_body_excluded_context(mask: u64) -> bool {
-    mask & (bit(Tag::Note) | bit(Tag::SourceCredit) | bit(Tag::QuotedContent)) != 0
+    mask & (bit(Tag::Note) | bit(Tag::Footnote) | bit(Tag::SourceCredit) | bit(Tag::QuotedContent))
+        != 0
 }
 
 fn normalize_text(raw: &str) -> Cow<'_, str> {
@@ -1122,6 +2154,9 @@ fn normalize_body_fragment(text: &str) -> String {
                 .expect("outline marker should have prefix");
             let open_paren_start = full.start() + prefix.as_str().len();
 
+            if in_table_or_bold(&cleaned, open_paren_start) {
+                return full.as_str().to_string();
+            }
             if LEVEL_NAME_PREFIX_RE.is_match(&cleaned[..open_paren_start]) {
                 return full.as_str().to_string();
             }
@@ -1137,6 +2172,15 @@ fn normalize_body_fragment(text: &str) -> String {
         .to_string()
 }
 
+/// Whether byte `offset` of `text` sits on a markdown table row or inside
+/// bold text opened earlier on its line, where bolding a marker would break
+/// the markup.
+fn in_table_or_bold(text: &str, offset: usize) -> bool {
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    strip_leading_blockquote_prefix(&text[line_start..]).starts_with('|')
+        || has_unclosed_bold(&text[line_start..offset])
+}
+
 fn is_standalone_bold_marker(text: &str) -> bool {
     let mut trimmed = text.trim();
     while let Some(rest) = trimmed.strip_prefix('>') {
@@ -1184,14 +2228,6 @@ fn body_blockquote_depth(tag: Tag, frames: &[BodyFrame]) -> usize {
     nearest_structural_depth
 }
 
-fn blockquote_prefix(depth: usize) -> String {
-    if depth == 0 {
-        String::new()
-    } else {
-        "> ".repeat(depth)
-    }
-}
-
 fn normalize_heading(heading: &str) -> String {
     let mut out = clean_body_fragment(heading);
     if out.ends_with(']') {
@@ -1204,6 +2240,46 @@ fn normalize_heading(heading: &str) -> String {
     out
 }
 
+/// Splits a range designation, `202...204` in identifiers or `202 to 204`
+/// in num values, into its first and last section.
+fn split_section_range(num: &str) -> Option<(String, String)> {
+    let (start, end) = num.split_once("...").or_else(|| num.split_once(" to "))?;
+    let (start, end) = (start.trim(), end.trim());
+    (!start.is_empty() && !end.is_empty()).then(|| (start.to_string(), end.to_string()))
+}
+
+fn section_status(attr: Option<&str>, bracketed_num: bool, heading: &str) -> USCSectionStatus {
+    if let Some(status) = attr.and_then(USCSectionStatus::from_word) {
+        return status;
+    }
+    if let Some(status) = STATUS_HEADING_RE
+        .captures(heading)
+        .and_then(|caps| USCSectionStatus::from_word(&caps[1]))
+    {
+        return status;
+    }
+    if bracketed_num {
+        USCSectionStatus::Omitted
+    } else {
+        USCSectionStatus::Active
+    }
+}
+
+/// Finds the new home of a transferred section in its heading or notes
+/// ("was editorially reclassified as section 10301 of Title 52").
+fn transfer_target(title_num: &str, heading: &str, blocks: &[USCSectionBlock]) -> Option<String> {
+    std::iter::once(heading)
+        .chain(blocks.iter().filter_map(|block| block.content.as_deref()))
+        .find_map(|text| {
+            let text = strip_markdown_links(text);
+            let caps = TRANSFER_TARGET_RE.captures(&text)?;
+            let title = caps
+                .get(2)
+                .map_or(title_num.to_string(), |m| m.as_str().to_lowercase());
+            Some(format!("{title}:{}", normalize_section_num(&caps[1])))
+        })
+}
+
 fn normalize_section_num(value: &str) -> String {
     UNICODE_DASH_RE.replace_all(value.trim(), "-").into_owned()
 }
@@ -1231,7 +2307,10 @@ fn parent_from_native_id(native_id: &str) -> String {
 
 fn is_title_path(path: &str) -> bool {
     let segment = path.rsplit('/').next().unwrap_or(path);
-    segment.starts_with('t') && segment[1..].chars().all(|c| c.is_ascii_digit())
+    segment.strip_prefix('t').is_some_and(|num| {
+        let num = appendix_owner(num).unwrap_or(num);
+        !num.is_empty() && num.chars().all(|c| c.is_ascii_digit())
+    })
 }
 
 fn level_type_to_prefix(level_type: &str) -> &str {
@@ -1255,6 +2334,43 @@ fn section_num_from_identifier(identifier: &str) -> Option<String> {
         .map(ToString::to_string)
 }
 
+/// Link target for a `<ref>` href: an internal section path for `/us/usc/`
+/// refs, an external URL for CFR, Statutes at Large, and public law refs.
+fn ref_link_from_href(href: &str, links: &ExternalLinks) -> Option<String> {
+    if let Some(cfr) = href.strip_prefix("/us/cfr/") {
+        let mut parts = cfr.split('/');
+        let title = parts.next()?.strip_prefix('t').filter(|t| !t.is_empty())?;
+        let rest = parts.next()?;
+        if let Some(part) = rest.strip_prefix("pt").filter(|p| !p.is_empty()) {
+            return ExternalLinks::render(&links.cfr_part, &[("title", title), ("part", part)]);
+        }
+        let section = rest.strip_prefix('s').filter(|s| !s.is_empty())?;
+        return ExternalLinks::render(
+            &links.cfr_section,
+            &[("title", title), ("section", section)],
+        );
+    }
+    if let Some(stat) = href.strip_prefix("/us/stat/") {
+        let (volume, page) = stat.split_once('/')?;
+        // Page ranges (`5233-5236`) link to their first page.
+        let page = page.split(['-', '/']).next().filter(|p| !p.is_empty())?;
+        return ExternalLinks::render(
+            &links.statutes_at_large,
+            &[("volume", volume), ("page", page)],
+        );
+    }
+    if let Some(pl) = href.strip_prefix("/us/pl/") {
+        let mut parts = pl.split('/');
+        let congress = parts.next().filter(|c| !c.is_empty())?;
+        let number = parts.next().filter(|n| !n.is_empty())?;
+        return ExternalLinks::render(
+            &links.public_law,
+            &[("congress", congress), ("number", number)],
+        );
+    }
+    usc_section_link_from_href(href)
+}
+
 fn usc_section_link_from_href(href: &str) -> Option<String> {
     let native = strip_usc_prefix(href)?;
     let mut title_num: Option<&str> = None;
@@ -1380,7 +2496,7 @@ fn uniquify(counts: &mut HashMap<String, usize>, base: &str) -> String {
 pub fn usc_level_index(level_type: &str) -> Option<usize> {
     match level_type {
         "title" => Some(0),
-        "subtitle" => Some(1),
+        "appendix" | "subtitle" => Some(1),
         "division" => Some(2),
         "subdivision" => Some(3),
         "chapter" => Some(4),
@@ -1391,6 +2507,14 @@ pub fn usc_level_index(level_type: &str) -> Option<usize> {
     }
 }
 
+/// The title an appendix document belongs to: `5a` (Title 5 Appendix) is
+/// owned by `5`. `None` for ordinary titles.
+pub fn appendix_owner(title_num: &str) -> Option<&str> {
+    title_num
+        .strip_suffix(['a', 'A'])
+        .filter(|owner| !owner.is_empty() && owner.chars().all(|c| c.is_ascii_digit()))
+}
+
 pub fn section_level_index() -> usize {
     8
 }
@@ -1400,9 +2524,10 @@ pub fn title_sort_key(title_num: &str) -> f64 {
         return v;
     }
 
-    let mut chars = title_num.chars();
-    let numeric_part: String = chars.by_ref().take_while(|c| c.is_ascii_digit()).collect();
-    let suffix: String = chars.collect();
+    let split = title_num
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(title_num.len());
+    let (numeric_part, suffix) = title_num.split_at(split);
 
     if numeric_part.is_empty() {
         return f64::INFINITY;
@@ -0,0 +1,59 @@
+use crate::sources::common::{find_long_dates, strip_markdown_links};
+use crate::sources::usc::effective_dates::PUBLIC_LAW_RE;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub use crate::types::SourceCreditEntry;
+
+static CHAPTER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bch\.\s*(\d+[A-Za-z]?)").unwrap());
+
+/// One `§` designation, or a `§§` list of them.
+static SECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"§§\s*[0-9][^,;\s]*(?:,\s*[0-9][^,;\s]*)*(?:,?\s+(?:and|to)\s+[0-9][^,;\s]*)?|§\s*[0-9][^,;\s]*").unwrap()
+});
+
+static STAT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d+)\s+Stat\.\s*(\d+(?:,\s*\d+)*)").unwrap());
+
+/// Splits a section's source credit, e.g. `(Pub. L. 117–169, title I,
+/// § 13, Aug. 16, 2022, 136 Stat. 1818; ...)`, into one entry per enacting
+/// or amending law. Entries naming no law, date, or Statutes at Large page
+/// are dropped. Markdown links in `text` are read as their text.
+pub fn parse_source_credit(text: &str) -> Vec<SourceCreditEntry> {
+    let text = strip_markdown_links(text);
+    let text = text.trim();
+    let text = text.strip_prefix('(').unwrap_or(text);
+    let text = text.strip_suffix('.').unwrap_or(text);
+    let text = text.strip_suffix(')').unwrap_or(text);
+
+    text.split(';')
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let public_law = PUBLIC_LAW_RE
+                .captures(entry)
+                .map(|caps| format!("{}-{}", &caps[1], &caps[2]));
+            let chapter = CHAPTER_RE.captures(entry).map(|caps| caps[1].to_string());
+            let section = SECTION_RE
+                .find(entry)
+                .map(|m| m.as_str().trim_end_matches('.').to_string());
+            let date = find_long_dates(entry)
+                .into_iter()
+                .next()
+                .map(|(_, date)| date);
+            let statutes_at_large = STAT_RE
+                .captures(entry)
+                .map(|caps| format!("{} Stat. {}", &caps[1], &caps[2]));
+            if public_law.is_none() && date.is_none() && statutes_at_large.is_none() {
+                return None;
+            }
+            Some(SourceCreditEntry {
+                public_law,
+                chapter,
+                section,
+                date,
+                statutes_at_large,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// How a title's `<toc>` elements compare with what the parser extracted.
+/// Identifiers are USLM identifiers without the `/us/usc/` prefix, e.g.
+/// `t3/ch1` or `t3/s15`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TocValidation {
+    pub title_num: String,
+    pub declared: usize,
+    pub extracted: usize,
+    /// Listed in a TOC but never extracted, in TOC order.
+    pub missing: Vec<String>,
+    /// Extracted but listed in no TOC, in document order. Only kinds some
+    /// TOC links to are checked: subchapters and parts appear in TOCs as
+    /// unlinked headers.
+    pub undeclared: Vec<String>,
+}
+
+impl TocValidation {
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty() && self.undeclared.is_empty()
+    }
+}
+
+/// The identifiers a title's `<toc>` entries link to, in document order and
+/// without repeats. Only links into the title itself count; footnotes inside
+/// entries are skipped.
+pub fn declared_toc_entries(xml: &str, title_num: &str) -> Vec<String> {
+    let prefix = format!("/us/usc/t{title_num}/");
+    let mut reader = Reader::from_str(xml);
+    let mut toc_depth = 0usize;
+    let mut note_depth = 0usize;
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"toc" => toc_depth += 1,
+                b"note" if toc_depth > 0 => note_depth += 1,
+                b"ref" if toc_depth > 0 && note_depth == 0 => {
+                    let href = e
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.local_name().as_ref() == b"href")
+                        .and_then(|attr| attr.unescape_value().ok());
+                    if let Some(href) = href.filter(|href| href.starts_with(&prefix)) {
+                        let id = href["/us/usc/".len()..].to_string();
+                        if seen.insert(id.clone()) {
+                            entries.push(id);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"toc" => toc_depth = toc_depth.saturating_sub(1),
+                b"note" if toc_depth > 0 => note_depth = note_depth.saturating_sub(1),
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Compares the `declared` TOC identifiers with the `extracted` ones.
+pub fn validate_toc(title_num: &str, declared: &[String], extracted: &[String]) -> TocValidation {
+    let declared_set = declared.iter().collect::<HashSet<_>>();
+    let declared_kinds = declared.iter().map(|id| kind(id)).collect::<HashSet<_>>();
+    let extracted_set = extracted.iter().collect::<HashSet<_>>();
+    TocValidation {
+        title_num: title_num.to_string(),
+        declared: declared.len(),
+        extracted: extracted.len(),
+        missing: declared
+            .iter()
+            .filter(|id| !extracted_set.contains(id))
+            .cloned()
+            .collect(),
+        undeclared: extracted
+            .iter()
+            .filter(|id| !declared_set.contains(id) && declared_kinds.contains(kind(id)))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// The letters opening an identifier's last segment: `ch` for `t3/ch5`,
+/// `s` for `t3/s15`.
+fn kind(id: &str) -> &str {
+    let segment = id.rsplit('/').next().unwrap_or(id);
+    let end = segment
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(segment.len());
+    &segment[..end]
+}
@@ -1,7 +1,9 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::uspl::discover::{discover_uspl_root, VolumeMetadata};
+use crate::sources::paths::USPL_PATHS;
+use crate::sources::uspl::discover::{discover_uspl_root, discover_uspl_version, VolumeMetadata};
 use crate::sources::uspl::markdown::law_to_markdown;
-use crate::sources::uspl::parser::parse_uslm_volume;
+use crate::sources::uspl::parser::parse_uslm_volume_until;
 use crate::sources::SourceAdapter;
 use crate::types::{ContentBlock, DiscoveryResult, NodeMeta, NodePayload, SectionContent};
 use async_trait::async_trait;
@@ -25,6 +27,17 @@ impl SourceAdapter for UsplAdapter {
         discover_uspl_root(cache, url, api_key).await
     }
 
+    /// Reads only the collections pages, skipping the per-package summaries.
+    async fn discover_version(
+        &self,
+        cache: &dyn Cache,
+        url: &str,
+        manual_start_url: Option<&str>,
+    ) -> Result<String, String> {
+        let api_key = manual_start_url.unwrap_or_default();
+        discover_uspl_version(cache, url, api_key).await
+    }
+
     async fn process_url(
         &self,
         context: &mut IngestContext<'_>,
@@ -43,6 +56,11 @@ impl SourceAdapter for UsplAdapter {
             .unwrap_or("?");
         format!("Volume {}", pkg)
     }
+
+    // v2: invisible characters dropped, per-level path templates.
+    fn parser_version(&self) -> u32 {
+        2
+    }
 }
 
 async fn process_volume(context: &mut IngestContext<'_>, item: &QueueItem) -> Result<(), String> {
@@ -81,7 +99,7 @@ async fn process_volume(context: &mut IngestContext<'_>, item: &QueueItem) -> Re
                     meta.congress,
                     congress_years(meta.congress)
                 )),
-                path: Some(format!("/{}", meta.congress)),
+                path: Some(USPL_PATHS.render("congress", &[("num", &meta.congress.to_string())])?),
                 readable_id: Some(format!("{}th Congress", meta.congress)),
                 heading_citation: Some(format!("{}th Congress", meta.congress)),
                 source_url: Some(format!(
@@ -89,6 +107,7 @@ async fn process_volume(context: &mut IngestContext<'_>, item: &QueueItem) -> Re
                     meta.congress
                 )),
                 accessed_at: Some(accessed_at.clone()),
+                review_required: false,
             },
             content: None,
         })
@@ -98,11 +117,16 @@ async fn process_volume(context: &mut IngestContext<'_>, item: &QueueItem) -> Re
     let mut law_sort_order: i32 = 0;
     let mut errors: Vec<String> = Vec::new();
 
-    // Collect laws first (parse_uslm_volume is synchronous)
-    let mut laws = Vec::new();
-    parse_uslm_volume(&xml, |law| {
-        laws.push(law);
-    });
+    // Collect laws on the blocking pool, stopping the parse once cancelled
+    let (laws, parse_result) = run_blocking(&context.cancel, move |cancel| {
+        let mut laws = Vec::new();
+        let result = parse_uslm_volume_until(&xml, || cancel.is_cancelled(), |law| laws.push(law));
+        (laws, result)
+    })
+    .await?;
+    if let Err(e) = parse_result {
+        errors.push(e.to_string());
+    }
 
     for law in laws {
         if law.congress == 0 || law.public_law_number.starts_with("0-") {
@@ -170,11 +194,15 @@ async fn process_volume(context: &mut IngestContext<'_>, item: &QueueItem) -> Re
                         "Public Law {} — {}",
                         law.public_law_number, law.official_title
                     )),
-                    path: Some(format!("/{}/{}", law.congress, law_num)),
+                    path: Some(USPL_PATHS.render(
+                        "law",
+                        &[("congress", &law.congress.to_string()), ("num", &law_num)],
+                    )?),
                     readable_id: Some(readable_id),
                     heading_citation: Some(heading_citation),
                     source_url: Some(source_url),
                     accessed_at: Some(accessed_at.clone()),
+                    review_required: false,
                 },
                 content: Some(serde_json::to_value(&content).unwrap()),
             })
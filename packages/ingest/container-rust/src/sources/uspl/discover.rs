@@ -52,12 +52,13 @@ fn with_api_key(url: &str, api_key: &str) -> String {
     }
 }
 
-/// Fetches all STATUTE packages from the govinfo collections API for congress >= MIN_CONGRESS.
-pub async fn discover_uspl_root(
+/// Pages through the govinfo collections API for STATUTE packages of
+/// congress >= MIN_CONGRESS.
+async fn fetch_statute_packages(
     cache: &dyn Cache,
     collections_url: &str,
     api_key: &str,
-) -> Result<DiscoveryResult, String> {
+) -> Result<Vec<PackageSummary>, String> {
     let mut all_packages: Vec<PackageSummary> = Vec::new();
 
     // Paginate through the collections API
@@ -99,14 +100,37 @@ pub async fn discover_uspl_root(
     if all_packages.is_empty() {
         return Err("No STATUTE packages found for congress >= 106".to_string());
     }
+    Ok(all_packages)
+}
 
-    // Compute version_id from the latest lastModified across all packages
-    let latest_modified = all_packages
+/// Version id from the latest lastModified across all packages.
+fn version_id_of(packages: &[PackageSummary]) -> String {
+    let latest_modified = packages
         .iter()
         .map(|p| p.last_modified.as_str())
         .max()
         .unwrap_or("1999-01-01T00:00:00Z");
-    let version_id = &latest_modified[..10]; // "YYYY-MM-DD"
+    latest_modified[..10].to_string() // "YYYY-MM-DD"
+}
+
+/// The version discovery would report, from the collections pages alone.
+pub async fn discover_uspl_version(
+    cache: &dyn Cache,
+    collections_url: &str,
+    api_key: &str,
+) -> Result<String, String> {
+    let packages = fetch_statute_packages(cache, collections_url, api_key).await?;
+    Ok(version_id_of(&packages))
+}
+
+/// Fetches all STATUTE packages from the govinfo collections API for congress >= MIN_CONGRESS.
+pub async fn discover_uspl_root(
+    cache: &dyn Cache,
+    collections_url: &str,
+    api_key: &str,
+) -> Result<DiscoveryResult, String> {
+    let all_packages = fetch_statute_packages(cache, collections_url, api_key).await?;
+    let version_id = version_id_of(&all_packages);
 
     // Fetch package details for each (volume number, USLM URL)
     // Group by congress for ordering, then sort by volume within congress
@@ -196,10 +220,11 @@ pub async fn discover_uspl_root(
         heading_citation: Some("U.S. Public Laws".to_string()),
         source_url: Some(collections_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
-        version_id: version_id.to_string(),
+        version_id,
         root_node,
         unit_roots,
     })
@@ -1,3 +1,4 @@
+use crate::sources::common::{flatten_outline_depth, MAX_OUTLINE_DEPTH};
 use crate::sources::uspl::parser::{Block, Inline, ParsedLaw};
 
 /// Render a `ParsedLaw` to a single markdown string.
@@ -14,7 +15,7 @@ pub fn law_to_markdown(law: &ParsedLaw) -> String {
         out.push_str(&format!("\n*Approved {}.*\n", law.approved_date));
     }
 
-    out.trim().to_string()
+    flatten_outline_depth(out.trim(), MAX_OUTLINE_DEPTH)
 }
 
 fn render_block(block: &Block, out: &mut String) {
@@ -1,3 +1,4 @@
+use crate::sources::common::{strip_invisible_chars, ParseError};
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
@@ -38,10 +39,26 @@ pub struct ParsedLaw {
 }
 
 /// Parse USLM XML for a single Statutes at Large volume.
-/// Calls `on_law` for each public law found.
-pub fn parse_uslm_volume<F>(xml: &str, mut on_law: F)
+/// Calls `on_law` for each public law found. Malformed XML stops the parse
+/// and returns the error; laws completed before it have already been emitted.
+pub fn parse_uslm_volume<F>(xml: &str, on_law: F) -> Result<(), ParseError>
 where
     F: FnMut(ParsedLaw),
+{
+    parse_uslm_volume_until(xml, || false, on_law)
+}
+
+/// Like [`parse_uslm_volume`], but stops early and returns `Ok(())` once
+/// `cancelled` returns true. It is checked before every XML event, so a
+/// cancelled run stops within one event rather than after the volume.
+pub fn parse_uslm_volume_until<C, F>(
+    xml: &str,
+    cancelled: C,
+    mut on_law: F,
+) -> Result<(), ParseError>
+where
+    C: Fn() -> bool,
+    F: FnMut(ParsedLaw),
 {
     let mut reader = Reader::from_str(xml);
     reader.config_mut().trim_text(false);
@@ -52,6 +69,8 @@ where
     let mut in_plaw = false;
     let mut skip_depth: Option<usize> = None; // skip subtrees (legislativeHistory, preface)
     let mut depth: usize = 0;
+    let mut element_path = String::new();
+    let mut path_lengths: Vec<usize> = Vec::new();
 
     // Per-law metadata
     let mut public_private = String::new();
@@ -78,12 +97,18 @@ where
     let mut ref_text_buf = String::new();
 
     loop {
+        if cancelled() {
+            break;
+        }
         match reader.read_event_into(&mut buf) {
             Ok(Event::Eof) => break,
             Ok(Event::Start(e)) => {
                 let name = e.local_name();
                 let tag = name.as_ref();
                 depth += 1;
+                path_lengths.push(element_path.len());
+                element_path.push('/');
+                element_path.push_str(&String::from_utf8_lossy(tag));
 
                 // Check for skip zone
                 if let Some(d) = skip_depth {
@@ -168,7 +193,7 @@ where
                     buf.clear();
                     continue;
                 }
-                let text = e.unescape().unwrap_or_default().into_owned();
+                let text = strip_invisible_chars(&e.unescape().unwrap_or_default()).into_owned();
 
                 if in_plaw && in_meta {
                     meta_buf.push_str(&text);
@@ -183,6 +208,9 @@ where
             Ok(Event::End(e)) => {
                 let name = e.local_name();
                 let tag = name.as_ref();
+                if let Some(length) = path_lengths.pop() {
+                    element_path.truncate(length);
+                }
 
                 // Check skip zone exit
                 if let Some(d) = skip_depth {
@@ -269,12 +297,18 @@ where
             }
             Ok(_) => {}
             Err(e) => {
-                eprintln!("USLM parse error: {e}");
-                break;
+                return Err(ParseError::at(
+                    xml,
+                    reader.error_position() as usize,
+                    &element_path,
+                    format!("USLM parse error: {e}"),
+                ));
             }
         }
         buf.clear();
     }
+
+    Ok(())
 }
 
 // ──────────────────────────────────────────────────────────────────────────────
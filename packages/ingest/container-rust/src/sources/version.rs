@@ -0,0 +1,153 @@
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::LazyLock;
+
+static RELEASE_POINT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{2,3})-(\d+)(.*)$").unwrap());
+static SEMANTIC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:[vV](\d+(?:\.\d+)*)|(\d+(?:\.\d+)+))$").unwrap());
+
+/// A source's version id as discovery reports it, parsed so versions can be
+/// ordered instead of compared as opaque strings.
+///
+/// Versions of different kinds do not compare, except a year against a date
+/// in another year; `partial_cmp` returns `None` for those pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceVersion {
+    /// USC release point `{congress}-{law}`, e.g. `118-200`. A trailing
+    /// exclusion such as `not118-158` marks a point that leaves out a law
+    /// enacted before it, so it sorts just before the plain point.
+    ReleasePoint {
+        congress: u32,
+        law: u32,
+        exclusion: Option<String>,
+    },
+    /// `YYYY-MM-DD`, as MGL and USPL report.
+    Date(NaiveDate),
+    /// `YYYY`, for state codes dated only by session or revision year.
+    Year(i32),
+    /// Numeric label such as `v2` or `2.1`, compared part by part.
+    Semantic(Vec<u32>),
+    /// Anything else, such as `undated-{hash}`; only equal to itself.
+    Opaque(String),
+}
+
+impl SourceVersion {
+    pub fn parse(version_id: &str) -> Self {
+        let version_id = version_id.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(version_id, "%Y-%m-%d") {
+            return Self::Date(date);
+        }
+        if version_id.len() == 4 {
+            if let Ok(year) = version_id.parse() {
+                return Self::Year(year);
+            }
+        }
+        if let Some(captures) = RELEASE_POINT_RE.captures(version_id) {
+            if let (Ok(congress), Ok(law)) = (captures[1].parse(), captures[2].parse()) {
+                return Self::ReleasePoint {
+                    congress,
+                    law,
+                    exclusion: Some(captures[3].to_string()).filter(|rest| !rest.is_empty()),
+                };
+            }
+        }
+        if let Some(captures) = SEMANTIC_RE.captures(version_id) {
+            let label = captures.get(1).or(captures.get(2)).unwrap().as_str();
+            if let Ok(parts) = label.split('.').map(str::parse).collect() {
+                return Self::Semantic(parts);
+            }
+        }
+        Self::Opaque(version_id.to_string())
+    }
+
+    /// Parses a full source version id (`{source_id}-{version_id}`), as the
+    /// orchestrator builds it from discovery.
+    pub fn from_source_version_id(source_id: &str, source_version_id: &str) -> Self {
+        let version_id = source_version_id
+            .strip_prefix(source_id)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .unwrap_or(source_version_id);
+        Self::parse(version_id)
+    }
+
+    /// Whether `self` is strictly newer than `other`; `None` when the two
+    /// cannot be ordered.
+    pub fn is_newer_than(&self, other: &Self) -> Option<bool> {
+        self.partial_cmp(other)
+            .map(|ordering| ordering == Ordering::Greater)
+    }
+}
+
+impl PartialOrd for SourceVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (
+                Self::ReleasePoint {
+                    congress,
+                    law,
+                    exclusion,
+                },
+                Self::ReleasePoint {
+                    congress: other_congress,
+                    law: other_law,
+                    exclusion: other_exclusion,
+                },
+            ) => {
+                let point = (congress, law).cmp(&(other_congress, other_law));
+                let exclusion = match (exclusion, other_exclusion) {
+                    (None, None) => Ordering::Equal,
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(a), Some(b)) if a == b => Ordering::Equal,
+                    (Some(_), Some(_)) => return (point != Ordering::Equal).then_some(point),
+                };
+                Some(point.then(exclusion))
+            }
+            (Self::Date(a), Self::Date(b)) => Some(a.cmp(b)),
+            (Self::Year(a), Self::Year(b)) => Some(a.cmp(b)),
+            (Self::Date(date), Self::Year(year)) => {
+                let ordering = date.year().cmp(year);
+                (ordering != Ordering::Equal).then_some(ordering)
+            }
+            (Self::Year(_), Self::Date(_)) => other.partial_cmp(self).map(Ordering::reverse),
+            (Self::Semantic(a), Self::Semantic(b)) => Some(a.cmp(b)),
+            (Self::Opaque(a), Self::Opaque(b)) if a == b => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SourceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReleasePoint {
+                congress,
+                law,
+                exclusion,
+            } => write!(
+                f,
+                "{congress}-{law}{}",
+                exclusion.as_deref().unwrap_or_default()
+            ),
+            Self::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            Self::Year(year) => write!(f, "{year}"),
+            Self::Semantic(parts) => {
+                let parts = parts.iter().map(u32::to_string).collect::<Vec<_>>();
+                write!(f, "v{}", parts.join("."))
+            }
+            Self::Opaque(label) => f.write_str(label),
+        }
+    }
+}
+
+/// Publish-gate check: why `candidate` must not replace the `published`
+/// version, or `None` when it may. Only a version that is definitely older
+/// is refused; re-ingesting the same version, or versions that cannot be
+/// ordered, pass.
+pub fn version_regression(published: &SourceVersion, candidate: &SourceVersion) -> Option<String> {
+    (published.partial_cmp(candidate) == Some(Ordering::Greater))
+        .then(|| format!("version {candidate} is older than the published version {published}"))
+}
@@ -1,5 +1,7 @@
+use crate::runtime::blocking::run_blocking;
 use crate::runtime::types::{Cache, IngestContext, QueueItem};
-use crate::sources::common::{body_block, push_block};
+use crate::sources::common::{body_blocks, insert_structural_levels, push_block, StructuralParent};
+use crate::sources::paths::VT_PATHS;
 use crate::sources::vt::discover::title_display_num_from_code;
 use crate::sources::vt::parser::{
     inline_section_cross_references, normalize_designator, parse_fullchapter_detail,
@@ -42,7 +44,11 @@ impl SourceAdapter for VtAdapter {
                     .cache
                     .fetch_cached(&item.url, &cache_key, None)
                     .await?;
-                let title = parse_title_index(&html, &item.url)?;
+                let page_url = item.url.clone();
+                let title = run_blocking(&context.cancel, move |_| {
+                    parse_title_index(&html, &page_url)
+                })
+                .await??;
                 let title_num_for_chapters = title.title_num.clone();
                 let title_display_num_for_chapters = title.title_display_num.clone();
                 let title_slug = normalize_designator(&title.title_num);
@@ -59,11 +65,12 @@ impl SourceAdapter for VtAdapter {
                             level_index: 0,
                             sort_order: context.build.unit_sort_order,
                             name: Some(title.title_name.clone()),
-                            path: Some(format!("/title/{title_slug}")),
+                            path: Some(VT_PATHS.render("title", &[("num", &title_slug)])?),
                             readable_id: Some(title.title_num.clone()),
                             heading_citation: Some(format!("Title {}", title.title_display_num)),
                             source_url: Some(item.url.clone()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
@@ -114,8 +121,12 @@ impl SourceAdapter for VtAdapter {
                     .cache
                     .fetch_cached(&fullchapter_url, &cache_key, None)
                     .await?;
-                let parsed =
-                    parse_fullchapter_detail(&html, &title_display_num, &chapter_display_num)?;
+                let (page_title_num, page_chapter_num) =
+                    (title_display_num.clone(), chapter_display_num.clone());
+                let parsed = run_blocking(&context.cancel, move |_| {
+                    parse_fullchapter_detail(&html, &page_title_num, &page_chapter_num)
+                })
+                .await??;
                 let chapter_name_hint = item.metadata["chapter_name_hint"]
                     .as_str()
                     .map(ToString::to_string)
@@ -132,6 +143,8 @@ impl SourceAdapter for VtAdapter {
                 let title_slug = normalize_designator(title_num);
                 let chapter_slug = normalize_designator(chapter_num);
                 let chapter_id = format!("{}/chapter-{chapter_slug}", item.parent_id);
+                let chapter_path = VT_PATHS
+                    .render("chapter", &[("title", &title_slug), ("num", &chapter_slug)])?;
 
                 context
                     .nodes
@@ -144,28 +157,43 @@ impl SourceAdapter for VtAdapter {
                             level_index: 1,
                             sort_order,
                             name: Some(chapter_name),
-                            path: Some(format!("/title/{title_slug}/chapter/{chapter_slug}")),
+                            path: Some(chapter_path.clone()),
                             readable_id: Some(chapter_display_num.clone()),
                             heading_citation: Some(format!("Chapter {chapter_display_num}")),
                             source_url: Some(item.url.clone()),
                             accessed_at: Some(context.build.accessed_at.to_string()),
+                            review_required: false,
                         },
                         content: None,
                     })
                     .await?;
 
+                let mut seen_levels: Vec<String> = Vec::new();
                 for (index, section) in parsed.sections.into_iter().enumerate() {
+                    let parent = insert_structural_levels(
+                        context,
+                        StructuralParent::chapter(&chapter_id, &chapter_path),
+                        &section.levels,
+                        &VT_PATHS,
+                        &item.url,
+                        &mut seen_levels,
+                    )
+                    .await?;
                     let section_num = section.section_num.clone();
                     let section_slug = normalize_designator(&section.section_num);
+                    let section_path = VT_PATHS.render(
+                        "section",
+                        &[("chapter_path", &chapter_path), ("num", &section_slug)],
+                    )?;
                     let section_url = format!(
                         "https://legislature.vermont.gov/statutes/section/{}/{}/{}",
                         title_num.to_ascii_lowercase(),
                         chapter_num.to_ascii_lowercase(),
                         section.section_num.to_ascii_lowercase()
                     );
-                    let body =
-                        inline_section_cross_references(&section.body, title_num, chapter_num);
-                    let mut blocks = vec![body_block(&body)];
+                    let mut blocks = body_blocks(context.build.profile, || {
+                        inline_section_cross_references(&section.body, title_num, chapter_num)
+                    });
                     push_block(
                         &mut blocks,
                         "note",
@@ -184,22 +212,21 @@ impl SourceAdapter for VtAdapter {
                         .nodes
                         .insert_node(NodePayload {
                             meta: NodeMeta {
-                                id: format!("{chapter_id}/section-{section_slug}"),
+                                id: parent.section_id(&section_slug),
                                 source_version_id: context.build.source_version_id.to_string(),
-                                parent_id: Some(chapter_id.clone()),
+                                parent_id: Some(parent.id),
                                 level_name: "section".to_string(),
-                                level_index: 2,
+                                level_index: parent.level_index + 1,
                                 sort_order: index as i32,
                                 name: Some(section.section_name),
-                                path: Some(format!(
-                                    "/title/{title_slug}/chapter/{chapter_slug}/section/{section_slug}"
-                                )),
+                                path: Some(section_path),
                                 readable_id: Some(section_num.clone()),
                                 heading_citation: Some(format!(
                                     "Vt. Stat. tit. {title_display_num} § {section_num}"
                                 )),
                                 source_url: Some(section_url),
                                 accessed_at: Some(context.build.accessed_at.to_string()),
+                                review_required: false,
                             },
                             content: Some(serde_json::to_value(&content).unwrap()),
                         })
@@ -230,9 +257,31 @@ impl SourceAdapter for VtAdapter {
         }
     }
 
+    // v2: subchapter levels, hidden markup and invisible characters dropped.
+    fn parser_version(&self) -> u32 {
+        2
+    }
+
     fn needs_zip_extraction(&self) -> bool {
         false
     }
+
+    fn link_target(&self, node: &NodeMeta) -> Option<String> {
+        // `/title/{t}/chapter/{c}/section/{s}` is linked as
+        // `/statutes/section/{t}/{c}/{s}`.
+        if node.level_name != "section" {
+            return None;
+        }
+        let path = node.path.as_deref()?;
+        let rest = path.strip_prefix("/title/")?;
+        let (title, rest) = rest.split_once("/chapter/")?;
+        let (chapter, section) = rest.split_once("/section/")?;
+        Some(format!("/statutes/section/{title}/{chapter}/{section}"))
+    }
+
+    fn is_internal_link(&self, href: &str) -> bool {
+        href.starts_with("/statutes/section/")
+    }
 }
 
 fn derive_fullchapter_url(chapter_url: &str) -> Result<String, String> {
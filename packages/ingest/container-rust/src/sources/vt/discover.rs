@@ -47,6 +47,7 @@ pub async fn discover_vt_root(
         heading_citation: Some("VT Statutes".to_string()),
         source_url: Some(start_url.to_string()),
         accessed_at: Some(chrono::Utc::now().to_rfc3339()),
+        review_required: false,
     };
 
     Ok(DiscoveryResult {
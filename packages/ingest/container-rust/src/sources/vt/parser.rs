@@ -1,3 +1,7 @@
+use crate::sources::common::{
+    parse_structural_heading, strip_hidden_html, strip_invisible_chars, StructuralLevel,
+    StructuralLevelStack,
+};
 use regex::Regex;
 use std::cmp::Ordering;
 use std::sync::LazyLock;
@@ -61,6 +65,8 @@ pub struct VtSectionDetail {
     pub section_name: String,
     pub body: String,
     pub history: Option<String>,
+    /// Enclosing subchapters, outermost first; empty for flat chapters.
+    pub levels: Vec<StructuralLevel>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -130,7 +136,8 @@ pub fn extract_version_id_from_landing_html(html: &str) -> Option<String> {
 }
 
 pub fn parse_title_links(html: &str, base_url: &str) -> Result<Vec<VtTitleLink>, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let mut links: Vec<VtTitleLink> = Vec::new();
 
@@ -173,7 +180,8 @@ pub fn parse_title_links(html: &str, base_url: &str) -> Result<Vec<VtTitleLink>,
 }
 
 pub fn parse_title_index(html: &str, base_url: &str) -> Result<VtTitleIndex, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let base = reqwest::Url::parse(base_url).map_err(|e| format!("Invalid base URL: {e}"))?;
     let title_num = TITLE_PATH_RE
@@ -248,7 +256,9 @@ pub fn parse_fullchapter_detail(
     fallback_title_display_num: &str,
     fallback_chapter_display_num: &str,
 ) -> Result<VtFullChapterDetail, String> {
-    let dom = parse_dom(html)?;
+    let html = strip_invisible_chars(html);
+    let html = strip_hidden_html(&html);
+    let dom = parse_dom(&html)?;
     let parser = dom.parser();
     let mut title_display_num = fallback_title_display_num.to_string();
     let mut title_name = String::new();
@@ -261,6 +271,8 @@ pub fn parse_fullchapter_detail(
     let mut current_section_heading = String::new();
     let mut current_body: Vec<String> = Vec::new();
     let mut current_history: Vec<String> = Vec::new();
+    let mut current_levels: Vec<StructuralLevel> = Vec::new();
+    let mut level_stack = StructuralLevelStack::default();
 
     for node in dom.nodes().iter() {
         let Some(tag) = node.as_tag() else {
@@ -293,6 +305,23 @@ pub fn parse_fullchapter_detail(
             continue;
         }
 
+        if tag_name == "strong" || tag_name == "h4" {
+            if let Some(level) = parse_structural_heading(&plain_text) {
+                if !current_section_num.is_empty() {
+                    sections.push(finalize_section(
+                        &current_section_num,
+                        &current_section_name,
+                        &current_body,
+                        &current_history,
+                        &current_levels,
+                    ));
+                    current_section_num.clear();
+                }
+                level_stack.open(level);
+            }
+            continue;
+        }
+
         if tag_name != "p" && tag_name != "b" {
             continue;
         }
@@ -312,8 +341,10 @@ pub fn parse_fullchapter_detail(
                     &current_section_name,
                     &current_body,
                     &current_history,
+                    &current_levels,
                 ));
             }
+            current_levels = level_stack.current();
             current_section_num = candidate_num;
             current_section_name = candidate_name;
             current_section_heading = plain_text.clone();
@@ -343,6 +374,7 @@ pub fn parse_fullchapter_detail(
             &current_section_name,
             &current_body,
             &current_history,
+            &current_levels,
         ));
     }
 
@@ -442,6 +474,7 @@ fn finalize_section(
     section_name: &str,
     body_parts: &[String],
     history_parts: &[String],
+    levels: &[StructuralLevel],
 ) -> VtSectionDetail {
     let body = body_parts.join("\n\n").trim().to_string();
     let fallback_body = if body.is_empty()
@@ -467,6 +500,7 @@ fn finalize_section(
         },
         body: fallback_body,
         history,
+        levels: levels.to_vec(),
     }
 }
 
@@ -12,7 +12,22 @@ pub enum SourceKind {
     Uspl,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl SourceKind {
+    /// The snake_case name used in sources.json and cache keys.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceKind::Usc => "usc",
+            SourceKind::Cgs => "cgs",
+            SourceKind::Mgl => "mgl",
+            SourceKind::Nh => "nh",
+            SourceKind::Rigl => "rigl",
+            SourceKind::Vt => "vt",
+            SourceKind::Uspl => "uspl",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NodeMeta {
     pub id: String,
     pub source_version_id: String,
@@ -26,6 +41,9 @@ pub struct NodeMeta {
     pub heading_citation: Option<String>,
     pub source_url: Option<String>,
     pub accessed_at: Option<String>,
+    /// Set when a validator, linter, or anomaly check wants a human to look at this node.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub review_required: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +80,115 @@ pub struct IngestConfig {
     pub callback_token: String,
     pub source_version_id: Option<String>,
     pub root_node_id: Option<String>,
+    /// Upper bound in seconds on processing one unit, every item it queues
+    /// included; defaults to 15 minutes when unset.
+    #[serde(default)]
+    pub unit_timeout_secs: Option<u64>,
+    /// How many units run at once; defaults to 8 when unset and is capped at
+    /// 16. Every running unit keeps its source document and unsent nodes in
+    /// memory, so peak memory grows with this; raise it only when the
+    /// container has headroom for that many of the source's largest units.
+    #[serde(default)]
+    pub unit_concurrency: Option<usize>,
+    /// Publish the staged source version once the run passes its gates. Set to
+    /// `false` to leave it staged for manual review.
+    #[serde(default = "default_publish")]
+    pub publish: bool,
+    /// Link targets from the prior source version that count as resolved
+    /// when checking inlined links. Without them, links are only checked on
+    /// runs covering every unit, whose targets are all emitted by the run.
+    #[serde(default)]
+    pub known_link_targets: Option<Vec<String>>,
+    /// Where node batches (and, for gRPC, logs) are delivered; defaults to
+    /// the HTTP callbacks.
+    #[serde(default)]
+    pub transport: OutputTransport,
+    /// Which section content to capture; defaults to everything.
+    #[serde(default)]
+    pub profile: IngestProfile,
+    /// Refetch pages that now 404 from the Wayback Machine; off when unset.
+    #[serde(default)]
+    pub wayback: Option<WaybackFallback>,
+    /// Tag sections with legal topics using the built-in keyword classifier;
+    /// off when unset.
+    #[serde(default)]
+    pub tag_topics: bool,
+    /// Source version currently published. A run whose version is older is
+    /// left staged instead of replacing it.
+    #[serde(default)]
+    pub published_source_version_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaybackFallback {
+    /// Version whose provenance manifests list the URLs allowed to fall back;
+    /// a 404 for any other URL is still an error.
+    pub prior_source_version_id: String,
+    /// `YYYYMMDDhhmmss` prefix to take the snapshot closest to, instead of
+    /// the most recent one.
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// Body of the container's `/package` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageRequest {
+    pub callback_base: String,
+    pub callback_token: String,
+    /// Source version whose node export, written while it was ingested, is
+    /// packaged.
+    pub source_version_id: String,
+    /// SPDX identifier of the dataset license, e.g. `CC0-1.0`.
+    pub license_id: String,
+    pub license_text: String,
+}
+
+/// Query of the container's `/freshness` endpoint; the callback API is where
+/// the last ingested versions are read from. Its token is sent as an
+/// `Authorization: Bearer` header, never in the query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreshnessQuery {
+    pub callback_base: String,
+}
+
+fn default_publish() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IngestProfile {
+    #[default]
+    Full,
+    /// Structural nodes plus each section's heading, notes, amendments and
+    /// source credit. Body text is never captured, so sections are smaller
+    /// and parsers skip the work of assembling it.
+    NotesOnly,
+}
+
+impl IngestProfile {
+    pub fn captures_body(self) -> bool {
+        self == Self::Full
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum OutputTransport {
+    /// JSON batches posted to `/api/callback/insertNodeBatch`, with level
+    /// stats posted to `/api/callback/setLevelStats`.
+    #[default]
+    Http,
+    /// Streamed batches acknowledged one by one over the `IngestSink` gRPC
+    /// service in `proto/ingest_sink.proto`. Requires the `grpc` feature.
+    Grpc { endpoint: String },
+    /// Batches published to a NATS JetStream `subject` with the HTTP callback
+    /// body as payload; logs still use the HTTP callbacks. Requires the
+    /// `nats` feature.
+    Nats { url: String, subject: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,7 +216,161 @@ pub struct SectionContent {
     pub metadata: Option<SectionMetadata>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SectionMetadata {
-    pub cross_references: Vec<crate::sources::usc::cross_references::SectionCrossReference>,
+    #[serde(default)]
+    pub cross_references: Vec<SectionCrossReference>,
+    /// Structured form of the section's "see also" annotations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub see_also: Vec<SeeAlsoReference>,
+    /// URL-friendly form of the heading, unique among the node's siblings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Legal-topic tags assigned during ingest, when topic tagging is on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    /// Set when the page this node was parsed from came from a web archive
+    /// because the source no longer serves it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_from: Option<ArchivedSource>,
+    /// Spans of the original text that have an editorial correction block.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub corrections: Vec<CorrectionSpan>,
+    /// What each effective-date note block says, in block order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effective_dates: Vec<EffectiveDateNote>,
+    /// Terms the node's text defines, in the order they are defined.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<DefinedTerm>,
+    /// The laws the source credit cites, in the order it cites them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_credits: Vec<SourceCreditEntry>,
+    /// One entry per `amendment` block, newest year first as published.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub amendments: Vec<AmendmentEntry>,
+    /// MathML of each `formula` block, in block order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formulas: Vec<FormulaEntry>,
+}
+
+/// A USC-style section citation found in a section body, with its byte span.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SectionCrossReference {
+    pub section: String,
+    #[serde(rename = "titleNum")]
+    pub title_num: Option<String>,
+    pub offset: usize,
+    pub length: usize,
+    pub link: Option<String>,
+}
+
+/// One target of a CGS "See Sec. ... re ..." annotation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SeeAlsoReference {
+    pub section: String,
+    pub link: String,
+    /// What the annotation says the target covers, e.g. "certification of
+    /// doulas"; empty when the annotation gives no subject.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+/// A term a section defines and the text defining it, starting at the
+/// defining verb ("means ...", "includes ...").
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefinedTerm {
+    pub term: String,
+    pub definition: String,
+}
+
+/// The year and subdivision of one `amendment` block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmendmentEntry {
+    /// Index of the amendment's content block.
+    pub block: usize,
+    pub year: u32,
+    /// The part of the section amended, e.g. `Subsec. (d)(1)`; absent when
+    /// the amendment applies to the section as a whole.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdivision: Option<String>,
+}
+
+/// The MathML source of one `formula` block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormulaEntry {
+    /// Index of the formula's content block.
+    pub block: usize,
+    pub mathml: String,
+}
+
+/// One law cited by a source credit. Laws before 1957 are cited by date
+/// and chapter rather than Public Law number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCreditEntry {
+    /// Public Law number, e.g. `117-169`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_law: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chapter: Option<String>,
+    /// Section designation within the law, e.g. `§ 13` or `§§ 102(b), 104(b)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Enactment date, ISO 8601.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Statutes at Large citation, e.g. `136 Stat. 1818`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statutes_at_large: Option<String>,
+}
+
+/// Dates and Public Laws named by an effective-date note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectiveDateNote {
+    /// Index of the note's content block.
+    pub block: usize,
+    /// ISO 8601 dates, e.g. `2022-10-01`.
+    pub dates: Vec<String>,
+    /// Public Law numbers, e.g. `117-169`.
+    #[serde(rename = "publicLaws")]
+    pub public_laws: Vec<String>,
+}
+
+/// Where an editorial correction applies in a node's original text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorrectionSpan {
+    /// Index of the content block holding the span.
+    pub block: usize,
+    /// Byte offset and length of the span within that block's content.
+    pub offset: usize,
+    pub length: usize,
+    pub replacement: String,
+}
+
+/// Archive snapshot that stood in for a source URL that returned 404.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedSource {
+    pub original_url: String,
+    pub snapshot_url: String,
+    /// Wayback `YYYYMMDDhhmmss` capture time.
+    pub snapshot_timestamp: String,
+}
+
+/// Counts for a structural level node, covering every unit of the run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelStats {
+    /// Direct children emitted under this node.
+    pub child_count: u32,
+    /// Content-bearing descendants at any depth.
+    pub section_count: u32,
+    /// Whitespace-separated words across those descendants' blocks.
+    pub word_count: u64,
+}
+
+/// Stats for the level node `id`, sent once every unit has finished.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeLevelStats {
+    pub id: String,
+    pub stats: LevelStats,
 }
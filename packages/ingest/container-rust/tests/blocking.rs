@@ -0,0 +1,70 @@
+use ingest::runtime::blocking::{run_blocking, spawn_blocking_stream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn stream_delivers_every_event() {
+    let cancel = CancellationToken::new();
+    let mut rx = spawn_blocking_stream(&cancel, 4, |sink| {
+        for n in 0..100 {
+            if !sink.emit(n) {
+                break;
+            }
+        }
+    });
+
+    let mut received = Vec::new();
+    while let Some(n) = rx.recv().await {
+        received.push(n);
+    }
+    assert_eq!(received, (0..100).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn stream_stops_producing_after_cancel() {
+    let cancel = CancellationToken::new();
+    let produced = Arc::new(AtomicUsize::new(0));
+    let produced_in_parser = produced.clone();
+    let mut rx = spawn_blocking_stream(&cancel, 1, move |sink| {
+        for n in 0..10_000 {
+            if !sink.emit(n) {
+                break;
+            }
+            produced_in_parser.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    assert_eq!(rx.recv().await, Some(0));
+    cancel.cancel();
+    while rx.recv().await.is_some() {}
+
+    assert!(produced.load(Ordering::SeqCst) < 10);
+}
+
+#[tokio::test]
+async fn run_blocking_returns_parse_result() {
+    let cancel = CancellationToken::new();
+    let result = run_blocking(&cancel, |_| "parsed".to_string()).await;
+    assert_eq!(result, Ok("parsed".to_string()));
+}
+
+#[tokio::test]
+async fn run_blocking_returns_early_when_cancelled() {
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        trigger.cancel();
+    });
+
+    let result = run_blocking(&cancel, |token| {
+        while !token.is_cancelled() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        "finished"
+    })
+    .await;
+    assert_eq!(result, Err("Parse cancelled".to_string()));
+}
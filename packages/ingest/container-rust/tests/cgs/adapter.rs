@@ -1,6 +1,7 @@
 use crate::common::{self, load_fixture, AdapterTestContext};
 use ingest::runtime::types::QueueItem;
 use ingest::sources::cgs::adapter::CgsAdapter;
+use ingest::sources::cgs::parser::CgsTocMap;
 use ingest::types::SectionContent;
 use std::fs;
 use std::path::Path;
@@ -24,6 +25,7 @@ async fn adapter_emits_title_chapter_and_sections() {
 
     t.add_fixture(&item.url, &html);
     t.run_item(item).await;
+    t.assert_output_pinned(3, "10ef0340bbec997b");
 
     t.expect_node("root/title-20/chapter-377a")
         .level("chapter")
@@ -67,6 +69,59 @@ async fn adapter_emits_title_chapter_and_sections() {
         .any(|block| block.type_ == "history_long"));
 }
 
+#[tokio::test]
+async fn adapter_emits_structured_see_also() {
+    let mut t = AdapterTestContext::new(CgsAdapter, "root");
+
+    let html = load_fixture("cgs/cgs_basic_chapter.htm").replace(
+        r#"<p class="annotation-first">"#,
+        r#"<p class="cross-ref-first">See Sec. 20-86aa re doula advisory committee.</p>
+<p class="annotation-first">"#,
+    );
+    let item = QueueItem {
+        url: "https://www.cgs.ct.gov/current/pub/chap_377a.htm".to_string(),
+        parent_id: "root/title-20".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "title_num": "20",
+            "chapter_id": "377a",
+            "unit_id": "test"
+        }),
+    };
+
+    t.add_fixture(&item.url, &html);
+    t.run_item(item).await;
+
+    let node = t.expect_node("root/title-20/chapter-377a/section-20-86bb");
+    let section_content =
+        serde_json::from_value::<SectionContent>(node.node.content.clone().unwrap())
+            .expect("section content should deserialize");
+    let reference_block = section_content
+        .blocks
+        .iter()
+        .find(|block| block.type_ == "cross_reference")
+        .expect("cross_reference block should exist");
+    assert_eq!(reference_block.label.as_deref(), Some("See Also"));
+    assert_eq!(
+        reference_block.content.as_deref(),
+        Some("[Sec. 20-86aa](/statutes/section/20-86aa) re doula advisory committee")
+    );
+    assert!(!section_content
+        .blocks
+        .iter()
+        .any(|block| block.type_ == "see_also"));
+
+    let see_also = section_content
+        .metadata
+        .expect("metadata should exist")
+        .see_also;
+    assert_eq!(see_also.len(), 1);
+    assert_eq!(see_also[0].section, "20-86aa");
+    assert_eq!(see_also[0].link, "/statutes/section/20-86aa");
+    assert_eq!(see_also[0].description, "doula advisory committee");
+}
+
 #[tokio::test]
 async fn adapter_inlines_cross_references_in_body_markdown() {
     let mut t = AdapterTestContext::new(CgsAdapter, "root");
@@ -124,3 +179,121 @@ async fn adapter_handles_article_units() {
         .path("/section/42a-1-101")
         .parent("root/title-42a/article-1");
 }
+
+#[tokio::test]
+async fn adapter_persists_and_reuses_chapter_toc() {
+    let mut t = AdapterTestContext::new(CgsAdapter, "root");
+
+    let html = load_fixture("cgs/cgs_basic_chapter.htm").replace(
+        "Sec. 20-86bb. Doulas. Certification. Use of title.</a>",
+        "</a>",
+    );
+    let item = QueueItem {
+        url: "https://www.cgs.ct.gov/current/pub/chap_377a.htm".to_string(),
+        parent_id: "root/title-20".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "title_num": "20",
+            "chapter_id": "377a",
+            "unit_id": "test"
+        }),
+    };
+
+    t.cache.artifacts.lock().unwrap().insert(
+        "toc/377a.json".to_string(),
+        serde_json::json!({
+            "entries": [
+                { "section_id": "sec_20-86bb", "label": "Sec. 20-86bb. Doulas." }
+            ]
+        })
+        .to_string(),
+    );
+    t.add_fixture(&item.url, &html);
+    t.run_item(item).await;
+
+    t.expect_node("root/title-20/chapter-377a/section-20-86bb")
+        .name("Doulas.");
+
+    let persisted = t
+        .cache
+        .artifacts
+        .lock()
+        .unwrap()
+        .get("toc/377a.json")
+        .cloned()
+        .expect("TOC should be persisted");
+    let toc = serde_json::from_str::<CgsTocMap>(&persisted).expect("TOC should deserialize");
+    assert_eq!(
+        toc.label("sec_20-86aa"),
+        Some("Sec. 20-86aa. Doula advisory committee. Duties. Membership.")
+    );
+    assert_eq!(toc.label("sec_20-86bb"), Some("Sec. 20-86bb. Doulas."));
+}
+
+#[tokio::test]
+async fn adapter_treats_invalid_persisted_toc_as_missing() {
+    let mut t = AdapterTestContext::new(CgsAdapter, "root");
+
+    let html = load_fixture("cgs/cgs_basic_chapter.htm");
+    let item = QueueItem {
+        url: "https://www.cgs.ct.gov/current/pub/chap_377a.htm".to_string(),
+        parent_id: "root/title-20".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "title_num": "20",
+            "chapter_id": "377a",
+            "unit_id": "test"
+        }),
+    };
+
+    t.cache
+        .artifacts
+        .lock()
+        .unwrap()
+        .insert("toc/377a.json".to_string(), "{not json".to_string());
+    t.add_fixture(&item.url, &html);
+    t.run_item(item).await;
+
+    t.expect_node("root/title-20/chapter-377a/section-20-86bb");
+    let persisted = t
+        .cache
+        .artifacts
+        .lock()
+        .unwrap()
+        .get("toc/377a.json")
+        .cloned()
+        .expect("TOC should be persisted");
+    assert!(serde_json::from_str::<CgsTocMap>(&persisted).is_ok());
+}
+
+#[tokio::test]
+async fn adapter_flags_chapter_with_toc_entries_missing_bodies() {
+    let mut t = AdapterTestContext::new(CgsAdapter, "root");
+
+    let html = load_fixture("cgs/cgs_basic_chapter.htm").replace(
+        r##"<p class="toc_catchln"><a href="#sec_20-86bb">"##,
+        r##"<p class="toc_catchln"><a href="#sec_20-86cc">Sec. 20-86cc. Reserved.</a></p>
+<p class="toc_catchln"><a href="#sec_20-86bb">"##,
+    );
+    let item = QueueItem {
+        url: "https://www.cgs.ct.gov/current/pub/chap_377a.htm".to_string(),
+        parent_id: "root/title-20".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "title_num": "20",
+            "chapter_id": "377a",
+            "unit_id": "test"
+        }),
+    };
+
+    t.add_fixture(&item.url, &html);
+    t.run_item(item).await;
+
+    t.expect_node("root/title-20/chapter-377a")
+        .review_required(true);
+    t.expect_node("root/title-20/chapter-377a/section-20-86bb")
+        .review_required(false);
+}
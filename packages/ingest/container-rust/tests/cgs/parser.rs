@@ -1,11 +1,12 @@
-use crate::common::load_fixture;
+use crate::common::{assert_no_invisible_chars, load_fixture};
 use ingest::sources::cgs::cross_references::{
-    extract_section_cross_references, inline_section_cross_references,
+    extract_section_cross_references, inline_section_cross_references, parse_see_also,
+    split_see_also,
 };
 use ingest::sources::cgs::parser::{
     extract_chapter_title_from_html, extract_section_ids_from_toc, format_designator_display,
-    format_designator_padded, normalize_designator, parse_cgs_chapter_html, parse_label,
-    CgsUnitKind,
+    format_designator_padded, normalize_designator, parse_cgs_chapter_html,
+    parse_cgs_chapter_html_with_toc, parse_label, CgsTocEntry, CgsTocMap, CgsUnitKind,
 };
 use std::fs;
 use std::path::Path;
@@ -204,6 +205,50 @@ fn extracts_section_name_from_toc() {
         .unwrap_or(false));
 }
 
+#[test]
+fn keeps_toc_entries_in_document_order() {
+    let html = load_fixture("cgs/cgs_basic_chapter.htm");
+    let parsed = parse_cgs_chapter_html(&html, "377a", "", CgsUnitKind::Chapter);
+    let ids = parsed.toc.section_ids().collect::<Vec<_>>();
+    assert_eq!(ids, vec!["sec_20-86aa", "sec_20-86bb"]);
+    assert!(parsed.toc.missing_sections(&parsed.sections).is_empty());
+}
+
+#[test]
+fn backfills_section_names_from_prior_toc() {
+    let html = r##"<html><body>
+<p class="toc_catchln"><a href="#sec_1-1">Sec. 1-1. Words and phrases.</a></p>
+<p class="toc_catchln"><a href="#sec_1-2"></a></p>
+<p><span class="catchln" id="sec_1-1">Sec. 1-1.</span> First body.</p>
+<p><span class="catchln" id="sec_1-2">Sec. 1-2.</span> Second body.</p>
+</body></html>"##;
+    let prior = CgsTocMap {
+        entries: vec![CgsTocEntry {
+            section_id: "sec_1-2".to_string(),
+            label: "Sec. 1-2. Definitions.".to_string(),
+        }],
+    };
+
+    let parsed = parse_cgs_chapter_html_with_toc(html, "001", "", CgsUnitKind::Chapter, &prior);
+
+    assert_eq!(parsed.sections[1].name.as_deref(), Some("Definitions."));
+    assert_eq!(parsed.toc.label("sec_1-2"), Some("Sec. 1-2. Definitions."));
+}
+
+#[test]
+fn reports_toc_entries_without_section_body() {
+    let html = r##"<html><body>
+<p class="toc_catchln"><a href="#sec_1-1">Sec. 1-1. Words and phrases.</a></p>
+<p class="toc_catchln"><a href="#sec_1-2">Sec. 1-2. Definitions.</a></p>
+<p><span class="catchln" id="sec_1-1">Sec. 1-1.</span> First body.</p>
+</body></html>"##;
+    let parsed = parse_cgs_chapter_html(html, "001", "", CgsUnitKind::Chapter);
+    assert_eq!(
+        parsed.toc.missing_sections(&parsed.sections),
+        vec!["sec_1-2".to_string()]
+    );
+}
+
 #[test]
 fn sets_correct_parent_string_id() {
     let html = load_fixture("cgs/cgs_basic_chapter.htm");
@@ -491,6 +536,48 @@ fn inlines_cgs_cross_references() {
     assert!(inlined.contains("[1-3](/statutes/section/1-3)"));
 }
 
+#[test]
+fn parses_see_also_targets_and_subjects() {
+    let text = "See Sec. 20-86bb re certification of doulas.\n\
+See Secs. 19a-490 and 19a-491 re licensing of institutions under section 19a-493.\n\
+See chapter 368a.";
+    let refs = parse_see_also(text);
+    let targets = refs
+        .iter()
+        .map(|r| (r.section.as_str(), r.description.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        vec![
+            ("20-86bb", "certification of doulas"),
+            ("19a-490", "licensing of institutions under section 19a-493"),
+            ("19a-491", "licensing of institutions under section 19a-493"),
+        ]
+    );
+    assert_eq!(refs[0].link, "/statutes/section/20-86bb");
+}
+
+#[test]
+fn see_also_subject_needs_re_after_a_citation() {
+    let refs = parse_see_also("See Sec. 4-5, as re-enacted by the 1949 revision.");
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].section, "4-5");
+    assert_eq!(refs[0].description, "");
+
+    let refs = parse_see_also("See Sec. 4-5 re: prior law.");
+    assert_eq!(refs[0].description, "prior law");
+}
+
+#[test]
+fn splits_see_also_lines_without_targets_from_references() {
+    let (refs, unlinked) = split_see_also(
+        "See Sec. 20-86bb re certification of doulas.\nSee chapter 368a.\nSee Sec. 20-86bb.",
+    );
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].section, "20-86bb");
+    assert_eq!(unlinked.as_deref(), Some("See chapter 368a."));
+}
+
 // ============================================================
 // Integration Tests with Real Mirror Data
 // ============================================================
@@ -567,3 +654,64 @@ fn extracts_toc_ids_for_title_42a_page() {
         "Title pages should not parse chapter TOC sections"
     );
 }
+
+#[test]
+fn skips_comments_and_hidden_elements() {
+    let html = load_fixture("cgs/cgs_hidden_content.htm");
+    let parsed = parse_cgs_chapter_html(&html, "377a", "", CgsUnitKind::Chapter);
+
+    assert_eq!(
+        parsed.toc.section_ids().collect::<Vec<_>>(),
+        vec!["sec_20-86aa"]
+    );
+    assert_eq!(parsed.sections.len(), 1);
+    let section = &parsed.sections[0];
+    for hidden in [
+        "editor:",
+        "effective pending",
+        "*",
+        "Reserved for a subsection",
+        "tocState",
+        "Enable JavaScript",
+    ] {
+        assert!(
+            !section.body.contains(hidden),
+            "body kept hidden text {hidden:?}: {}",
+            section.body
+        );
+    }
+    assert!(section
+        .body
+        .contains("certified by the Department of Public Health; and"));
+    assert_eq!(
+        section.history_long.as_deref(),
+        Some("History: P.A. 23-147 effective July 1, 2023.")
+    );
+}
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let html = load_fixture("cgs/cgs_invisible_chars.htm");
+    let result = parse_cgs_chapter_html(&html, "007", "", CgsUnitKind::Chapter);
+    let section = &result.sections[0];
+    assert_eq!(section.name.as_deref(), Some("Payment of fees."));
+    assert!(
+        section
+            .body
+            .contains("(a) Each municipality shall collect the fee."),
+        "{}",
+        section.body
+    );
+    assert!(section
+        .body
+        .contains("(b) The fee shall be deposited with the treasurer."));
+    assert_eq!(
+        section.history_short.as_deref(),
+        Some("(1949 Rev., S. 101.)")
+    );
+    assert_eq!(
+        section.history_long.as_deref(),
+        Some("History: Section added in 1949.")
+    );
+    assert_no_invisible_chars(&format!("{section:?}"));
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "cgs")]
+
 mod cgs;
 mod common;
@@ -4,10 +4,13 @@ use ingest::runtime::fetcher::Fetcher;
 use ingest::runtime::types::{
     BlobStore, BuildContext, Cache, IngestContext, NodeStore, QueueItem, UrlQueue,
 };
-use ingest::types::NodePayload;
+use ingest::sources::paths::PathIndex;
+use ingest::types::{IngestProfile, NodeMeta, NodePayload};
+use serde_json::{json, Value};
 use std::collections::{HashMap, VecDeque};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 pub fn fixtures_dir() -> String {
     format!("{}/tests/fixtures", env!("CARGO_MANIFEST_DIR"))
@@ -19,22 +22,124 @@ pub fn load_fixture(filename: &str) -> String {
         .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", path.display(), e))
 }
 
+/// Fails if `text` still holds a soft hyphen, zero-width character, word
+/// joiner, or byte-order mark.
+pub fn assert_no_invisible_chars(text: &str) {
+    let invisible = [
+        '\u{ad}', '\u{200b}', '\u{200c}', '\u{200d}', '\u{2060}', '\u{feff}',
+    ];
+    assert!(
+        !text.contains(invisible),
+        "invisible character left in {text:?}"
+    );
+}
+
+/// Starts a test node: a content-less section `id` under `root` in source
+/// version `v1`. Set anything a test depends on, then [`NodeBuilder::build`].
+pub fn node(id: &str) -> NodeBuilder {
+    NodeBuilder(NodePayload {
+        meta: NodeMeta {
+            id: id.to_string(),
+            source_version_id: "v1".to_string(),
+            parent_id: Some("root".to_string()),
+            level_name: "section".to_string(),
+            level_index: 1,
+            sort_order: 0,
+            name: None,
+            path: None,
+            readable_id: None,
+            heading_citation: None,
+            source_url: None,
+            accessed_at: None,
+            review_required: false,
+        },
+        content: None,
+    })
+}
+
+pub struct NodeBuilder(NodePayload);
+
+impl NodeBuilder {
+    pub fn version(mut self, source_version_id: &str) -> Self {
+        self.0.meta.source_version_id = source_version_id.to_string();
+        self
+    }
+
+    pub fn parent(mut self, parent_id: &str) -> Self {
+        self.0.meta.parent_id = Some(parent_id.to_string());
+        self
+    }
+
+    pub fn level(mut self, level_name: &str, level_index: i32) -> Self {
+        self.0.meta.level_name = level_name.to_string();
+        self.0.meta.level_index = level_index;
+        self
+    }
+
+    pub fn sort_order(mut self, sort_order: i32) -> Self {
+        self.0.meta.sort_order = sort_order;
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.0.meta.name = Some(name.to_string());
+        self
+    }
+
+    pub fn path(mut self, path: &str) -> Self {
+        self.0.meta.path = Some(path.to_string());
+        self
+    }
+
+    pub fn heading_citation(mut self, citation: &str) -> Self {
+        self.0.meta.heading_citation = Some(citation.to_string());
+        self
+    }
+
+    pub fn source_url(mut self, url: &str) -> Self {
+        self.0.meta.source_url = Some(url.to_string());
+        self
+    }
+
+    /// Content made of `blocks`, a JSON array of content blocks.
+    pub fn blocks(mut self, blocks: Value) -> Self {
+        self.0.content = Some(json!({ "blocks": blocks }));
+        self
+    }
+
+    /// Content with a single body block.
+    pub fn body(self, text: &str) -> Self {
+        self.blocks(json!([{ "type": "body", "content": text }]))
+    }
+
+    pub fn build(self) -> NodePayload {
+        self.0
+    }
+}
+
 #[derive(Clone)]
 pub struct MockNodeStore {
     pub nodes: Arc<Mutex<Vec<NodePayload>>>,
+    pub paths: Arc<Mutex<PathIndex>>,
 }
 
 impl MockNodeStore {
     pub fn new() -> Self {
         Self {
             nodes: Arc::new(Mutex::new(Vec::new())),
+            paths: Arc::new(Mutex::new(PathIndex::new())),
         }
     }
 }
 
 #[async_trait]
 impl NodeStore for MockNodeStore {
-    async fn insert_node(&self, node: NodePayload) -> Result<(), String> {
+    async fn insert_node(&self, mut node: NodePayload) -> Result<(), String> {
+        // Adapters should never render two nodes to one path, so tests treat
+        // the collision the runtime would flag as a failure.
+        if let Some(collision) = self.paths.lock().unwrap().claim(&mut node.meta) {
+            return Err(collision);
+        }
         self.nodes.lock().unwrap().push(node);
         Ok(())
     }
@@ -53,12 +158,14 @@ impl BlobStore for MockBlobStore {
 
 pub struct MockCache {
     pub fixtures: Arc<Mutex<HashMap<String, String>>>,
+    pub artifacts: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl MockCache {
     pub fn new() -> Self {
         Self {
             fixtures: Arc::new(Mutex::new(HashMap::new())),
+            artifacts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -93,6 +200,18 @@ impl Cache for MockCache {
     ) -> Result<String, String> {
         self.fetch_cached(url, "", None).await
     }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.artifacts.lock().unwrap().get(key).cloned())
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        self.artifacts
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), content.to_string());
+        Ok(())
+    }
 }
 
 pub struct MockFetcher {
@@ -161,12 +280,14 @@ pub fn create_test_context<'a>(
             root_node_id,
             accessed_at: "2024-01-01",
             unit_sort_order: 1,
+            profile: IngestProfile::Full,
         },
         nodes: Box::new(node_store),
         blobs: Arc::new(MockBlobStore),
         cache: Arc::new(cache),
         queue: Arc::new(queue),
         logger: Arc::new(MockLogger),
+        cancel: CancellationToken::new(),
     }
 }
 
@@ -179,6 +300,7 @@ pub struct AdapterTestContext<'a, A: SourceAdapter> {
     pub queue: MockUrlQueue,
     pub source_version_id: String,
     pub root_node_id: String,
+    pub profile: IngestProfile,
     pub _marker: std::marker::PhantomData<&'a ()>,
 }
 
@@ -191,6 +313,7 @@ impl<'a, A: SourceAdapter> AdapterTestContext<'a, A> {
             queue: MockUrlQueue::new(),
             source_version_id: "v1".to_string(),
             root_node_id: root_node_id.to_string(),
+            profile: IngestProfile::Full,
             _marker: std::marker::PhantomData,
         }
     }
@@ -206,6 +329,7 @@ impl<'a, A: SourceAdapter> AdapterTestContext<'a, A> {
             self.node_store.clone(),
             MockCache {
                 fixtures: self.cache.fixtures.clone(),
+                artifacts: self.cache.artifacts.clone(),
             },
             MockUrlQueue {
                 enqueued: queue_items.clone(),
@@ -213,6 +337,7 @@ impl<'a, A: SourceAdapter> AdapterTestContext<'a, A> {
             &self.source_version_id,
             &self.root_node_id,
         );
+        ctx.build.profile = self.profile;
 
         // Enqueue the initial item
         queue_items.lock().unwrap().push_back(initial_item);
@@ -255,6 +380,26 @@ impl<'a, A: SourceAdapter> AdapterTestContext<'a, A> {
     pub fn get_nodes(&self) -> Vec<NodePayload> {
         self.node_store.nodes.lock().unwrap().clone()
     }
+
+    /// Pins the emitted nodes to the adapter's `parser_version`: any change to
+    /// them fails until the version is bumped and the digest re-pinned, so
+    /// artifacts derived from the old output are never reused.
+    pub fn assert_output_pinned(&self, parser_version: u32, digest: &str) {
+        let output = serde_json::to_string(&self.get_nodes()).unwrap();
+        let actual = output
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+            });
+        let actual = format!("{actual:016x}");
+        let current = self.adapter.parser_version();
+        assert!(
+            current == parser_version && actual == digest,
+            "output is pinned to parser_version {parser_version} ({digest}) but \
+             parser_version is {current} and output is {actual}; bump parser_version \
+             when output changes and re-pin to the new version and digest"
+        );
+    }
 }
 
 pub struct NodeMatcher {
@@ -321,6 +466,15 @@ impl NodeMatcher {
         self
     }
 
+    pub fn review_required(self, expected: bool) -> Self {
+        assert_eq!(
+            self.node.meta.review_required, expected,
+            "Review flag mismatch for node {}",
+            self.node.meta.id
+        );
+        self
+    }
+
     pub fn content_contains(self, text: &str) -> Self {
         let content = self.node.content.as_ref().expect("Node has no content");
         let content_str = serde_json::to_string(content).unwrap();
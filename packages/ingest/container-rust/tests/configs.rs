@@ -1,4 +1,5 @@
-use ingest::sources::configs::SourcesConfig;
+use ingest::runtime::cache::artifact_namespace;
+use ingest::sources::configs::{ExternalLinks, HeadingCase, NoteTaxonomy, SourcesConfig};
 use ingest::types::SourceKind;
 
 #[test]
@@ -79,3 +80,318 @@ fn test_load_default_with_env_var() {
         Some("https://malegislature.gov/Laws/GeneralLaws")
     );
 }
+
+#[test]
+fn test_note_taxonomy_defaults_and_overrides() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "note_taxonomy": {
+                    "topics": { "effectiveDate": "effective_date" },
+                    "default_labels": { "effective_date": "Effective Date" }
+                }
+            },
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": "https://www.cga.ct.gov/current/pub/titles.htm"
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+
+    assert_eq!(
+        config.note_taxonomy(SourceKind::Cgs),
+        NoteTaxonomy::default()
+    );
+
+    let usc = config.note_taxonomy(SourceKind::Usc);
+    assert_eq!(
+        usc.classify(Some("EffectiveDate"), None, ""),
+        "effective_date"
+    );
+    assert_eq!(usc.default_label("effective_date"), Some("Effective Date"));
+    assert_eq!(usc.classify(Some("amendments"), None, ""), "amendments");
+    assert_eq!(
+        usc.classify(
+            Some("editorialNotes"),
+            Some("crossHeading"),
+            "Editorial Notes"
+        ),
+        "heading"
+    );
+    assert_eq!(
+        usc.classify(Some("statutoryNotes"), None, "Prior Provisions"),
+        "note"
+    );
+}
+
+#[test]
+fn test_default_note_taxonomy_types_statutory_notes() {
+    let taxonomy = NoteTaxonomy::default();
+
+    assert_eq!(
+        taxonomy.classify(Some("shortTitleOfAmendment"), None, ""),
+        "short_title"
+    );
+    assert_eq!(
+        taxonomy.classify(Some("transferOfFunctions"), None, ""),
+        "transfer_of_functions"
+    );
+    assert_eq!(
+        taxonomy.default_label("references_in_text"),
+        Some("References in Text")
+    );
+
+    assert_eq!(
+        taxonomy.classify(Some("miscellaneous"), None, "Savings Provisions"),
+        "savings_provision"
+    );
+    assert_eq!(
+        taxonomy.classify(None, None, "Short Title of 1990 Amendments"),
+        "short_title"
+    );
+    assert_eq!(
+        taxonomy.classify(None, None, "Termination of Reporting Requirements"),
+        "termination"
+    );
+    assert_eq!(
+        taxonomy.classify(Some("miscellaneous"), None, "Presidential Succession"),
+        "note"
+    );
+}
+
+#[test]
+fn test_heading_case_title_and_sentence() {
+    let title = HeadingCase::TitleCase;
+    assert_eq!(
+        title.apply("GENERAL PROVISIONS RELATING TO THE PUBLIC HEALTH"),
+        "General Provisions Relating to the Public Health"
+    );
+    assert_eq!(
+        title.apply("SUBCHAPTER II—ADMINISTRATION OF THE U.S. CODE"),
+        "Subchapter II—Administration of the U.S. Code"
+    );
+    assert_eq!(title.apply("PART A—IN GENERAL"), "Part A—In General");
+    assert_eq!(title.apply("CHAPTER 21A"), "Chapter 21A");
+    assert_eq!(title.apply("Crimes and Offenses"), "Crimes and Offenses");
+    assert_eq!(
+        title.apply("HIV HEALTH CARE SERVICES PROGRAM"),
+        "HIV Health Care Services Program"
+    );
+    assert_eq!(
+        title.apply("RESEARCH ON ACQUIRED IMMUNE DEFICIENCY SYNDROME (AIDS)"),
+        "Research on Acquired Immune Deficiency Syndrome (AIDS)"
+    );
+    assert_eq!(
+        title.apply("Grants to the CDC and NIH"),
+        "Grants to the CDC and NIH"
+    );
+
+    let sentence = HeadingCase::SentenceCase;
+    assert_eq!(
+        sentence.apply("GENERAL PROVISIONS OF TITLE IV"),
+        "General provisions of title IV"
+    );
+
+    assert_eq!(
+        HeadingCase::Preserve.apply("GENERAL PROVISIONS"),
+        "GENERAL PROVISIONS"
+    );
+}
+
+#[test]
+fn test_heading_case_from_config() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "heading_case": "title_case"
+            },
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": "https://www.cga.ct.gov/current/pub/titles.htm"
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+    assert_eq!(config.heading_case(SourceKind::Usc), HeadingCase::TitleCase);
+    assert_eq!(config.heading_case(SourceKind::Cgs), HeadingCase::Preserve);
+}
+
+#[test]
+fn test_slug_stop_words_from_config() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "slug_stop_words": ["The", "of", "relating"]
+            },
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": "https://www.cga.ct.gov/current/pub/titles.htm"
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+    assert_eq!(
+        config.slug_stop_words(SourceKind::Usc),
+        ["the", "of", "relating"]
+    );
+    assert!(config
+        .slug_stop_words(SourceKind::Cgs)
+        .contains(&"and".to_string()));
+}
+
+#[test]
+fn test_external_links_from_config() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "external_links": {
+                    "cfr_section": "https://www.law.cornell.edu/cfr/text/{title}/{section}",
+                    "public_law": ""
+                }
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+    let usc = config.external_links(SourceKind::Usc);
+    let defaults = ExternalLinks::default();
+    assert_eq!(
+        ExternalLinks::render(&usc.cfr_section, &[("title", "42"), ("section", "405.1")]),
+        Some("https://www.law.cornell.edu/cfr/text/42/405.1".to_string())
+    );
+    assert_eq!(ExternalLinks::render(&usc.public_law, &[]), None);
+    assert_eq!(usc.statutes_at_large, defaults.statutes_at_large);
+    assert_eq!(config.external_links(SourceKind::Cgs), defaults);
+}
+
+#[test]
+fn test_extraction_options_hash_tracks_extraction_options() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "heading_case": "title_case"
+            },
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": "https://www.cga.ct.gov/current/pub/titles.htm"
+            },
+            "mgl": {
+                "name": "Massachusetts General Laws",
+                "jurisdiction": "state",
+                "region": "MA",
+                "doc_type": "statute",
+                "description": "Renamed description only",
+                "root_url": "https://malegislature.gov/Laws/GeneralLaws"
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+    let usc = config.extraction_options_hash(SourceKind::Usc);
+    let cgs = config.extraction_options_hash(SourceKind::Cgs);
+    assert_eq!(usc.len(), 16);
+    assert_ne!(usc, cgs);
+    assert_eq!(cgs, config.extraction_options_hash(SourceKind::Mgl));
+    assert_eq!(usc, config.extraction_options_hash(SourceKind::Usc));
+
+    assert_eq!(
+        artifact_namespace(SourceKind::Cgs, 3, &cgs),
+        format!("cgs/parser-v3/{cgs}")
+    );
+}
+
+#[test]
+fn refuses_runs_for_sources_in_maintenance() {
+    let json = r#"
+    {
+        "sources": {
+            "usc": {
+                "name": "United States Code",
+                "jurisdiction": "federal",
+                "region": "US",
+                "doc_type": "statute",
+                "description": "Federal statutory law of the United States",
+                "root_url": "https://uscode.house.gov/download/download.shtml",
+                "maintenance": "OLRC download page is being redesigned"
+            },
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": "https://www.cga.ct.gov/current/pub/titles.htm"
+            }
+        }
+    }
+    "#;
+
+    let config: SourcesConfig = serde_json::from_str(json).expect("Failed to parse config");
+    let err = config.ensure_accepting_runs(SourceKind::Usc).unwrap_err();
+    assert!(err.contains("usc is in maintenance"), "{err}");
+    assert!(
+        err.ends_with("OLRC download page is being redesigned"),
+        "{err}"
+    );
+    assert!(config.ensure_accepting_runs(SourceKind::Cgs).is_ok());
+}
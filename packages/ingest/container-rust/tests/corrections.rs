@@ -0,0 +1,108 @@
+mod common;
+
+use common::node;
+use ingest::runtime::corrections::{canonical_node_id, Correction, CorrectionOverlay};
+use ingest::types::{NodePayload, SectionContent};
+use serde_json::json;
+
+fn section(id: &str, blocks: serde_json::Value) -> NodePayload {
+    node(id)
+        .version("cgs-2025")
+        .level("section", 2)
+        .name("Definitions")
+        .blocks(blocks)
+        .build()
+}
+
+fn overlay(value: serde_json::Value) -> CorrectionOverlay {
+    serde_json::from_value(json!({ "corrections": value })).unwrap()
+}
+
+fn content_of(node: &NodePayload) -> SectionContent {
+    serde_json::from_value(node.content.clone().unwrap()).unwrap()
+}
+
+#[test]
+fn canonical_ids_drop_the_version_segment() {
+    assert_eq!(
+        canonical_node_id("cgs/2025/root/title-1/section-1-1"),
+        "cgs/root/title-1/section-1-1"
+    );
+    assert_eq!(canonical_node_id("cgs/2025"), "cgs/2025");
+}
+
+#[test]
+fn appends_a_correction_block_and_keeps_the_original_text() {
+    let overlay = overlay(json!({
+        "cgs/root/title-1/section-1-1": [
+            { "text": "the the", "replacement": "the", "note": "Duplicated word." }
+        ]
+    }));
+    let mut node = section(
+        "cgs/2026/root/title-1/section-1-1",
+        json!([
+            { "type": "body", "content": "(a) As used in this title:" },
+            { "type": "body", "content": "(b) In the the general statutes, words import the singular." }
+        ]),
+    );
+
+    let unmatched = overlay.apply(&mut node).unwrap();
+    assert!(unmatched.is_empty());
+
+    let content = content_of(&node);
+    assert_eq!(content.blocks.len(), 3);
+    assert_eq!(
+        content.blocks[1].content.as_deref(),
+        Some("(b) In the the general statutes, words import the singular.")
+    );
+    let block = &content.blocks[2];
+    assert_eq!(block.type_, "correction");
+    assert_eq!(block.label.as_deref(), Some("Editorial Correction"));
+    assert_eq!(
+        block.content.as_deref(),
+        Some("\"the the\" should read \"the\". Duplicated word.")
+    );
+
+    let spans = content.metadata.unwrap().corrections;
+    assert_eq!(spans.len(), 1);
+    assert_eq!(
+        (spans[0].block, spans[0].offset, spans[0].length),
+        (1, 7, 7)
+    );
+    assert_eq!(spans[0].replacement, "the");
+}
+
+#[test]
+fn returns_corrections_whose_text_is_gone() {
+    let stale = Correction {
+        text: "recieve".to_string(),
+        replacement: "receive".to_string(),
+        note: None,
+    };
+    let overlay = overlay(json!({ "cgs/root/title-1/section-1-1": [stale] }));
+    let original = json!([{ "type": "body", "content": "Each clerk shall receive fees." }]);
+    let mut node = section("cgs/2026/root/title-1/section-1-1", original.clone());
+
+    assert_eq!(overlay.apply(&mut node).unwrap(), vec![stale]);
+    assert_eq!(node.content, Some(json!({ "blocks": original })));
+
+    let mut other = section("cgs/2026/root/title-1/section-1-2", original.clone());
+    assert!(overlay.apply(&mut other).unwrap().is_empty());
+    assert_eq!(other.content, Some(json!({ "blocks": original })));
+}
+
+#[test]
+fn missing_corrections_file_is_an_empty_overlay() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(
+        CorrectionOverlay::load_from_file(dir.path().join("corrections.json"))
+            .unwrap()
+            .is_empty()
+    );
+
+    let path = dir.path().join("broken.json");
+    std::fs::write(&path, "{").unwrap();
+    assert!(CorrectionOverlay::load_from_file(&path).is_err());
+
+    CorrectionOverlay::load_from_file("../corrections.json").unwrap();
+}
@@ -0,0 +1,94 @@
+use ingest::runtime::coverage::{select_units, UnitSelector, VersionCoverage};
+use ingest::types::UnitRoot;
+use serde_json::json;
+
+fn unit(title_num: &str) -> UnitRoot {
+    UnitRoot {
+        id: format!("title-{title_num}"),
+        title_num: title_num.to_string(),
+        url: format!("https://example.test/title_{title_num}.htm"),
+        level_name: "title".to_string(),
+        level_index: 0,
+    }
+}
+
+fn titles() -> Vec<UnitRoot> {
+    ["1", "2", "5a", "25", "26", "42a"]
+        .into_iter()
+        .map(unit)
+        .collect()
+}
+
+fn ids(units: &[UnitRoot]) -> Vec<&str> {
+    units.iter().map(|unit| unit.id.as_str()).collect()
+}
+
+fn selectors(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn parses_exact_and_range_selectors() {
+    assert_eq!(
+        UnitSelector::parse(" Title-5A ").unwrap(),
+        UnitSelector::Exact("title-5a".to_string())
+    );
+    assert_eq!(
+        UnitSelector::parse("1..25").unwrap(),
+        UnitSelector::Range(1, 25)
+    );
+    assert!(UnitSelector::parse("").is_err());
+    assert!(UnitSelector::parse("25..1").is_err());
+    assert!(UnitSelector::parse("1..x").is_err());
+}
+
+#[test]
+fn keeps_every_unit_without_selectors() {
+    let (units, coverage) = select_units(titles(), None).unwrap();
+    assert_eq!(units.len(), 6);
+    assert!(coverage.is_none());
+
+    let (units, coverage) = select_units(titles(), Some(&[])).unwrap();
+    assert_eq!(units.len(), 6);
+    assert!(coverage.is_none());
+}
+
+#[test]
+fn records_coverage_for_a_subset_of_units() {
+    let selectors = selectors(&["1..25", "title-42a"]);
+    let (units, coverage) = select_units(titles(), Some(&selectors)).unwrap();
+
+    assert_eq!(
+        ids(&units),
+        vec!["title-1", "title-2", "title-5a", "title-25", "title-42a"]
+    );
+    let coverage = coverage.expect("partial run should record coverage");
+    assert_eq!(
+        coverage,
+        VersionCoverage {
+            selectors: selectors.clone(),
+            unit_ids: ids(&units).iter().map(|id| id.to_string()).collect(),
+            discovered_units: 6,
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(&coverage).unwrap()["discoveredUnits"],
+        json!(6)
+    );
+}
+
+#[test]
+fn selecting_every_unit_records_no_coverage() {
+    let (units, coverage) = select_units(titles(), Some(&selectors(&["1..99"]))).unwrap();
+    assert_eq!(units.len(), 6);
+    assert!(coverage.is_none());
+}
+
+#[test]
+fn rejects_selectors_that_match_nothing() {
+    let err = select_units(titles(), Some(&selectors(&["2", "title-99", "30..40"]))).unwrap_err();
+    assert_eq!(
+        err,
+        "Unit selectors matched no discovered units: title-99, 30..40"
+    );
+}
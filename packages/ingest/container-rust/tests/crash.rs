@@ -0,0 +1,121 @@
+use ingest::runtime::blocking::run_blocking;
+use ingest::runtime::crash::{
+    install_panic_hook, with_crash_recorder, CrashRecorder, MAX_LOG_EVENTS,
+};
+use ingest::runtime::eta::JobProgress;
+use ingest::runtime::orchestrator::SimpleUrlQueue;
+use ingest::runtime::types::{QueueItem, UrlQueue};
+use serde_json::json;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+fn item(url: &str) -> QueueItem {
+    QueueItem {
+        url: url.to_string(),
+        parent_id: "cgs/2025/root".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: json!({ "unit_id": "title-42a" }),
+    }
+}
+
+#[test]
+fn keeps_only_the_latest_log_events() {
+    let crash = CrashRecorder::new();
+    for i in 0..MAX_LOG_EVENTS + 5 {
+        crash.record_log("info", &format!("event {i}"), None);
+    }
+
+    let dump = crash.dump("boom", &JobProgress::new("cgs"));
+    assert_eq!(dump.events.len(), MAX_LOG_EVENTS);
+    assert_eq!(dump.events[0].message, "event 5");
+    assert_eq!(dump.events.last().unwrap().message, "event 104");
+}
+
+#[test]
+fn dump_lists_each_running_unit_with_its_item_and_queue() {
+    let crash = CrashRecorder::new();
+    let queue = Arc::new(SimpleUrlQueue::new());
+    crash.start_unit("title-42a", queue.clone());
+    crash.begin_item("title-42a", &item("https://example.test/art_001.htm"));
+    queue.enqueue(item("https://example.test/art_002.htm"));
+    crash.start_unit("title-1", Arc::new(SimpleUrlQueue::new()));
+    crash.finish_unit("title-1");
+
+    let dump = crash.dump("boom", &JobProgress::new("cgs"));
+    assert_eq!(dump.units.len(), 1);
+    assert_eq!(dump.units[0].unit_id, "title-42a");
+    assert_eq!(
+        dump.units[0].item.as_ref().map(|item| item.url.as_str()),
+        Some("https://example.test/art_001.htm")
+    );
+    assert_eq!(dump.units[0].queue.len(), 1);
+    assert!(dump.panic.is_none());
+
+    let value = serde_json::to_value(&dump).unwrap();
+    assert_eq!(value["units"][0]["item"]["levelName"], "chapter");
+    assert_eq!(value["progress"]["sourceId"], "cgs");
+    assert!(dump.key().starts_with("crash-dumps/cgs/"));
+}
+
+#[tokio::test]
+async fn panic_hook_attributes_the_panic_to_its_own_job() {
+    install_panic_hook();
+    let crash = Arc::new(CrashRecorder::new());
+    let other = Arc::new(CrashRecorder::new());
+    assert!(!crash.panicked());
+
+    let running = tokio::spawn(with_crash_recorder(other.clone(), async {
+        tokio::task::yield_now().await;
+    }));
+    let joined = tokio::spawn(with_crash_recorder(crash.clone(), async {
+        panic!("parser blew up");
+    }))
+    .await;
+    assert!(joined.is_err());
+    running.await.unwrap();
+
+    assert!(crash.panicked());
+    assert!(!other.panicked());
+    let report = crash
+        .dump("Unit task failed to join", &JobProgress::new("cgs"))
+        .panic
+        .unwrap();
+    assert_eq!(report.message, "parser blew up");
+    assert!(report.location.unwrap().contains("tests/crash.rs"));
+}
+
+#[tokio::test]
+async fn blocking_parses_report_panics_to_the_calling_job() {
+    install_panic_hook();
+    let crash = Arc::new(CrashRecorder::new());
+
+    let result = with_crash_recorder(
+        crash.clone(),
+        run_blocking(&CancellationToken::new(), |_| -> () {
+            panic!("blocking parse blew up")
+        }),
+    )
+    .await;
+    assert!(result.is_err());
+
+    let report = crash.dump("boom", &JobProgress::new("usc")).panic.unwrap();
+    assert_eq!(report.message, "blocking parse blew up");
+    assert!(report.location.unwrap().contains("tests/crash.rs"));
+}
+
+#[tokio::test]
+async fn join_errors_carry_panics_outside_any_job() {
+    install_panic_hook();
+    let crash = CrashRecorder::new();
+
+    let err = tokio::spawn(async { panic!("unscoped task") })
+        .await
+        .unwrap_err();
+    assert!(!crash.panicked());
+    crash.record_join_panic(err.into_panic());
+
+    let report = crash.dump("boom", &JobProgress::new("cgs")).panic.unwrap();
+    assert_eq!(report.message, "unscoped task");
+    assert_eq!(report.location, None);
+}
@@ -0,0 +1,121 @@
+use ingest::export::dataset::{package_dataset, DatasetManifest, DatasetPackager, PackageOptions};
+use ruzstd::decoding::StreamingDecoder;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+
+const NODES: &str = concat!(
+    r#"{"meta":{"id":"cgs/title-1","source_version_id":"cgs-2026","parent_id":"cgs","level_name":"title","level_index":0,"sort_order":0,"accessed_at":"2026-01-02T03:04:05.250+00:00"},"content":null}"#,
+    "\n",
+    r#"{"meta":{"id":"cgs/title-1/section-1-1","source_version_id":"cgs-2026","parent_id":"cgs/title-1","level_name":"section","level_index":1,"sort_order":0,"accessed_at":"2026-01-01T00:00:00+00:00"},"content":{"blocks":[{"type":"body","content":"Text."}]}}"#,
+    "\n",
+);
+
+fn options() -> PackageOptions {
+    PackageOptions {
+        source_version_id: "cgs-2026".to_string(),
+        license_id: "CC0-1.0".to_string(),
+        license_text: "No rights reserved.\n".to_string(),
+    }
+}
+
+fn package(nodes: &str) -> Result<Vec<u8>, String> {
+    let mut archive = Vec::new();
+    package_dataset(nodes.as_bytes(), &options(), &mut archive)?;
+    Ok(archive)
+}
+
+fn unpack(archive: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut tar = Vec::new();
+    StreamingDecoder::new(archive)
+        .unwrap()
+        .read_to_end(&mut tar)
+        .unwrap();
+    let mut entries = Vec::new();
+    for entry in tar::Archive::new(tar.as_slice()).entries().unwrap() {
+        let mut entry = entry.unwrap();
+        assert_eq!(entry.header().mtime().unwrap(), 1_767_323_045);
+        let path = entry.path().unwrap().to_string_lossy().into_owned();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).unwrap();
+        entries.push((path, bytes));
+    }
+    entries
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[test]
+fn packages_export_with_manifest_license_and_checksums() {
+    let archive = package(NODES).unwrap();
+    let entries = unpack(&archive);
+    let paths = entries
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            "cgs-2026/manifest.json",
+            "cgs-2026/LICENSE",
+            "cgs-2026/nodes.jsonl",
+            "cgs-2026/SHA256SUMS",
+        ]
+    );
+
+    let manifest: DatasetManifest = serde_json::from_slice(&entries[0].1).unwrap();
+    assert_eq!(manifest.source_version_id, "cgs-2026");
+    assert_eq!(manifest.license, "CC0-1.0");
+    assert_eq!(manifest.created_at, "2026-01-02T03:04:05Z");
+    assert_eq!(manifest.node_count, 2);
+    assert_eq!(manifest.files[1].path, "nodes.jsonl");
+    assert_eq!(manifest.files[1].sha256, sha256_hex(NODES.as_bytes()));
+    assert_eq!(entries[2].1, NODES.as_bytes());
+
+    let checksums = String::from_utf8(entries[3].1.clone()).unwrap();
+    let expected = entries[..3]
+        .iter()
+        .map(|(path, bytes)| {
+            format!(
+                "{}  {}\n",
+                sha256_hex(bytes),
+                path.trim_start_matches("cgs-2026/")
+            )
+        })
+        .collect::<String>();
+    assert_eq!(checksums, expected);
+
+    assert_eq!(package(NODES).unwrap(), archive);
+}
+
+#[test]
+fn packages_chunks_added_one_at_a_time_like_a_single_export() {
+    let (first, second) = NODES.split_at(NODES.find('\n').unwrap() + 1);
+    let mut packager = DatasetPackager::new(options()).unwrap();
+    packager.add_nodes(first.as_bytes()).unwrap();
+    // Blank lines and a missing final newline don't change the dataset.
+    packager.add_nodes("\n".as_bytes()).unwrap();
+    packager.add_nodes(second.trim_end().as_bytes()).unwrap();
+    let mut archive = Vec::new();
+    packager.finish(&mut archive).unwrap();
+    assert_eq!(archive, package(NODES).unwrap());
+
+    let mut packager = DatasetPackager::new(options()).unwrap();
+    packager.add_nodes(NODES.as_bytes()).unwrap();
+    let error = packager
+        .add_nodes(NODES.replacen("cgs-2026", "cgs-2025", 1).as_bytes())
+        .unwrap_err();
+    assert!(error.contains("line 3"), "{error}");
+}
+
+#[test]
+fn rejects_nodes_from_another_source_version() {
+    let mixed = NODES.replacen("cgs-2026", "cgs-2025", 1);
+    let error = package(&mixed).unwrap_err();
+    assert!(error.contains("line 1"), "{error}");
+    assert!(package("\n").is_err());
+}
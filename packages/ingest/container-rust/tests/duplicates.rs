@@ -0,0 +1,62 @@
+mod common;
+
+use common::node;
+use ingest::runtime::duplicates::DuplicateDetector;
+use ingest::runtime::report::IngestReport;
+use ingest::runtime::review::ReviewRegistry;
+use ingest::types::NodePayload;
+use serde_json::json;
+
+const BODY: &str = "The commissioner shall adopt regulations to carry out the purposes \
+of this chapter, including standards for the licensing of facilities, the inspection of \
+premises, the keeping of records, and the imposition of civil penalties for violations \
+of any provision of this chapter or any regulation adopted under it.";
+
+fn section(id: &str, blocks: serde_json::Value) -> NodePayload {
+    node(id).blocks(blocks).build()
+}
+
+fn body(text: &str) -> serde_json::Value {
+    json!([{ "type": "body", "content": text }])
+}
+
+#[test]
+fn reports_renumbered_sections_with_identical_bodies() {
+    let detector = DuplicateDetector::new();
+    detector.observe(&section("s1", body(BODY)));
+    detector.observe(&section(
+        "s2",
+        json!([
+            { "type": "body", "content": BODY.to_uppercase() },
+            { "type": "note", "content": "Renumbered from section 1." }
+        ]),
+    ));
+    detector.observe(&section(
+        "s3",
+        body(
+            "Any person aggrieved by a final decision of the board may appeal to the \
+superior court within thirty days after notice of the decision is mailed, and the court \
+may affirm, reverse, or modify the decision or remand the matter for further proceedings \
+as justice requires under the uniform administrative procedure act.",
+        ),
+    ));
+
+    let pairs = detector.near_duplicates();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].node_id, "s1");
+    assert_eq!(pairs[0].other_node_id, "s2");
+    assert_eq!(pairs[0].similarity, 1.0);
+
+    let report = IngestReport::from_review(&ReviewRegistry::new()).with_duplicates(&detector);
+    assert_eq!(report.near_duplicates, pairs);
+    assert!(report.publish_blockers().is_empty());
+}
+
+#[test]
+fn ignores_short_boilerplate_bodies() {
+    let detector = DuplicateDetector::new();
+    detector.observe(&section("s1", body("Repealed by P.A. 90-1.")));
+    detector.observe(&section("s2", body("Repealed by P.A. 90-1.")));
+
+    assert!(detector.near_duplicates().is_empty());
+}
@@ -0,0 +1,76 @@
+use ingest::runtime::eta::{estimate_remaining, JobProgress, UnitTimings};
+use ingest::types::SourceKind;
+use std::time::{Duration, Instant};
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn schedules_queued_units_on_the_first_free_worker() {
+    assert_eq!(estimate_remaining(&[10.0], &[20.0, 5.0, 5.0], 2), 20.0);
+    assert_eq!(estimate_remaining(&[], &[20.0, 5.0, 5.0], 1), 30.0);
+    assert_eq!(estimate_remaining(&[3.0], &[], 8), 3.0);
+    assert_eq!(estimate_remaining(&[], &[], 8), 0.0);
+}
+
+#[test]
+fn estimates_from_history_and_this_runs_timings() {
+    let mut history = UnitTimings::default();
+    history.record("usc-1", 10.0);
+    history.record("usc-2", 20.0);
+    let progress = JobProgress::new("usc");
+    progress.plan(history, 1, ids(&["usc-1", "usc-2", "usc-3"]));
+    let start = Instant::now();
+
+    // usc-3 has no timing of its own, so it is expected to take the median.
+    assert_eq!(progress.snapshot_at(start).eta_secs, Some(50.0));
+
+    progress.start_at("usc-1", start);
+    let snapshot = progress.snapshot_at(start + Duration::from_secs(4));
+    assert_eq!(snapshot.running_units, 1);
+    assert_eq!(snapshot.eta_secs, Some(46.0));
+
+    progress.finish_at("usc-1", true, start + Duration::from_secs(8));
+    let snapshot = progress.snapshot_at(start + Duration::from_secs(8));
+    assert_eq!(snapshot.total_units, 3);
+    assert_eq!(snapshot.completed_units, 1);
+    assert_eq!(snapshot.running_units, 0);
+    assert_eq!(snapshot.eta_secs, Some(40.0));
+    assert_eq!(progress.timings().units["usc-1"], 8.0);
+}
+
+#[test]
+fn reports_no_eta_without_history_and_skips_failed_timings() {
+    let progress = JobProgress::new("cgs");
+    progress.plan(UnitTimings::default(), 8, ids(&["a", "b"]));
+    let start = Instant::now();
+    assert_eq!(progress.snapshot_at(start).eta_secs, None);
+
+    progress.start_at("a", start);
+    progress.finish_at("a", false, start + Duration::from_secs(5));
+    let snapshot = progress.snapshot_at(start + Duration::from_secs(5));
+    assert_eq!(snapshot.failed_units, 1);
+    assert_eq!(snapshot.eta_secs, None);
+    assert!(progress.timings().units.is_empty());
+
+    assert_eq!(UnitTimings::key(SourceKind::Cgs), "timings/cgs.json");
+}
+
+#[test]
+fn orders_units_slowest_first_with_unknown_units_at_the_median() {
+    let mut history = UnitTimings::default();
+    history.record("usc-1", 10.0);
+    history.record("usc-6", 60.0);
+    history.record("usc-7", 40.0);
+    history.record("usc-42", 1200.0);
+    let mut units = ids(&["usc-1", "usc-3", "usc-42", "usc-5", "usc-7"]);
+    history.order_slowest_first(&mut units, String::clone);
+
+    // usc-3 and usc-5 have no timing and take the median, keeping their order.
+    assert_eq!(units, ids(&["usc-42", "usc-3", "usc-5", "usc-7", "usc-1"]));
+
+    let mut units = ids(&["usc-2", "usc-1"]);
+    UnitTimings::default().order_slowest_first(&mut units, String::clone);
+    assert_eq!(units, ids(&["usc-2", "usc-1"]));
+}
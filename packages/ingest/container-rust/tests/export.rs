@@ -0,0 +1,114 @@
+mod common;
+
+use common::node;
+use ingest::export::print::{render_print_html, PrintOptions};
+use ingest::types::NodePayload;
+use serde_json::{json, Value};
+
+fn subtree_node(
+    id: &str,
+    parent_id: &str,
+    level_name: &str,
+    sort_order: i32,
+    name: &str,
+    blocks: Option<Value>,
+) -> NodePayload {
+    let node = node(id)
+        .parent(parent_id)
+        .level(level_name, 0)
+        .sort_order(sort_order)
+        .name(name)
+        .heading_citation(&format!("Cite {id}"))
+        .source_url(&format!("https://example.test/{id}"));
+    match blocks {
+        Some(blocks) => node.blocks(blocks),
+        None => node,
+    }
+    .build()
+}
+
+fn chapter_subtree() -> Vec<NodePayload> {
+    vec![
+        subtree_node("t1", "root", "title", 0, "Title One", None),
+        subtree_node(
+            "t1/c1/s2",
+            "t1/c1",
+            "section",
+            2,
+            "Second <section>",
+            Some(json!([
+                { "type": "body", "content": "See [§ 1](/statutes/section/1) & **more**." },
+                { "type": "note", "label": "History", "content": "Enacted 1990." }
+            ])),
+        ),
+        subtree_node("t1/c1", "t1", "chapter", 0, "Chapter One", None),
+        subtree_node(
+            "t1/c1/s1",
+            "t1/c1",
+            "section",
+            1,
+            "First",
+            Some(json!([
+                { "type": "body", "content": "Intro.\n\n> (a) Sub.\n\n> > (1) Para." }
+            ])),
+        ),
+        subtree_node("t1/c2", "t1", "chapter", 1, "Chapter Two", None),
+        subtree_node("t9", "root", "title", 9, "Unrelated", None),
+    ]
+}
+
+#[test]
+fn renders_subtree_in_sort_order_with_page_breaks() {
+    let html = render_print_html(&chapter_subtree(), "t1", &PrintOptions::default()).unwrap();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Title One</title>"));
+    assert!(html.contains("<section class=\"title\" id=\"t1\">"));
+    assert!(html.contains("<section class=\"chapter page-break\" id=\"t1/c1\">"));
+    assert!(html.contains("<p class=\"citation\">Cite t1/c1/s1</p>"));
+    assert!(!html.contains("Unrelated"));
+
+    let first = html.find("id=\"t1/c1/s1\"").unwrap();
+    let second = html.find("id=\"t1/c1/s2\"").unwrap();
+    let chapter_two = html.find("id=\"t1/c2\"").unwrap();
+    assert!(first < second && second < chapter_two);
+}
+
+#[test]
+fn renders_markdown_blocks_as_escaped_html() {
+    let html = render_print_html(&chapter_subtree(), "t1/c1", &PrintOptions::default()).unwrap();
+
+    assert!(html.contains("<h2>Second &lt;section&gt;</h2>"));
+    assert!(html.contains(
+        "<p>See <a href=\"/statutes/section/1\">§ 1</a> &amp; <strong>more</strong>.</p>"
+    ));
+    assert!(html.contains("<h3>History</h3>"));
+    assert!(html.contains("<p style=\"margin-left:2em\">(a) Sub.</p>"));
+    assert!(html.contains("<p style=\"margin-left:4em\">(1) Para.</p>"));
+    assert!(!html.contains("page-break\" id=\"t1/c1\""));
+}
+
+#[test]
+fn options_filter_blocks_and_add_source_urls() {
+    let options = PrintOptions {
+        title: Some("Download".to_string()),
+        page_break_levels: Vec::new(),
+        block_types: vec!["body".to_string()],
+        include_citations: false,
+        include_source_urls: true,
+    };
+    let html = render_print_html(&chapter_subtree(), "t1", &options).unwrap();
+
+    assert!(html.contains("<title>Download</title>"));
+    assert!(!html.contains("page-break\""));
+    assert!(!html.contains("class=\"citation\""));
+    assert!(!html.contains("Enacted 1990."));
+    assert!(html.contains("<p class=\"source\">Source: https://example.test/t1/c1/s1</p>"));
+    assert!(!html.contains("Source: https://example.test/t1/c1<"));
+}
+
+#[test]
+fn missing_root_is_an_error() {
+    let err = render_print_html(&chapter_subtree(), "nope", &PrintOptions::default()).unwrap_err();
+    assert!(err.contains("nope"));
+}
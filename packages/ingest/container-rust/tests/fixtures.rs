@@ -0,0 +1,400 @@
+#![cfg(all(
+    feature = "cgs",
+    feature = "mgl",
+    feature = "nh",
+    feature = "rigl",
+    feature = "usc",
+    feature = "vt"
+))]
+
+use ingest::sources::cgs::parser::{
+    extract_section_ids_from_toc, parse_cgs_chapter_html, CgsUnitKind,
+};
+use ingest::sources::mgl::parser::{
+    parse_chapter_detail, parse_part_detail, parse_section_content, MglApiChapter, MglApiPart,
+    MglApiSection,
+};
+use ingest::sources::usc::parser::{usc_stream_events, USCStreamEvent};
+use ingest::sources::{nh, rigl, vt};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+// Page URLs the index parsers resolve links against; each parser rejects
+// links off its source's host.
+const MGL_URL: &str = "https://malegislature.gov/api/Parts";
+const NH_URL: &str = "https://gc.nh.gov/rsa/html/";
+const RIGL_URL: &str = "https://webserver.rilegislature.gov/Statutes/Statutes.html";
+const VT_URL: &str = "https://legislature.vermont.gov/statutes/";
+
+/// One parsed unit: the id it would be stored under and the text emitted for it.
+struct Record {
+    id: String,
+    text: String,
+}
+
+impl Record {
+    fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(fixture_files(&path));
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Parses a fixture with the parser its source directory and file name call
+/// for. `None` means no route exists for that shape yet.
+fn parse_fixture(source: &str, name: &str, text: &str) -> Option<Result<Vec<Record>, String>> {
+    if name.ends_with(".body.md") {
+        return Some(Ok(vec![Record::new(name, text)]));
+    }
+    match source {
+        "cgs" => Some(Ok(parse_cgs(name, text))),
+        "mgl" if name.ends_with(".json") => Some(parse_mgl(text)),
+        "nh" => parse_nh(name, text),
+        "rigl" => parse_rigl(name, text),
+        "usc" if name.ends_with(".xml") => Some(parse_usc(name, text)),
+        "vt" => parse_vt(name, text),
+        _ => None,
+    }
+}
+
+fn parse_cgs(name: &str, html: &str) -> Vec<Record> {
+    if name.starts_with("cgs_title_") {
+        return extract_section_ids_from_toc(html)
+            .into_iter()
+            .map(|id| Record::new(id, ""))
+            .collect();
+    }
+    let kind = if name.starts_with("cgs_art_") {
+        CgsUnitKind::Article
+    } else {
+        CgsUnitKind::Chapter
+    };
+    let parsed = parse_cgs_chapter_html(html, "fixture", "", kind);
+    parsed
+        .sections
+        .into_iter()
+        .flat_map(|section| {
+            let notes = [
+                section.name,
+                section.history_short,
+                section.history_long,
+                section.citations,
+                section.see_also,
+            ];
+            std::iter::once(section.body)
+                .chain(notes.into_iter().flatten())
+                .map(|text| Record::new(section.string_id.clone(), text))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn parse_mgl(json: &str) -> Result<Vec<Record>, String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let sections = if value.get("Chapters").is_some() {
+        let part: MglApiPart = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        let part = parse_part_detail(&part, MGL_URL);
+        return Ok(vec![Record::new(part.part_code, part.part_name)]);
+    } else if value.get("Sections").is_some() {
+        let chapter: MglApiChapter = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        let parsed = parse_chapter_detail(&chapter, MGL_URL);
+        let mut records = vec![Record::new(parsed.chapter_code, parsed.chapter_name)];
+        records.extend(section_records(&chapter.Sections));
+        return Ok(records);
+    } else {
+        vec![serde_json::from_value::<MglApiSection>(value).map_err(|e| e.to_string())?]
+    };
+    Ok(section_records(&sections))
+}
+
+fn section_records(sections: &[MglApiSection]) -> Vec<Record> {
+    sections
+        .iter()
+        .filter(|section| section.Text.is_some())
+        .flat_map(|section| {
+            let content = parse_section_content(section);
+            [
+                Record::new(section.Code.clone(), content.heading),
+                Record::new(section.Code.clone(), content.body),
+            ]
+        })
+        .collect()
+}
+
+fn parse_nh(name: &str, html: &str) -> Option<Result<Vec<Record>, String>> {
+    use nh::parser::{
+        parse_chapter_index, parse_merged_chapter_sections, parse_section_detail,
+        parse_title_index, parse_title_links,
+    };
+
+    let section = |detail: nh::parser::NhSectionDetail| {
+        let mut records = vec![
+            Record::new(detail.section_num.clone(), detail.section_name),
+            Record::new(detail.section_num.clone(), detail.body),
+        ];
+        records.extend(
+            detail
+                .source_note
+                .map(|note| Record::new(detail.section_num, note)),
+        );
+        records
+    };
+    let records = if name == "nhtoc.htm" {
+        parse_title_links(html, &format!("{NH_URL}nhtoc.htm")).map(|titles| {
+            titles
+                .into_iter()
+                .map(|title| Record::new(title.title_num, title.title_name))
+                .collect()
+        })
+    } else if name.starts_with("title_") {
+        parse_title_index(html, &format!("{NH_URL}NHTOC/index.htm")).map(|title| {
+            title
+                .chapters
+                .into_iter()
+                .map(|chapter| Record::new(chapter.chapter_num, chapter.chapter_name))
+                .collect()
+        })
+    } else if name.starts_with("chapter_") && name.ends_with("_mrg.htm") {
+        parse_merged_chapter_sections(html)
+            .map(|sections| sections.into_iter().flat_map(section).collect())
+    } else if name.starts_with("chapter_") {
+        parse_chapter_index(html, &format!("{NH_URL}NHTOC/index.htm")).map(|chapter| {
+            chapter
+                .sections
+                .into_iter()
+                .map(|link| Record::new(link.section_num, link.section_name))
+                .collect()
+        })
+    } else if name.starts_with("section_") {
+        parse_section_detail(html).map(section)
+    } else {
+        return None;
+    };
+    Some(records)
+}
+
+fn parse_rigl(name: &str, html: &str) -> Option<Result<Vec<Record>, String>> {
+    use rigl::parser::{
+        parse_chapter_index, parse_section_detail, parse_title_index, parse_title_links,
+    };
+
+    let records = if name == "statutes.html" {
+        parse_title_links(html, RIGL_URL).map(|titles| {
+            titles
+                .into_iter()
+                .map(|title| Record::new(title.title_num, ""))
+                .collect()
+        })
+    } else if name.starts_with("title_") {
+        parse_title_index(html, RIGL_URL).map(|title| {
+            title
+                .chapters
+                .into_iter()
+                .map(|chapter| Record::new(chapter.chapter_num, chapter.chapter_name))
+                .collect()
+        })
+    } else if name.starts_with("chapter_") {
+        parse_chapter_index(html, RIGL_URL).map(|chapter| {
+            chapter
+                .sections
+                .into_iter()
+                .map(|link| Record::new(link.section_num, link.section_name))
+                .collect()
+        })
+    } else if name.starts_with("section_") {
+        parse_section_detail(html).map(|detail| {
+            let mut records = vec![
+                Record::new(detail.section_num.clone(), detail.section_name),
+                Record::new(detail.section_num.clone(), detail.body),
+            ];
+            records.extend(
+                detail
+                    .history
+                    .map(|history| Record::new(detail.section_num, history)),
+            );
+            records
+        })
+    } else {
+        return None;
+    };
+    Some(records)
+}
+
+fn parse_usc(name: &str, xml: &str) -> Result<Vec<Record>, String> {
+    let digits = name
+        .trim_start_matches("usc")
+        .trim_start_matches("_title_")
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    let title_num = if digits.is_empty() { "1" } else { &digits };
+
+    let mut records = Vec::new();
+    for event in usc_stream_events(xml, title_num) {
+        match event {
+            USCStreamEvent::Title(name) => records.push(Record::new(title_num, name)),
+            USCStreamEvent::Level(level) => {
+                records.push(Record::new(level.identifier, level.heading))
+            }
+            USCStreamEvent::Section(section) => {
+                records.push(Record::new(section.section_key.clone(), section.heading));
+                records.push(Record::new(section.section_key.clone(), section.body));
+                records.extend(section.blocks.into_iter().filter_map(|block| {
+                    block
+                        .content
+                        .map(|content| Record::new(section.section_key.clone(), content))
+                }));
+            }
+            USCStreamEvent::Error(err) => return Err(format!("{err:?}")),
+        }
+    }
+    Ok(records)
+}
+
+fn parse_vt(name: &str, html: &str) -> Option<Result<Vec<Record>, String>> {
+    use vt::parser::{parse_fullchapter_detail, parse_title_index, parse_title_links};
+
+    let records = if name == "statutes.html" {
+        parse_title_links(html, VT_URL).map(|titles| {
+            titles
+                .into_iter()
+                .map(|title| Record::new(title.title_num, title.title_name))
+                .collect()
+        })
+    } else if name.starts_with("title_") {
+        let code = name.trim_start_matches("title_").trim_end_matches(".html");
+        parse_title_index(html, &format!("{VT_URL}title/{code}")).map(|title| {
+            title
+                .chapters
+                .into_iter()
+                .map(|chapter| Record::new(chapter.chapter_num, chapter.chapter_name))
+                .collect()
+        })
+    } else if name.starts_with("fullchapter_") {
+        parse_fullchapter_detail(html, "", "").map(|chapter| {
+            chapter
+                .sections
+                .into_iter()
+                .flat_map(|section| {
+                    let mut records = vec![
+                        Record::new(section.section_num.clone(), section.section_name),
+                        Record::new(section.section_num.clone(), section.body),
+                    ];
+                    records.extend(
+                        section
+                            .history
+                            .map(|history| Record::new(section.section_num, history)),
+                    );
+                    records
+                })
+                .collect()
+        })
+    } else {
+        return None;
+    };
+    Some(records)
+}
+
+/// Describes the first way `text` is not balanced markdown: an odd number of
+/// `**` markers, unmatched square brackets, or a link without a target.
+fn markdown_problem(text: &str) -> Option<String> {
+    for paragraph in text.split("\n\n") {
+        if paragraph.matches("**").count() % 2 != 0 {
+            return Some(format!("unbalanced bold in {paragraph:?}"));
+        }
+        let mut depth = 0i32;
+        for ch in paragraph.chars() {
+            match ch {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            if depth < 0 {
+                return Some(format!("unmatched ']' in {paragraph:?}"));
+            }
+        }
+        if depth != 0 {
+            return Some(format!("unmatched '[' in {paragraph:?}"));
+        }
+        for (index, _) in paragraph.match_indices("](") {
+            let target = &paragraph[index + 2..];
+            let end = target.find(')');
+            if end.is_none_or(|end| end == 0 || target[..end].contains(char::is_whitespace)) {
+                return Some(format!("malformed link target in {paragraph:?}"));
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn every_committed_fixture_parses_cleanly() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut problems = Vec::new();
+    let mut parsed = 0;
+    for path in fixture_files(&root) {
+        let relative = path.strip_prefix(&root).unwrap().display().to_string();
+        let source = relative.split('/').next().unwrap_or_default();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let text = std::fs::read_to_string(&path).unwrap();
+
+        let outcome = catch_unwind(AssertUnwindSafe(|| parse_fixture(source, &name, &text)));
+        let records = match outcome {
+            Err(_) => {
+                problems.push(format!("{relative}: parser panicked"));
+                continue;
+            }
+            Ok(None) => {
+                problems.push(format!(
+                    "{relative}: no parser route; add one to tests/fixtures.rs"
+                ));
+                continue;
+            }
+            Ok(Some(Err(err))) => {
+                problems.push(format!("{relative}: {err}"));
+                continue;
+            }
+            Ok(Some(Ok(records))) => records,
+        };
+        parsed += 1;
+        for record in records {
+            if record.id.trim().is_empty() {
+                problems.push(format!("{relative}: empty id for {:?}", record.text));
+            }
+            if let Some(problem) = markdown_problem(&record.text) {
+                problems.push(format!("{relative} ({}): {problem}", record.id));
+            }
+        }
+    }
+
+    assert!(parsed > 0, "no fixtures found under {}", root.display());
+    assert!(problems.is_empty(), "{}", problems.join("\n"));
+}
+
+#[test]
+fn flags_unbalanced_markdown() {
+    assert_eq!(
+        markdown_problem("**(a)** See [section 2](/section/2)."),
+        None
+    );
+    assert_eq!(markdown_problem("[Repealed]\n\n**(b)** Text."), None);
+    assert!(markdown_problem("**(a) Text.").is_some());
+    assert!(markdown_problem("See [section 2(/section/2).").is_some());
+    assert!(markdown_problem("See section 2](/section/2).").is_some());
+    assert!(markdown_problem("See [section 2]().").is_some());
+}
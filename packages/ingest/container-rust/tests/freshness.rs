@@ -0,0 +1,138 @@
+use ingest::runtime::freshness::IngestedVersion;
+use ingest::types::SourceKind;
+use serde_json::json;
+
+fn ingested(source_id: &str, source_version_id: &str) -> IngestedVersion {
+    IngestedVersion {
+        source_id: source_id.to_string(),
+        source_version_id: source_version_id.to_string(),
+        completed_at: "2026-01-05T00:00:00Z".to_string(),
+        published: true,
+    }
+}
+
+#[test]
+fn ingested_version_is_behind_a_newer_upstream_version() {
+    let usc = ingested("usc", "usc-118-200");
+    assert_eq!(usc.is_behind("118-250"), Some(true));
+    assert_eq!(usc.is_behind("118-200"), Some(false));
+    assert_eq!(usc.is_behind("118-158"), Some(false));
+
+    let mgl = ingested("mgl", "mgl-2025-06-01");
+    assert_eq!(mgl.is_behind("2025-09-15"), Some(true));
+    assert_eq!(mgl.is_behind("undated-3f2a"), None);
+}
+
+#[test]
+fn ingested_version_round_trips_under_its_source_key() {
+    let version = ingested("cgs", "cgs-2025");
+    assert_eq!(IngestedVersion::key(SourceKind::Cgs), "ingested/cgs.json");
+
+    let value = serde_json::to_value(&version).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "sourceId": "cgs",
+            "sourceVersionId": "cgs-2025",
+            "completedAt": "2026-01-05T00:00:00Z",
+            "published": true,
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<IngestedVersion>(value).unwrap(),
+        version
+    );
+}
+
+#[cfg(all(feature = "server", feature = "cgs"))]
+#[tokio::test]
+async fn report_compares_the_ingested_version_with_an_upstream_probe() {
+    use axum::routing::get;
+    use axum::Router;
+    use ingest::runtime::freshness::freshness_report;
+    use ingest::sources::configs::SourcesConfig;
+
+    let record = serde_json::to_string(&ingested("cgs", "cgs-2025")).unwrap();
+    let app = Router::new()
+        .route(
+            "/current/pub/titles.htm",
+            get(|| async {
+                r#"<p>General Statutes of Connecticut, revised to January 1, 2026.</p>
+<a href="title_01.htm">Title 1 - Provisions of General Application</a>"#
+            }),
+        )
+        .route("/api/proxy/r2-read", get(move || async move { record }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let config: SourcesConfig = serde_json::from_value(json!({
+        "sources": {
+            "cgs": {
+                "name": "Connecticut General Statutes",
+                "jurisdiction": "state",
+                "region": "CT",
+                "doc_type": "statute",
+                "description": "Connecticut state statutory law",
+                "root_url": format!("{base}/current/pub/titles.htm"),
+            }
+        }
+    }))
+    .unwrap();
+
+    let report = freshness_report(&reqwest::Client::new(), &base, "token", &config).await;
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].source, SourceKind::Cgs);
+    assert_eq!(report[0].errors, Vec::<String>::new());
+    assert_eq!(
+        report[0]
+            .ingested
+            .as_ref()
+            .map(|v| v.source_version_id.as_str()),
+        Some("cgs-2025")
+    );
+    assert_eq!(report[0].upstream_version_id.as_deref(), Some("2026"));
+    assert_eq!(report[0].stale, Some(true));
+}
+
+#[cfg(all(feature = "server", feature = "uspl"))]
+#[tokio::test]
+async fn uspl_probe_reads_only_the_collections_pages() {
+    use axum::extract::{OriginalUri, State};
+    use axum::Router;
+    use ingest::runtime::freshness::probe_upstream_version;
+    use std::sync::{Arc, Mutex};
+
+    let requested = Arc::new(Mutex::new(Vec::<String>::new()));
+    let app = Router::new()
+        .fallback(
+            |State(requested): State<Arc<Mutex<Vec<String>>>>, OriginalUri(uri): OriginalUri| async move {
+                requested.lock().unwrap().push(uri.path().to_string());
+                json!({
+                    "packages": [
+                        { "packageId": "STATUTE-136", "lastModified": "2025-03-01T00:00:00Z", "congress": "117" },
+                        { "packageId": "STATUTE-137", "lastModified": "2025-09-22T00:00:00Z", "congress": "118" },
+                    ],
+                    "nextPage": null,
+                })
+                .to_string()
+            },
+        )
+        .with_state(requested.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let version = probe_upstream_version(
+        &reqwest::Client::new(),
+        SourceKind::Uspl,
+        &format!("{base}/collections/STATUTE/1999-01-01T00%3A00%3A00Z"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(version, "2025-09-22");
+    assert_eq!(
+        *requested.lock().unwrap(),
+        ["/collections/STATUTE/1999-01-01T00%3A00%3A00Z"]
+    );
+}
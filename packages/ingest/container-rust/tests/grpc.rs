@@ -0,0 +1,69 @@
+#![cfg(feature = "grpc")]
+
+use ingest::runtime::grpc::{LevelStats, Node, NodeBatch};
+use ingest::types::{NodeLevelStats, NodeMeta, NodePayload};
+use prost::Message;
+use serde_json::json;
+
+#[test]
+fn node_batches_round_trip_through_protobuf() {
+    let payload = NodePayload {
+        meta: NodeMeta {
+            id: "usc/v1/root/title-1/section-1".to_string(),
+            source_version_id: "v1".to_string(),
+            parent_id: Some("usc/v1/root/title-1".to_string()),
+            level_name: "section".to_string(),
+            level_index: 2,
+            sort_order: 7,
+            name: Some("Words denoting number".to_string()),
+            path: Some("/title/1/section/1".to_string()),
+            readable_id: Some("1 USC 1".to_string()),
+            heading_citation: None,
+            source_url: None,
+            accessed_at: Some("2026-01-01T00:00:00Z".to_string()),
+            review_required: true,
+        },
+        content: Some(json!({ "blocks": [{ "type": "body", "content": "Text." }] })),
+    };
+
+    let batch = NodeBatch {
+        sequence: 3,
+        unit_id: "title-1".to_string(),
+        nodes: vec![Node::from(&payload)],
+        level_stats: Vec::new(),
+    };
+    let decoded = NodeBatch::decode(batch.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(decoded, batch);
+    assert!(decoded.level_stats.is_empty());
+
+    let node = &decoded.nodes[0];
+    assert_eq!(node.parent_id.as_deref(), Some("usc/v1/root/title-1"));
+    assert_eq!(node.heading_citation, None);
+    assert!(node.review_required);
+    let content: serde_json::Value =
+        serde_json::from_str(node.content_json.as_deref().unwrap()).unwrap();
+    assert_eq!(content, payload.content.unwrap());
+}
+
+#[test]
+fn level_stats_batches_round_trip_through_protobuf() {
+    let level = NodeLevelStats {
+        id: "usc/v1/root/title-1".to_string(),
+        stats: ingest::types::LevelStats {
+            child_count: 3,
+            section_count: 41,
+            word_count: 9_000_000_000,
+        },
+    };
+
+    let batch = NodeBatch {
+        sequence: 4,
+        unit_id: String::new(),
+        nodes: Vec::new(),
+        level_stats: vec![LevelStats::from(&level)],
+    };
+    let decoded = NodeBatch::decode(batch.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(decoded, batch);
+    assert_eq!(decoded.level_stats[0].id, "usc/v1/root/title-1");
+    assert_eq!(decoded.level_stats[0].word_count, 9_000_000_000);
+}
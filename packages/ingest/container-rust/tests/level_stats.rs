@@ -0,0 +1,109 @@
+mod common;
+
+use common::node;
+use ingest::runtime::level_stats::LevelTree;
+use ingest::types::{LevelStats, NodeLevelStats, NodePayload};
+
+fn tree_node(id: &str, parent_id: &str, level_name: &str, body: Option<&str>) -> NodePayload {
+    let node = node(id).parent(parent_id).level(level_name, 0);
+    match body {
+        Some(body) => node.body(body),
+        None => node,
+    }
+    .build()
+}
+
+fn stats_of<'a>(levels: &'a [NodeLevelStats], id: &str) -> &'a LevelStats {
+    &levels
+        .iter()
+        .find(|level| level.id == id)
+        .expect("level stats")
+        .stats
+}
+
+#[test]
+fn counts_descendants_of_level_nodes_at_finish() {
+    let mut tree = LevelTree::new();
+
+    tree.observe(&tree_node("t1", "root", "title", None));
+    tree.observe(&tree_node("t1/c1", "t1", "chapter", None));
+    tree.observe(&tree_node(
+        "t1/c1/s1",
+        "t1/c1",
+        "section",
+        Some("one two three"),
+    ));
+    tree.observe(&tree_node(
+        "t1/c1/s2",
+        "t1/c1",
+        "section",
+        Some("four  five\nsix seven"),
+    ));
+    tree.observe(&tree_node("t1/c2", "t1", "chapter", None));
+    tree.observe(&tree_node("t1/s3", "t1", "section", Some("eight")));
+
+    let levels = tree.finish();
+    let ids: Vec<_> = levels.iter().map(|level| level.id.as_str()).collect();
+    assert_eq!(ids, ["t1", "t1/c1", "t1/c2"]);
+
+    assert_eq!(
+        stats_of(&levels, "t1"),
+        &LevelStats {
+            child_count: 3,
+            section_count: 3,
+            word_count: 8,
+        }
+    );
+    assert_eq!(
+        stats_of(&levels, "t1/c1"),
+        &LevelStats {
+            child_count: 2,
+            section_count: 2,
+            word_count: 7,
+        }
+    );
+    assert_eq!(stats_of(&levels, "t1/c2"), &LevelStats::default());
+    assert!(tree.finish().is_empty());
+}
+
+#[test]
+fn counts_sections_emitted_before_their_level_node() {
+    let mut tree = LevelTree::new();
+    tree.observe(&tree_node("c1/s1", "c1", "section", Some("alpha beta")));
+    tree.observe(&tree_node("c1", "root", "chapter", None));
+
+    let levels = tree.finish();
+    assert_eq!(
+        stats_of(&levels, "c1"),
+        &LevelStats {
+            child_count: 1,
+            section_count: 1,
+            word_count: 2,
+        }
+    );
+}
+
+#[test]
+fn level_emitted_by_several_units_counts_all_of_them() {
+    let mut tree = LevelTree::new();
+    for (volume, law) in [(127, "pl-5"), (128, "pl-76")] {
+        tree.observe(&tree_node("congress-113", "root", "congress", None));
+        tree.observe(&tree_node(
+            &format!("congress-113/{law}"),
+            "congress-113",
+            "law",
+            Some(&format!("Volume {volume}.")),
+        ));
+    }
+
+    let levels = tree.finish();
+    assert_eq!(levels.len(), 1);
+    assert_eq!(
+        stats_of(&levels, "congress-113"),
+        &LevelStats {
+            child_count: 2,
+            section_count: 2,
+            word_count: 4,
+        }
+    );
+}
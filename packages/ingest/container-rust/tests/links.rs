@@ -0,0 +1,118 @@
+#![cfg(all(feature = "mgl", feature = "nh", feature = "usc", feature = "vt"))]
+
+mod common;
+
+use common::{load_fixture, node, AdapterTestContext};
+use ingest::runtime::links::LinkChecker;
+use ingest::runtime::report::IngestReport;
+use ingest::runtime::review::ReviewRegistry;
+use ingest::runtime::types::QueueItem;
+use ingest::sources::nh::adapter::NhAdapter;
+use ingest::sources::vt::adapter::VtAdapter;
+use ingest::sources::SourceAdapter;
+use ingest::types::NodePayload;
+use serde_json::json;
+
+fn section_with_body(id: &str, path: &str, body: &str) -> NodePayload {
+    node(id)
+        .level("section", 2)
+        .name("Section")
+        .path(path)
+        .body(body)
+        .build()
+}
+
+#[tokio::test]
+async fn vt_links_between_emitted_sections_resolve() {
+    let mut t = AdapterTestContext::new(VtAdapter, "vt/v1/root");
+    t.add_fixture(
+        "https://legislature.vermont.gov/statutes/title/02",
+        &load_fixture("vt/title_02.html"),
+    );
+    t.add_fixture(
+        "https://legislature.vermont.gov/statutes/fullchapter/02/001",
+        &load_fixture("vt/fullchapter_02_001.html"),
+    );
+    t.add_fixture(
+        "https://legislature.vermont.gov/statutes/fullchapter/02/002",
+        &load_fixture("vt/fullchapter_02_002.html"),
+    );
+    t.run_item(QueueItem {
+        url: "https://legislature.vermont.gov/statutes/title/02".to_string(),
+        parent_id: "vt/v1/root".to_string(),
+        level_name: "title".to_string(),
+        level_index: 0,
+        metadata: json!({ "unit_id": "vt-title-02", "title_num": "02", "sort_order": 0 }),
+    })
+    .await;
+
+    let checker = LinkChecker::new(Vec::new());
+    for node in t.get_nodes() {
+        checker.observe(&VtAdapter, &node);
+    }
+
+    assert!(checker
+        .broken_links()
+        .iter()
+        .all(|link| link.href != "/statutes/section/02/001/2"));
+}
+
+#[test]
+fn reports_links_to_missing_sections_unless_known() {
+    let adapter = NhAdapter;
+    let checker = LinkChecker::new(vec!["/title/i/chapter/2/section/2-1".to_string()]);
+
+    checker.observe(
+        &adapter,
+        &section_with_body(
+            "nh/root/title-i/chapter-1/section-1-1",
+            "/title/i/chapter/1/section/1-1",
+            "See [1-2](/title/i/chapter/1/section/1-2), [2-1](/title/i/chapter/2/section/2-1) \
+             and [5 U.S.C. 552](/usc/title-5/section-552).",
+        ),
+    );
+    checker.observe(
+        &adapter,
+        &section_with_body(
+            "nh/root/title-i/chapter-1/section-1-3",
+            "/title/i/chapter/1/section/1-3",
+            "Cross-reference [1-1](/title/i/chapter/1/section/1-1).",
+        ),
+    );
+
+    let broken = checker.broken_links();
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].node_id, "nh/root/title-i/chapter-1/section-1-1");
+    assert_eq!(broken[0].href, "/title/i/chapter/1/section/1-2");
+
+    let report = IngestReport::from_review(&ReviewRegistry::new()).with_links(&checker);
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(
+        value["brokenLinks"][0]["href"],
+        "/title/i/chapter/1/section/1-2"
+    );
+}
+
+#[test]
+fn link_targets_match_each_source_inliner() {
+    use ingest::sources::mgl::adapter::MglAdapter;
+    use ingest::sources::usc::adapter::UscAdapter;
+
+    let usc = section_with_body("usc/t5/s552", "/section/5/552", "");
+    assert_eq!(
+        UscAdapter.link_target(&usc.meta).as_deref(),
+        Some("/statutes/section/5/552")
+    );
+
+    let mgl = section_with_body("mgl/s1", "/part/i/chapter/2/section/3", "");
+    assert_eq!(
+        MglAdapter.link_target(&mgl.meta).as_deref(),
+        Some("/statutes/chapter/2/section/3")
+    );
+
+    let vt = section_with_body("vt/s1", "/title/02/chapter/001/section/2", "");
+    assert_eq!(
+        VtAdapter.link_target(&vt.meta).as_deref(),
+        Some("/statutes/section/02/001/2")
+    );
+}
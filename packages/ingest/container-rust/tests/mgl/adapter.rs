@@ -37,6 +37,7 @@ async fn test_adapter_extracts_part_chapter_and_sections() {
 
     t.add_fixture(&item.url, &part_json);
     t.run_item(item).await;
+    t.assert_output_pinned(2, "50531c2d46c17187");
 
     t.expect_node("mgl/v1/root/part-i")
         .level("part")
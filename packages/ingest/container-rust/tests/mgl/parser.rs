@@ -1,3 +1,4 @@
+use crate::common::{assert_no_invisible_chars, load_fixture};
 use ingest::sources::mgl::parser::{
     designator_sort_order, normalize_designator, parse_chapter_detail, parse_section_content,
     MglApiChapter, MglApiSection,
@@ -53,3 +54,19 @@ fn test_parse_section_content() {
         .body
         .contains("A copy of the notice shall be filed."));
 }
+
+#[test]
+fn test_strips_soft_hyphens_and_zero_width_characters() {
+    let json = load_fixture("mgl/mgl_section_2_invisible.json");
+    let section: MglApiSection = serde_json::from_str(&json).unwrap();
+    let content = parse_section_content(&section);
+    assert_eq!(
+        content.heading,
+        "Sovereignty and jurisdiction of commonwealth"
+    );
+    assert_eq!(
+        content.body,
+        "The sovereignty and jurisdiction of the commonwealth shall extend to all places within its boundaries."
+    );
+    assert_no_invisible_chars(&format!("{content:?}"));
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "mgl")]
+
 mod common;
 mod mgl;
@@ -6,8 +6,9 @@ use ingest::runtime::types::QueueItem;
 use ingest::runtime::types::{BuildContext, IngestContext, NodeStore, UrlQueue};
 use ingest::sources::nh::adapter::NhAdapter;
 use ingest::sources::SourceAdapter;
-use ingest::types::{NodePayload, SectionContent};
+use ingest::types::{IngestProfile, NodePayload, SectionContent};
 use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 #[tokio::test]
 async fn adapter_emits_title_chapter_and_section_nodes() {
@@ -38,6 +39,7 @@ async fn adapter_emits_title_chapter_and_section_nodes() {
     };
 
     t.run_item(item).await;
+    t.assert_output_pinned(2, "ad0121b1ca42c194");
 
     t.expect_node("nh/v1/root/title-i")
         .level("title")
@@ -96,8 +98,20 @@ async fn adapter_parses_merged_ucc_chapter_when_section_links_absent() {
         .name("UNIFORM COMMERCIAL CODE")
         .path("/title/xxxiv-a/chapter/382-a");
 
+    t.expect_node("nh/v1/root/title-xxxiv-a/chapter-382-a/article-1")
+        .level("article")
+        .name("GENERAL PROVISIONS")
+        .path("/title/xxxiv-a/chapter/382-a/article/1")
+        .heading_citation("Article 1");
+
+    t.expect_node("nh/v1/root/title-xxxiv-a/chapter-382-a/article-1/part-1")
+        .level("part")
+        .path("/title/xxxiv-a/chapter/382-a/article/1/part/1");
+
     t.expect_node("nh/v1/root/title-xxxiv-a/chapter-382-a/section-382-a-1-101")
         .level("section")
+        .parent("nh/v1/root/title-xxxiv-a/chapter-382-a/article-1/part-1")
+        .path("/title/xxxiv-a/chapter/382-a/section/382-a-1-101")
         .readable_id("382-A:1-101")
         .content_contains("Uniform Commercial Code")
         .content_contains("Source");
@@ -119,6 +133,7 @@ async fn adapter_propagates_unit_id_when_queuing_nested_items() {
         node_store,
         MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         },
         MockUrlQueue {
             enqueued: queue.enqueued.clone(),
@@ -254,16 +269,19 @@ async fn adapter_supports_aggregated_batch_callbacks() {
             root_node_id: "nh/v1/root",
             accessed_at: "2024-01-01",
             unit_sort_order: 0,
+            profile: IngestProfile::Full,
         },
         nodes: Box::new(node_store.clone()),
         blobs: Arc::new(crate::common::MockBlobStore),
         cache: Arc::new(MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         }),
         queue: Arc::new(MockUrlQueue {
             enqueued: queue.enqueued.clone(),
         }),
         logger: Arc::new(crate::common::MockLogger),
+        cancel: CancellationToken::new(),
     };
 
     queue.enqueue(QueueItem {
@@ -1,4 +1,4 @@
-use crate::common::load_fixture;
+use crate::common::{assert_no_invisible_chars, load_fixture};
 use ingest::sources::nh::parser::{
     compare_designators, inline_nh_cross_references, normalize_designator, normalize_text,
     normalize_text_for_comparison, parse_chapter_index, parse_merged_chapter_sections,
@@ -119,6 +119,31 @@ fn parses_ucc_sections_from_merged_chapter_page() {
         .contains("2006, 169:1"));
 }
 
+#[test]
+fn attaches_article_and_part_levels_from_merged_chapter_page() {
+    let html = load_fixture("nh/chapter_382-a_mrg.htm");
+    let parsed = parse_merged_chapter_sections(&html).expect("merged chapter should parse");
+
+    let first = &parsed[0];
+    assert_eq!(first.levels.len(), 2);
+    assert_eq!(first.levels[0].level_name, "article");
+    assert_eq!(first.levels[0].num, "1");
+    assert_eq!(first.levels[0].name, "GENERAL PROVISIONS");
+    assert_eq!(first.levels[1].level_name, "part");
+    assert_eq!(first.levels[1].name, "General Provisions");
+
+    let definitions = parsed
+        .iter()
+        .find(|section| section.section_num == "382-A:1-201")
+        .expect("1-201 should parse");
+    assert_eq!(definitions.levels[0].num, "1");
+    assert_eq!(definitions.levels[1].num, "2");
+    assert_eq!(
+        definitions.levels[1].name,
+        "General Definitions and Principles of Interpretation"
+    );
+}
+
 #[test]
 fn compares_designators_deterministically() {
     assert_eq!(compare_designators("21-J:6-a", "21-J:6-b"), Ordering::Less);
@@ -166,3 +191,20 @@ fn baseline_extract_codesect_text(html: &str) -> String {
     }
     String::new()
 }
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let html = load_fixture("nh/section_21-j-31_invisible.htm");
+    let parsed = parse_section_detail(&html).expect("section detail should parse");
+    assert_eq!(parsed.section_name, "Penalty for Failure to File");
+    assert_eq!(
+        parsed.body,
+        "Any taxpayer who fails to file a return when due shall pay a penalty."
+    );
+    assert!(parsed
+        .source_note
+        .as_deref()
+        .expect("source note should exist")
+        .contains("1985, 204:1. 1991, 163:7."));
+    assert_no_invisible_chars(&format!("{parsed:?}"));
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "nh")]
+
 mod common;
 mod nh;
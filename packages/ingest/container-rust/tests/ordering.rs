@@ -0,0 +1,102 @@
+mod common;
+
+use common::{create_test_context, load_fixture, node, MockCache, MockNodeStore, MockUrlQueue};
+use ingest::runtime::ordering::{ParentOrder, ParentOrderedStore};
+use ingest::runtime::types::{NodeStore, QueueItem};
+use ingest::types::NodePayload;
+use std::collections::HashMap;
+
+fn child(id: &str, parent_id: &str) -> NodePayload {
+    node(id).parent(parent_id).build()
+}
+
+fn ids(nodes: &[NodePayload]) -> Vec<&str> {
+    nodes.iter().map(|node| node.meta.id.as_str()).collect()
+}
+
+/// The first node listed before a parent that is also in `nodes`.
+fn first_orphan(nodes: &[NodePayload]) -> Option<&str> {
+    let positions = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.meta.id.as_str(), index))
+        .collect::<HashMap<_, _>>();
+    nodes.iter().enumerate().find_map(|(index, node)| {
+        let parent = positions.get(node.meta.parent_id.as_deref()?)?;
+        (*parent > index).then_some(node.meta.id.as_str())
+    })
+}
+
+#[test]
+fn releases_children_of_released_parents_immediately() {
+    let mut order = ParentOrder::new("root");
+    assert_eq!(ids(&order.release(child("t1", "root"))), ["t1"]);
+    assert_eq!(ids(&order.release(child("t1/s1", "t1"))), ["t1/s1"]);
+}
+
+#[test]
+fn holds_children_until_their_parent_arrives() {
+    let mut order = ParentOrder::new("root");
+    assert_eq!(ids(&order.release(child("t1", "root"))), ["t1"]);
+
+    assert!(order.release(child("t1/c1/s1", "t1/c1")).is_empty());
+    assert!(order.release(child("t1/c1/s2", "t1/c1")).is_empty());
+    assert_eq!(
+        ids(&order.release(child("t1/c1", "t1"))),
+        ["t1/c1", "t1/c1/s1", "t1/c1/s2"]
+    );
+    assert!(order.finish().is_empty());
+}
+
+#[test]
+fn releases_children_of_a_parent_that_never_arrives_at_finish() {
+    let mut order = ParentOrder::new("root");
+    assert!(order.release(child("t1/c9/p1/s1", "t1/c9/p1")).is_empty());
+    assert!(order.release(child("t1/c9/p1", "t1/c9")).is_empty());
+    assert!(order.release(child("t1/c9/s2", "t1/c9")).is_empty());
+
+    assert_eq!(
+        ids(&order.finish()),
+        ["t1/c9/p1", "t1/c9/p1/s1", "t1/c9/s2"]
+    );
+}
+
+#[cfg(feature = "usc")]
+#[tokio::test]
+async fn usc_title_reaches_the_store_parents_first() {
+    use ingest::sources::usc::adapter::UscAdapter;
+    use ingest::sources::SourceAdapter;
+
+    let item = QueueItem {
+        url: "http://example.com".to_string(),
+        parent_id: "root".to_string(),
+        level_name: "title".to_string(),
+        level_index: 0,
+        metadata: serde_json::json!({ "title_num": "42" }),
+    };
+    let run = |ordered: bool| {
+        let item = item.clone();
+        async move {
+            let store = MockNodeStore::new();
+            let cache = MockCache::new();
+            cache.add_fixture(&item.url, &load_fixture("usc/usc42_s302.xml"));
+            let mut context =
+                create_test_context(store.clone(), cache, MockUrlQueue::new(), "v1", "root");
+            if ordered {
+                context.nodes = Box::new(ParentOrderedStore::new(store.clone(), "root"));
+            }
+            UscAdapter.process_url(&mut context, &item).await.unwrap();
+            context.nodes.flush().await.unwrap();
+            let nodes = store.nodes.lock().unwrap().clone();
+            nodes
+        }
+    };
+
+    // The parser emits each level after the sections inside it.
+    let emitted = run(false).await;
+    assert!(first_orphan(&emitted).is_some());
+
+    let stored = run(true).await;
+    assert_eq!(stored.len(), emitted.len());
+    assert_eq!(first_orphan(&stored), None);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::node;
+use ingest::sources::paths::{
+    path_templates, slugify, PathIndex, PathTemplates, SlugIndex, DEFAULT_SLUG_STOP_WORDS,
+    MGL_PATHS, USC_PATHS,
+};
+use ingest::types::{NodeMeta, SourceKind};
+
+fn meta(id: &str, path: &str) -> NodeMeta {
+    node(id).level("part", 1).path(path).build().meta
+}
+
+#[test]
+fn renders_level_templates() {
+    assert_eq!(
+        USC_PATHS.render("section", &[("title", "10"), ("num", "101")]),
+        Ok("/section/10/101".to_string())
+    );
+    assert_eq!(
+        USC_PATHS.render("part", &[("title", "10"), ("levels", "subtitle-A/part-I")]),
+        Ok("/10/subtitle-A/part-I".to_string())
+    );
+    assert_eq!(
+        path_templates(SourceKind::Vt).render(
+            "subchapter",
+            &[
+                ("parent", "/title/1/chapter/3"),
+                ("level", "subchapter"),
+                ("num", "2")
+            ]
+        ),
+        Ok("/title/1/chapter/3/subchapter/2".to_string())
+    );
+}
+
+#[test]
+fn rejects_missing_and_unused_values() {
+    let error = MGL_PATHS.render("chapter", &[("num", "1")]).unwrap_err();
+    assert!(error.contains("{part}"), "{error}");
+
+    let error = MGL_PATHS
+        .render("part", &[("num", "i"), ("chapter", "1")])
+        .unwrap_err();
+    assert!(error.contains("does not use chapter"), "{error}");
+
+    assert!(MGL_PATHS.render("article", &[("num", "1")]).is_err());
+    assert!(PathTemplates::new(&[("part", "/part/{num")])
+        .render("part", &[("num", "1")])
+        .is_err());
+}
+
+#[test]
+fn moves_the_second_node_rendering_to_the_same_path_aside() {
+    let mut index = PathIndex::new();
+    let mut subtitle = meta("usc/t10/subtitle-a", "/10/subtitle-A");
+    assert_eq!(index.claim(&mut subtitle), None);
+    assert_eq!(index.claim(&mut subtitle), None);
+
+    let mut part = meta("usc/t10/part-a", "/10/subtitle-A");
+    let reason = index
+        .claim(&mut part)
+        .expect("collision should be reported");
+    assert!(reason.contains("usc/t10/subtitle-a"), "{reason}");
+    assert_eq!(part.path.as_deref(), Some("/10/subtitle-A-2"));
+    assert_eq!(subtitle.path.as_deref(), Some("/10/subtitle-A"));
+
+    let mut chapter = meta("usc/t10/chapter-a", "/10/subtitle-A");
+    assert!(index.claim(&mut chapter).is_some());
+    assert_eq!(chapter.path.as_deref(), Some("/10/subtitle-A-3"));
+
+    let mut part_again = meta("usc/t10/part-a", "/10/subtitle-A");
+    assert_eq!(index.claim(&mut part_again), None);
+    assert_eq!(part_again.path.as_deref(), Some("/10/subtitle-A-2"));
+}
+
+fn stop_words() -> Vec<String> {
+    DEFAULT_SLUG_STOP_WORDS
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+fn section(id: &str, parent_id: &str, name: &str) -> NodeMeta {
+    NodeMeta {
+        parent_id: Some(parent_id.to_string()),
+        name: Some(name.to_string()),
+        path: None,
+        ..meta(id, "")
+    }
+}
+
+#[test]
+fn slugifies_headings_without_stop_words() {
+    let stop_words = stop_words();
+    let slug = |heading: &str| slugify(heading, &stop_words);
+    assert_eq!(slug("Definitions."), Some("definitions".to_string()));
+    assert_eq!(slug("Short title"), Some("short-title".to_string()));
+    assert_eq!(
+        slug("Suspension of driver\u{2019}s license for failure to pay"),
+        Some("suspension-drivers-license-failure-pay".to_string())
+    );
+    assert_eq!(slug("[Repealed]"), Some("repealed".to_string()));
+    assert_eq!(slug("Of and the"), Some("of-and-the".to_string()));
+    assert_eq!(slug("\u{a7}\u{a7} \u{2014}"), None);
+
+    let long = slug(&"appropriations ".repeat(10)).unwrap();
+    assert!(long.len() <= 60, "{long}");
+    assert!(long.ends_with("appropriations"), "{long}");
+}
+
+#[test]
+fn numbers_repeated_headings_under_the_same_parent_only() {
+    let mut index = SlugIndex::new(stop_words());
+    let assign = |index: &mut SlugIndex, id: &str, parent: &str| {
+        index.assign(&section(id, parent, "Definitions")).unwrap()
+    };
+
+    assert_eq!(assign(&mut index, "c1/s1", "c1"), "definitions");
+    assert_eq!(assign(&mut index, "c1/s2", "c1"), "definitions-2");
+    assert_eq!(assign(&mut index, "c2/s1", "c2"), "definitions");
+    assert_eq!(assign(&mut index, "c1/s1", "c1"), "definitions");
+    assert_eq!(assign(&mut index, "c1/s3", "c1"), "definitions-3");
+
+    let unnamed = NodeMeta {
+        name: None,
+        ..section("c1/s4", "c1", "")
+    };
+    assert_eq!(index.assign(&unnamed), None);
+}
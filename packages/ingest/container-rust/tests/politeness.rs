@@ -0,0 +1,71 @@
+use ingest::runtime::politeness::PolitenessTracker;
+use ingest::runtime::report::IngestReport;
+use ingest::runtime::review::ReviewRegistry;
+use std::time::{Duration, Instant};
+
+#[test]
+fn summarizes_requests_rate_and_429s_per_host() {
+    let tracker = PolitenessTracker::new();
+    let start = Instant::now();
+
+    tracker.record_at("https://www.govinfo.gov/a.xml", 200, Some(33), start);
+    tracker.record_at(
+        "https://WWW.GOVINFO.GOV/b.xml",
+        429,
+        Some(10),
+        start + Duration::from_secs(1),
+    );
+    tracker.record_at(
+        "https://www.govinfo.gov/c.xml",
+        200,
+        None,
+        start + Duration::from_secs(2),
+    );
+    tracker.record_at("https://gc.nh.gov/rsa/html/1.htm", 200, Some(10), start);
+
+    let summary = tracker.summary();
+    assert_eq!(summary.len(), 2);
+
+    let nh = &summary[0];
+    assert_eq!(nh.host, "gc.nh.gov");
+    assert_eq!(nh.requests, 1);
+    assert_eq!(nh.average_requests_per_second, None);
+
+    let govinfo = &summary[1];
+    assert_eq!(govinfo.host, "www.govinfo.gov");
+    assert_eq!(govinfo.requests, 3);
+    assert_eq!(govinfo.average_requests_per_second, Some(1.0));
+    assert_eq!(govinfo.throttle_requests_per_second, Some(10));
+    assert_eq!(govinfo.throttled_requests, 2);
+    assert_eq!(govinfo.rate_limited_responses, 1);
+}
+
+#[test]
+fn report_includes_compliance_section() {
+    let tracker = PolitenessTracker::new();
+    tracker.record(
+        "https://legislature.vermont.gov/statutes/title/02",
+        200,
+        None,
+    );
+
+    let report = IngestReport::from_review(&ReviewRegistry::new()).with_compliance(&tracker);
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(value["compliance"][0]["host"], "legislature.vermont.gov");
+    assert_eq!(value["compliance"][0]["requests"], 1);
+    assert_eq!(value["compliance"][0]["rateLimitedResponses"], 0);
+}
+
+#[test]
+fn counts_origin_requests_apart_from_cache_hits() {
+    let tracker = PolitenessTracker::new();
+    tracker.record("https://www.govinfo.gov/a.xml", 429, Some(10));
+    tracker.record_cached("https://www.govinfo.gov/b.xml", Some(10));
+    tracker.record_cached("https://www.govinfo.gov/c.xml", None);
+
+    let summary = tracker.summary();
+    assert_eq!(summary[0].requests, 3);
+    assert_eq!(summary[0].origin_requests, 1);
+    assert_eq!(summary[0].rate_limited_responses, 1);
+    assert_eq!(summary[0].throttled_requests, 2);
+}
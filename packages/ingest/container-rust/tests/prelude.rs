@@ -0,0 +1,67 @@
+#![cfg(all(
+    feature = "usc",
+    feature = "cgs",
+    feature = "mgl",
+    feature = "nh",
+    feature = "rigl",
+    feature = "vt",
+    feature = "uspl"
+))]
+
+use ingest::prelude::*;
+use std::fs;
+use std::path::Path;
+
+fn load_fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    fs::read_to_string(path).expect("fixture should exist")
+}
+
+#[test]
+fn prelude_exposes_usc_parser() {
+    let xml = load_fixture("usc/usc_title_1.xml");
+    let result: USCParseResult = parse_usc_xml(&xml, "1", "");
+    assert_eq!(result.title_num, "1");
+    assert!(!result.sections.is_empty());
+
+    let mut sections = 0;
+    parse_usc_xml_stream(&xml, "1", |event| {
+        if let USCStreamEvent::Section(_) = event {
+            sections += 1;
+        }
+    });
+    assert_eq!(sections, result.sections.len());
+}
+
+#[test]
+fn prelude_exposes_adapters_and_source_kinds() {
+    let adapters: [(&str, &dyn SourceAdapter); 7] = [
+        ("usc", &USC_ADAPTER),
+        ("cgs", &CGS_ADAPTER),
+        ("mgl", &MGL_ADAPTER),
+        ("nh", &NH_ADAPTER),
+        ("rigl", &RIGL_ADAPTER),
+        ("vt", &VT_ADAPTER),
+        ("uspl", &USPL_ADAPTER),
+    ];
+    for (name, adapter) in adapters {
+        let kind: SourceKind = serde_json::from_value(serde_json::json!(name)).unwrap();
+        let item = QueueItem {
+            url: String::new(),
+            parent_id: "root".to_string(),
+            level_name: "other".to_string(),
+            level_index: 0,
+            metadata: serde_json::Value::Null,
+        };
+        assert_eq!(
+            adapter_for(kind).unit_label(&item),
+            adapter.unit_label(&item)
+        );
+        assert_eq!(
+            try_adapter_for(kind).unwrap().unit_label(&item),
+            adapter.unit_label(&item)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use async_trait::async_trait;
+use common::MockCache;
+use ingest::runtime::provenance::{
+    FetchLog, FetchRecord, ProvenanceCache, UnitManifest, UpstreamResponse,
+};
+use ingest::runtime::types::Cache;
+use std::sync::Arc;
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+/// Cache that reports its own fetch records, as the HTTP cache does.
+struct RecordingCache {
+    inner: MockCache,
+    log: FetchLog,
+}
+
+#[async_trait]
+impl Cache for RecordingCache {
+    async fn fetch_cached(
+        &self,
+        url: &str,
+        key: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        let content = self
+            .inner
+            .fetch_cached(url, key, throttle_requests_per_second)
+            .await?;
+        let mut record = FetchRecord::new(url, Some(key), 200, b"zip archive bytes");
+        record
+            .headers
+            .insert("etag".to_string(), "\"v1\"".to_string());
+        self.log.record(record);
+        Ok(content)
+    }
+
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        self.inner
+            .fetch_uncached(url, throttle_requests_per_second)
+            .await
+    }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        self.inner.read_artifact(key).await
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        self.inner.write_artifact(key, content).await
+    }
+
+    fn take_fetch_record(&self, url: &str) -> Option<FetchRecord> {
+        self.log.take(url)
+    }
+}
+
+#[tokio::test]
+async fn records_fetches_from_content_when_cache_has_no_records() {
+    let inner = MockCache::new();
+    inner.add_fixture("https://example.test/a", "abc");
+    let cache = ProvenanceCache::new(Arc::new(inner));
+
+    assert_eq!(
+        cache
+            .fetch_cached("https://example.test/a", "src/a.html", None)
+            .await
+            .unwrap(),
+        "abc"
+    );
+    cache
+        .fetch_uncached("https://example.test/a", None)
+        .await
+        .unwrap();
+    assert!(cache
+        .fetch_cached("https://example.test/missing", "k", None)
+        .await
+        .is_err());
+
+    let fetches = cache.fetches();
+    assert_eq!(fetches.len(), 2);
+    assert_eq!(fetches[0].cache_key.as_deref(), Some("src/a.html"));
+    assert_eq!(fetches[0].sha256, ABC_SHA256);
+    assert_eq!(fetches[0].size_bytes, 3);
+    assert_eq!(fetches[1].cache_key, None);
+}
+
+#[tokio::test]
+async fn prefers_the_cache_layers_own_fetch_record() {
+    let inner = MockCache::new();
+    inner.add_fixture("https://example.test/title.zip", "<xml/>");
+    let cache = ProvenanceCache::new(Arc::new(RecordingCache {
+        inner,
+        log: FetchLog::default(),
+    }));
+
+    cache
+        .fetch_cached("https://example.test/title.zip", "usc/title.zip", None)
+        .await
+        .unwrap();
+
+    let fetches = cache.fetches();
+    assert_eq!(fetches.len(), 1);
+    assert_eq!(fetches[0].size_bytes, "zip archive bytes".len() as u64);
+    assert_eq!(
+        fetches[0].headers.get("etag").map(String::as_str),
+        Some("\"v1\"")
+    );
+
+    let manifest = UnitManifest {
+        unit_id: "title-1".to_string(),
+        source_version_id: "usc-2026".to_string(),
+        url: "https://example.test/title.zip".to_string(),
+        parser_version: 1,
+        artifact_namespace: "usc/parser-v1/0000000000000000".to_string(),
+        started_at: "2026-01-01T00:00:00Z".to_string(),
+        finished_at: "2026-01-01T00:01:00Z".to_string(),
+        completed: true,
+        fetches,
+    };
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(json["parserVersion"], 1);
+    assert_eq!(json["fetches"][0]["cacheKey"], "usc/title.zip");
+    assert_eq!(
+        serde_json::from_value::<UnitManifest>(json).unwrap(),
+        manifest
+    );
+}
+
+#[test]
+fn fetch_record_takes_origin_response_from_proxy_header() {
+    let header = urlencoding::encode(
+        r#"{"status":200,"fetchedAt":"2026-01-02T03:04:05.000Z","headers":{"ETag":"\"v7\""}}"#,
+    );
+    let upstream = UpstreamResponse::from_header(&header).expect("header should parse");
+    let record =
+        FetchRecord::new("https://example.com/a", Some("a"), 200, b"abc").with_upstream(upstream);
+
+    assert_eq!(record.fetched_at, "2026-01-02T03:04:05.000Z");
+    assert_eq!(
+        record.headers.get("etag").map(String::as_str),
+        Some("\"v7\"")
+    );
+    assert_eq!(record.sha256, ABC_SHA256);
+    assert!(UpstreamResponse::from_header("not json").is_none());
+}
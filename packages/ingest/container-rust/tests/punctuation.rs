@@ -0,0 +1,81 @@
+mod common;
+
+use common::node;
+use ingest::sources::punctuation::{NormalizationRule, SourceText};
+use ingest::types::NodePayload;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+const SOURCE: &str = "<p>Sec.&nbsp;1. <b>Fees</b> \u{2014} \u{201c}fee\u{201d}\n   means co\u{00ad}operative <i>dues</i>.</p>";
+
+fn section(id: &str, name: &str, body: &str) -> NodePayload {
+    node(id).name(name).body(body).build()
+}
+
+#[test]
+fn counts_characters_changed_per_rule() {
+    let source = SourceText::new(SOURCE);
+    let changed = source
+        .compare("Sec. 1. Fees - \"fee\" means cooperative dues.")
+        .unwrap();
+    assert_eq!(
+        changed,
+        BTreeMap::from([
+            (NormalizationRule::Whitespace, 3),
+            (NormalizationRule::NonBreakingSpace, 1),
+            (NormalizationRule::Dash, 1),
+            (NormalizationRule::Quote, 2),
+            (NormalizationRule::Invisible, 1),
+        ])
+    );
+    assert_eq!(
+        source.compare("Fees \u{2014} \u{201c}fee\u{201d}"),
+        Some(BTreeMap::new())
+    );
+}
+
+#[test]
+fn rejects_text_the_rules_do_not_explain() {
+    let source = SourceText::new(SOURCE);
+    assert_eq!(source.compare("Fees \"fee\" means"), None);
+    assert_eq!(source.compare("Sec. 1. Fees -- \"fee\""), None);
+    assert_eq!(source.compare("Fees - 'fee'"), None);
+}
+
+#[test]
+fn reads_ampersands_followed_by_multibyte_text() {
+    let source = SourceText::new("<p>R&\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9} &amp; co.</p>");
+    assert_eq!(
+        source.compare("R&\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9} & co."),
+        Some(BTreeMap::new())
+    );
+}
+
+#[test]
+fn audits_sampled_headings_and_body_paragraphs() {
+    let source = SourceText::new(
+        &json!({
+            "Name": "Board of \u{201c}trustees\u{201d}",
+            "Text": "<p>(a) See section&nbsp;2.</p><table><tr><td>Rate</td><td>3.0%</td></tr></table>"
+        })
+        .to_string(),
+    );
+    let nodes = [
+        section(
+            "s1",
+            "Board of \"trustees\"",
+            "(a) See [section 2](/section/2).\n\n| Rate | 3.0% |\n| --- | --- |",
+        ),
+        section("s2", "Not in the source", ""),
+        section("s3", "Board of trustees", "**(a)** See section 2."),
+    ];
+
+    let audit = source.audit(&nodes, 2);
+    assert_eq!(audit.fragments_checked, 6);
+    assert_eq!(audit.fragments_matched, 5);
+    assert_eq!(audit.changed[&NormalizationRule::Quote], 2);
+    assert_eq!(audit.changed[&NormalizationRule::NonBreakingSpace], 2);
+    assert_eq!(audit.unmatched.len(), 1);
+    assert_eq!(audit.unmatched[0].node_id, "s3");
+    assert_eq!(audit.unmatched[0].field, "heading");
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::node;
+use ingest::runtime::report::IngestReport;
+use ingest::runtime::review::{
+    lint_node, lint_node_for_profile, review_node, ReviewRegistry, ReviewSource,
+};
+use ingest::types::{IngestProfile, NodePayload};
+use serde_json::json;
+
+fn section_node(id: &str, name: Option<&str>, body: &str) -> NodePayload {
+    let section = node(id).body(body);
+    match name {
+        Some(name) => section.name(name),
+        None => section,
+    }
+    .build()
+}
+
+#[test]
+fn lint_accepts_named_section_with_body() {
+    let node = section_node(
+        "root/section-1",
+        Some("Definitions."),
+        "As used in this title.",
+    );
+    assert!(lint_node(&node).is_empty());
+}
+
+#[test]
+fn lint_reports_missing_name_and_empty_body() {
+    let node = section_node("root/section-1", None, "  ");
+    assert_eq!(
+        lint_node(&node),
+        vec![
+            "missing name".to_string(),
+            "section has no body text".to_string()
+        ]
+    );
+}
+
+#[test]
+fn notes_only_lint_accepts_section_without_blocks() {
+    let node = section_node("root/section-1", Some("Definitions."), "");
+    assert!(lint_node_for_profile(&node, IngestProfile::NotesOnly).is_empty());
+
+    let registry = ReviewRegistry::for_profile(IngestProfile::NotesOnly);
+    let mut node = node;
+    review_node(&mut node, &registry);
+    assert!(!node.meta.review_required);
+}
+
+#[test]
+fn review_sets_flag_and_collects_node_ids() {
+    let registry = ReviewRegistry::new();
+
+    let mut clean = section_node("root/section-1", Some("Definitions."), "Text.");
+    review_node(&mut clean, &registry);
+    assert!(!clean.meta.review_required);
+
+    let mut empty = section_node("root/section-2", Some("Repealed."), "");
+    review_node(&mut empty, &registry);
+    assert!(empty.meta.review_required);
+
+    let mut duplicate = section_node("root/section-1", Some("Definitions."), "Text.");
+    review_node(&mut duplicate, &registry);
+    assert!(duplicate.meta.review_required);
+
+    let mut adapter_flagged = section_node("root/section-3", Some("Terms."), "Text.");
+    adapter_flagged.meta.review_required = true;
+    review_node(&mut adapter_flagged, &registry);
+
+    let sources: Vec<ReviewSource> = registry.flags().iter().map(|flag| flag.source).collect();
+    assert_eq!(
+        sources,
+        vec![
+            ReviewSource::Linter,
+            ReviewSource::AnomalyDetector,
+            ReviewSource::Validator
+        ]
+    );
+
+    let report = IngestReport::from_review(&registry);
+    assert_eq!(
+        report.review_node_ids,
+        vec!["root/section-2", "root/section-1", "root/section-3"]
+    );
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(value["reviewFlags"][1]["source"], "anomaly_detector");
+    assert_eq!(value["reviewFlags"][1]["nodeId"], "root/section-1");
+}
+
+#[test]
+fn levels_re_emitted_by_another_unit_are_not_duplicates() {
+    let registry = ReviewRegistry::new();
+    let congress = || {
+        node("root/congress-118")
+            .level("congress", 0)
+            .name("118th Congress")
+    };
+
+    for _ in 0..2 {
+        let mut level = congress().build();
+        review_node(&mut level, &registry);
+        assert!(!level.meta.review_required);
+    }
+
+    let mut renamed = congress().name("119th Congress").build();
+    review_node(&mut renamed, &registry);
+    assert!(renamed.meta.review_required);
+
+    let mut with_content = congress().body("Text.").build();
+    review_node(&mut with_content, &registry);
+    assert!(with_content.meta.review_required);
+
+    let flags = registry.flags();
+    assert_eq!(flags.len(), 2);
+    assert!(flags
+        .iter()
+        .all(|flag| flag.source == ReviewSource::AnomalyDetector));
+}
+
+#[test]
+fn review_flag_is_omitted_from_unflagged_node_json() {
+    let node = section_node("root/section-1", Some("Definitions."), "Text.");
+    let value = serde_json::to_value(&node.meta).unwrap();
+    assert!(value.get("review_required").is_none());
+}
+
+#[test]
+fn publish_is_blocked_by_failed_units_and_anomalies_only() {
+    let registry = ReviewRegistry::new();
+    registry.flag("root/section-1", ReviewSource::Linter, "missing name");
+    registry.flag(
+        "root/section-2",
+        ReviewSource::Validator,
+        "flagged during parsing",
+    );
+    let report = IngestReport::from_review(&registry);
+    assert!(report.publish_blockers().is_empty());
+
+    registry.flag(
+        "root/section-1",
+        ReviewSource::AnomalyDetector,
+        "duplicate node id",
+    );
+    let mut report = IngestReport::from_review(&registry);
+    report.failed_units = vec!["title-1".to_string()];
+    assert_eq!(
+        report.publish_blockers(),
+        vec![
+            "1 unit(s) failed: title-1".to_string(),
+            "1 anomaly flag(s) raised".to_string()
+        ]
+    );
+}
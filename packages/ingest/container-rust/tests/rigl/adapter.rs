@@ -6,8 +6,9 @@ use ingest::runtime::types::QueueItem;
 use ingest::runtime::types::{BuildContext, IngestContext, NodeStore, UrlQueue};
 use ingest::sources::rigl::adapter::RiglAdapter;
 use ingest::sources::SourceAdapter;
-use ingest::types::{NodePayload, SectionContent};
+use ingest::types::{IngestProfile, NodePayload, SectionContent};
 use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 #[tokio::test]
 async fn adapter_emits_title_chapter_and_section_nodes() {
@@ -40,6 +41,7 @@ async fn adapter_emits_title_chapter_and_section_nodes() {
     };
 
     t.run_item(item).await;
+    t.assert_output_pinned(2, "aa936997d4de7f6f");
 
     t.expect_node("rigl/v1/root/title-1")
         .level("title")
@@ -108,6 +110,51 @@ async fn adapter_handles_reserved_chapters_without_sections() {
     );
 }
 
+#[tokio::test]
+async fn adapter_nests_sections_under_chapter_index_parts() {
+    let mut t = AdapterTestContext::new(RiglAdapter, "rigl/v1/root");
+
+    let chapter_url = "https://webserver.rilegislature.gov/Statutes/TITLE6A/6A-9/INDEX.htm";
+    t.add_fixture(
+        chapter_url,
+        r#"<html><body>
+            <h2><center>Chapter 9<br>Secured Transactions</center></h2>
+            <h4>Part 1 General Provisions</h4>
+            <p><a href="6A-9-101.htm">§&nbsp;6A-9-101.&nbsp;Short title.</a></p>
+        </body></html>"#,
+    );
+    t.add_fixture(
+        "https://webserver.rilegislature.gov/Statutes/TITLE6A/6A-9/6A-9-101.htm",
+        &load_fixture("rigl/section_42-11-2.htm"),
+    );
+
+    let item = QueueItem {
+        url: chapter_url.to_string(),
+        parent_id: "rigl/v1/root/title-6a".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "unit_id": "rigl-title-6a",
+            "title_num": "6A",
+            "chapter_num": "6A-9",
+            "sort_order": 0
+        }),
+    };
+    t.run_item(item).await;
+
+    t.expect_node("rigl/v1/root/title-6a/chapter-6a-9/part-1")
+        .level("part")
+        .name("General Provisions")
+        .path("/title/6a/chapter/6a-9/part/1");
+
+    let section = t.expect_node("rigl/v1/root/title-6a/chapter-6a-9/section-6a-9-101");
+    assert_eq!(section.node.meta.level_index, 3);
+    section
+        .level("section")
+        .parent("rigl/v1/root/title-6a/chapter-6a-9/part-1")
+        .path("/title/6a/chapter/6a-9/section/6a-9-101");
+}
+
 #[tokio::test]
 async fn adapter_inlines_cross_references_and_history_note() {
     let mut t = AdapterTestContext::new(RiglAdapter, "rigl/v1/root");
@@ -168,6 +215,7 @@ async fn adapter_propagates_unit_id_when_enqueuing_nested_rigl_items() {
         node_store,
         MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         },
         MockUrlQueue {
             enqueued: queue.enqueued.clone(),
@@ -302,16 +350,19 @@ async fn adapter_supports_aggregated_batch_callbacks() {
             root_node_id: "rigl/v1/root",
             accessed_at: "2024-01-01",
             unit_sort_order: 0,
+            profile: IngestProfile::Full,
         },
         nodes: Box::new(node_store.clone()),
         blobs: Arc::new(crate::common::MockBlobStore),
         cache: Arc::new(MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         }),
         queue: Arc::new(MockUrlQueue {
             enqueued: queue.enqueued.clone(),
         }),
         logger: Arc::new(crate::common::MockLogger),
+        cancel: CancellationToken::new(),
     };
 
     queue.enqueue(QueueItem {
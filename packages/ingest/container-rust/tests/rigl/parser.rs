@@ -1,4 +1,4 @@
-use crate::common::load_fixture;
+use crate::common::{assert_no_invisible_chars, load_fixture};
 use ingest::sources::rigl::parser::{
     compare_designators, normalize_designator, normalize_text, normalize_text_for_comparison,
     parse_chapter_index, parse_section_detail, parse_title_index, parse_title_links,
@@ -53,6 +53,35 @@ fn parses_reserved_chapter_index() {
     assert!(parsed.sections.is_empty());
 }
 
+#[test]
+fn attaches_article_levels_to_chapter_index_sections() {
+    let html = r#"<html><body>
+        <h2><center>Chapter 9<br>Uniform Commercial Code - Secured Transactions</center></h2>
+        <h3>Index of Sections</h3>
+        <h4>Part 1 General Provisions</h4>
+        <p><a href="6A-9-101.htm">§&nbsp;6A-9-101.&nbsp;Short title.</a></p>
+        <p><a href="6A-9-102.htm">§&nbsp;6A-9-102.&nbsp;Definitions.</a></p>
+        <h4>Part 2 Effectiveness of Security Agreement</h4>
+        <p><a href="6A-9-201.htm">§&nbsp;6A-9-201.&nbsp;General effectiveness.</a></p>
+    </body></html>"#;
+    let parsed = parse_chapter_index(
+        html,
+        "https://webserver.rilegislature.gov/Statutes/TITLE6A/6A-9/INDEX.htm",
+    )
+    .expect("chapter index should parse");
+
+    assert_eq!(parsed.sections.len(), 3);
+    assert_eq!(parsed.sections[0].levels.len(), 1);
+    assert_eq!(parsed.sections[0].levels[0].level_name, "part");
+    assert_eq!(parsed.sections[0].levels[0].num, "1");
+    assert_eq!(parsed.sections[1].levels[0].num, "1");
+    assert_eq!(parsed.sections[2].levels[0].num, "2");
+    assert_eq!(
+        parsed.sections[2].levels[0].name,
+        "Effectiveness of Security Agreement"
+    );
+}
+
 #[test]
 fn parses_section_and_routes_history_block() {
     let html = load_fixture("rigl/section_1-2-1.htm");
@@ -258,3 +287,38 @@ fn excerpt_at(value: &str, index: usize) -> String {
     let end = (index + 40).min(value.len());
     value[start..end].to_string()
 }
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let html = load_fixture("rigl/section_1-2-1_invisible.htm");
+    let parsed = parse_section_detail(&html).expect("section detail should parse");
+    assert_eq!(parsed.title_name, "Aeronautics");
+    assert_eq!(parsed.section_name, "Powers of the director.");
+    assert_eq!(
+        parsed.body,
+        "The director shall have supervision over state airports."
+    );
+    assert_eq!(parsed.history.as_deref(), Some("P.L. 1935, ch. 2250, § 1."));
+    assert_no_invisible_chars(&format!("{parsed:?}"));
+}
+
+#[test]
+fn ignores_bold_cross_references_that_start_like_headings() {
+    let html = r#"<html><body>
+        <h2><center>Chapter 9<br>Uniform Commercial Code - Secured Transactions</center></h2>
+        <h4>Part 1 General Provisions</h4>
+        <p><a href="6A-9-101.htm">§&nbsp;6A-9-101.&nbsp;Short title.</a></p>
+        <p><b>Part 2 of this chapter applies to § 6A-9-109.</b></p>
+        <p>Article 3 notes are kept with the section.</p>
+        <p><a href="6A-9-102.htm">§&nbsp;6A-9-102.&nbsp;Definitions.</a></p>
+    </body></html>"#;
+    let parsed = parse_chapter_index(
+        html,
+        "https://webserver.rilegislature.gov/Statutes/TITLE6A/6A-9/INDEX.htm",
+    )
+    .expect("chapter index should parse");
+
+    assert_eq!(parsed.sections.len(), 2);
+    assert_eq!(parsed.sections[1].levels.len(), 1);
+    assert_eq!(parsed.sections[1].levels[0].num, "1");
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "rigl")]
+
 mod common;
 mod rigl;
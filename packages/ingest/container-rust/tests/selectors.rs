@@ -0,0 +1,59 @@
+mod common;
+
+use common::load_fixture;
+use ingest::sources::selectors::SelectorDocument;
+
+#[test]
+fn selects_xml_elements_through_child_combinators() {
+    let xml = load_fixture("usc/usc_invisible_chars.xml");
+    let document = SelectorDocument::parse(&xml).unwrap();
+
+    let headings = document.select("chapter > heading").unwrap();
+    assert_eq!(headings.len(), 1);
+    assert_eq!(headings[0].tag, "heading");
+    assert_eq!(headings[0].text, "Rules of Con\u{ad}struction");
+
+    let nums = document.select("section num[value=\"1\"]").unwrap();
+    assert_eq!(nums.len(), 1);
+    assert_eq!(nums[0].text, "§ 1.");
+}
+
+#[test]
+fn selects_html_by_class_and_keeps_inner_markup() {
+    let html = r#"<div><p class="source">Source: <b>P.A. 1</b>
+        and   P.A. 2</p><p>body</p></div>"#;
+    let document = SelectorDocument::parse(html).unwrap();
+
+    let matches = document.select("p.source").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].text, "Source: P.A. 1 and P.A. 2");
+    assert!(matches[0].inner_html.contains("<b>P.A. 1</b>"));
+    assert!(document.select("table").unwrap().is_empty());
+}
+
+#[test]
+fn distinguishes_child_from_descendant_and_unions_alternatives() {
+    let html = r#"<div><p class="source">Source: <b>P.A. 1</b></p><p>body</p></div>"#;
+    let document = SelectorDocument::parse(html).unwrap();
+
+    assert_eq!(document.select("div > p").unwrap().len(), 2);
+    assert!(document.select("div > b").unwrap().is_empty());
+    assert_eq!(document.select("div b").unwrap().len(), 1);
+
+    let tags = document
+        .select("b, div>p.source")
+        .unwrap()
+        .into_iter()
+        .map(|matched| matched.tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, ["p", "b"]);
+}
+
+#[test]
+fn rejects_unparseable_selectors() {
+    let document = SelectorDocument::parse("<p>text</p>").unwrap();
+    for selector in ["p[", "> p", "p >", "p,"] {
+        let error = document.select(selector).unwrap_err();
+        assert!(error.contains("Invalid selector"), "{error}");
+    }
+}
@@ -0,0 +1,535 @@
+#![cfg(feature = "e2e")]
+
+use axum::extract::{Json, Query, State};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use ingest::server::{serve, AppState};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+const TITLES_URL: &str = "https://www.cga.ct.gov/current/pub/titles.htm";
+const TITLE_URL: &str = "https://www.cga.ct.gov/current/pub/title_42a.htm";
+const ARTICLE_URL: &str = "https://www.cga.ct.gov/current/pub/art_001.htm";
+const STATUTE_URL: &str = "https://api.govinfo.gov/collections/STATUTE/1999-01-01T00%3A00%3A00Z";
+
+/// Callback routes `packages/ingest/src/worker.ts` serves; anything else 404s
+/// there and here.
+const CALLBACK_ROUTES: &[&str] = &[
+    "/api/callback/ensureSourceVersion",
+    "/api/callback/publishSourceVersion",
+    "/api/callback/containerLog",
+    "/api/callback/ingestError",
+    "/api/callback/containerStop",
+    "/api/callback/ingestReport",
+    "/api/callback/unitStart",
+    "/api/callback/insertNodeBatch",
+    "/api/callback/setLevelStats",
+    "/api/callback/progress",
+];
+
+/// Mirrors `CONTAINER_WRITABLE_PREFIXES` in the worker.
+const CONTAINER_WRITABLE_PREFIXES: &[&str] = &[
+    "artifacts/",
+    "crash-dumps/",
+    "exports/",
+    "ingested/",
+    "provenance/",
+    "timings/",
+];
+
+/// Stand-in for the worker: serves source pages through the cache proxy from
+/// fixtures, keeps R2 objects in memory, and records every callback. Routes,
+/// auth and response headers follow `packages/ingest/src/worker.ts`.
+#[derive(Clone, Default)]
+struct StubBackend {
+    pages: Arc<HashMap<String, String>>,
+    /// How long the "origin" takes to answer for a URL.
+    delays: Arc<HashMap<String, Duration>>,
+    /// URLs already fetched from the "origin"; later reads are cache hits.
+    cached: Arc<Mutex<HashSet<String>>>,
+    objects: Arc<Mutex<HashMap<String, String>>>,
+    callbacks: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl StubBackend {
+    fn callbacks(&self, path: &str) -> Vec<Value> {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(recorded, _)| recorded == path)
+            .map(|(_, body)| body.clone())
+            .collect()
+    }
+
+    fn paths(&self) -> Vec<String> {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    headers.get("authorization").and_then(|v| v.to_str().ok()) == Some("Bearer token")
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({ "error": "Unauthorized" })),
+    )
+        .into_response()
+}
+
+async fn cache_read(
+    State(backend): State<StubBackend>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let url = body["url"].as_str().unwrap_or_default();
+    if let Some(delay) = backend.delays.get(url) {
+        tokio::time::sleep(*delay).await;
+    }
+    let Some(cache_key) = body["cacheKey"].as_str() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Missing cacheKey" })),
+        )
+            .into_response();
+    };
+    let Some(page) = backend.pages.get(url) else {
+        return (
+            StatusCode::BAD_GATEWAY,
+            [("X-Upstream-Status", "404")],
+            Json(json!({ "error": format!("Failed to fetch {url}: 404") })),
+        )
+            .into_response();
+    };
+
+    let upstream_response = json!({
+        "status": 200,
+        "fetchedAt": "2025-01-01T00:00:00.000Z",
+        "headers": { "content-type": "text/html" },
+    })
+    .to_string();
+    let mut response = (
+        [
+            ("Content-Type", "application/octet-stream".to_string()),
+            ("X-Cache-Key", format!("cache/{cache_key}")),
+            (
+                "X-Upstream-Response",
+                urlencoding::encode(&upstream_response).into_owned(),
+            ),
+        ],
+        page.clone(),
+    )
+        .into_response();
+    // The worker only reports an upstream status when it went to the origin.
+    if backend.cached.lock().unwrap().insert(url.to_string()) {
+        response
+            .headers_mut()
+            .insert("X-Upstream-Status", "200".parse().unwrap());
+    }
+    response
+}
+
+async fn r2_read(
+    State(backend): State<StubBackend>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let Some(key) = query.get("key") else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Missing key" })),
+        )
+            .into_response();
+    };
+    match backend.objects.lock().unwrap().get(key) {
+        Some(content) => content.clone().into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Object not found: {key}") })),
+        )
+            .into_response(),
+    }
+}
+
+async fn r2_write(
+    State(backend): State<StubBackend>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let (Some(key), Some(content)) = (body["key"].as_str(), body["content"].as_str()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Missing key or content" })),
+        )
+            .into_response();
+    };
+    if key.contains("..")
+        || !CONTAINER_WRITABLE_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": format!("Key not writable: {key}") })),
+        )
+            .into_response();
+    }
+    backend
+        .objects
+        .lock()
+        .unwrap()
+        .insert(key.to_string(), content.to_string());
+    Json(json!({ "ok": true })).into_response()
+}
+
+async fn callback(
+    State(backend): State<StubBackend>,
+    uri: Uri,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if !authorized(&headers) {
+        return unauthorized();
+    }
+    let path = uri.path().to_string();
+    // The worker reads the report as raw text; every other callback is JSON.
+    let body: Value = match serde_json::from_str(&body) {
+        Ok(body) => body,
+        Err(_) if path == "/api/callback/ingestReport" => Value::String(body),
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let reply = match path.as_str() {
+        "/api/callback/insertNodeBatch" => {
+            json!({ "accepted": body["nodes"].as_array().map_or(0, Vec::len) })
+        }
+        "/api/callback/setLevelStats" => {
+            json!({ "updated": body["levels"].as_array().map_or(0, Vec::len) })
+        }
+        "/api/callback/ingestReport" => json!({ "ok": true, "reportKey": "reports/job.json" }),
+        _ => json!({ "ok": true }),
+    };
+    backend.callbacks.lock().unwrap().push((path, body));
+    Json(reply).into_response()
+}
+
+fn cgs_pages() -> HashMap<String, String> {
+    let titles = r#"<html><body>
+<p>General Statutes of Connecticut, revised to January 1, 2025.</p>
+<a href="title_42a.htm">Title 42a - Uniform Commercial Code</a>
+</body></html>"#;
+    let title = r#"<html><head><title>Title 42a - Uniform Commercial Code</title></head><body>
+<a class="toc_ch_link" href="art_001.htm">Article 1</a>
+</body></html>"#;
+    HashMap::from([
+        (TITLES_URL.to_string(), titles.to_string()),
+        (TITLE_URL.to_string(), title.to_string()),
+        (
+            ARTICLE_URL.to_string(),
+            include_str!("fixtures/cgs/cgs_art_001.htm").to_string(),
+        ),
+    ])
+}
+
+/// The govinfo API for one congress published in two Statutes at Large
+/// volumes, one law in each.
+fn uspl_pages() -> HashMap<String, String> {
+    let collections = json!({
+        "packages": [
+            { "packageId": "STATUTE-127", "lastModified": "2025-09-22T00:00:00Z", "congress": "113" },
+            { "packageId": "STATUTE-128", "lastModified": "2025-09-22T00:00:00Z", "congress": "113" },
+        ],
+        "nextPage": null,
+    });
+    let mut pages = HashMap::from([(
+        format!("{STATUTE_URL}?pageSize=100&offsetMark=*&api_key="),
+        collections.to_string(),
+    )]);
+    for (volume, session, law) in [(127, 1, 5), (128, 2, 76)] {
+        let package = format!("https://api.govinfo.gov/packages/STATUTE-{volume}");
+        let summary = json!({
+            "volume": volume.to_string(),
+            "congress": "113",
+            "session": session.to_string(),
+            "dateIssued": "2015-01-01",
+            "lastModified": "2025-09-22T00:00:00Z",
+            "download": { "uslmLink": format!("{package}/uslm") },
+        });
+        let uslm = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<statutesAtLarge xmlns="http://xml.house.gov/schemas/uslm/1.0">
+  <collection><component role="statutesPart"><publicLaws><component>
+    <pLaw>
+      <meta>
+        <publicPrivate>Public</publicPrivate>
+        <congress>113</congress>
+        <docNumber>{law}</docNumber>
+      </meta>
+      <main>
+        <officialTitle>An Act to test volume {volume}.</officialTitle>
+        <section><num>1.</num><content>Law {law} of the 113th Congress.</content></section>
+      </main>
+    </pLaw>
+  </component></publicLaws></component></collection>
+</statutesAtLarge>"#
+        );
+        pages.insert(format!("{package}/summary?api_key="), summary.to_string());
+        pages.insert(format!("{package}/uslm?api_key="), uslm);
+    }
+    pages
+}
+
+async fn spawn_stub_backend(
+    pages: HashMap<String, String>,
+    delays: HashMap<String, Duration>,
+) -> (StubBackend, String) {
+    let backend = StubBackend {
+        pages: Arc::new(pages),
+        delays: Arc::new(delays),
+        ..StubBackend::default()
+    };
+
+    let mut app = Router::new()
+        .route("/api/proxy/cache-read", post(cache_read))
+        .route("/api/proxy/r2-read", get(r2_read))
+        .route("/api/proxy/r2-write", post(r2_write));
+    for route in CALLBACK_ROUTES {
+        app = app.route(route, post(callback));
+    }
+    let app = app.with_state(backend.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (backend, base)
+}
+
+/// Runs one ingest of `source` against `backend` until the container shuts
+/// down.
+async fn run_ingest(callback_base: &str, source: &str, unit_timeout_secs: Option<u64>) {
+    std::env::set_var("CONFIGS_PATH", format!("{}/..", env!("CARGO_MANIFEST_DIR")));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let container = format!("http://{}", listener.local_addr().unwrap());
+    let state = Arc::new(AppState::new(Duration::from_millis(500)));
+    let server = tokio::spawn(serve(listener, state));
+
+    let response = reqwest::Client::new()
+        .post(format!("{container}/ingest"))
+        .json(&json!({
+            "source": source,
+            "sourceId": source,
+            "selectors": null,
+            "units": null,
+            "manualStartUrl": null,
+            "callbackBase": callback_base,
+            "callbackToken": "token",
+            "sourceVersionId": null,
+            "rootNodeId": null,
+            "unitTimeoutSecs": unit_timeout_secs,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({ "status": "accepted" })
+    );
+
+    // The server only exits once the job is done and the idle timeout passes.
+    tokio::time::timeout(Duration::from_secs(30), server)
+        .await
+        .expect("container did not shut down after the job")
+        .unwrap()
+        .unwrap();
+}
+
+/// Stats `setLevelStats` received for the level node `id`.
+fn level_stats(backend: &StubBackend, id: &str) -> Value {
+    backend
+        .callbacks("/api/callback/setLevelStats")
+        .iter()
+        .flat_map(|batch| batch["levels"].as_array().cloned().unwrap_or_default())
+        .find(|level| level["id"] == id)
+        .map(|level| level["stats"].clone())
+        .unwrap_or_else(|| panic!("no level stats for {id}"))
+}
+
+fn unit_error(backend: &StubBackend) -> Option<String> {
+    backend
+        .callbacks("/api/callback/progress")
+        .iter()
+        .find(|progress| progress["status"] == "error")
+        .map(|progress| progress["error"].as_str().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn ingest_runs_from_accept_to_container_stop() {
+    let (backend, callback_base) = spawn_stub_backend(cgs_pages(), HashMap::new()).await;
+    run_ingest(&callback_base, "cgs", None).await;
+
+    assert!(backend.callbacks("/api/callback/ingestError").is_empty());
+    let ensured = backend.callbacks("/api/callback/ensureSourceVersion");
+    assert_eq!(ensured.len(), 1);
+    assert_eq!(ensured[0]["sourceVersionId"], "cgs-2025");
+
+    let node_ids = backend
+        .callbacks("/api/callback/insertNodeBatch")
+        .iter()
+        .flat_map(|batch| batch["nodes"].as_array().cloned().unwrap_or_default())
+        .map(|node| node["meta"]["id"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    assert!(node_ids.contains(&"cgs/2025/root/title-42a".to_string()));
+    assert!(node_ids.contains(&"cgs/2025/root/title-42a/article-1/section-42a-1-101".to_string()));
+
+    let title_stats = level_stats(&backend, "cgs/2025/root/title-42a");
+    assert_eq!(title_stats["child_count"], 1);
+    assert!(title_stats["section_count"].as_u64() > Some(0));
+
+    assert_eq!(
+        backend.callbacks("/api/callback/publishSourceVersion"),
+        vec![json!({ "sourceVersionId": "cgs-2025" })]
+    );
+
+    let paths = backend.paths();
+    let position = |path: &str| paths.iter().position(|recorded| recorded == path);
+    assert!(
+        position("/api/callback/ensureSourceVersion") < position("/api/callback/insertNodeBatch")
+    );
+    assert!(
+        position("/api/callback/ingestReport") < position("/api/callback/publishSourceVersion")
+    );
+    assert_eq!(
+        paths.last().map(String::as_str),
+        Some("/api/callback/containerStop")
+    );
+    assert_eq!(
+        backend.callbacks("/api/callback/containerStop"),
+        vec![json!({ "reason": "completed" })]
+    );
+
+    let objects = backend.objects.lock().unwrap();
+    let manifest = objects
+        .iter()
+        .find(|(key, _)| key.starts_with("provenance/cgs-2025/"))
+        .map(|(_, content)| serde_json::from_str::<Value>(content).unwrap())
+        .expect("unit manifest written through r2-write");
+    let fetch = &manifest["fetches"][0];
+    assert_eq!(fetch["fetchedAt"], "2025-01-01T00:00:00.000Z");
+    assert_eq!(fetch["headers"]["content-type"], "text/html");
+
+    // Every node sent is also in the export /package reads.
+    let export: Value = serde_json::from_str(&objects["exports/cgs-2025/index.json"]).unwrap();
+    let exported = export["chunks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|key| objects[key.as_str().unwrap()].lines())
+        .map(|line| serde_json::from_str::<Value>(line).unwrap()["meta"]["id"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(exported[0], "cgs/2025/root");
+    assert_eq!(exported.len(), node_ids.len() + 1);
+}
+
+#[tokio::test]
+async fn congress_shared_by_two_volumes_still_publishes() {
+    let (backend, callback_base) = spawn_stub_backend(uspl_pages(), HashMap::new()).await;
+    run_ingest(&callback_base, "uspl", None).await;
+
+    assert!(backend.callbacks("/api/callback/ingestError").is_empty());
+    let node_ids = backend
+        .callbacks("/api/callback/insertNodeBatch")
+        .iter()
+        .flat_map(|batch| batch["nodes"].as_array().cloned().unwrap_or_default())
+        .map(|node| node["meta"]["id"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    // Each volume emits the congress; the worker keeps the first.
+    let congress = "uspl/2025-09-22/congress-113";
+    assert_eq!(node_ids.iter().filter(|id| *id == congress).count(), 2);
+    assert!(node_ids.contains(&format!("{congress}/pl-5")));
+    assert!(node_ids.contains(&format!("{congress}/pl-76")));
+    // Its stats are sent once and cover both volumes.
+    assert_eq!(
+        level_stats(&backend, congress),
+        json!({ "child_count": 2, "section_count": 2, "word_count": 14 })
+    );
+
+    assert_eq!(
+        backend.callbacks("/api/callback/publishSourceVersion"),
+        vec![json!({ "sourceVersionId": "uspl-2025-09-22" })]
+    );
+    let objects = backend.objects.lock().unwrap();
+    let ingested: Value = serde_json::from_str(&objects["ingested/uspl.json"]).unwrap();
+    assert_eq!(ingested["sourceVersionId"], "uspl-2025-09-22");
+}
+
+#[tokio::test]
+async fn freshness_requires_the_token_in_the_authorization_header() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let container = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(serve(
+        listener,
+        Arc::new(AppState::new(Duration::from_secs(5))),
+    ));
+
+    let response = reqwest::Client::new()
+        .get(format!(
+            "{container}/freshness?callbackBase=http://127.0.0.1:1&callbackToken=token"
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn unit_timeout_covers_the_whole_unit_not_each_item() {
+    // Each page answers well inside the timeout; the unit as a whole doesn't.
+    let delays = HashMap::from([
+        (TITLE_URL.to_string(), Duration::from_millis(700)),
+        (ARTICLE_URL.to_string(), Duration::from_millis(700)),
+    ]);
+    let (backend, callback_base) = spawn_stub_backend(cgs_pages(), delays).await;
+    run_ingest(&callback_base, "cgs", Some(1)).await;
+
+    let error = unit_error(&backend).expect("the unit should time out");
+    assert!(error.starts_with("Timed out after 1s"), "{error}");
+    assert_eq!(
+        backend.callbacks("/api/callback/containerStop").len(),
+        1,
+        "the job should still finish"
+    );
+}
+
+#[tokio::test]
+async fn unit_timeout_stops_a_unit_stuck_on_a_hanging_fetch() {
+    let delays = HashMap::from([(ARTICLE_URL.to_string(), Duration::from_secs(3600))]);
+    let (backend, callback_base) = spawn_stub_backend(cgs_pages(), delays).await;
+    run_ingest(&callback_base, "cgs", Some(1)).await;
+
+    let error = unit_error(&backend).expect("the unit should time out");
+    assert_eq!(
+        error,
+        format!("Timed out after 1s processing {ARTICLE_URL}")
+    );
+    assert_eq!(backend.callbacks("/api/callback/containerStop").len(), 1);
+}
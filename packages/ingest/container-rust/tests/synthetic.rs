@@ -0,0 +1,142 @@
+use ingest::sources::synthetic::{cgs_chapter, usc_title, SyntheticShape};
+
+#[test]
+fn parses_shape_specs() {
+    assert_eq!(
+        SyntheticShape::parse("3x10x2").unwrap(),
+        SyntheticShape {
+            chapters: 3,
+            sections: 10,
+            notes: 2,
+            depth: 2,
+        }
+    );
+    assert_eq!(SyntheticShape::parse("1x1x0x6").unwrap().depth, 6);
+    assert!(SyntheticShape::parse("3x10").is_err());
+    assert!(SyntheticShape::parse("3xtenx2").is_err());
+    assert!(SyntheticShape::parse("1x1x1x7").is_err());
+}
+
+#[test]
+fn counts_provisions_across_outline_depths() {
+    let shape = |depth| SyntheticShape {
+        depth,
+        ..SyntheticShape::default()
+    };
+    assert_eq!(shape(0).provisions_per_section(), 0);
+    assert_eq!(shape(1).provisions_per_section(), 2);
+    assert_eq!(shape(3).provisions_per_section(), 2 + 4 + 8);
+}
+
+#[test]
+fn output_is_reproducible() {
+    let shape = SyntheticShape::default();
+    assert_eq!(usc_title("42", &shape), usc_title("42", &shape));
+    assert_ne!(usc_title("42", &shape), usc_title("43", &shape));
+    assert_eq!(cgs_chapter("7", 2, &shape), cgs_chapter("7", 2, &shape));
+    assert_ne!(cgs_chapter("7", 1, &shape), cgs_chapter("7", 2, &shape));
+}
+
+#[cfg(feature = "usc")]
+#[test]
+fn usc_title_parses_to_the_requested_shape() {
+    use ingest::sources::usc::parser::parse_usc_xml;
+
+    let shape = SyntheticShape {
+        chapters: 3,
+        sections: 4,
+        notes: 3,
+        depth: 3,
+    };
+    let result = parse_usc_xml(&usc_title("42", &shape), "42", "");
+
+    assert_eq!(result.title_name, "SYNTHETIC TITLE 42");
+    assert_eq!(result.levels.len(), 3);
+    assert!(result
+        .levels
+        .iter()
+        .all(|level| level.level_type == "chapter"));
+    let section_nums = result
+        .sections
+        .iter()
+        .map(|section| section.section_num.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        section_nums,
+        (1..=12).map(|n| n.to_string()).collect::<Vec<_>>()
+    );
+
+    let section = &result.sections[0];
+    assert!(section.body.contains("(a)"));
+    assert!(section.body.contains("(A)"));
+    let kinds = section
+        .blocks
+        .iter()
+        .map(|block| block.type_.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(kinds.len(), 1 + shape.notes);
+    assert_eq!(kinds[0], "source_credit");
+}
+
+#[cfg(feature = "usc")]
+#[test]
+fn usc_stream_handles_a_title_larger_than_any_fixture() {
+    use ingest::sources::usc::parser::{usc_stream_events, USCStreamEvent};
+
+    let shape = SyntheticShape {
+        chapters: 40,
+        sections: 50,
+        notes: 4,
+        depth: 3,
+    };
+    let xml = usc_title("42", &shape);
+
+    let mut sections = 0;
+    let mut blocks = 0;
+    for event in usc_stream_events(&xml, "42") {
+        match event {
+            USCStreamEvent::Section(section) => {
+                sections += 1;
+                blocks += section.blocks.len();
+            }
+            USCStreamEvent::Error(err) => panic!("USC parsing error: {err}"),
+            _ => {}
+        }
+    }
+    assert_eq!(sections, shape.total_sections());
+    assert_eq!(blocks, shape.total_sections() * (1 + shape.notes));
+}
+
+#[cfg(feature = "cgs")]
+#[test]
+fn cgs_chapters_parse_to_the_requested_shape() {
+    use ingest::sources::cgs::parser::{parse_cgs_chapter_html, CgsUnitKind};
+
+    let shape = SyntheticShape {
+        chapters: 2,
+        sections: 5,
+        notes: 3,
+        depth: 2,
+    };
+    for chapter in 1..=shape.chapters {
+        let html = cgs_chapter("7", chapter, &shape);
+        let parsed = parse_cgs_chapter_html(&html, &chapter.to_string(), "", CgsUnitKind::Chapter);
+
+        assert_eq!(
+            parsed.chapter_number.as_deref(),
+            Some(chapter.to_string().as_str())
+        );
+        assert_eq!(parsed.sections.len(), shape.sections);
+        let first = &parsed.sections[0];
+        assert_eq!(
+            first.string_id,
+            format!("cgs/section/7-{}", (chapter - 1) * shape.sections + 1)
+        );
+        assert!(first.name.is_some());
+        assert!(first.body.contains("(1)"));
+        assert!(first.history_short.is_some());
+        assert!(first.history_long.is_some());
+        assert!(first.citations.is_some());
+        assert!(first.see_also.is_some());
+    }
+}
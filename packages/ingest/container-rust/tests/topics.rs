@@ -0,0 +1,93 @@
+mod common;
+
+use async_trait::async_trait;
+use common::node;
+use ingest::runtime::topics::{tag_topics, KeywordClassifier, TopicClassifier};
+use ingest::types::{NodePayload, SectionContent};
+use serde_json::json;
+
+fn section(heading: &str, blocks: serde_json::Value) -> NodePayload {
+    node("cgs/section-1")
+        .parent("cgs/chapter-1")
+        .level("section", 2)
+        .name(heading)
+        .blocks(blocks)
+        .build()
+}
+
+fn topics_of(node: &NodePayload) -> Vec<String> {
+    let content: SectionContent = serde_json::from_value(node.content.clone().unwrap()).unwrap();
+    content
+        .metadata
+        .map(|metadata| metadata.topics)
+        .unwrap_or_default()
+}
+
+#[test]
+fn tags_on_one_heading_keyword_or_repeated_body_keywords() {
+    let classifier = KeywordClassifier::default();
+
+    assert_eq!(
+        classifier.classify_text("Penalty for felony theft", ""),
+        ["criminal"]
+    );
+    assert!(classifier
+        .classify_text("Definitions", "No tax shall be imposed on this release.")
+        .is_empty());
+    assert_eq!(
+        classifier.classify_text(
+            "Definitions",
+            "Each taxpayer shall file a return. The tax is due in April."
+        ),
+        ["tax"]
+    );
+    assert!(classifier
+        .classify_text("Taxidermy permits", "Permits for taxidermists.")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn stores_sorted_topics_from_body_paragraphs_only() {
+    let mut node = section(
+        "Custody of children in divorce proceedings",
+        json!([
+            { "type": "body", "content": "A school may not withhold a student's records." },
+            { "type": "amendments", "content": "The 1995 act amended the tax and tax rates." }
+        ]),
+    );
+    tag_topics(&KeywordClassifier::default(), &mut node)
+        .await
+        .unwrap();
+    assert_eq!(topics_of(&node), ["education", "family"]);
+
+    let mut level = section("Criminal Procedure", json!([]));
+    level.content = None;
+    tag_topics(&KeywordClassifier::default(), &mut level)
+        .await
+        .unwrap();
+    assert!(level.content.is_none());
+}
+
+struct FixedClassifier(Vec<&'static str>);
+
+#[async_trait]
+impl TopicClassifier for FixedClassifier {
+    async fn classify(&self, _node: &NodePayload) -> Vec<String> {
+        self.0.iter().map(|topic| topic.to_string()).collect()
+    }
+}
+
+#[tokio::test]
+async fn accepts_a_custom_classifier() {
+    let mut node = section("Anything", json!([{ "type": "body", "content": "text" }]));
+    tag_topics(&FixedClassifier(vec!["tax", "banking", "tax"]), &mut node)
+        .await
+        .unwrap();
+    assert_eq!(topics_of(&node), ["banking", "tax"]);
+
+    let mut untagged = section("Anything", json!([{ "type": "body", "content": "text" }]));
+    tag_topics(&FixedClassifier(Vec::new()), &mut untagged)
+        .await
+        .unwrap();
+    assert!(!untagged.content.unwrap().to_string().contains("topics"));
+}
@@ -0,0 +1,62 @@
+use ingest::types::{IngestConfig, OutputTransport};
+use serde_json::{json, Value};
+
+fn config_json() -> Value {
+    json!({
+        "source": "usc",
+        "sourceId": "usc",
+        "selectors": null,
+        "units": null,
+        "manualStartUrl": null,
+        "callbackBase": "http://localhost:8787",
+        "callbackToken": "token",
+        "sourceVersionId": null,
+        "rootNodeId": null,
+    })
+}
+
+#[test]
+fn transport_defaults_to_http_callbacks() {
+    let config: IngestConfig = serde_json::from_value(config_json()).unwrap();
+    assert_eq!(config.transport, OutputTransport::Http);
+}
+
+#[test]
+fn transport_selects_grpc_and_nats() {
+    let mut grpc = config_json();
+    grpc["transport"] = json!({ "kind": "grpc", "endpoint": "http://sink:50051" });
+    let config: IngestConfig = serde_json::from_value(grpc).unwrap();
+    assert_eq!(
+        config.transport,
+        OutputTransport::Grpc {
+            endpoint: "http://sink:50051".to_string()
+        }
+    );
+
+    let mut nats = config_json();
+    nats["transport"] = json!({
+        "kind": "nats",
+        "url": "nats://queue:4222",
+        "subject": "fastlaw.nodes",
+    });
+    let config: IngestConfig = serde_json::from_value(nats).unwrap();
+    assert_eq!(
+        config.transport,
+        OutputTransport::Nats {
+            url: "nats://queue:4222".to_string(),
+            subject: "fastlaw.nodes".to_string(),
+        }
+    );
+}
+
+#[cfg(feature = "nats")]
+#[tokio::test]
+async fn nats_sink_reports_unreachable_server() {
+    use ingest::runtime::nats::NatsNodeSink;
+
+    let err = NatsNodeSink::connect("nats://127.0.0.1:1", "fastlaw.nodes")
+        .await
+        .err()
+        .expect("connect should fail");
+    assert!(err.contains("nats://127.0.0.1:1"));
+}
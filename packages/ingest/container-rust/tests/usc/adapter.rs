@@ -1,7 +1,7 @@
 use crate::common::{load_fixture, AdapterTestContext};
 use ingest::runtime::types::QueueItem;
 use ingest::sources::usc::adapter::UscAdapter;
-use ingest::types::SectionContent;
+use ingest::types::{IngestProfile, SectionContent};
 
 #[tokio::test]
 async fn test_adapter_extracts_levels_and_sections() {
@@ -37,6 +37,7 @@ async fn test_adapter_extracts_levels_and_sections() {
 
     t.add_fixture(&item.url, xml);
     t.run_item(item).await;
+    t.assert_output_pinned(2, "b586ad4ee0d9f902");
 
     t.expect_node("root/t1/root")
         .level("title")
@@ -53,8 +54,7 @@ async fn test_adapter_extracts_levels_and_sections() {
         .content_contains("In determining the meaning");
 }
 
-#[tokio::test]
-async fn test_adapter_matches_42_usc_302_nodepayload() {
+async fn run_42_usc_302() -> AdapterTestContext<'static, UscAdapter> {
     let mut t = AdapterTestContext::new(UscAdapter, "root");
 
     let xml = load_fixture("usc/usc42_s302.xml");
@@ -68,6 +68,12 @@ async fn test_adapter_matches_42_usc_302_nodepayload() {
 
     t.add_fixture(&item.url, &xml);
     t.run_item(item).await;
+    t
+}
+
+#[tokio::test]
+async fn test_adapter_matches_42_usc_302_nodepayload() {
+    let t = run_42_usc_302().await;
 
     let section = t
         .expect_node("root/t42/ch7/schI/section-302")
@@ -85,13 +91,17 @@ async fn test_adapter_matches_42_usc_302_nodepayload() {
         .expect("section content should exist");
     let section_content = serde_json::from_value::<SectionContent>(content)
         .expect("section content should deserialize");
+    let metadata = section_content
+        .metadata
+        .clone()
+        .expect("section metadata should exist");
     assert!(
-        section_content.metadata.is_none(),
+        metadata.cross_references.is_empty(),
         "cross-reference metadata should not be stored in content",
     );
 
     let blocks = &section_content.blocks;
-    assert_eq!(blocks.len(), 16);
+    assert_eq!(blocks.len(), 35);
 
     assert_eq!(blocks[0].type_, "body");
     assert_eq!(blocks[0].label, None);
@@ -120,21 +130,14 @@ async fn test_adapter_matches_42_usc_302_nodepayload() {
     assert_eq!(blocks[3].label.as_deref(), Some("Editorial Notes"));
     assert_eq!(blocks[3].content, None);
 
-    assert_eq!(blocks[4].type_, "amendments");
-    assert_eq!(blocks[4].label.as_deref(), Some("Amendments"));
-    assert!(blocks[4]
-        .content
-        .as_deref()
-        .is_some_and(|content| !content.starts_with("**Amendments**")));
-
-    assert_eq!(blocks[5].type_, "heading");
+    assert_eq!(blocks[24].type_, "heading");
     assert_eq!(
-        blocks[5].label.as_deref(),
+        blocks[24].label.as_deref(),
         Some("Statutory Notes and Related Subsidiaries"),
     );
-    assert_eq!(blocks[5].content, None);
+    assert_eq!(blocks[24].content, None);
 
-    let note_labels = blocks[6..]
+    let note_labels = blocks[25..]
         .iter()
         .map(|block| block.label.as_deref().unwrap_or(""))
         .collect::<Vec<_>>();
@@ -155,6 +158,43 @@ async fn test_adapter_matches_42_usc_302_nodepayload() {
     );
 }
 
+#[tokio::test]
+async fn test_adapter_splits_42_usc_302_amendments_by_year() {
+    let t = run_42_usc_302().await;
+
+    let content = t
+        .expect_node("root/t42/ch7/schI/section-302")
+        .node
+        .content
+        .expect("section content should exist");
+    let content = serde_json::from_value::<SectionContent>(content)
+        .expect("section content should deserialize");
+    let blocks = &content.blocks;
+
+    assert_eq!(blocks[4].type_, "heading");
+    assert_eq!(blocks[4].label.as_deref(), Some("Amendments"));
+    assert_eq!(blocks[5].type_, "amendment");
+    assert_eq!(blocks[5].label.as_deref(), Some("1984"));
+    assert!(blocks[5]
+        .content
+        .as_deref()
+        .is_some_and(|content| content.starts_with("Subsec. (a)(11). [Pub. L. 98–369](")));
+    assert!(blocks[5..24].iter().all(|block| block.type_ == "amendment"));
+    assert_eq!(blocks[24].type_, "heading");
+
+    let amendments = content
+        .metadata
+        .expect("section metadata should exist")
+        .amendments;
+    assert_eq!(amendments.len(), 19);
+    assert_eq!(amendments[0].block, 5);
+    assert_eq!(
+        amendments[0].subdivision.as_deref(),
+        Some("Subsec. (a)(11)")
+    );
+    assert_eq!(amendments[18].year, 1939);
+}
+
 #[tokio::test]
 async fn test_adapter_handles_source_with_no_children() {
     let mut t = AdapterTestContext::new(UscAdapter, "root");
@@ -186,3 +226,156 @@ async fn test_adapter_handles_source_with_no_children() {
         .level("title")
         .name("Shipping");
 }
+
+#[tokio::test]
+async fn test_adapter_emits_appendix_container_in_its_own_unit() {
+    let mut t = AdapterTestContext::new(UscAdapter, "root");
+
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t5a">
+            <meta><title>Title 5 APPENDIX</title></meta>
+            <main>
+                <appendix identifier="/us/usc/t5a">
+                    <num value="5a">Title 5—</num>
+                    <heading>Appendix</heading>
+                    <chapter identifier="/us/usc/t5a/ch1">
+                        <num value="1">Chapter 1</num>
+                        <heading>Advisory Committees</heading>
+                        <section identifier="/us/usc/t5a/s1">
+                            <num value="1">§ 1.</num>
+                            <heading>Short title</heading>
+                            <content>This Act may be cited as the Federal Advisory Committee Act.</content>
+                        </section>
+                    </chapter>
+                </appendix>
+            </main>
+        </uscDoc>"#;
+
+    let item = QueueItem {
+        url: "http://example.com".to_string(),
+        parent_id: "root".to_string(),
+        level_name: "appendix".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({ "title_num": "5a" }),
+    };
+
+    t.add_fixture(&item.url, xml);
+    t.run_item(item).await;
+
+    let appendix = t
+        .expect_node("root/t5a/root")
+        .level("appendix")
+        .parent("root")
+        .name("Appendix")
+        .readable_id("5 App.")
+        .node;
+    assert_eq!(
+        appendix.meta.heading_citation.as_deref(),
+        Some("Title 5 Appendix")
+    );
+
+    t.expect_node("root/t5a/ch1")
+        .level("chapter")
+        .parent("root/t5a/root");
+
+    t.expect_node("root/t5a/ch1/section-1")
+        .parent("root/t5a/ch1")
+        .readable_id("5 USC App. 1")
+        .path("/section/5a/1")
+        .content_contains("Federal Advisory Committee Act");
+}
+
+#[tokio::test]
+async fn test_adapter_cites_section_ranges() {
+    let mut t = AdapterTestContext::new(UscAdapter, "root");
+
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t3">
+            <main>
+                <title identifier="/us/usc/t3">
+                    <num value="3">Title 3—</num>
+                    <heading>The President</heading>
+                    <section status="repealed" identifier="/us/usc/t3/s202...204">
+                        <num value="202 to 204">[§§ 202 to 204.</num>
+                        <heading> Repealed. Pub. L. 109–177, title VI, § 605(c), Mar. 9, 2006, 120 Stat. 255]</heading>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let item = QueueItem {
+        url: "http://example.com".to_string(),
+        parent_id: "root".to_string(),
+        level_name: "title".to_string(),
+        level_index: 0,
+        metadata: serde_json::json!({ "title_num": "3" }),
+    };
+
+    t.add_fixture(&item.url, xml);
+    t.run_item(item).await;
+
+    t.expect_node("root/t3/root/section-202_to_204")
+        .readable_id("3 USC 202 to 204")
+        .path("/section/3/202_to_204");
+}
+
+#[tokio::test]
+async fn test_adapter_notes_only_profile_omits_body_block() {
+    let mut t = AdapterTestContext::new(UscAdapter, "root");
+    t.profile = IngestProfile::NotesOnly;
+
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t1">
+            <main>
+                <title identifier="/us/usc/t1">
+                    <num value="1">Title 1</num>
+                    <heading>General Provisions</heading>
+                    <chapter identifier="/us/usc/t1/ch1">
+                        <num value="1">Chapter 1</num>
+                        <heading>Rules of Construction</heading>
+                        <section identifier="/us/usc/t1/s1">
+                            <num value="1">§ 1.</num>
+                            <heading>Words denoting number, gender, etc.</heading>
+                            <content>In determining the meaning of any Act of Congress...</content>
+                            <notes>
+                                <note topic="amendments">
+                                    <p>2012—Pub. L. 112–1 amended section.</p>
+                                </note>
+                                <note topic="effectiveDate">
+                                    <heading>Effective Date</heading>
+                                    <p>Section effective Oct. 1, 2012, see section 2 of Pub. L. 112–1.</p>
+                                </note>
+                            </notes>
+                        </section>
+                    </chapter>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let item = QueueItem {
+        url: "http://example.com".to_string(),
+        parent_id: "root".to_string(),
+        level_name: "title".to_string(),
+        level_index: 0,
+        metadata: serde_json::json!({ "title_num": "1" }),
+    };
+
+    t.add_fixture(&item.url, xml);
+    t.run_item(item).await;
+
+    t.expect_node("root/t1/ch1").level("chapter");
+    let section = t.expect_node("root/t1/ch1/section-1").node;
+    let content: SectionContent = serde_json::from_value(section.content.unwrap()).unwrap();
+    let kinds: Vec<&str> = content
+        .blocks
+        .iter()
+        .map(|block| block.type_.as_str())
+        .collect();
+    assert_eq!(kinds, vec!["heading", "amendment", "effective_date"]);
+
+    let effective_dates = content.metadata.unwrap().effective_dates;
+    assert_eq!(effective_dates.len(), 1);
+    assert_eq!(effective_dates[0].block, 2);
+    assert_eq!(effective_dates[0].dates, vec!["2012-10-01"]);
+    assert_eq!(effective_dates[0].public_laws, vec!["112-1"]);
+}
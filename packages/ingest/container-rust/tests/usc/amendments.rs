@@ -0,0 +1,74 @@
+use ingest::sources::usc::amendments::split_amendments_block;
+use ingest::types::ContentBlock;
+
+fn amendments_block(content: &str) -> ContentBlock {
+    ContentBlock {
+        type_: "amendments".to_string(),
+        content: Some(content.to_string()),
+        label: Some("Amendments".to_string()),
+    }
+}
+
+#[test]
+fn splits_amendments_note_into_yearly_entries() {
+    let block = amendments_block(
+        "2022—Subsecs. (b) to (d). Pub. L. 117–263 added subsec. (b).\n\n\
+         Pub. L. 117–263, § 5(a), which directed a substitution, was executed.\n\n\
+         1984—Subsec. (a)(10)(A)(i). Pub. L. 98–369 added par. (11).\n\n\
+         1948—Act June 25, 1948, included “tense”.",
+    );
+    let (blocks, entries) = split_amendments_block(&block, 3).unwrap();
+
+    assert_eq!(blocks[0].type_, "heading");
+    assert_eq!(blocks[0].label.as_deref(), Some("Amendments"));
+    assert_eq!(blocks[0].content, None);
+
+    let amendments = blocks[1..]
+        .iter()
+        .map(|block| {
+            assert_eq!(block.type_, "amendment");
+            (
+                block.label.as_deref().unwrap(),
+                block.content.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        amendments,
+        vec![
+            (
+                "2022",
+                "Subsecs. (b) to (d). Pub. L. 117–263 added subsec. (b)."
+            ),
+            (
+                "2022",
+                "Pub. L. 117–263, § 5(a), which directed a substitution, was executed."
+            ),
+            (
+                "1984",
+                "Subsec. (a)(10)(A)(i). Pub. L. 98–369 added par. (11)."
+            ),
+            ("1948", "Act June 25, 1948, included “tense”."),
+        ]
+    );
+
+    let entries = entries
+        .iter()
+        .map(|entry| (entry.block, entry.year, entry.subdivision.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            (4, 2022, Some("Subsecs. (b) to (d)")),
+            (5, 2022, None),
+            (6, 1984, Some("Subsec. (a)(10)(A)(i)")),
+            (7, 1948, None),
+        ]
+    );
+}
+
+#[test]
+fn leaves_notes_without_a_leading_year_whole() {
+    let block = amendments_block("Section was amended generally by Pub. L. 98–497.");
+    assert!(split_amendments_block(&block, 0).is_none());
+}
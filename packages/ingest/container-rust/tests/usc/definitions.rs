@@ -0,0 +1,76 @@
+use ingest::sources::usc::definitions::{definitions_block, extract_definitions};
+
+fn terms(body: &str) -> Vec<(String, String)> {
+    extract_definitions(body)
+        .into_iter()
+        .map(|definition| (definition.term, definition.definition))
+        .collect()
+}
+
+#[test]
+fn extracts_the_term_means_sentences() {
+    let body = "**(a)** For the purposes of any Federal law, an individual shall be considered married if valid.\n\n\
+        **(b)** In this section, the term “State” means a State, the District of Columbia, or any territory.";
+    assert_eq!(
+        terms(body),
+        vec![(
+            "State".to_string(),
+            "means a State, the District of Columbia, or any territory.".to_string()
+        )]
+    );
+}
+
+#[test]
+fn extracts_list_items_and_nested_definitions() {
+    let body = "As used in this chapter the term—\n\n\
+        > **(1)** “State” includes the District of Columbia.\n\n\
+        > **(2)** **Employing office.—**\n\n\
+        > The term “employing office” means—\n\n\
+        > > **(A)** each office of the Executive Office of the President; and\n\n\
+        > > **(B)** the Executive Residence at the White House.\n\n\
+        > **(3)** “State” means a State of the Union.";
+    assert_eq!(
+        terms(body),
+        vec![
+            (
+                "State".to_string(),
+                "includes the District of Columbia.".to_string()
+            ),
+            (
+                "employing office".to_string(),
+                "means— (A) each office of the Executive Office of the President; and (B) the Executive Residence at the White House.".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn splits_grouped_and_consecutive_definitions() {
+    let body =
+        "The words “person”, “human being”, and “child”, shall include every infant born alive. \
+        The term “born alive”, with respect to a member of the species, means complete expulsion.";
+    let found = terms(body);
+    assert_eq!(
+        found
+            .iter()
+            .map(|(term, _)| term.as_str())
+            .collect::<Vec<_>>(),
+        vec!["person", "human being", "child", "born alive"]
+    );
+    assert!(found[..3]
+        .iter()
+        .all(|(_, definition)| definition == "shall include every infant born alive."));
+    assert_eq!(found[3].1, "means complete expulsion.");
+
+    assert!(terms("The term “function” embraces any duty, power, or authority.").is_empty());
+
+    let block = definitions_block(&extract_definitions(
+        "The term “Secretary” means the Secretary of State.",
+    ));
+    assert_eq!(block.type_, "definitions");
+    assert_eq!(block.label.as_deref(), Some("Definitions"));
+    assert_eq!(
+        block.content.as_deref(),
+        Some("- **Secretary** means the Secretary of State.")
+    );
+}
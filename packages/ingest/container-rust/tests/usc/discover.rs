@@ -32,3 +32,33 @@ async fn test_discover_usc_root_relative_href() {
         "https://uscode.house.gov/download/releasepoints/us/pl/119/73not60/xml_usc54@119-73not60.zip"
     );
 }
+
+#[tokio::test]
+async fn test_discover_usc_root_queues_appendices_after_their_title() {
+    let mock_html = r#"
+        <a href="releasepoints/us/pl/119/73not60/xml_usc05A@119-73not60.zip">Title 5 Appendix</a>
+        <a href="releasepoints/us/pl/119/73not60/xml_usc06@119-73not60.zip">Title 6</a>
+        <a href="releasepoints/us/pl/119/73not60/xml_usc05@119-73not60.zip">Title 5</a>
+    "#;
+
+    let mut fetcher = MockFetcher::new();
+    fetcher.add_fixture(USC_DOWNLOAD_PAGE_URL, mock_html);
+
+    let result = discover_usc_root(&fetcher, USC_DOWNLOAD_PAGE_URL, None)
+        .await
+        .expect("Discovery failed");
+
+    let units = result
+        .unit_roots
+        .iter()
+        .map(|u| (u.id.as_str(), u.level_name.as_str(), u.level_index))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        units,
+        vec![
+            ("title-5", "title", 0),
+            ("appendix-5a", "appendix", 1),
+            ("title-6", "title", 0),
+        ]
+    );
+}
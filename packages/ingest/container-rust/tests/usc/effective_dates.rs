@@ -0,0 +1,38 @@
+use ingest::sources::usc::effective_dates::parse_effective_date_note;
+
+#[test]
+fn extracts_iso_dates_and_public_laws() {
+    let note = parse_effective_date_note(
+        3,
+        "Section applicable to any fiscal year beginning on or after Oct. 1, 1978, see section 6(a) of Pub. L. 95–570, set out as an Effective Date of 1978 Amendment note under section 102 of this title.",
+    )
+    .unwrap();
+    assert_eq!(note.block, 3);
+    assert_eq!(note.dates, vec!["1978-10-01"]);
+    assert_eq!(note.public_laws, vec!["95-570"]);
+}
+
+#[test]
+fn skips_dates_that_identify_an_act() {
+    let note = parse_effective_date_note(
+        0,
+        "Section effective noon, Jan. 20, 1949, see section 3 of act Jan. 19, 1949.",
+    )
+    .unwrap();
+    assert_eq!(note.dates, vec!["1949-01-20"]);
+    assert!(note.public_laws.is_empty());
+
+    let note = parse_effective_date_note(
+        0,
+        "Amendment by Pub. L. 117–169 effective September 30, 2022, and Pub. L. 117–169 applies to Sept. 30, 2022, and June 1, 2023.",
+    )
+    .unwrap();
+    assert_eq!(note.dates, vec!["2022-09-30", "2023-06-01"]);
+    assert_eq!(note.public_laws, vec!["117-169"]);
+}
+
+#[test]
+fn notes_without_dates_or_laws_have_no_entry() {
+    assert!(parse_effective_date_note(0, "Section effective on enactment.").is_none());
+    assert!(parse_effective_date_note(0, "Effective Feb. 30, 2020.").is_none());
+}
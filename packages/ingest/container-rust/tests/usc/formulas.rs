@@ -0,0 +1,32 @@
+use ingest::sources::usc::formulas::mathml_to_text;
+
+#[test]
+fn renders_fractions_scripts_and_roots_as_plain_text() {
+    let mathml = r#"<math xmlns="http://www.w3.org/1998/Math/MathML">
+        <mi>A</mi><mo>=</mo>
+        <mfrac><mrow><mi>B</mi><mo>+</mo><mi>C</mi></mrow><mn>2</mn></mfrac>
+        <mo>×</mo><msup><mi>r</mi><mn>2</mn></msup>
+        <mo>−</mo><msub><mi>x</mi><mi>i</mi></msub>
+        <mo>+</mo><msqrt><mi>D</mi></msqrt>
+        <mo>+</mo><mroot><mi>E</mi><mn>3</mn></mroot>
+    </math>"#;
+    assert_eq!(
+        mathml_to_text(mathml),
+        "A = (B + C)/2 × r^2 − x_i + √D + 3√E"
+    );
+}
+
+#[test]
+fn renders_fences_and_tables_and_drops_annotations() {
+    let mathml = r#"<math>
+        <semantics>
+            <mrow>
+                <mi>f</mi><mfenced><mi>a</mi><mi>b</mi></mfenced>
+                <mo>=</mo>
+                <mtable><mtr><mtd><mn>1</mn></mtd><mtd><mn>0</mn></mtd></mtr><mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr></mtable>
+            </mrow>
+            <annotation encoding="TeX">f(a,b)</annotation>
+        </semantics>
+    </math>"#;
+    assert_eq!(mathml_to_text(mathml), "f(a, b) = 1, 0; 0, 1");
+}
@@ -1,4 +1,10 @@
 mod adapter;
+mod amendments;
 mod cross_references;
+mod definitions;
 mod discover;
+mod effective_dates;
+mod formulas;
 mod parser;
+mod source_credits;
+mod toc;
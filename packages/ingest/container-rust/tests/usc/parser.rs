@@ -1,6 +1,15 @@
-use crate::common::load_fixture;
+use crate::common::{assert_no_invisible_chars, load_fixture};
+use ingest::sources::configs::{ExternalLinks, NoteTaxonomy};
 use ingest::sources::usc::parser::usc_level_index;
-use ingest::sources::usc::parser::{parse_usc_xml, USCParentRef};
+use ingest::sources::usc::parser::{
+    parse_usc_xml, parse_usc_xml_stream, usc_stream_events, usc_stream_events_from_reader,
+    usc_stream_events_with_taxonomy, with_usc_zip_events, USCParentRef, USCSectionStatus,
+    USCStreamEvent,
+};
+use ingest::types::IngestProfile;
+use std::io::{BufReader, Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
 
 #[test]
 fn extracts_correct_title_number() {
@@ -149,7 +158,7 @@ fn does_not_bold_internal_cross_references() {
 }
 
 #[test]
-fn excludes_footnote_ref_numbers_from_body_text() {
+fn replaces_footnote_ref_numbers_with_markers() {
     let xml = r#"<?xml version="1.0"?>
         <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
             <main>
@@ -168,8 +177,13 @@ fn excludes_footnote_ref_numbers_from_body_text() {
 
     let result = parse_usc_xml(xml, "42", "");
     let section = result.sections.first().expect("section should exist");
-    assert!(section.body.contains("Alpha, and Beta."));
+    assert!(section.body.contains("Alpha,[^1] and Beta."));
     assert!(!section.body.contains("Alpha,1"));
+    assert!(!section.body.contains("So in original"));
+    let footnote = section.blocks.last().expect("footnote block should exist");
+    assert_eq!(footnote.type_, "footnote");
+    assert_eq!(footnote.label.as_deref(), Some("Footnote 1"));
+    assert_eq!(footnote.content.as_deref(), Some("So in original."));
 }
 
 #[test]
@@ -234,7 +248,7 @@ fn skips_cross_heading_notes_and_keeps_each_note_separate() {
     let note_block = section
         .blocks
         .iter()
-        .find(|block| block.type_ == "note")
+        .find(|block| block.type_ == "references_in_text")
         .expect("note block should exist");
     assert_eq!(note_block.label.as_deref(), Some("References in Text"));
     assert_eq!(
@@ -280,7 +294,10 @@ fn extracts_usc_42_section_27_notes_and_source_credit_cleanly() {
         .expect("source credit block should exist");
     assert_eq!(
         source_credit_block.content,
-        Some("(July 9, 1918, ch. 143, ch. XV, § 8, 40 Stat. 887.)".to_string())
+        Some(
+            "(July 9, 1918, ch. 143, ch. XV, § 8, [40 Stat. 887](https://www.govinfo.gov/link/statute/40/887).)"
+                .to_string()
+        )
     );
     let heading_block = section
         .blocks
@@ -291,7 +308,7 @@ fn extracts_usc_42_section_27_notes_and_source_credit_cleanly() {
     let note = section
         .blocks
         .iter()
-        .find(|block| block.type_ == "note")
+        .find(|block| block.type_ == "references_in_text")
         .expect("note block should exist");
     assert_eq!(note.label.as_deref(), Some("References in Text"));
     assert!(
@@ -332,7 +349,7 @@ fn parses_note_paragraphs_as_markdown_paragraphs() {
     let note = section
         .blocks
         .iter()
-        .find(|block| block.type_ == "note")
+        .find(|block| block.type_ == "references_in_text")
         .expect("note block should exist");
 
     assert_eq!(note.label.as_deref(), Some("References in Text"));
@@ -499,8 +516,8 @@ fn extracts_amendments_and_notes() {
     let notes = section201
         .blocks
         .iter()
-        .find(|block| block.type_ == "note")
-        .expect("Section 201 should have general notes");
+        .find(|block| block.type_ == "change_of_name")
+        .expect("Section 201 should have a change of name note");
     assert_eq!(notes.label.as_deref(), Some("Change of Name"));
 }
 
@@ -703,3 +720,701 @@ fn test_url_collision_overlapping_parts() {
     assert_eq!(result.sections[0].path, "/section/10/101");
     assert_eq!(result.sections[1].path, "/section/10/101-2");
 }
+
+#[test]
+fn stream_iterator_matches_callback_stream() {
+    let xml = load_fixture("usc/usc_title_1.xml");
+
+    let mut callback_events = Vec::new();
+    parse_usc_xml_stream(&xml, "1", |event| {
+        callback_events.push(format!("{event:?}"))
+    });
+    let iterator_events: Vec<String> = usc_stream_events(&xml, "1")
+        .map(|event| format!("{event:?}"))
+        .collect();
+
+    assert!(!iterator_events.is_empty());
+    assert_eq!(iterator_events, callback_events);
+}
+
+#[test]
+fn stream_iterator_supports_early_exit() {
+    let xml = load_fixture("usc/usc_title_1.xml");
+
+    let first_sections: Vec<String> = usc_stream_events(&xml, "1")
+        .filter_map(|event| match event {
+            USCStreamEvent::Section(section) => Some(section.section_num),
+            _ => None,
+        })
+        .take(2)
+        .collect();
+    assert_eq!(first_sections, vec!["1", "2"]);
+
+    let sections_before_3 = usc_stream_events(&xml, "1")
+        .take_while(|event| {
+            !matches!(event, USCStreamEvent::Section(section) if section.section_num == "3")
+        })
+        .filter(|event| matches!(event, USCStreamEvent::Section(_)))
+        .count();
+    assert_eq!(sections_before_3, 2);
+}
+
+#[test]
+fn stream_errors_carry_position_and_element_path() {
+    let xml = "<uscDoc>\n  <main>\n    <title identifier=\"/us/usc/t99\">\n      <heading>Broken</chapter>\n";
+    let error = usc_stream_events(xml, "99")
+        .find_map(|event| match event {
+            USCStreamEvent::Error(error) => Some(error),
+            _ => None,
+        })
+        .expect("malformed XML should produce an error event");
+
+    let position = error.position.expect("error should carry a position");
+    assert_eq!(position.line, 4);
+    assert_eq!(error.element_path, "/uscDoc/main/title/heading");
+    assert!(error.message.starts_with("XML parsing error"));
+}
+
+fn section_summaries(events: impl Iterator<Item = USCStreamEvent>) -> Vec<String> {
+    events
+        .filter_map(|event| match event {
+            USCStreamEvent::Section(section) => {
+                Some(format!("{} {}", section.section_key, section.body))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn reader_stream_matches_string_stream() {
+    let xml = load_fixture("usc/usc_title_1.xml");
+    let from_str = section_summaries(usc_stream_events(&xml, "1"));
+    let from_reader = section_summaries(usc_stream_events_from_reader(
+        BufReader::with_capacity(64, xml.as_bytes()),
+        "1",
+    ));
+
+    assert!(!from_str.is_empty());
+    assert_eq!(from_reader, from_str);
+}
+
+#[test]
+fn streams_the_xml_entry_of_a_title_zip() {
+    let xml = load_fixture("usc/usc_title_1.xml");
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("README.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"not xml").unwrap();
+    zip.start_file("usc01.xml", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(xml.as_bytes()).unwrap();
+    let archive = zip.finish().unwrap();
+
+    let from_zip = with_usc_zip_events(archive, "1", |events| {
+        section_summaries(events.with_profile(IngestProfile::NotesOnly))
+    })
+    .unwrap();
+    let expected =
+        section_summaries(usc_stream_events(&xml, "1").with_profile(IngestProfile::NotesOnly));
+    assert_eq!(from_zip, expected);
+
+    let empty = ZipWriter::new(Cursor::new(Vec::new())).finish().unwrap();
+    assert!(with_usc_zip_events(empty, "1", |events| events.count()).is_err());
+}
+
+#[test]
+fn reader_stream_errors_carry_byte_offset() {
+    let xml = "<uscDoc>\n  <main>\n    <heading>Broken</chapter>\n";
+    let error = usc_stream_events_from_reader(xml.as_bytes(), "99")
+        .find_map(|event| match event {
+            USCStreamEvent::Error(error) => Some(error),
+            _ => None,
+        })
+        .expect("malformed XML should produce an error event");
+
+    assert_eq!(error.position, None);
+    assert!(error.message.contains(" at byte "));
+    assert_eq!(error.element_path, "/uscDoc/main/heading");
+}
+
+#[test]
+fn classifies_notes_with_custom_taxonomy() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
+            <main>
+                <title identifier="/us/usc/t42">
+                    <section identifier="/us/usc/t42/s27">
+                        <num value="27">§ 27.</num>
+                        <heading>Definitions</heading>
+                        <content>Body text.</content>
+                        <notes>
+                            <note topic="effectiveDate">
+                                <p>Section effective on enactment.</p>
+                            </note>
+                            <note topic="amendments">
+                                <p>2000—Pub. L. 106–1 amended section.</p>
+                            </note>
+                        </notes>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let mut taxonomy = NoteTaxonomy::default();
+    taxonomy
+        .topics
+        .insert("effectivedate".to_string(), "effective_date".to_string());
+    taxonomy
+        .default_labels
+        .insert("effective_date".to_string(), "Effective Date".to_string());
+
+    let section = usc_stream_events_with_taxonomy(xml, "42", taxonomy)
+        .find_map(|event| match event {
+            USCStreamEvent::Section(section) => Some(section),
+            _ => None,
+        })
+        .expect("section should exist");
+
+    let kinds: Vec<(&str, Option<&str>)> = section
+        .blocks
+        .iter()
+        .map(|block| (block.type_.as_str(), block.label.as_deref()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("effective_date", Some("Effective Date")),
+            ("amendments", Some("Amendments"))
+        ]
+    );
+}
+
+#[test]
+fn links_cfr_statutes_at_large_and_public_law_refs() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
+            <main>
+                <title identifier="/us/usc/t42">
+                    <section identifier="/us/usc/t42/s27">
+                        <num value="27">§ 27.</num>
+                        <heading>Definitions</heading>
+                        <content>See <ref href="/us/cfr/t42/s405.1">42 CFR 405.1</ref> and <ref href="/us/cfr/t20/pt404">20 CFR part 404</ref>.</content>
+                        <sourceCredit>(<ref href="/us/pl/117/328/dP/tI/s102/a">Pub. L. 117–328, div. P, title I, § 102(a)</ref>, Dec. 29, 2022, <ref href="/us/stat/136/5233-5234">136 Stat. 5233</ref>.)</sourceCredit>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let section = parse_usc_xml(xml, "42", "").sections.remove(0);
+    assert_eq!(
+        section.body,
+        "See [42 CFR 405.1](https://www.ecfr.gov/current/title-42/section-405.1) and [20 CFR part 404](https://www.ecfr.gov/current/title-20/part-404)."
+    );
+    assert_eq!(
+        section.blocks[0].content.as_deref(),
+        Some("([Pub. L. 117–328, div. P, title I, § 102(a)](https://www.govinfo.gov/link/plaw/117/public/328), Dec. 29, 2022, [136 Stat. 5233](https://www.govinfo.gov/link/statute/136/5233).)")
+    );
+
+    let links = ExternalLinks {
+        cfr_part: String::new(),
+        public_law: "https://example.com/pl/{congress}-{number}".to_string(),
+        ..ExternalLinks::default()
+    };
+    let section = usc_stream_events(xml, "42")
+        .with_external_links(links)
+        .find_map(|event| match event {
+            USCStreamEvent::Section(section) => Some(section),
+            _ => None,
+        })
+        .expect("section should exist");
+    assert!(section.body.ends_with(" and 20 CFR part 404."));
+    assert!(section.blocks[0]
+        .content
+        .as_deref()
+        .is_some_and(|credit| credit.contains("](https://example.com/pl/117-328)")));
+}
+
+#[test]
+fn flags_repealed_omitted_transferred_and_reserved_sections() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
+            <main>
+                <title identifier="/us/usc/t42">
+                    <section identifier="/us/usc/t42/s1971">
+                        <num value="1971">§ 1971.</num>
+                        <heading>Voting rights</heading>
+                        <content>Body text.</content>
+                    </section>
+                    <section identifier="/us/usc/t42/s1973">
+                        <num value="1973">[§ 1973.</num>
+                        <heading> Transferred]</heading>
+                        <notes type="uscNote">
+                            <note topic="codification">
+                                <heading>Codification</heading>
+                                <p>Section 1973 was editorially reclassified as <ref href="/us/usc/t52/s10301">section 10301 of Title 52</ref>, Voting and Elections.</p>
+                            </note>
+                        </notes>
+                    </section>
+                    <section identifier="/us/usc/t42/s1974">
+                        <num value="1974">[§ 1974.</num>
+                        <heading> Transferred to section 1975a of this title]</heading>
+                    </section>
+                    <section status="repealed" identifier="/us/usc/t42/s1975">
+                        <num value="1975">[§ 1975.</num>
+                        <heading> Pub. L. 85–315, Sept. 9, 1957, 71 Stat. 634]</heading>
+                    </section>
+                    <section identifier="/us/usc/t42/s1976">
+                        <num value="1976">[§ 1976.</num>
+                        <heading> Omitted]</heading>
+                    </section>
+                    <section identifier="/us/usc/t42/s1977">
+                        <num value="1977">[§ 1977.</num>
+                        <heading> Reserved]</heading>
+                    </section>
+                    <section identifier="/us/usc/t42/s1978">
+                        <num value="1978">[§ 1978.</num>
+                        <heading> Vacant]</heading>
+                    </section>
+                    <section identifier="/us/usc/t42/s1979">
+                        <num value="1979">[§ 1979.</num>
+                        <heading> Equal rights under the law]</heading>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let result = parse_usc_xml(xml, "42", "");
+    let statuses: Vec<(&str, USCSectionStatus, Option<&str>)> = result
+        .sections
+        .iter()
+        .map(|section| {
+            (
+                section.section_num.as_str(),
+                section.status,
+                section.transferred_to.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("1971", USCSectionStatus::Active, None),
+            ("1973", USCSectionStatus::Transferred, Some("52:10301")),
+            ("1974", USCSectionStatus::Transferred, Some("42:1975a")),
+            ("1975", USCSectionStatus::Repealed, None),
+            ("1976", USCSectionStatus::Omitted, None),
+            ("1977", USCSectionStatus::Reserved, None),
+            ("1978", USCSectionStatus::Reserved, None),
+            ("1979", USCSectionStatus::Omitted, None),
+        ]
+    );
+
+    let xml = load_fixture("usc/usc03.xml");
+    let result = parse_usc_xml(&xml, "3", "");
+    let status_of = |num: &str| {
+        result
+            .sections
+            .iter()
+            .find(|section| section.section_num == num)
+            .map(|section| section.status)
+    };
+    assert_eq!(status_of("1"), Some(USCSectionStatus::Active));
+    assert_eq!(status_of("2"), Some(USCSectionStatus::Repealed));
+}
+
+#[test]
+fn emits_section_ranges_as_one_range_node() {
+    let xml = load_fixture("usc/usc03.xml");
+    let result = parse_usc_xml(&xml, "3", "");
+
+    let range = result
+        .sections
+        .iter()
+        .find(|section| section.range_start.as_deref() == Some("202"))
+        .expect("range section should exist");
+    assert_eq!(range.section_num, "202_to_204");
+    assert_eq!(range.range_end.as_deref(), Some("204"));
+    assert_eq!(range.section_key, "3:202_to_204");
+    assert_eq!(range.path, "/section/3/202_to_204");
+    assert_eq!(range.status, USCSectionStatus::Repealed);
+
+    let single = result
+        .sections
+        .iter()
+        .find(|section| section.section_num == "201")
+        .expect("section 201 should exist");
+    assert_eq!(single.range_start, None);
+    assert_eq!(single.range_end, None);
+}
+
+#[test]
+fn emits_structured_quotes_in_notes_as_quoted_blocks() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
+            <main>
+                <title identifier="/us/usc/t42">
+                    <section identifier="/us/usc/t42/s27">
+                        <num value="27">§ 27.</num>
+                        <heading>Definitions</heading>
+                        <content>Body text.</content>
+                        <notes type="uscNote">
+                            <note topic="referencesInText">
+                                <heading>References in Text</heading>
+                                <p>Section 5 of the Act provides:<quotedContent>
+                                    <section><num value="5">“SEC. 5.</num><heading> SHORT TITLE.</heading>
+                                        <subsection><num value="a">“(a)</num><heading> <inline class="small-caps">In General</inline>.—</heading>
+                                            <chapeau>This Act may be cited as—</chapeau>
+                                            <paragraph><num value="1">“(1)</num><content> the Example Act.”</content></paragraph>
+                                        </subsection>
+                                    </section>
+                                </quotedContent></p>
+                                <p>The Act is classified to this chapter.</p>
+                            </note>
+                            <note topic="effectiveDate">
+                                <heading>Effective Date</heading>
+                                <p>Section 3 of the Act provided that: <quotedContent>“This Act shall take effect on <date date="2000-01-01">January 1, 2000</date>.”</quotedContent></p>
+                            </note>
+                        </notes>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let section = parse_usc_xml(xml, "42", "").sections.remove(0);
+    let blocks: Vec<(&str, Option<&str>, Option<&str>)> = section
+        .blocks
+        .iter()
+        .map(|block| {
+            (
+                block.type_.as_str(),
+                block.label.as_deref(),
+                block.content.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![
+            (
+                "references_in_text",
+                Some("References in Text"),
+                Some("Section 5 of the Act provides:\n\nThe Act is classified to this chapter."),
+            ),
+            (
+                "quoted",
+                Some("References in Text"),
+                Some(
+                    "> **“SEC. 5.** **SHORT TITLE.**\n\n\
+                     > > **“(a)** **In General.—**\n\n\
+                     > > This Act may be cited as—\n\n\
+                     > > > **“(1)** the Example Act.”"
+                ),
+            ),
+            (
+                "effective_date",
+                Some("Effective Date"),
+                Some("Section 3 of the Act provided that: “This Act shall take effect on January 1, 2000.”"),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn notes_only_profile_skips_body_text() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t42">
+            <main>
+                <title identifier="/us/usc/t42">
+                    <section identifier="/us/usc/t42/s27">
+                        <num value="27">§ 27.</num>
+                        <heading>Definitions</heading>
+                        <subsection identifier="/us/usc/t42/s27/a">
+                            <num value="a">(a)</num>
+                            <content>Body text.</content>
+                        </subsection>
+                        <sourceCredit>(July 1, 1944.)</sourceCredit>
+                        <notes>
+                            <note topic="amendments">
+                                <p>2000—Pub. L. 106–1 amended section.</p>
+                            </note>
+                        </notes>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let section = usc_stream_events(xml, "42")
+        .with_profile(IngestProfile::NotesOnly)
+        .find_map(|event| match event {
+            USCStreamEvent::Section(section) => Some(section),
+            _ => None,
+        })
+        .expect("section should exist");
+
+    assert_eq!(section.heading, "Definitions");
+    assert_eq!(section.body, "");
+    let kinds: Vec<&str> = section
+        .blocks
+        .iter()
+        .map(|block| block.type_.as_str())
+        .collect();
+    assert_eq!(kinds, vec!["source_credit", "amendments"]);
+}
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let xml = load_fixture("usc/usc_invisible_chars.xml");
+    let result = parse_usc_xml(&xml, "1", "");
+    assert_eq!(result.title_name, "General Provisions");
+    assert_eq!(result.levels[0].heading, "Rules of Construction");
+    let section = &result.sections[0];
+    assert_eq!(
+        section.heading,
+        "Words denoting number, gender, and so forth"
+    );
+    assert_eq!(
+        section.body,
+        "In determining the meaning of any Act of Congress, unless the context indicates otherwise—"
+    );
+    assert_no_invisible_chars(&format!("{result:?}"));
+}
+
+#[test]
+fn renders_section_tables_as_markdown_tables() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t26">
+            <main>
+                <title identifier="/us/usc/t26">
+                    <section identifier="/us/usc/t26/s1">
+                        <num value="1">§ 1.</num>
+                        <heading>Tax imposed</heading>
+                        <subsection identifier="/us/usc/t26/s1/a">
+                            <num value="a">(a)</num>
+                            <chapeau>There is hereby imposed a tax determined in accordance with the following table:</chapeau>
+                            <table xmlns="http://www.w3.org/1999/xhtml">
+                                <thead>
+                                    <tr><th colspan="2"><p>Rates for (A) joint returns</p></th></tr>
+                                    <tr>
+                                        <th style="text-align:center;"><p>If taxable income is:</p></th>
+                                        <th style="text-align:center;"><p>The tax is:</p></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <tr>
+                                        <td style=" text-align:left; vertical-align:top;">Not over $36,900</td>
+                                        <td style=" text-align:right;">15% of <i>taxable</i> income.</td>
+                                    </tr>
+                                    <tr>
+                                        <td>Over $36,900 | but not over $89,150</td>
+                                        <td><p>$5,535,</p><p>plus 28%</p></td>
+                                    </tr>
+                                    <tr><td colspan="2">Over $140,000</td></tr>
+                                </tbody>
+                            </table>
+                            <continuation>The amounts are adjusted for inflation.</continuation>
+                        </subsection>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let result = parse_usc_xml(xml, "26", "");
+    let section = result.sections.first().expect("section should exist");
+    assert_eq!(
+        section.body,
+        "**(a)** There is hereby imposed a tax determined in accordance with the following table:\n\n\
+         **Rates for (A) joint returns**\n\n\
+         | If taxable income is: | The tax is: |\n\
+         | :--- | ---: |\n\
+         | Not over $36,900 | 15% of taxable income. |\n\
+         | Over $36,900 \\| but not over $89,150 | $5,535, plus 28% |\n\
+         | Over $140,000 |  |\n\n\
+         The amounts are adjusted for inflation."
+    );
+}
+
+#[test]
+fn renders_layouts_in_notes_and_keeps_body_order_around_tables() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t99">
+            <main>
+                <title identifier="/us/usc/t99">
+                    <section identifier="/us/usc/t99/s1">
+                        <num value="1">§ 1.</num>
+                        <heading>Schedule</heading>
+                        <content>
+                            <table xmlns="http://www.w3.org/1999/xhtml">
+                                <tr><td>Class A</td><td>$10</td></tr>
+                            </table>
+                            <p>Fees are payable in advance.</p>
+                        </content>
+                        <notes type="uscNote">
+                            <note topic="miscellaneous">
+                                <heading>Prior Provisions</heading>
+                                <p>Former sections:</p>
+                                <layout>
+                                    <header><column>Sec.</column><column/><column>Now</column></header>
+                                    <row><column>1a</column><column>Repealed</column><column>2</column></row>
+                                </layout>
+                            </note>
+                        </notes>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let result = parse_usc_xml(xml, "99", "");
+    let section = result.sections.first().expect("section should exist");
+    assert_eq!(
+        section.body,
+        "|  |  |\n| --- | --- |\n| Class A | $10 |\n\nFees are payable in advance."
+    );
+    let note = section
+        .blocks
+        .iter()
+        .find(|block| block.label.as_deref() == Some("Prior Provisions"))
+        .expect("note should exist");
+    assert_eq!(
+        note.content.as_deref(),
+        Some("Former sections:\n\n| Sec. |  | Now |\n| --- | --- | --- |\n| 1a | Repealed | 2 |")
+    );
+}
+
+#[test]
+fn numbers_footnotes_per_section_in_reference_order() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t3">
+            <main>
+                <title identifier="/us/usc/t3">
+                    <section identifier="/us/usc/t3/s105">
+                        <num value="105">§ 105.</num>
+                        <heading>Assistance<ref class="footnoteRef" idref="fn3">3</ref></heading>
+                        <subsection>
+                            <num>(a)</num>
+                            <content>The President<ref class="footnoteRef" idref="fn2">2</ref> may appoint employees<ref class="footnoteRef" idref="fn1">1</ref> without regard to the provisions<ref class="footnoteRef" idref="fn2">2</ref> of law.<footnote id="fn1"><num>1</num> So in original. Probably should be “employees”.</footnote></content>
+                        </subsection>
+                        <notes type="uscNote">
+                            <note topic="amendments">
+                                <heading>Amendments</heading>
+                                <p>1978—Subsec. (a)<ref class="footnoteRef" idref="fn4">4</ref> added.</p>
+                            </note>
+                        </notes>
+                        <note type="footnote" id="fn2"><num>2</num> As amended Sept. 15, 1977.</note>
+                        <note type="footnote" id="fn3"><num>3</num> Section catchline.</note>
+                        <note type="footnote" id="fn4"><num>4</num> See <ref href="/us/usc/t3/s106">section 106</ref>.</note>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let result = parse_usc_xml(xml, "3", "");
+    let section = result.sections.first().expect("section should exist");
+    assert_eq!(section.heading, "Assistance");
+    assert_eq!(
+        section.body,
+        "**(a)** The President[^2] may appoint employees[^3] without regard to the provisions[^2] of law."
+    );
+    let amendments = section
+        .blocks
+        .iter()
+        .find(|block| block.type_ == "amendments")
+        .expect("amendments note should exist");
+    assert_eq!(
+        amendments.content.as_deref(),
+        Some("1978—Subsec. (a)[^4] added.")
+    );
+
+    let footnotes = section
+        .blocks
+        .iter()
+        .filter(|block| block.type_ == "footnote")
+        .map(|block| {
+            (
+                block.label.as_deref().unwrap(),
+                block.content.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        footnotes,
+        vec![
+            ("Footnote 1", "Section catchline."),
+            ("Footnote 2", "As amended Sept. 15, 1977."),
+            (
+                "Footnote 3",
+                "So in original. Probably should be “employees”."
+            ),
+            ("Footnote 4", "See section 106."),
+        ]
+    );
+}
+
+#[test]
+fn captures_mathml_as_formula_blocks() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t26">
+            <main>
+                <title identifier="/us/usc/t26">
+                    <section identifier="/us/usc/t26/s1">
+                        <num value="1">§ 1.</num>
+                        <heading>Tax imposed</heading>
+                        <content>The amount is <math xmlns="http://www.w3.org/1998/Math/MathML"><mi>T</mi><mo>=</mo><mfrac><mi>I</mi><mn>12</mn></mfrac></math> for each month.</content>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let section = parse_usc_xml(xml, "26", "").sections.remove(0);
+    assert_eq!(section.body, "The amount is T = I/12 for each month.");
+    assert_eq!(section.blocks.len(), 1);
+    assert_eq!(section.blocks[0].type_, "formula");
+    assert_eq!(section.blocks[0].content.as_deref(), Some("T = I/12"));
+    assert_eq!(section.formulas.len(), 1);
+    assert_eq!(section.formulas[0].block, 0);
+    assert!(section.formulas[0].mathml.starts_with("<math "));
+    assert!(section.formulas[0].mathml.ends_with("</math>"));
+}
+
+#[test]
+fn emits_figures_and_graphics_as_image_blocks() {
+    let xml = r#"<?xml version="1.0"?>
+        <uscDoc xmlns="http://xml.house.gov/schemas/uslm/1.0" identifier="/us/usc/t4">
+            <main>
+                <title identifier="/us/usc/t4">
+                    <section identifier="/us/usc/t4/s1">
+                        <num value="1">§ 1.</num>
+                        <heading>Flag; stripes and stars on</heading>
+                        <content>The flag shall be as follows:</content>
+                        <figure><img src="images/usc004_flag.png"/><caption>The Flag of the United States</caption></figure>
+                        <content>Rate table: <graphic href="/graphics/usc026_rates.gif" alt="Table of rates"/></content>
+                    </section>
+                </title>
+            </main>
+        </uscDoc>"#;
+
+    let section = parse_usc_xml(xml, "4", "").sections.remove(0);
+    assert_eq!(section.body, "The flag shall be as follows: Rate table:");
+    let blocks: Vec<(&str, Option<&str>, Option<&str>)> = section
+        .blocks
+        .iter()
+        .map(|block| {
+            (
+                block.type_.as_str(),
+                block.label.as_deref(),
+                block.content.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![
+            (
+                "image",
+                Some("usc004_flag.png"),
+                Some("The Flag of the United States")
+            ),
+            ("image", Some("usc026_rates.gif"), Some("Table of rates")),
+        ]
+    );
+}
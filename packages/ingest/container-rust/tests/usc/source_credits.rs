@@ -0,0 +1,72 @@
+use ingest::sources::usc::source_credits::{parse_source_credit, SourceCreditEntry};
+
+#[test]
+fn reads_linked_citations_as_text() {
+    let entries = parse_source_credit(
+        "([Pub. L. 117–328, div. P, title I, § 102(a)](https://www.govinfo.gov/link/plaw/117/public/328), Dec. 29, 2022, [136 Stat. 5233](https://www.govinfo.gov/link/statute/136/5233).)",
+    );
+    assert_eq!(
+        entries,
+        vec![SourceCreditEntry {
+            public_law: Some("117-328".to_string()),
+            section: Some("§ 102(a)".to_string()),
+            date: Some("2022-12-29".to_string()),
+            statutes_at_large: Some("136 Stat. 5233".to_string()),
+            ..Default::default()
+        }]
+    );
+}
+
+#[test]
+fn parses_each_law_in_a_source_credit() {
+    let entries = parse_source_credit(
+        "(June 25, 1948, ch. 644, 62 Stat. 678; Oct. 15, 1949, ch. 695, § 2(a), 63 Stat. 880; Pub. L. 117–328, div. P, title I, §§ 102(b), 104(b), Dec. 29, 2022, 136 Stat. 5233, 5235.)",
+    );
+    assert_eq!(
+        entries,
+        vec![
+            SourceCreditEntry {
+                chapter: Some("644".to_string()),
+                date: Some("1948-06-25".to_string()),
+                statutes_at_large: Some("62 Stat. 678".to_string()),
+                ..Default::default()
+            },
+            SourceCreditEntry {
+                chapter: Some("695".to_string()),
+                section: Some("§ 2(a)".to_string()),
+                date: Some("1949-10-15".to_string()),
+                statutes_at_large: Some("63 Stat. 880".to_string()),
+                ..Default::default()
+            },
+            SourceCreditEntry {
+                public_law: Some("117-328".to_string()),
+                section: Some("§§ 102(b), 104(b)".to_string()),
+                date: Some("2022-12-29".to_string()),
+                statutes_at_large: Some("136 Stat. 5233, 5235".to_string()),
+                ..Default::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_added_and_amended_entries() {
+    let entries = parse_source_credit(
+        "(Added Oct. 31, 1951, ch. 655, § 2(b), 65 Stat. 710; amended Pub. L. 98–497, title I, § 107(d), Oct. 19, 1984, 98 Stat. 2291.)",
+    );
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].section.as_deref(), Some("§ 2(b)"));
+    assert_eq!(
+        entries[0].statutes_at_large.as_deref(),
+        Some("65 Stat. 710")
+    );
+    assert_eq!(entries[1].public_law.as_deref(), Some("98-497"));
+    assert_eq!(entries[1].section.as_deref(), Some("§ 107(d)"));
+    assert_eq!(entries[1].date.as_deref(), Some("1984-10-19"));
+}
+
+#[test]
+fn skips_text_that_cites_no_law() {
+    assert!(parse_source_credit("").is_empty());
+    assert!(parse_source_credit("(As amended generally.)").is_empty());
+}
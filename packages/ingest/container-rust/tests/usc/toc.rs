@@ -0,0 +1,27 @@
+use crate::common::load_fixture;
+use ingest::sources::usc::toc::{declared_toc_entries, validate_toc};
+
+#[test]
+fn reads_linked_toc_entries_and_skips_footnotes() {
+    let xml = load_fixture("usc/usc03.xml");
+    let declared = declared_toc_entries(&xml, "3");
+
+    assert_eq!(declared.len(), 66);
+    assert_eq!(&declared[..3], ["t3/ch1", "t3/s1", "t3/ch2"]);
+    assert!(declared.iter().all(|id| id.starts_with("t3/")));
+    assert!(!declared.iter().any(|id| id.contains("ch5/sch")));
+}
+
+#[test]
+fn flags_missing_and_undeclared_ids() {
+    let declared = ["t3/ch1", "t3/s1", "t3/s2"].map(String::from);
+    let extracted = ["t3/ch1", "t3/ch1/schI", "t3/s1", "t3/s3"].map(String::from);
+    let toc = validate_toc("3", &declared, &extracted);
+
+    assert!(!toc.is_consistent());
+    assert_eq!(toc.declared, 3);
+    assert_eq!(toc.extracted, 4);
+    assert_eq!(toc.missing, ["t3/s2"]);
+    // No TOC links a subchapter, so an unlisted one is not flagged.
+    assert_eq!(toc.undeclared, ["t3/s3"]);
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "usc")]
+
 mod common;
 mod usc;
@@ -1,9 +1,13 @@
+use crate::common::assert_no_invisible_chars;
+use ingest::sources::common::{flatten_outline_depth, MAX_OUTLINE_DEPTH};
 use ingest::sources::uspl::markdown::law_to_markdown;
-use ingest::sources::uspl::parser::{parse_uslm_volume, Block, Inline, ParsedLaw};
+use ingest::sources::uspl::parser::{
+    parse_uslm_volume, parse_uslm_volume_until, Block, Inline, ParsedLaw,
+};
 
 fn parse_single(xml: &str) -> Option<ParsedLaw> {
     let mut laws = Vec::new();
-    parse_uslm_volume(xml, |law| laws.push(law));
+    parse_uslm_volume(xml, |law| laws.push(law)).unwrap();
     laws.into_iter().next()
 }
 
@@ -80,7 +84,7 @@ fn skips_private_laws() {
   </pLaw>
 </statutesAtLarge>"#;
     let mut laws = Vec::new();
-    parse_uslm_volume(xml, |law| laws.push(law));
+    parse_uslm_volume(xml, |law| laws.push(law)).unwrap();
     assert!(laws.is_empty(), "private laws should be skipped");
 }
 
@@ -106,10 +110,48 @@ fn multiple_laws_in_volume() {
   </pLaw>
 </statutesAtLarge>"#;
     let mut laws = Vec::new();
-    parse_uslm_volume(xml, |law| laws.push(law));
+    parse_uslm_volume(xml, |law| laws.push(law)).unwrap();
     assert_eq!(laws.len(), 2);
     assert_eq!(laws[0].public_law_number, "113-1");
     assert_eq!(laws[1].public_law_number, "113-2");
+
+    // Cancelling after the first law stops the parse before the second.
+    let laws = std::cell::RefCell::new(Vec::new());
+    parse_uslm_volume_until(
+        xml,
+        || !laws.borrow().is_empty(),
+        |law| laws.borrow_mut().push(law),
+    )
+    .unwrap();
+    assert_eq!(laws.borrow().len(), 1);
+}
+
+#[test]
+fn reports_position_and_element_path_for_malformed_xml() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<statutesAtLarge xmlns="http://xml.house.gov/schemas/uslm/1.0">
+  <pLaw>
+    <meta>
+      <publicPrivate>Public</publicPrivate>
+      <congress>113</congress>
+      <docNumber>1</docNumber>
+    </meta>
+    <main/>
+  </pLaw>
+  <pLaw>
+    <meta>
+      <congress>113</docNumber>
+    </meta>
+  </pLaw>
+</statutesAtLarge>"#;
+    let mut laws = Vec::new();
+    let err = parse_uslm_volume(xml, |law| laws.push(law)).unwrap_err();
+
+    assert_eq!(laws.len(), 1, "laws before the error are still emitted");
+    let position = err.position.expect("error should carry a position");
+    assert_eq!(position.line, 13);
+    assert_eq!(err.element_path, "/statutesAtLarge/pLaw/meta/congress");
+    assert!(err.to_string().contains("at line 13, column "));
 }
 
 // ── Parser: content blocks ────────────────────────────────────────────────────
@@ -325,6 +367,40 @@ fn renders_usc_link_in_markdown() {
     );
 }
 
+#[test]
+fn flattens_quotes_nested_beyond_max_outline_depth() {
+    let mut block = Block::Para(vec![Inline::Text("Deep text (zz).".to_string())]);
+    for _ in 0..(MAX_OUTLINE_DEPTH + 3) {
+        block = Block::Quoted(vec![block]);
+    }
+    let law = ParsedLaw {
+        public_law_number: "113-1".to_string(),
+        stat_citation: String::new(),
+        official_title: String::new(),
+        approved_date: String::new(),
+        congress: 113,
+        blocks: vec![block],
+        source_page: String::new(),
+    };
+
+    let md = law_to_markdown(&law);
+    let expected = format!("{}Deep text (zz).", "> ".repeat(MAX_OUTLINE_DEPTH));
+    assert!(md.lines().any(|line| line == expected), "md: {md}");
+    assert!(
+        !md.contains(&"> ".repeat(MAX_OUTLINE_DEPTH + 1)),
+        "md: {md}"
+    );
+}
+
+#[test]
+fn flatten_outline_depth_keeps_shallow_lines() {
+    let md = "Intro\n> **(a)** One\n> > > **(i)** Three\n>\n> > > > Four";
+    assert_eq!(
+        flatten_outline_depth(md, 2),
+        "Intro\n> **(a)** One\n> > **(i)** Three\n>\n> > Four"
+    );
+}
+
 // ── VolumeMetadata ────────────────────────────────────────────────────────────
 
 #[test]
@@ -346,3 +422,15 @@ fn volume_metadata_returns_none_for_too_few_fields() {
     let result = VolumeMetadata::parse("only|three|fields");
     assert!(result.is_none());
 }
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let xml = wrap_law(
+        "",
+        "<section><num>1.</num><heading>Short Ti&#173;tle</heading><content>This Act may be cited as the Test\u{200b} Act.\u{feff}</content></section>",
+    );
+    let markdown = law_to_markdown(&parse_single(&xml).expect("law"));
+    assert!(markdown.contains("Short Title"), "{markdown}");
+    assert!(markdown.contains("the Test Act."), "{markdown}");
+    assert_no_invisible_chars(&markdown);
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "uspl")]
+
 mod common;
 mod uspl;
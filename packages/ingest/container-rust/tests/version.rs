@@ -0,0 +1,65 @@
+use ingest::sources::version::{version_regression, SourceVersion};
+
+fn v(id: &str) -> SourceVersion {
+    SourceVersion::parse(id)
+}
+
+#[test]
+fn parses_each_discovered_version_shape() {
+    assert!(matches!(
+        v("118-200"),
+        SourceVersion::ReleasePoint {
+            congress: 118,
+            law: 200,
+            exclusion: None
+        }
+    ));
+    assert!(matches!(v("2024-05-01"), SourceVersion::Date(_)));
+    assert_eq!(v("2024"), SourceVersion::Year(2024));
+    assert_eq!(v("v2"), SourceVersion::Semantic(vec![2]));
+    assert_eq!(v("1.10.0"), SourceVersion::Semantic(vec![1, 10, 0]));
+    assert_eq!(
+        v("undated-00ff"),
+        SourceVersion::Opaque("undated-00ff".to_string())
+    );
+
+    for id in [
+        "118-200not118-158",
+        "2024-05-01",
+        "2024",
+        "v1.2",
+        "undated-00ff",
+    ] {
+        assert_eq!(v(id).to_string(), id);
+    }
+    assert_eq!(
+        SourceVersion::from_source_version_id("usc", "usc-118-200"),
+        v("118-200")
+    );
+}
+
+#[test]
+fn orders_versions_numerically_not_as_strings() {
+    assert!(v("118-200") > v("118-34"));
+    assert!(v("119-1") > v("118-200"));
+    assert!(v("118-200not118-158") < v("118-200"));
+    assert!(v("118-201not118-158") > v("118-200"));
+    assert!(v("2024-10-01") > v("2024-09-30"));
+    assert!(v("2025") > v("2024-12-31"));
+    assert!(v("v1.10") > v("v1.9"));
+
+    assert_eq!(v("2024").partial_cmp(&v("2024-06-01")), None);
+    assert_eq!(v("118-200").partial_cmp(&v("2024")), None);
+    assert_eq!(v("undated-1").partial_cmp(&v("undated-2")), None);
+    assert_eq!(v("119-1").is_newer_than(&v("118-200")), Some(true));
+    assert_eq!(v("118-200").is_newer_than(&v("118-200")), Some(false));
+}
+
+#[test]
+fn blocks_only_definite_regressions() {
+    let error = version_regression(&v("118-200"), &v("118-34")).unwrap();
+    assert!(error.contains("118-34"), "{error}");
+    assert!(version_regression(&v("118-200"), &v("118-200")).is_none());
+    assert!(version_regression(&v("118-200"), &v("119-1")).is_none());
+    assert!(version_regression(&v("undated-1"), &v("undated-2")).is_none());
+}
@@ -4,8 +4,9 @@ use ingest::runtime::types::QueueItem;
 use ingest::runtime::types::{BuildContext, IngestContext, NodeStore, UrlQueue};
 use ingest::sources::vt::adapter::VtAdapter;
 use ingest::sources::SourceAdapter;
-use ingest::types::{NodePayload, SectionContent};
+use ingest::types::{IngestProfile, NodePayload, SectionContent};
 use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 #[tokio::test]
 async fn adapter_emits_title_chapter_and_section_nodes_from_fullchapter() {
@@ -38,6 +39,7 @@ async fn adapter_emits_title_chapter_and_section_nodes_from_fullchapter() {
     };
 
     t.run_item(item).await;
+    t.assert_output_pinned(2, "3b6de8448cb26e6d");
 
     t.expect_node("vt/v1/root/title-02")
         .level("title")
@@ -102,6 +104,61 @@ async fn adapter_emits_title_chapter_and_section_nodes_from_fullchapter() {
         }));
 }
 
+#[tokio::test]
+async fn adapter_nests_sections_under_subchapters() {
+    let mut t = AdapterTestContext::new(VtAdapter, "vt/v1/root");
+
+    let chapter_url = "https://legislature.vermont.gov/statutes/chapter/09/063";
+    let fullchapter_url = "https://legislature.vermont.gov/statutes/fullchapter/09/063";
+    t.add_fixture(
+        fullchapter_url,
+        &load_fixture("vt/fullchapter_09_063_medium.html"),
+    );
+
+    let item = QueueItem {
+        url: chapter_url.to_string(),
+        parent_id: "vt/v1/root/title-09".to_string(),
+        level_name: "chapter".to_string(),
+        level_index: 1,
+        metadata: serde_json::json!({
+            "unit_id": "vt-title-09",
+            "title_num": "09",
+            "title_display_num": "9",
+            "chapter_num": "063",
+            "chapter_display_num": "63",
+            "fullchapter_url": fullchapter_url,
+            "sort_order": 0
+        }),
+    };
+
+    t.run_item(item).await;
+
+    t.expect_node("vt/v1/root/title-09/chapter-063/subchapter-1")
+        .level("subchapter")
+        .name("General Provisions")
+        .path("/title/09/chapter/063/subchapter/1")
+        .readable_id("1")
+        .heading_citation("Subchapter 1");
+
+    t.expect_node("vt/v1/root/title-09/chapter-063/section-2451a")
+        .level("section")
+        .parent("vt/v1/root/title-09/chapter-063/subchapter-1")
+        .path("/title/09/chapter/063/section/2451a")
+        .heading_citation("Vt. Stat. tit. 9 § 2451a");
+
+    let subchapters = t
+        .get_nodes()
+        .iter()
+        .filter(|node| node.meta.level_name == "subchapter")
+        .count();
+    assert_eq!(subchapters, 1);
+    assert!(t
+        .get_nodes()
+        .iter()
+        .filter(|node| node.meta.level_name == "section")
+        .all(|node| node.meta.level_index == 3));
+}
+
 #[tokio::test]
 async fn adapter_fetches_fullchapter_without_section_page_fixtures() {
     let adapter = VtAdapter;
@@ -124,6 +181,7 @@ async fn adapter_fetches_fullchapter_without_section_page_fixtures() {
         node_store.clone(),
         crate::common::MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         },
         crate::common::MockUrlQueue {
             enqueued: queue.enqueued.clone(),
@@ -243,16 +301,19 @@ async fn adapter_supports_aggregated_batch_callbacks() {
             root_node_id: "vt/v1/root",
             accessed_at: "2024-01-01",
             unit_sort_order: 0,
+            profile: IngestProfile::Full,
         },
         nodes: Box::new(node_store.clone()),
         blobs: Arc::new(crate::common::MockBlobStore),
         cache: Arc::new(crate::common::MockCache {
             fixtures: cache.fixtures.clone(),
+            artifacts: cache.artifacts.clone(),
         }),
         queue: Arc::new(crate::common::MockUrlQueue {
             enqueued: queue.enqueued.clone(),
         }),
         logger: Arc::new(crate::common::MockLogger),
+        cancel: CancellationToken::new(),
     };
 
     queue.enqueue(QueueItem {
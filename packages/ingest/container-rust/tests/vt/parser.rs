@@ -1,4 +1,4 @@
-use crate::common::load_fixture;
+use crate::common::{assert_no_invisible_chars, load_fixture};
 use ingest::sources::vt::parser::{
     compare_designators, inline_section_cross_references, normalize_designator, normalize_text,
     normalize_text_for_comparison, parse_fullchapter_detail, parse_title_index, parse_title_links,
@@ -57,6 +57,27 @@ fn parses_fullchapter_sections_and_routes_history() {
     );
 }
 
+#[test]
+fn attaches_subchapter_levels_to_sections() {
+    let html = load_fixture("vt/fullchapter_09_063_medium.html");
+    let parsed = parse_fullchapter_detail(&html, "9", "63").expect("fullchapter should parse");
+
+    assert_eq!(parsed.sections.len(), 3);
+    for section in &parsed.sections {
+        assert_eq!(section.levels.len(), 1);
+        assert_eq!(section.levels[0].level_name, "subchapter");
+        assert_eq!(section.levels[0].num, "001");
+        assert_eq!(section.levels[0].name, "General Provisions");
+    }
+
+    let flat = load_fixture("vt/fullchapter_02_001.html");
+    let parsed = parse_fullchapter_detail(&flat, "2", "1").expect("fullchapter should parse");
+    assert!(parsed
+        .sections
+        .iter()
+        .all(|section| section.levels.is_empty()));
+}
+
 #[test]
 fn preserves_bold_markers_from_source_content() {
     let html = r#"
@@ -226,3 +247,24 @@ fn excerpt_at(value: &str, index: usize) -> String {
     let end = (index + 40).min(value.len());
     value[start..end].to_string()
 }
+
+#[test]
+fn strips_soft_hyphens_and_zero_width_characters() {
+    let html = load_fixture("vt/fullchapter_02_002_invisible.html");
+    let parsed = parse_fullchapter_detail(&html, "2", "2").expect("fullchapter should parse");
+    assert_eq!(parsed.title_name, "Legislature");
+    let section = &parsed.sections[0];
+    assert_eq!(
+        section.section_name,
+        "Joint Legislative Management Committee"
+    );
+    assert_eq!(
+        section.body,
+        "There is created a Joint Legislative Management Committee."
+    );
+    assert_eq!(
+        section.history.as_deref(),
+        Some("Added 2018, No. 102, § 3.")
+    );
+    assert_no_invisible_chars(&format!("{parsed:?}"));
+}
@@ -1,2 +1,4 @@
+#![cfg(feature = "vt")]
+
 mod common;
 mod vt;
@@ -0,0 +1,186 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{node, MockCache};
+use ingest::runtime::cache::{is_not_found_error, not_found_error};
+use ingest::runtime::types::Cache;
+use ingest::runtime::wayback::{
+    availability_url, parse_availability, ItemArchive, WaybackCache, WaybackLog,
+};
+use ingest::types::{IngestConfig, NodePayload, SectionContent};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const GONE_URL: &str = "https://example.test/statutes/1-1.htm";
+const SNAPSHOT_JSON: &str = r#"{
+    "url": "https://example.test/statutes/1-1.htm",
+    "archived_snapshots": {
+        "closest": {
+            "status": "200",
+            "available": true,
+            "url": "http://web.archive.org/web/20250102030405/https://example.test/statutes/1-1.htm",
+            "timestamp": "20250102030405"
+        }
+    }
+}"#;
+
+/// Serves fixtures like [`MockCache`] but reports missing URLs as upstream 404s.
+struct GoneCache(MockCache);
+
+#[async_trait]
+impl Cache for GoneCache {
+    async fn fetch_cached(
+        &self,
+        url: &str,
+        key: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        self.0
+            .fetch_cached(url, key, throttle_requests_per_second)
+            .await
+            .map_err(|_| not_found_error(url))
+    }
+
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        throttle_requests_per_second: Option<u32>,
+    ) -> Result<String, String> {
+        self.0
+            .fetch_uncached(url, throttle_requests_per_second)
+            .await
+            .map_err(|_| not_found_error(url))
+    }
+
+    async fn read_artifact(&self, key: &str) -> Result<Option<String>, String> {
+        self.0.read_artifact(key).await
+    }
+
+    async fn write_artifact(&self, key: &str, content: &str) -> Result<(), String> {
+        self.0.write_artifact(key, content).await
+    }
+}
+
+fn wayback_cache(prior_urls: &[&str]) -> (WaybackCache, Arc<ItemArchive>, Arc<WaybackLog>) {
+    let inner = MockCache::new();
+    inner.add_fixture(&availability_url(GONE_URL, None), SNAPSHOT_JSON);
+    inner.add_fixture(
+        "https://web.archive.org/web/20250102030405id_/https://example.test/statutes/1-1.htm",
+        "<html>archived</html>",
+    );
+    let item = Arc::new(ItemArchive::default());
+    let log = Arc::new(WaybackLog::new());
+    let cache = WaybackCache::new(
+        Arc::new(GoneCache(inner)),
+        prior_urls
+            .iter()
+            .map(|url| url.to_string())
+            .collect::<HashSet<_>>(),
+        None,
+        item.clone(),
+        log.clone(),
+    );
+    (cache, item, log)
+}
+
+fn section_node() -> NodePayload {
+    node("root/section-1-1")
+        .version("v2")
+        .name("Definitions.")
+        .source_url(GONE_URL)
+        .body("Text.")
+        .build()
+}
+
+#[test]
+fn parses_only_successful_snapshots() {
+    let source = parse_availability(GONE_URL, SNAPSHOT_JSON)
+        .unwrap()
+        .expect("snapshot should be available");
+    assert_eq!(
+        source.snapshot_url,
+        "https://web.archive.org/web/20250102030405/https://example.test/statutes/1-1.htm"
+    );
+    assert_eq!(source.snapshot_timestamp, "20250102030405");
+
+    let archived_404 = SNAPSHOT_JSON.replace(r#""status": "200""#, r#""status": "404""#);
+    assert_eq!(parse_availability(GONE_URL, &archived_404).unwrap(), None);
+    assert_eq!(
+        parse_availability(GONE_URL, r#"{"archived_snapshots": {}}"#).unwrap(),
+        None
+    );
+    assert_eq!(
+        availability_url(GONE_URL, Some("20240101")),
+        "https://archive.org/wayback/available?url=https%3A%2F%2Fexample.test%2Fstatutes%2F1-1.htm&timestamp=20240101"
+    );
+}
+
+#[tokio::test]
+async fn falls_back_for_urls_in_the_prior_manifest() {
+    let (cache, item, log) = wayback_cache(&[GONE_URL]);
+
+    let content = cache.fetch_cached(GONE_URL, "cgs/1-1.htm", None).await;
+    assert_eq!(content.unwrap(), "<html>archived</html>");
+    assert_eq!(log.fallbacks().len(), 1);
+
+    let mut node = section_node();
+    item.mark(&mut node).unwrap();
+    let content: SectionContent = serde_json::from_value(node.content.unwrap()).unwrap();
+    let archived = content.metadata.unwrap().archived_from.unwrap();
+    assert_eq!(archived.original_url, GONE_URL);
+
+    item.begin_item();
+    let mut next = section_node();
+    item.mark(&mut next).unwrap();
+    assert!(next.content.unwrap()["metadata"].is_null());
+}
+
+#[tokio::test]
+async fn marks_only_nodes_built_from_the_archived_page() {
+    let (cache, item, _log) = wayback_cache(&[GONE_URL]);
+    cache
+        .fetch_cached(GONE_URL, "cgs/1-1.htm", None)
+        .await
+        .unwrap();
+
+    let mut anchored = section_node();
+    anchored.meta.source_url = Some(format!("{GONE_URL}#sec_1-1"));
+    item.mark(&mut anchored).unwrap();
+    let content: SectionContent = serde_json::from_value(anchored.content.unwrap()).unwrap();
+    assert!(content.metadata.unwrap().archived_from.is_some());
+
+    let mut other = section_node();
+    other.meta.source_url = Some("https://example.test/statutes/1-2.htm".to_string());
+    item.mark(&mut other).unwrap();
+    assert!(other.content.unwrap()["metadata"].is_null());
+}
+
+#[tokio::test]
+async fn keeps_the_404_for_urls_the_prior_version_never_fetched() {
+    let (cache, item, log) = wayback_cache(&[]);
+
+    let error = cache.fetch_uncached(GONE_URL, None).await.unwrap_err();
+    assert!(is_not_found_error(&error));
+    assert!(item.archived().is_empty());
+    assert!(log.fallbacks().is_empty());
+}
+
+#[test]
+fn wayback_fallback_is_off_unless_configured() {
+    let base = json!({
+        "source": "cgs",
+        "sourceId": "cgs",
+        "callbackBase": "http://localhost",
+        "callbackToken": "token",
+    });
+    let config: IngestConfig = serde_json::from_value(base.clone()).unwrap();
+    assert_eq!(config.wayback, None);
+
+    let mut with_wayback = base;
+    with_wayback["wayback"] = json!({ "priorSourceVersionId": "cgs-2025" });
+    let config: IngestConfig = serde_json::from_value(with_wayback).unwrap();
+    let wayback = config.wayback.unwrap();
+    assert_eq!(wayback.prior_source_version_id, "cgs-2025");
+    assert_eq!(wayback.timestamp, None);
+}
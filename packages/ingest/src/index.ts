@@ -20,6 +20,7 @@ export {
 	getNodeIdByStringId,
 	getOrCreateSource,
 	insertNode,
+	publishSourceVersion,
 } from "./lib/versioning";
 // Zip utils
 export { streamXmlFromZip, streamXmlFromZipStream } from "./lib/zip-utils";
@@ -27,6 +27,7 @@ export interface IngestJobRecord {
 	processed_nodes: number;
 	error_count: number;
 	last_error: string | null;
+	report_key: string | null;
 	started_at: string;
 	completed_at: string | null;
 	created_at: string;
@@ -281,3 +282,18 @@ export async function recordTitleError(
 		)
 		.run();
 }
+
+export async function recordIngestReport(
+	db: D1Database,
+	jobId: string,
+	reportKey: string,
+): Promise<void> {
+	await db
+		.prepare(
+			`UPDATE ingest_jobs
+			SET report_key = ?, updated_at = CURRENT_TIMESTAMP
+			WHERE id = ?`,
+		)
+		.bind(reportKey, jobId)
+		.run();
+}
@@ -6,6 +6,8 @@ export interface SourceConfig {
 	region: string;
 	doc_type: string;
 	description: string;
+	/** Set while the source is in maintenance; new ingest runs are refused with this reason. */
+	maintenance?: string;
 }
 
 interface SourcesData {
@@ -149,7 +149,7 @@ async function resolveSourceVersionId(
 	const latest = await env.DB.prepare(
 		`SELECT id
 		 FROM source_versions
-		 WHERE source_id = ?
+		 WHERE source_id = ? AND published_at IS NOT NULL
 		 ORDER BY version_date DESC
 		 LIMIT 1`,
 	)
@@ -1,4 +1,10 @@
-import type { DiffResult, NodeInsert, SourceVersion } from "../types";
+import type {
+	DiffResult,
+	NodeInsert,
+	NodeLevelStats,
+	SourceVersion,
+	VersionCoverage,
+} from "../types";
 
 /**
  * Get or create a source by its code
@@ -34,24 +40,58 @@ export async function getOrCreateSource(
 }
 
 /**
- * Get or create a source version for a given date
+ * Get or create a source version for a given date. `coverage` is set when
+ * the run was limited to a subset of units and replaces any earlier value.
+ * A `staged` version stays hidden from readers until `publishSourceVersion`;
+ * re-running an existing version leaves its published state alone.
  */
 export async function ensureSourceVersion(
 	db: D1Database,
 	sourceId: string,
 	versionDate: string,
 	rootNodeId: string,
+	coverage: VersionCoverage | null = null,
+	staged = false,
 ): Promise<void> {
 	const canonicalName = `${sourceId}-${versionDate}`;
 
 	await db
 		.prepare(`
-			INSERT INTO source_versions (id, source_id, version_date, root_node_id)
-			VALUES (?, ?, ?, ?)
-			ON CONFLICT(id) DO UPDATE SET root_node_id = excluded.root_node_id
+			INSERT INTO source_versions (id, source_id, version_date, root_node_id, coverage, published_at)
+			VALUES (?, ?, ?, ?, ?, CASE WHEN ? THEN NULL ELSE CURRENT_TIMESTAMP END)
+			ON CONFLICT(id) DO UPDATE SET
+				root_node_id = excluded.root_node_id,
+				coverage = excluded.coverage
 		`)
-		.bind(canonicalName, sourceId, versionDate, rootNodeId)
+		.bind(
+			canonicalName,
+			sourceId,
+			versionDate,
+			rootNodeId,
+			coverage ? JSON.stringify(coverage) : null,
+			staged ? 1 : 0,
+		)
+		.run();
+}
+
+/**
+ * Make a staged source version live. Returns false when no version of
+ * `sourceId` has that id; publishing twice keeps the first timestamp.
+ */
+export async function publishSourceVersion(
+	db: D1Database,
+	sourceId: string,
+	sourceVersionId: string,
+): Promise<boolean> {
+	const result = await db
+		.prepare(`
+			UPDATE source_versions
+			SET published_at = COALESCE(published_at, CURRENT_TIMESTAMP)
+			WHERE id = ? AND source_id = ?
+		`)
+		.bind(sourceVersionId, sourceId)
 		.run();
+	return result.meta.changes > 0;
 }
 
 /**
@@ -64,7 +104,7 @@ export async function getLatestVersion(
 	const result = await db
 		.prepare(`
 			SELECT * FROM source_versions
-			WHERE source_id = ?
+			WHERE source_id = ? AND published_at IS NOT NULL
 			ORDER BY version_date DESC
 			LIMIT 1
 		`)
@@ -244,3 +284,30 @@ export async function insertNodes(
 
 	return nodeIdMap;
 }
+
+/**
+ * Store the child, section, and word counts of level nodes, sent once a run
+ * has finished every unit. Returns the number of nodes updated.
+ */
+export async function setLevelStats(
+	db: D1Database,
+	levels: NodeLevelStats[],
+): Promise<number> {
+	let updated = 0;
+
+	for (let i = 0; i < levels.length; i += BATCH_SIZE) {
+		const batch = levels.slice(i, i + BATCH_SIZE);
+		const statements = batch.map((level) =>
+			db
+				.prepare("UPDATE nodes SET level_stats = ? WHERE id = ?")
+				.bind(JSON.stringify(level.stats), level.id),
+		);
+
+		const results = await db.batch(statements);
+		for (const result of results) {
+			updated += result.meta.changes ?? 0;
+		}
+	}
+
+	return updated;
+}
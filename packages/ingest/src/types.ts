@@ -26,9 +26,20 @@ export interface SourceVersion {
 	source_id: string;
 	version_date: string;
 	root_node_id: string | null;
+	/** JSON coverage of a partial version; null when it covers every unit. */
+	coverage: string | null;
+	/** When the version went live; null while it is staged. */
+	published_at: string | null;
 	created_at: string;
 }
 
+/** Units a partially published source version covers. */
+export interface VersionCoverage {
+	selectors: string[];
+	unitIds: string[];
+	discoveredUnits: number;
+}
+
 export interface NodeMeta {
 	id: string;
 	source_version_id: string;
@@ -55,6 +66,16 @@ export interface NodePayload {
 	content: unknown | null;
 }
 
+/** Counts for a structural level node, covering every unit of the run. */
+export interface NodeLevelStats {
+	id: string;
+	stats: {
+		child_count: number;
+		section_count: number;
+		word_count: number;
+	};
+}
+
 export interface DiffResult {
 	added: string[];
 	removed: string[];
@@ -13,6 +13,7 @@ import {
 	incrementUnitProcessedNodes,
 	markUnitCompleted,
 	markUnitRunning,
+	recordIngestReport,
 	recordTitleError,
 } from "./lib/ingest-jobs";
 import { hash64, hash64ToHex } from "./lib/packfile/hash";
@@ -22,15 +23,20 @@ import { VectorIngestWorkflow } from "./lib/vector/workflow";
 import {
 	computeDiff,
 	ensureSourceVersion,
+	getLatestVersion,
 	getOrCreateSource,
 	insertNodes,
+	publishSourceVersion,
+	setLevelStats,
 } from "./lib/versioning";
 import type {
 	Env,
 	IngestNode,
+	NodeLevelStats,
 	NodeMeta,
 	NodePayload,
 	VectorWorkflowParams,
+	VersionCoverage,
 } from "./types";
 
 type AppContext = {
@@ -191,7 +197,7 @@ app.get("/api/versions", async (c) => {
 const INGEST_JOB_COLUMNS = `
 	id, source_code, source_version_id, status,
 	total_titles, processed_titles, total_nodes, processed_nodes,
-	error_count, last_error,
+	error_count, last_error, report_key,
 	started_at, completed_at, created_at, updated_at`;
 
 const ABORTABLE_JOB_STATUSES = new Set(["planning", "running"]);
@@ -249,6 +255,22 @@ app.get("/api/ingest/jobs/:jobId/units", async (c) => {
 	return c.json({ units: results });
 });
 
+app.get("/api/ingest/jobs/:jobId/report", async (c) => {
+	const job = await c.env.DB.prepare(
+		"SELECT report_key FROM ingest_jobs WHERE id = ?",
+	)
+		.bind(c.req.param("jobId"))
+		.first<{ report_key: string | null }>();
+
+	if (!job) return c.json({ error: "Job not found" }, 404);
+	if (!job.report_key) return c.json({ error: "No report for job" }, 404);
+	const obj = await c.env.STORAGE.get(job.report_key);
+	if (!obj) return c.json({ error: `Object not found: ${job.report_key}` }, 404);
+	return new Response(obj.body, {
+		headers: { "Content-Type": "application/json" },
+	});
+});
+
 app.post("/api/ingest/jobs/:jobId/abort", async (c) => {
 	const jobId = c.req.param("jobId");
 	const job = await c.env.DB.prepare(
@@ -371,6 +393,14 @@ app.post("/api/ingest/:source", async (c) => {
 				500,
 			);
 		}
+		if (config.maintenance !== undefined) {
+			return c.json(
+				{
+					error: `Source ${sourceCode} is in maintenance and not accepting ingest runs: ${config.maintenance}`,
+				},
+				409,
+			);
+		}
 
 		const unitSelectors = (c.req.query("units") ?? "")
 			.split(",")
@@ -387,6 +417,8 @@ app.post("/api/ingest/:source", async (c) => {
 			config.doc_type,
 		);
 
+		// Runs whose version is older than the published one stay staged.
+		const publishedVersion = await getLatestVersion(c.env.DB, sourceId);
 		const jobId = await createIngestJob(c.env.DB, sourceCode);
 
 		// In local dev, containers run in Docker and can't reach the host via
@@ -423,6 +455,7 @@ app.post("/api/ingest/:source", async (c) => {
 									: undefined,
 						callbackBase,
 						callbackToken,
+						publishedSourceVersionId: publishedVersion?.id,
 					}),
 				}),
 			)
@@ -456,15 +489,19 @@ app.post("/api/callback/ensureSourceVersion", async (c) => {
 	if (await isJobAborted(c.env.DB, params.jobId)) {
 		return c.json({ error: "Job aborted" }, 409);
 	}
-	const { sourceId, sourceVersionId, rootNode, units } = await c.req.json<{
-		sourceId: string;
-		sourceVersionId: string;
-		rootNode: NodeMeta;
-		units: Array<{ id: string; title_num: string; url: string }>;
-	}>();
+	const { sourceId, sourceVersionId, rootNode, units, coverage, staged } =
+		await c.req.json<{
+			sourceId: string;
+			sourceVersionId: string;
+			rootNode: NodeMeta;
+			units: Array<{ id: string; title_num: string; url: string }>;
+			coverage?: VersionCoverage | null;
+			staged?: boolean;
+		}>();
 
 	console.log(
-		`[Worker] ensureSourceVersion callback. jobId=${params.jobId}, svid=${sourceVersionId}, units=${units.length}`,
+		`[Worker] ensureSourceVersion callback. jobId=${params.jobId}, svid=${sourceVersionId}, units=${units.length}` +
+			(coverage ? ` (partial: ${coverage.discoveredUnits} discovered)` : ""),
 	);
 
 	// ensureSourceVersion takes (db, sourceId, versionDate, rootNodeId).
@@ -472,7 +509,14 @@ app.post("/api/callback/ensureSourceVersion", async (c) => {
 	const versionDate = sourceVersionId.replace(`${sourceId}-`, "");
 
 	try {
-		await ensureSourceVersion(c.env.DB, sourceId, versionDate, rootNode.id);
+		await ensureSourceVersion(
+			c.env.DB,
+			sourceId,
+			versionDate,
+			rootNode.id,
+			coverage ?? null,
+			staged === true,
+		);
 
 		// Insert root node
 		await insertNodes(c.env.DB, [
@@ -503,6 +547,34 @@ app.post("/api/callback/ensureSourceVersion", async (c) => {
 	return c.json({ ok: true });
 });
 
+app.post("/api/callback/publishSourceVersion", async (c) => {
+	const token = extractBearerToken(c.req.raw);
+	const params = await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
+	if (await isJobAborted(c.env.DB, params.jobId)) {
+		return c.json({ error: "Job aborted" }, 409);
+	}
+	const { sourceVersionId } = await c.req.json<{ sourceVersionId: string }>();
+
+	const published = await publishSourceVersion(
+		c.env.DB,
+		params.sourceId,
+		sourceVersionId,
+	);
+	if (!published) {
+		return c.json(
+			{
+				error: `Unknown source version for ${params.sourceId}: ${sourceVersionId}`,
+			},
+			404,
+		);
+	}
+	console.log(
+		`[Worker] publishSourceVersion callback processed. jobId=${params.jobId}, svid=${sourceVersionId}`,
+	);
+
+	return c.json({ ok: true });
+});
+
 // ──────────────────────────────────────────────────────────────
 app.post("/api/callback/containerLog", async (c) => {
 	const token = extractBearerToken(c.req.raw);
@@ -530,10 +602,13 @@ app.post("/api/callback/containerLog", async (c) => {
 app.post("/api/callback/ingestError", async (c) => {
 	const token = extractBearerToken(c.req.raw);
 	const params = await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
-	const { error } = await c.req.json<{ error: string }>();
+	const { error, crashDump } = await c.req.json<{
+		error: string;
+		crashDump?: string;
+	}>();
 
 	console.error(
-		`[Worker] ingestError callback received. jobId=${params.jobId}, error=${error}`,
+		`[Worker] ingestError callback received. jobId=${params.jobId}, error=${error}${crashDump ? `, crashDump=${crashDump}` : ""}`,
 	);
 	await recordTitleError(c.env.DB, params.jobId, error, true);
 
@@ -567,6 +642,23 @@ app.post("/api/callback/containerStop", async (c) => {
 	return c.json({ ok: true });
 });
 
+app.post("/api/callback/ingestReport", async (c) => {
+	const token = extractBearerToken(c.req.raw);
+	const params = await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
+	const report = await c.req.text();
+
+	const reportKey = `reports/${params.jobId}.json`;
+	await c.env.STORAGE.put(reportKey, report, {
+		httpMetadata: { contentType: "application/json" },
+	});
+	await recordIngestReport(c.env.DB, params.jobId, reportKey);
+	console.log(
+		`[Worker] ingestReport callback received. jobId=${params.jobId}, bytes=${report.length}`,
+	);
+
+	return c.json({ ok: true, reportKey });
+});
+
 // ──────────────────────────────────────────────────────────────
 app.post("/api/callback/unitStart", async (c) => {
 	const token = extractBearerToken(c.req.raw);
@@ -647,6 +739,22 @@ app.post("/api/callback/insertNodeBatch", async (c) => {
 	return c.json({ accepted: nodes.length });
 });
 
+app.post("/api/callback/setLevelStats", async (c) => {
+	const token = extractBearerToken(c.req.raw);
+	const params = await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
+	if (await isJobAborted(c.env.DB, params.jobId)) {
+		return c.json({ error: "Job aborted" }, 409);
+	}
+	const { levels } = await c.req.json<{ levels: NodeLevelStats[] }>();
+
+	console.log(
+		`[Worker] setLevelStats callback received. count=${levels.length}`,
+	);
+	const updated = await setLevelStats(c.env.DB, levels);
+
+	return c.json({ updated });
+});
+
 app.post("/api/callback/progress", async (c) => {
 	const token = extractBearerToken(c.req.raw);
 	const params = await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
@@ -733,8 +841,9 @@ async function uploadMultipartFromStream(
 	bucket: R2Bucket,
 	key: string,
 	stream: ReadableStream<Uint8Array>,
+	customMetadata?: Record<string, string>,
 ): Promise<number> {
-	const upload = await bucket.createMultipartUpload(key);
+	const upload = await bucket.createMultipartUpload(key, { customMetadata });
 	type UploadedPart = Awaited<ReturnType<R2MultipartUpload["uploadPart"]>>;
 	const uploadedParts: UploadedPart[] = [];
 	const reader = stream.getReader();
@@ -809,7 +918,7 @@ async function uploadMultipartFromStream(
 		}
 
 		if (uploadedParts.length === 0) {
-			await bucket.put(key, new Uint8Array(0));
+			await bucket.put(key, new Uint8Array(0), { customMetadata });
 			return 0;
 		}
 
@@ -873,13 +982,76 @@ function getValidatedCacheRequest(payload: CacheRequest): {
 	};
 }
 
+/** The origin answered a cache fill with a non-2xx status. */
+class UpstreamFetchError extends Error {
+	readonly status: number;
+
+	constructor(url: string, status: number) {
+		super(`Failed to fetch ${url}: ${status}`);
+		this.status = status;
+	}
+}
+
+type CachedObject = {
+	r2Key: string;
+	totalSize: number;
+	/** Origin status when this request fetched from it; unset on cache hits. */
+	upstreamStatus?: number;
+};
+
+/**
+ * `X-Upstream-Status` tells the container the proxy contacted the origin
+ * and what it answered, so crawl reports count origin requests and 429s
+ * rather than proxy responses.
+ */
+function upstreamHeaders(
+	upstreamStatus: number | undefined,
+): Record<string, string> {
+	return upstreamStatus === undefined
+		? {}
+		: { "X-Upstream-Status": String(upstreamStatus) };
+}
+
+// Upstream headers kept with a cached object for provenance. R2 caps custom
+// metadata at 2 KiB, so only headers that identify the response are kept.
+const PROVENANCE_HEADERS = [
+	"cache-control",
+	"content-disposition",
+	"content-length",
+	"content-type",
+	"date",
+	"etag",
+	"last-modified",
+];
+
+/**
+ * The origin response a cached object was filled from, stored as R2 custom
+ * metadata and returned on every read as `X-Upstream-Response`
+ * (URI-encoded JSON), so provenance reflects the origin rather than the
+ * proxy even on cache hits.
+ */
+function upstreamResponseMetadata(response: Response): Record<string, string> {
+	const headers: Record<string, string> = {};
+	for (const name of PROVENANCE_HEADERS) {
+		const value = response.headers.get(name);
+		if (value !== null) headers[name] = value;
+	}
+	return {
+		upstreamResponse: JSON.stringify({
+			status: response.status,
+			fetchedAt: new Date().toISOString(),
+			headers,
+		}),
+	};
+}
+
 async function populateCacheObject(
 	bucket: R2Bucket,
 	url: string,
 	r2Key: string,
 	extractZip: boolean,
 	throttleRps?: number,
-): Promise<{ r2Key: string; totalSize: number }> {
+): Promise<CachedObject> {
 	await throttleRequestsPerSecond(throttleRps);
 
 	console.log("fetching", url);
@@ -887,7 +1059,7 @@ async function populateCacheObject(
 		headers: { "User-Agent": "fastlaw-ingest/1.0" },
 	});
 	if (!response.ok) {
-		throw new Error(`Failed to fetch ${url}: ${response.status}`);
+		throw new UpstreamFetchError(url, response.status);
 	}
 	if (!response.body) {
 		throw new Error("empty_response_body");
@@ -898,22 +1070,24 @@ async function populateCacheObject(
 		throw new Error("html_response");
 	}
 
+	const customMetadata = upstreamResponseMetadata(response);
 	if (extractZip) {
 		const totalSize = await uploadMultipartFromStream(
 			bucket,
 			r2Key,
 			response.body,
+			customMetadata,
 		);
 		console.log(`Cached ZIP ${url} → ${r2Key} (${totalSize} bytes, multipart)`);
-		return { r2Key, totalSize };
+		return { r2Key, totalSize, upstreamStatus: response.status };
 	}
 
 	const responseBytes = await response.arrayBuffer();
-	await bucket.put(r2Key, responseBytes);
+	await bucket.put(r2Key, responseBytes, { customMetadata });
 	const stored = await bucket.head(r2Key);
 	const totalSize = stored?.size ?? 0;
 	console.log(`Cached ${url} → ${r2Key} (${totalSize} bytes)`);
-	return { r2Key, totalSize };
+	return { r2Key, totalSize, upstreamStatus: response.status };
 }
 
 async function ensureCachedObject(
@@ -922,7 +1096,7 @@ async function ensureCachedObject(
 	cacheKey: string,
 	extractZip: boolean,
 	throttleRps?: number,
-): Promise<{ r2Key: string; totalSize: number }> {
+): Promise<CachedObject> {
 	const r2Key = `${CACHE_R2_PREFIX}${cacheKey}`;
 	const head = await bucket.head(r2Key);
 	if (head) {
@@ -943,14 +1117,14 @@ app.post("/api/proxy/cache", async (c) => {
 	try {
 		const { url, extractZip, cacheKey, throttleRequestsPerSecond } =
 			getValidatedCacheRequest(await c.req.json<CacheRequest>());
-		const result = await ensureCachedObject(
+		const { r2Key, totalSize, upstreamStatus } = await ensureCachedObject(
 			c.env.STORAGE,
 			url,
 			cacheKey,
 			extractZip,
 			throttleRequestsPerSecond,
 		);
-		return c.json(result);
+		return c.json({ r2Key, totalSize }, 200, upstreamHeaders(upstreamStatus));
 	} catch (error) {
 		if (error instanceof Error) {
 			if (error.message === "Missing cacheKey") {
@@ -965,8 +1139,12 @@ app.post("/api/proxy/cache", async (c) => {
 			if (error.message === "empty_response_body") {
 				return c.json({ error: error.message }, 502);
 			}
-			if (error.message.startsWith("Failed to fetch")) {
-				return c.json({ error: error.message }, 502);
+			if (error instanceof UpstreamFetchError) {
+				return c.json(
+					{ error: error.message },
+					502,
+					upstreamHeaders(error.status),
+				);
 			}
 			return c.json({ error: error.message }, 500);
 		}
@@ -985,7 +1163,7 @@ app.post("/api/proxy/cache-read", async (c) => {
 	try {
 		const { url, extractZip, cacheKey, throttleRequestsPerSecond } =
 			getValidatedCacheRequest(await c.req.json<CacheRequest>());
-		const { r2Key } = await ensureCachedObject(
+		const { r2Key, upstreamStatus } = await ensureCachedObject(
 			c.env.STORAGE,
 			url,
 			cacheKey,
@@ -996,10 +1174,15 @@ app.post("/api/proxy/cache-read", async (c) => {
 		if (!obj) {
 			return c.json({ error: `Object not found: ${r2Key}` }, 404);
 		}
+		const upstreamResponse = obj.customMetadata?.upstreamResponse;
 		return new Response(obj.body, {
 			headers: {
 				"Content-Type": "application/octet-stream",
 				"X-Cache-Key": r2Key,
+				...upstreamHeaders(upstreamStatus),
+				...(upstreamResponse
+					? { "X-Upstream-Response": encodeURIComponent(upstreamResponse) }
+					: {}),
 			},
 		});
 	} catch (error) {
@@ -1016,8 +1199,12 @@ app.post("/api/proxy/cache-read", async (c) => {
 			if (error.message === "empty_response_body") {
 				return c.json({ error: error.message }, 502);
 			}
-			if (error.message.startsWith("Failed to fetch")) {
-				return c.json({ error: error.message }, 502);
+			if (error instanceof UpstreamFetchError) {
+				return c.json(
+					{ error: error.message },
+					502,
+					upstreamHeaders(error.status),
+				);
 			}
 			return c.json({ error: error.message }, 500);
 		}
@@ -1060,6 +1247,43 @@ app.get("/api/proxy/r2-read", async (c) => {
 	});
 });
 
+// Prefixes the container may write under. Fetched source bytes (`cache/`)
+// and packfiles are only written by the Worker itself.
+const CONTAINER_WRITABLE_PREFIXES = [
+	"artifacts/",
+	"crash-dumps/",
+	"exports/",
+	"ingested/",
+	"provenance/",
+	"timings/",
+];
+
+app.post("/api/proxy/r2-write", async (c) => {
+	try {
+		const token = extractBearerToken(c.req.raw);
+		await verifyCallbackToken(token, c.env.CALLBACK_SECRET);
+	} catch {
+		return c.json({ error: "Unauthorized" }, 401);
+	}
+
+	const { key, content } = await c.req.json<{
+		key?: string;
+		content?: string;
+	}>();
+	if (!key || content === undefined) {
+		return c.json({ error: "Missing key or content" }, 400);
+	}
+	if (
+		key.includes("..") ||
+		!CONTAINER_WRITABLE_PREFIXES.some((prefix) => key.startsWith(prefix))
+	) {
+		return c.json({ error: `Key not writable: ${key}` }, 403);
+	}
+
+	await c.env.STORAGE.put(key, content);
+	return c.json({ ok: true });
+});
+
 // ──────────────────────────────────────────────────────────────
 // Vector workflow
 // ──────────────────────────────────────────────────────────────
@@ -127,7 +127,7 @@ export async function getLatestSourceVersion(
 	return db
 		.prepare(
 			`SELECT * FROM source_versions
-       WHERE source_id = ?
+       WHERE source_id = ? AND published_at IS NOT NULL
        ORDER BY version_date DESC
        LIMIT 1`,
 		)
@@ -163,7 +163,7 @@ export async function listSourceVersions(
 const INGEST_JOB_COLUMNS = `
 	id, source_code, source_version_id, status,
 	total_titles, processed_titles, total_nodes, processed_nodes,
-	error_count, last_error,
+	error_count, last_error, report_key,
 	started_at, completed_at, created_at, updated_at`;
 
 const ABORTABLE_JOB_STATUSES = new Set(["planning", "running"]);
@@ -15,6 +15,7 @@ export interface SourceVersionRecord {
 	source_id: string;
 	version_date: string;
 	root_node_id: string | null;
+	published_at: string | null;
 	created_at: string | null;
 }
 
@@ -37,6 +38,7 @@ export interface IngestJobRecord {
 	processed_nodes: number;
 	error_count: number;
 	last_error: string | null;
+	report_key: string | null;
 	started_at: string;
 	completed_at: string | null;
 	created_at: string;